@@ -0,0 +1,107 @@
+//! Benchmarks against `colored` & `owo-colors`, ran by `cargo bench -F bench-compare`
+//!
+//! Each bench renders the same text through all three crates, giving relative numbers
+#[cfg(not(feature = "macro-only"))]
+criterion::criterion_main!(benches::benches);
+/// `macro-only` leaves out every type benched, so there is nothing to run
+#[cfg(feature = "macro-only")]
+fn main() {}
+
+#[cfg(not(feature = "macro-only"))]
+mod benches {
+    use std::fmt::Write as _;
+
+    use criterion::{criterion_group, Criterion};
+    use easy_sgr::{CleanKind, Color::*, EasySGR, Style::*};
+
+    criterion_group!(benches, all);
+
+    fn all(c: &mut Criterion) {
+        // colored skips styling when it finds no terminal, leaving it nothing to do
+        colored::control::set_override(true);
+        word(c);
+        line(c);
+    }
+    /// A single bold red word
+    fn word(c: &mut Criterion) {
+        let mut group = c.benchmark_group("compare/word");
+        group.bench_function("easy-sgr", |b| {
+            b.iter(|| {
+                "word"
+                    .style(Bold)
+                    .color(RedFg)
+                    .clean(CleanKind::Reset)
+                    .to_string()
+            });
+        });
+        group.bench_function("colored", |b| {
+            use colored::Colorize as _;
+            b.iter(|| "word".bold().red().to_string());
+        });
+        group.bench_function("owo-colors", |b| {
+            use owo_colors::OwoColorize as _;
+            b.iter(|| "word".bold().red().to_string());
+        });
+        group.finish();
+    }
+    /// A line of 1000 tokens, alternating between bold blue, plain & green
+    fn line(c: &mut Criterion) {
+        let mut group = c.benchmark_group("compare/line");
+        let tokens: Vec<_> = (0..1000).map(|i| format!("tok{i} ")).collect();
+        let mut out = String::new();
+        group.bench_function("easy-sgr", |b| {
+            b.iter(|| {
+                out.clear();
+                for (i, token) in tokens.iter().enumerate() {
+                    let _ = match i % 3 {
+                        0 => write!(
+                            out,
+                            "{}",
+                            token
+                                .as_str()
+                                .style(Bold)
+                                .color(BlueFg)
+                                .clean(CleanKind::Reset)
+                        ),
+                        1 => write!(out, "{token}"),
+                        _ => write!(
+                            out,
+                            "{}",
+                            token.as_str().color(GreenFg).clean(CleanKind::Reset)
+                        ),
+                    };
+                }
+                out.len()
+            });
+        });
+        group.bench_function("colored", |b| {
+            use colored::Colorize as _;
+            b.iter(|| {
+                out.clear();
+                for (i, token) in tokens.iter().enumerate() {
+                    let _ = match i % 3 {
+                        0 => write!(out, "{}", token.as_str().bold().blue()),
+                        1 => write!(out, "{token}"),
+                        _ => write!(out, "{}", token.as_str().green()),
+                    };
+                }
+                out.len()
+            });
+        });
+        group.bench_function("owo-colors", |b| {
+            use owo_colors::OwoColorize as _;
+            b.iter(|| {
+                out.clear();
+                for (i, token) in tokens.iter().enumerate() {
+                    let _ = match i % 3 {
+                        0 => write!(out, "{}", token.bold().blue()),
+                        1 => write!(out, "{token}"),
+                        _ => write!(out, "{}", token.green()),
+                    };
+                }
+                out.len()
+            });
+        });
+        group.finish();
+    }
+}
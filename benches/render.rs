@@ -0,0 +1,217 @@
+//! Benchmarks of rendering, ran by `cargo bench`
+//!
+//! Timed by criterion, which keeps each run in `target/criterion`, showing the change since the last.
+//! A bench is only ran when its name matches the given filter: `cargo bench -- segments`.
+//! Outside of `cargo bench`, such as under `cargo test --benches`, each runs once
+#[cfg(not(feature = "macro-only"))]
+criterion::criterion_main!(benches::benches);
+/// `macro-only` leaves out every type benched, so there is nothing to run
+#[cfg(feature = "macro-only")]
+fn main() {}
+
+#[cfg(not(feature = "macro-only"))]
+mod benches {
+    use std::fmt::Write as _;
+
+    use criterion::{criterion_group, Criterion};
+    use easy_sgr::{
+        offset_map, sanitize, CleanKind, Color::*, DiscreteSGR, DisplaySegments, EasySGR,
+        SGRBuilder, SGRWriter, SanitizePolicy, Style::*, StyleSet,
+    };
+
+    criterion_group!(benches, all);
+
+    fn all(c: &mut Criterion) {
+        word(c);
+        discrete(c);
+        builder(c);
+        segments(c);
+        #[cfg(feature = "macros")]
+        macros(c);
+        large_input(c);
+    }
+    /// A single styled word
+    fn word(c: &mut Criterion) {
+        let mut group = c.benchmark_group("word");
+        let word = "word".style(Bold).color(RedFg).clean(CleanKind::Reset);
+        group.bench_function("to_string", |b| b.iter(|| word.to_string()));
+        group.bench_function("render_to_string", |b| b.iter(|| word.render_to_string()));
+        let mut out = String::with_capacity(64);
+        group.bench_function("write_reused", |b| {
+            b.iter(|| {
+                out.clear();
+                let _ = write!(out, "{word}");
+                out.len()
+            });
+        });
+        group.finish();
+    }
+    /// Displaying styles & colors, written precomputed or through the builder of `standard_display`
+    fn discrete(c: &mut Criterion) {
+        let mut group = c.benchmark_group("discrete");
+        let styles = [Bold, NotItalic, Underline, Reset];
+        let colors = [RedFg, BrightBlueBg, ByteFg(208), RgbBg(10, 20, 30)];
+        let mut out = String::with_capacity(256);
+        group.bench_function("style_display", |b| {
+            b.iter(|| {
+                out.clear();
+                for style in &styles {
+                    let _ = write!(out, "{style}");
+                }
+                out.len()
+            });
+        });
+        group.bench_function("style_standard_display", |b| {
+            b.iter(|| {
+                out.clear();
+                for style in &styles {
+                    let _ = style.standard_display(&mut out);
+                }
+                out.len()
+            });
+        });
+        group.bench_function("color_display", |b| {
+            b.iter(|| {
+                out.clear();
+                for color in &colors {
+                    let _ = write!(out, "{color}");
+                }
+                out.len()
+            });
+        });
+        group.bench_function("color_standard_display", |b| {
+            b.iter(|| {
+                out.clear();
+                for color in &colors {
+                    let _ = color.standard_display(&mut out);
+                }
+                out.len()
+            });
+        });
+        group.finish();
+    }
+    /// The codes of 1000 strings, a builder created for each or one reused through `clear`
+    ///
+    /// Most strings have more codes than fit inline, so a reused builder keeps its allocation
+    fn builder(c: &mut Criterion) {
+        let mut group = c.benchmark_group("builder");
+        let strings: Vec<_> = tokens(1000)
+            .into_iter()
+            .map(|(text, style)| {
+                text.apply(style)
+                    .color(RgbBg(10, 20, 30))
+                    .style(Underline)
+                    .style(Overline)
+            })
+            .collect();
+        let mut out = String::new();
+        group.bench_function("new_per_string", |b| {
+            b.iter(|| {
+                out.clear();
+                let mut writer = SGRWriter::from(&mut out);
+                for string in &strings {
+                    let mut builder = SGRBuilder::default();
+                    string.place_all(&mut builder);
+                    let _ = writer.write_builder(&builder);
+                }
+                out.len()
+            });
+        });
+        let mut builder = SGRBuilder::default();
+        group.bench_function("reused", |b| {
+            b.iter(|| {
+                out.clear();
+                let mut writer = SGRWriter::from(&mut out);
+                for string in &strings {
+                    builder.clear();
+                    string.place_all(&mut builder);
+                    let _ = writer.write_builder(&builder);
+                }
+                out.len()
+            });
+        });
+        group.finish();
+    }
+    /// The tokens of a syntax highlighted line & their styles
+    fn tokens(count: usize) -> Vec<(String, StyleSet)> {
+        let styles = [
+            StyleSet::from((Bold, BlueFg)),
+            StyleSet::default(),
+            StyleSet::from(GreenFg),
+            StyleSet::default(),
+            StyleSet::from((Italic, BrightBlackFg)),
+        ];
+        (0..count)
+            .map(|i| (format!("tok{i} "), styles[i % styles.len()].clone()))
+            .collect()
+    }
+    /// A line of 1000 segments, each styled on its own or only writing the changes between them
+    fn segments(c: &mut Criterion) {
+        let mut group = c.benchmark_group("segments");
+        let tokens = tokens(1000);
+        let strings: Vec<_> = tokens
+            .iter()
+            .map(|(text, style)| text.as_str().apply(style.clone()).clean(CleanKind::Reset))
+            .collect();
+        let mut out = String::new();
+        group.bench_function("naive", |b| {
+            b.iter(|| {
+                out.clear();
+                for string in &strings {
+                    let _ = write!(out, "{string}");
+                }
+                out.len()
+            });
+        });
+        group.bench_function("diff", |b| {
+            b.iter(|| {
+                out.clear();
+                let _ = write!(out, "{}", DisplaySegments(&tokens));
+                out.len()
+            });
+        });
+        group.finish();
+    }
+    /// Strings created by the macros at compile time, against those built at runtime
+    #[cfg(feature = "macros")]
+    fn macros(c: &mut Criterion) {
+        let mut group = c.benchmark_group("macros");
+        group.bench_function("sgr_static", |b| {
+            b.iter(|| easy_sgr::sgr_static!("{[bold red]}word{[]}").len());
+        });
+        group.bench_function("runtime", |b| {
+            b.iter(|| {
+                "word"
+                    .style(Bold)
+                    .color(RedFg)
+                    .clean(CleanKind::Reset)
+                    .to_string()
+            });
+        });
+        let name = "world";
+        group.bench_function("format", |b| {
+            b.iter(|| easy_sgr::format!("{[bold red]}hello {name}{[]}"));
+        });
+        group.finish();
+    }
+    /// Stripping & measuring 1MB of styled text
+    fn large_input(c: &mut Criterion) {
+        let mut group = c.benchmark_group("1mb");
+        let mut text = String::new();
+        for (token, _) in tokens(1000).iter().cycle() {
+            if text.len() >= 1 << 20 {
+                break;
+            }
+            let _ = write!(text, "\x1b[1;34m{token}\x1b[0m");
+        }
+        group.bench_function("sanitize_strip_all", |b| {
+            b.iter(|| sanitize(&text, SanitizePolicy::StripAll).len());
+        });
+        group.bench_function("offset_map", |b| b.iter(|| offset_map(&text).width()));
+        #[cfg(feature = "table")]
+        group.bench_function("visible_width", |b| {
+            b.iter(|| easy_sgr::visible_width(&text));
+        });
+        group.finish();
+    }
+}
@@ -0,0 +1,25 @@
+#![no_main]
+//! Fuzzes the macro string parser, which must never panic
+//!
+//! Run from within `macros` using `cargo +nightly fuzz run parse_string`
+
+use libfuzzer_sys::fuzz_target;
+
+// proc-macro crates cannot export functions, so the parser is included directly
+#[path = "../../src/parse.rs"]
+#[allow(dead_code)]
+mod parse;
+
+fn check_curly(ch: char) -> Option<&'static str> {
+    match ch {
+        '}' => Some("{}"),
+        '{' => Some("{{"),
+        _ => None,
+    }
+}
+
+fuzz_target!(|s: &str| {
+    let _ = parse::sgr_string(s, check_curly);
+    let _ = parse::stripped_string(s, check_curly);
+    let _ = parse::unwrap_string(s);
+});
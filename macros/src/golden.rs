@@ -0,0 +1,103 @@
+//! Runs the parser over the inputs within `tests/golden`, comparing against the checked-in outputs
+//!
+//! Each line of a `.txt` file is an input, as the contents of a string literal,
+//! its output being written to the `.golden` file of the same name.
+//! Files named after a feature, i.e. `extended.txt`, are only ran with that feature.
+//! Run with `GOLDEN=overwrite` to regenerate the outputs
+use std::{fmt::Write, fs, path::Path};
+
+use crate::parse::{lift_captures, sgr_string, stripped_string, KEYWORDS};
+
+/// The features that have their own input file
+const FEATURES: &[(&str, bool)] = &[("extended", cfg!(feature = "extended"))];
+
+#[test]
+fn golden() {
+    let overwrite = std::env::var_os("GOLDEN").is_some_and(|v| v == "overwrite");
+    let mut mismatches = Vec::new();
+    for (name, input) in inputs() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name)
+            .with_extension("golden");
+        let output = render(&input);
+        if overwrite {
+            fs::write(&path, &output).unwrap();
+        } else if fs::read_to_string(&path).ok().as_deref() != Some(&*output) {
+            mismatches.push(path.display().to_string());
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "golden outputs differ, run with `GOLDEN=overwrite` and review the diff:\n{}",
+        mismatches.join("\n")
+    );
+}
+#[test]
+fn every_keyword_covered() {
+    let inputs: Vec<String> = inputs().into_iter().map(|(_, input)| input).collect();
+    let words: Vec<&str> = inputs
+        .iter()
+        .flat_map(|input| input.split(|ch: char| ch.is_whitespace() || "{}[]>".contains(ch)))
+        .collect();
+    for (keyword, ..) in KEYWORDS {
+        assert!(
+            words.contains(keyword),
+            "`{keyword}` is within no golden input, add it to `tests/golden`"
+        );
+    }
+}
+/// Returns the name & contents of every input file ran with the current features
+fn inputs() -> Vec<(String, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut inputs: Vec<(String, String)> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (name, fs::read_to_string(path).unwrap())
+        })
+        .filter(|(name, _)| {
+            FEATURES
+                .iter()
+                .all(|&(feature, enabled)| enabled || feature != name)
+        })
+        .collect();
+    inputs.sort();
+    inputs
+}
+/// Renders the output of every line of an input file
+fn render(input: &str) -> String {
+    let check_curly = |ch| match ch {
+        '}' => Some("{}"),
+        '{' => Some("{{"),
+        _ => None,
+    };
+    let mut out = String::new();
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let _ = writeln!(out, "input: {line}");
+        match sgr_string(line, check_curly) {
+            Ok(sgr) => {
+                let (lifted, captures) = lift_captures(&sgr);
+                let _ = writeln!(out, "sgr: {lifted:?}");
+                if !captures.is_empty() {
+                    let _ = writeln!(out, "captures: {captures:?}");
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(out, "sgr: {e:?}");
+            }
+        }
+        match stripped_string(line, check_curly) {
+            Ok(stripped) => {
+                let _ = writeln!(out, "stripped: {stripped:?}");
+            }
+            Err(e) => {
+                let _ = writeln!(out, "stripped: {e:?}");
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
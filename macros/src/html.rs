@@ -0,0 +1,272 @@
+use crate::parse::{is_plain_arg, matching_brace, split_spec, CAPTURE_PREFIX, MERGE_ADAPTER};
+
+/// The path of the adapter every format param of `format_html` is formatted through
+const ESCAPE_ADAPTER: &str = "::easy_sgr::html::HtmlEscape";
+/// The colors of the 16 system colors, those of easy-sgr's `palette256`
+const SYSTEM: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+/// The levels of each component within the color cube
+const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the RGB value of an index of the 256 color palette, as easy-sgr's `to_rgb`
+const fn to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => SYSTEM[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_STEPS[(i / 36) as usize],
+                CUBE_STEPS[(i / 6 % 6) as usize],
+                CUBE_STEPS[(i % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let v = 8 + 10 * (index - 232);
+            (v, v, v)
+        }
+    }
+}
+/// The attributes in effect, those written as CSS by easy-sgr's `html::css`
+///
+/// Updated as easy-sgr's `StyleSet::apply_codes` does,
+/// the default colors being the same as no color
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct State {
+    foreground: Option<(u8, u8, u8)>,
+    background: Option<(u8, u8, u8)>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    inverse: bool,
+    hidden: bool,
+    strikethrough: bool,
+    framed: bool,
+    encircled: bool,
+    overline: bool,
+}
+impl State {
+    /// Updates the attributes as a terminal would once the codes are written
+    fn apply(&mut self, codes: &[u8]) {
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                8 => self.hidden = true,
+                9 => self.strikethrough = true,
+                51 => self.framed = true,
+                52 => self.encircled = true,
+                53 => self.overline = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                28 => self.hidden = false,
+                29 => self.strikethrough = false,
+                54 => (self.framed, self.encircled) = (false, false),
+                55 => self.overline = false,
+                30..=49 => {
+                    let color = match code % 10 {
+                        8 => {
+                            let mut next = || codes.next().unwrap_or(0);
+                            match next() {
+                                5 => Some(to_rgb(next())),
+                                2 => Some((next(), next(), next())),
+                                _ => continue,
+                            }
+                        }
+                        9 => None,
+                        n => Some(to_rgb(n)),
+                    };
+                    if code < 40 {
+                        self.foreground = color;
+                    } else {
+                        self.background = color;
+                    }
+                }
+                90..=97 => self.foreground = Some(to_rgb(code - 90 + 8)),
+                100..=107 => self.background = Some(to_rgb(code - 100 + 8)),
+                _ => (),
+            }
+        }
+    }
+    /// Returns the CSS of the attributes, the same as easy-sgr's `html::css`
+    fn css(&self) -> String {
+        let mut declarations = Vec::new();
+        let (foreground, background) = if self.inverse {
+            (self.background, self.foreground)
+        } else {
+            (self.foreground, self.background)
+        };
+        let rgb = |(r, g, b): (u8, u8, u8)| format!("rgb({r}, {g}, {b})");
+        if self.hidden {
+            declarations.push(String::from("color: transparent"));
+        } else if let Some(color) = foreground {
+            declarations.push(format!("color: {}", rgb(color)));
+        }
+        if let Some(color) = background {
+            declarations.push(format!("background-color: {}", rgb(color)));
+        }
+        for (set, declaration) in [
+            (self.bold, "font-weight: bold"),
+            (self.dim, "opacity: 0.5"),
+            (self.italic, "font-style: italic"),
+        ] {
+            if set {
+                declarations.push(String::from(declaration));
+            }
+        }
+        let lines: Vec<&str> = [
+            (self.underline, "underline"),
+            (self.strikethrough, "line-through"),
+            (self.overline, "overline"),
+        ]
+        .into_iter()
+        .filter_map(|(set, line)| set.then_some(line))
+        .collect();
+        if !lines.is_empty() {
+            declarations.push(format!("text-decoration: {}", lines.join(" ")));
+        }
+        if self.framed {
+            declarations.push(String::from("border: 1px solid"));
+        } else if self.encircled {
+            declarations.push(String::from("border: 1px solid; border-radius: 1em"));
+        }
+        declarations.join("; ")
+    }
+}
+/// Closes the open `<span>` & opens another when the CSS of the attributes changed
+///
+/// `open` is the CSS of the open `<span>`, empty when none is open
+fn place(state: &State, open: &mut String, buf: &mut String) {
+    let css = state.css();
+    if css == *open {
+        return;
+    }
+    if !open.is_empty() {
+        buf.push_str("</span>");
+    }
+    if !css.is_empty() {
+        buf.push_str("<span style=\"");
+        buf.push_str(&css);
+        buf.push_str("\">");
+    }
+    *open = css;
+}
+/// Turns a parsed format string into HTML, each change of the attributes opening a `<span>`
+///
+/// Text is escaped, while every format param is formatted through [`ESCAPE_ADAPTER`],
+/// i.e. `{name:>5}` becomes `{__easy_sgr_capture_0}`, with
+/// `::easy_sgr::html::HtmlEscape(::std::format_args!("{:>5}", (name)))` being returned.
+/// `captures` are the expressions lifted out of `s` by [`lift_captures`](crate::parse::lift_captures)
+///
+/// # Errors
+///
+/// A message is returned for parameters not known at compile time,
+/// dynamic colors & merged captures, and for arguments that are not inline, i.e. `{}` or `{0}`
+pub fn html_string(s: &str, captures: &[String]) -> Result<(String, Vec<String>), String> {
+    let mut buf = String::with_capacity(s.len());
+    let mut escaped = Vec::new();
+    let (mut state, mut open) = (State::default(), String::new());
+    let mut rest = s;
+    while let Some(ch) = rest.chars().next() {
+        if let Some(sequence) = rest.strip_prefix("\x1b[") {
+            let end = sequence.find('m').unwrap_or(sequence.len());
+            let params = &sequence[..end];
+            if params.contains('{') {
+                return Err(String::from(
+                    "Dynamic colors cannot be written as HTML, as they are not known at compile time",
+                ));
+            }
+            let codes: Vec<u8> = params
+                .split(';')
+                .map(|param| param.parse().unwrap_or(0))
+                .collect();
+            state.apply(&codes);
+            rest = sequence.get(end + 1..).unwrap_or_default();
+            continue;
+        }
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            place(&state, &mut open, &mut buf);
+            buf.push_str(&rest[..2]);
+            rest = &rest[2..];
+            continue;
+        }
+        if ch == '{' {
+            let end = matching_brace(rest).unwrap_or(rest.len() - 1);
+            let (arg, spec) = split_spec(&rest[1..end]);
+            let arg = arg.trim();
+            let expr = match arg.strip_prefix(CAPTURE_PREFIX) {
+                Some(i) => i
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| captures.get(i))
+                    .map_or(arg, String::as_str),
+                None if !arg.is_empty()
+                    && is_plain_arg(arg)
+                    && !arg.starts_with(char::is_numeric) =>
+                {
+                    arg
+                }
+                None => {
+                    return Err(format!(
+                    "format_html! only formats inline arguments, such as `{{name}}`, found `{}`",
+                    &rest[..=end]
+                ))
+                }
+            };
+            if expr.starts_with(MERGE_ADAPTER) {
+                return Err(String::from(
+                    "Merged captures cannot be written as HTML, as they write escapes",
+                ));
+            }
+            place(&state, &mut open, &mut buf);
+            buf.push('{');
+            buf.push_str(CAPTURE_PREFIX);
+            buf.push_str(&escaped.len().to_string());
+            buf.push('}');
+            escaped.push(format!(
+                "{ESCAPE_ADAPTER}(::std::format_args!({:?}, ({expr})))",
+                format!("{{{spec}}}")
+            ));
+            rest = &rest[end + 1..];
+            continue;
+        }
+        place(&state, &mut open, &mut buf);
+        match ch {
+            '&' => buf.push_str("&amp;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            '"' => buf.push_str("&quot;"),
+            '\'' => buf.push_str("&#39;"),
+            ch => buf.push(ch),
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+    if !open.is_empty() {
+        buf.push_str("</span>");
+    }
+    Ok((buf, escaped))
+}
@@ -16,13 +16,24 @@
 #![allow(clippy::enum_glob_use)]
 
 use parse::Error;
+use std::iter::Peekable;
+
 use proc_macro::{
     token_stream::IntoIter, Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream,
     TokenTree,
 };
 
-use crate::parse::{create_raw_string, sgr_string, unwrap_string, UnwrappedLiteral};
+use crate::html::html_string;
+use crate::parse::{
+    create_raw_string, keyword_manifest as manifest, lift_captures, sgr_string, static_string,
+    stripped_string, unsupported_literal, unwrap_string, UnwrappedLiteral, CAPTURE_PREFIX,
+};
 
+/// Compares the parser against the checked-in outputs within `tests/golden`
+#[cfg(test)]
+mod golden;
+/// Turns parsed strings into HTML, for `format_html`
+mod html;
 /// Contains strictly string parsing implementation
 mod parse;
 #[cfg(test)]
@@ -182,6 +193,22 @@ def_macros!(
     ///println!("{}", args);
     ///```
     format_args : FormatArgs,
+    /// Creates a String of HTML using interpolation of runtime expressions,
+    /// SGR keywords substituted by `<span>`s styled with their CSS.
+    ///
+    /// Text is HTML escaped at compile time, format params at runtime
+    /// through `easy_sgr::html::HtmlEscape`, so only inline arguments may be used.
+    /// The CSS is that of `easy_sgr::html::css`
+    ///
+    /// # Examples
+    ///
+    ///```rust,ignore
+    ///# use easy_sgr::format_html;
+    ///let name = "<b>";
+    ///let html = format_html!("{[bold]}hi {name}{[]}!");
+    ///assert_eq!(html, r#"<span style="font-weight: bold">hi &lt;b&gt;</span>!"#);
+    ///```
+    format_html : FormatHtml,
     /// Creates a string literal,
     /// SGR keywords substituted.
     ///
@@ -191,8 +218,48 @@ def_macros!(
     ///# use easy_sgr_macros::sgr;
     ///let my_string = sgr!("{[italic red]}This should be italic & red!{[]}");
     ///```
-    sgr : Sgr
+    sgr : Sgr,
+    /// Creates a string literal that is the same as the formatted string,
+    /// SGR keywords substituted.
+    ///
+    /// Unlike [`sgr!`], `{{` & `}}` are unescaped,
+    /// and any format param such as `{}` or `{name}` is a compile error
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///# use easy_sgr_macros::sgr_static;
+    ///const PROMPT: &str = sgr_static!("{[bold green]}>{[]} {{}}");
+    ///assert_eq!(PROMPT, "\x1b[1;32m>\x1b[0m {}");
+    ///```
+    sgr_static : SgrStatic
 );
+/// Creates a JSON str literal listing every keyword, its category & its codes
+///
+/// Made from the same table the other macros parse keywords with,
+/// so editor tooling can offer completions:
+///
+///```json
+///{"keywords":[{"keyword":"reset","category":"style","codes":[0]},..]}
+///```
+///
+/// Complex colors such as `208` or `on-#ff0000` are not listed.
+/// Takes no arguments
+///
+/// # Examples
+///
+///```rust
+///# use easy_sgr_macros::keyword_manifest;
+///const MANIFEST: &str = keyword_manifest!();
+///assert!(MANIFEST.contains(r#"{"keyword":"bold","category":"style","codes":[1]}"#));
+///```
+#[proc_macro]
+pub fn keyword_manifest(input: TokenStream) -> TokenStream {
+    input.into_iter().next().map_or_else(
+        || Literal::string(&manifest()).to_stream(),
+        |token| compile_error(token.span(), "keyword_manifest! takes no arguments"),
+    )
+}
 /// The type of macro
 ///
 /// Is used to differentiate how to go about parsing
@@ -204,25 +271,27 @@ enum MacroKind {
     EPrintln,
     Format,
     FormatArgs,
+    FormatHtml,
     Print,
     Println,
     Sgr,
+    SgrStatic,
     Write,
     Writeln,
 }
 impl MacroKind {
     /// Returns the name of the macro variant,
-    /// or in the case of [`MacroKind::Sgr`] returning an empty string.
+    /// or in the case of [`MacroKind::Sgr`] & [`MacroKind::SgrStatic`] returning an empty string.
     const fn name(&self) -> &str {
         use MacroKind::*;
         match self {
             EPrint => "eprint",
             EPrintln => "eprintln",
-            Format => "format",
+            Format | FormatHtml => "format",
             FormatArgs => "format_args",
             Print => "print",
             Println => "println",
-            Sgr => "",
+            Sgr | SgrStatic => "",
             Write => "write",
             Writeln => "writeln",
         }
@@ -254,52 +323,159 @@ impl MacroKind {
 /// means that parsing an empty [`TokenStream`] like in the other method may lead to the
 /// error not being reported. Therefore a string will still be returned to some capacity,
 /// which will then be picked up by the compiler to report the relevant error.
+///
+/// When the input is prefixed by a condition, `if <condition>;`,
+/// both a styled and stripped expansion are created: see [`build_conditional`]
 fn build_macro(kind: MacroKind, input: TokenStream) -> TokenStream {
-    match kind {
-        MacroKind::Sgr => match build_args::<true>(kind, input) {
+    match split_condition(input) {
+        // the returned fmt::Arguments would not outlive the branch it was created in
+        Ok((Some(_), _)) if kind == MacroKind::FormatArgs => compile_error(
+            Span::mixed_site(),
+            "a condition cannot be used within format_args",
+        ),
+        Ok((Some(condition), input)) => match expand(kind, input.clone(), false) {
+            Ok(styled) => match expand(kind, input, true) {
+                Ok(stripped) => build_conditional(condition, styled, stripped),
+                Err(tokens) => tokens,
+            },
+            Err(tokens) => tokens,
+        },
+        Ok((None, input)) => match expand(kind, input, false) {
             Ok(tokens) | Err(tokens) => tokens,
         },
-        _ => match build_args::<false>(kind, input) {
-            Ok(tokens) | Err(tokens) => create_macro(kind.name(), Span::mixed_site(), tokens),
+        Err(tokens) => tokens,
+    }
+}
+/// Expands the macro according to the given [`MacroKind`]
+///
+/// `strip` indicates whether SGR keywords are to be left out of the string literal
+///
+/// # Errors
+///
+/// An `Err(TokenStream)` is returned when an error is found,
+/// see [`build_macro`]
+fn expand(kind: MacroKind, input: TokenStream, strip: bool) -> Result<TokenStream, TokenStream> {
+    match kind {
+        MacroKind::Sgr => build_args::<true>(kind, input, strip),
+        MacroKind::SgrStatic => build_args::<false>(kind, input, strip),
+        _ => match build_args::<false>(kind, input, strip) {
+            Ok(tokens) => Ok(create_macro(kind.name(), Span::mixed_site(), tokens)),
+            Err(tokens) => Err(create_macro(kind.name(), Span::mixed_site(), tokens)),
         },
     }
 }
+/// Splits the condition, `if <condition>;`, from the start of the given [`TokenStream`]
+///
+/// Returns `None` in place of the condition when the input does not start with `if`
+///
+/// # Errors
+///
+/// A [`compile_error`] is returned when the condition is empty or not followed by a `;`
+fn split_condition(input: TokenStream) -> Result<(Option<TokenStream>, TokenStream), TokenStream> {
+    let mut tokens = input.clone().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "if" => (),
+        _ => return Ok((None, input)),
+    }
+    let mut condition = Vec::new();
+    for token in tokens.by_ref() {
+        match token {
+            TokenTree::Punct(p) if p == ';' && condition.is_empty() => {
+                return Err(compile_error(p.span(), "missing condition"))
+            }
+            TokenTree::Punct(p) if p == ';' => {
+                return Ok((Some(condition.to_stream()), tokens.collect()))
+            }
+            _ => condition.push(token),
+        }
+    }
+    Err(compile_error(
+        Span::mixed_site(),
+        "expected `;` after condition",
+    ))
+}
+/// Creates the runtime branch selecting between the styled and stripped expansions:
+///
+/// ```plain
+/// if (<condition>) { <styled> } else { <stripped> }
+/// ```
+fn build_conditional(
+    condition: TokenStream,
+    styled: TokenStream,
+    stripped: TokenStream,
+) -> TokenStream {
+    build_stream!( from_trees
+        Ident::new("if", Span::mixed_site()),
+        Group::new(Delimiter::Parenthesis, condition),
+        Group::new(Delimiter::Brace, styled),
+        Ident::new("else", Span::mixed_site()),
+        Group::new(Delimiter::Brace, stripped)
+    )
+}
 /// Builds the arguments that should be within the returned macro call
 ///
 /// In the case of [`MacroKind::Sgr`] this would just be a string literal,
 /// else some kind of error.
 ///
+/// `strip` indicates whether SGR keywords are to be left out of the string literal
+///
 /// # Errors
 ///
 /// See [`StreamParts::from_parts`]
 fn build_args<const MERGE_CURLY: bool>(
     kind: MacroKind,
     input: TokenStream,
+    strip: bool,
 ) -> Result<TokenStream, TokenStream> {
     let tokens = input.into_iter();
-    let stream = match StreamParts::from_parts(kind, tokens) {
-        Ok(stream) => stream,
-        Err(tokens) => return Err(tokens),
-    };
+    let stream = StreamParts::from_parts(kind, tokens)?;
+
+    if let StreamKind::Standard(fragments) | StreamKind::Writer(_, Some((_, fragments))) =
+        &stream.kind
+    {
+        for literal in &fragments.literals {
+            if let Some(unsupported) = unsupported_literal(&literal.to_string()) {
+                let name = match kind {
+                    MacroKind::Sgr => "sgr",
+                    MacroKind::SgrStatic => "sgr_static",
+                    _ => kind.name(),
+                };
+                return Err(compile_error(
+                    literal.span(),
+                    &std::format!("{unsupported} are not supported by {name}!, use a str literal"),
+                ));
+            }
+        }
+    }
 
     let (literal, parsed_literal) = match &stream.kind {
-        StreamKind::Standard(literal) | StreamKind::Writer(_, Some((_, literal))) => (
-            Some(literal),
-            unwrap_string(&literal.to_string()).map_or_else(
-                || ParsedLiteral::InvalidToken(TokenTree::from(literal.clone())),
-                |unwrapped| ParsedLiteral::parse::<MERGE_CURLY>(&unwrapped),
-            ),
-        ),
+        StreamKind::Standard(fragments) | StreamKind::Writer(_, Some((_, fragments))) => {
+            let (literal, parsed_literal) = fragments.parse::<MERGE_CURLY>(strip);
+            (Some(literal), parsed_literal)
+        }
         StreamKind::Writer(_, None) | StreamKind::Empty => (None, ParsedLiteral::Empty),
     };
     Ok(match parsed_literal {
-        ParsedLiteral::String(literal) => match stream.kind {
-            StreamKind::Writer(writer, Some((punct, _))) => {
-                build_stream!(writer, punct, literal, stream.tokens)
+        ParsedLiteral::String(output, captures) => {
+            // formatting diagnostics then point at the inputted literal
+            let span = literal.map_or_else(Span::mixed_site, Literal::span);
+            let (output, captures) = match kind {
+                MacroKind::SgrStatic => (static_literal(&output, &captures, span)?, captures),
+                MacroKind::FormatHtml => html_string(&output, &captures)
+                    .map_err(|message| compile_error(span, &message))?,
+                _ => (output, captures),
+            };
+            let mut literal = Literal::string(&output);
+            literal.set_span(span);
+            let tokens = append_captures(stream.tokens, &captures, span)?;
+            match stream.kind {
+                StreamKind::Writer(writer, Some((punct, _))) => {
+                    build_stream!(writer, punct, literal, tokens)
+                }
+                StreamKind::Writer(writer, None) => build_stream!(writer, literal, tokens),
+                _ => build_stream!(literal, tokens),
             }
-            StreamKind::Writer(writer, None) => build_stream!(writer, literal, stream.tokens),
-            _ => build_stream!(literal, stream.tokens),
-        },
+        }
         ParsedLiteral::RawString(string) => {
             build_stream!(
                 match stream.kind {
@@ -318,17 +494,67 @@ fn build_args<const MERGE_CURLY: bool>(
                 build_stream!(writer, punct, stream.tokens)
             }
             StreamKind::Writer(writer, None) => build_stream!(writer),
-            _ if kind == MacroKind::Sgr => {
+            _ if matches!(kind, MacroKind::Sgr | MacroKind::SgrStatic) => {
                 return Err(compile_error(Span::mixed_site(), "missing string literal"))
             }
             _ => build_stream!(),
         },
     })
 }
+/// Appends the expressions lifted by [`lift_captures`] to the tokens as named arguments
+///
+/// # Errors
+///
+/// A [`compile_error`] is returned when an expression cannot be tokenized
+fn append_captures(
+    tokens: Peekable<IntoIter>,
+    captures: &[std::string::String],
+    span: Span,
+) -> Result<TokenStream, TokenStream> {
+    let mut tokens: Vec<TokenTree> = tokens.collect();
+    for (i, capture) in captures.iter().enumerate() {
+        let expr: TokenStream = capture.parse().map_err(|_| {
+            compile_error(
+                span,
+                &std::format!("invalid expression in format string: `{capture}`"),
+            )
+        })?;
+        if !matches!(tokens.last(), Some(TokenTree::Punct(p)) if *p == ',') {
+            tokens.push(Punct::new(',', Spacing::Alone).into());
+        }
+        tokens.push(Ident::new(&std::format!("{CAPTURE_PREFIX}{i}"), Span::mixed_site()).into());
+        tokens.push(Punct::new('=', Spacing::Alone).into());
+        tokens.push(Group::new(Delimiter::Parenthesis, expr).into());
+    }
+    Ok(tokens.to_stream())
+}
+/// Unescapes the format string of [`MacroKind::SgrStatic`]
+///
+/// # Errors
+///
+/// A [`compile_error`] is returned when a capture or format param is found
+fn static_literal(
+    output: &str,
+    captures: &[std::string::String],
+    span: Span,
+) -> Result<std::string::String, TokenStream> {
+    let param = match captures.first() {
+        Some(capture) => std::format!("{{{capture}}}"),
+        None => match static_string(output) {
+            Ok(s) => return Ok(s),
+            Err(param) => param.to_owned(),
+        },
+    };
+    Err(compile_error(
+        span,
+        &std::format!("sgr_static! cannot format arguments, found `{param}`"),
+    ))
+}
 enum ParsedLiteral {
-    String(Literal),
+    /// The parsed string & the expressions lifted out of it
+    String(std::string::String, Vec<std::string::String>),
     RawString(TokenStream),
-    InvalidToken(TokenTree),
+    InvalidToken(TokenStream),
     InvalidString(Error),
     Empty,
 }
@@ -337,7 +563,9 @@ impl ParsedLiteral {
     ///
     /// A constant bool is used to indicate whether to merge open curly brackets.
     /// This means wether `{{` should be turned into `{` or `{{`
-    fn parse<const MERGE_CURLY: bool>(unwrapped: &UnwrappedLiteral) -> Self {
+    ///
+    /// `strip` indicates whether SGR keywords are to be left out
+    fn parse<const MERGE_CURLY: bool>(unwrapped: &UnwrappedLiteral, strip: bool) -> Self {
         use UnwrappedLiteral::*;
         let check_curly = |ch| match ch {
             '}' => Some("{}"),
@@ -345,25 +573,182 @@ impl ParsedLiteral {
             _ => None,
         };
         match unwrapped {
-            String(s) => match sgr_string(s, check_curly) {
-                Ok(s) => Self::String(Literal::string(&s)),
+            String(s) => match if strip {
+                stripped_string(s, check_curly)
+            } else {
+                sgr_string(s, check_curly)
+            } {
+                // sgr creates a plain str, so there is nothing to capture
+                Ok(s) if MERGE_CURLY => Self::String(s, Vec::new()),
+                Ok(s) => {
+                    let (s, captures) = lift_captures(&s);
+                    Self::String(s, captures.into_iter().map(ToOwned::to_owned).collect())
+                }
                 Err(e) => Self::InvalidString(e),
             },
-            // using FromStr is the only way to return a raw string
-            RawString(s, i) => Self::RawString(
+            RawString(s, i) => {
+                // raw strings cannot escape the inserted ESC, so those holding
+                // params, i.e. parsed differently when stripped, become str literals
+                let escaped = escaped_contents(*unwrapped);
+                if sgr_string(&escaped, check_curly) != stripped_string(&escaped, check_curly) {
+                    return Self::parse::<MERGE_CURLY>(&String(&escaped), strip);
+                }
+                // using FromStr is the only way to return a raw string,
+                // if it somehow fails the original literal is left to the compiler
                 create_raw_string(s, *i)
                     .parse()
-                    .expect("Raw string parsing failed, should never fail"),
-            ),
+                    .map_or(Self::InvalidString(Error::CompilerPassOff), Self::RawString)
+            }
         }
     }
 }
+/// The string literal of a macro call
+///
+/// Made up of either a single literal, adjacent literals
+/// or literals within `concat!(..)`
+#[derive(Debug)]
+struct Fragments {
+    /// The literals, in order
+    literals: Vec<Literal>,
+    /// The original tokens, returned when the literals cannot be merged
+    source: TokenStream,
+}
+impl Fragments {
+    /// Takes the fragments from the start of the given tokens
+    ///
+    /// Returns `Ok(None)` when there are no tokens left
+    ///
+    /// # Errors
+    ///
+    /// The consumed tokens are returned when they do not form fragments,
+    /// i.e. `concat!` containing something other than literals
+    fn take(tokens: &mut Peekable<IntoIter>) -> Result<Option<Self>, TokenStream> {
+        match tokens.next() {
+            Some(first) if literal_of(&first).is_some() => {
+                let mut fragments = vec![first];
+                while let Some(token) = tokens.next_if(|token| literal_of(token).is_some()) {
+                    fragments.push(token);
+                }
+                let literals = fragments.iter().filter_map(literal_of).collect();
+                let source = fragments.into_iter().collect();
+                Ok(Some(Self { literals, source }))
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "concat" => {
+                Self::concat(ident, tokens).map(Some)
+            }
+            Some(t) => Err(build_stream!(t)),
+            None => Ok(None),
+        }
+    }
+    /// Takes the literals from `concat!(..)`, `ident` being `concat`
+    ///
+    /// # Errors
+    ///
+    /// See [`Fragments::take`]
+    fn concat(ident: Ident, tokens: &mut Peekable<IntoIter>) -> Result<Self, TokenStream> {
+        let punct = match tokens.next() {
+            Some(TokenTree::Punct(p)) if p == '!' => p,
+            Some(t) => return Err(build_stream!(ident, t)),
+            None => return Err(build_stream!(ident)),
+        };
+        let group = match tokens.next() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => g,
+            Some(t) => return Err(build_stream!(ident, punct, t)),
+            None => return Err(build_stream!(ident, punct)),
+        };
+        let source = build_stream!(ident, punct, group.clone());
+        let mut literals = Vec::new();
+        let mut inner = group.stream().into_iter();
+        // literals separated by commas, a trailing comma allowed
+        while let Some(token) = inner.next() {
+            match (literal_of(&token), inner.next()) {
+                (Some(literal), None) => literals.push(literal),
+                (Some(literal), Some(TokenTree::Punct(p))) if p == ',' => {
+                    literals.push(literal);
+                }
+                _ => return Err(source),
+            }
+        }
+        if literals.is_empty() {
+            return Err(source);
+        }
+        Ok(Self { literals, source })
+    }
+    /// Merges & parses the fragments
+    ///
+    /// Returns the literal that errors should be reported against,
+    /// for multiple fragments this is the first where parsing fails
+    fn parse<const MERGE_CURLY: bool>(&self, strip: bool) -> (&Literal, ParsedLiteral) {
+        let parse =
+            |s: &str| ParsedLiteral::parse::<MERGE_CURLY>(&UnwrappedLiteral::String(s), strip);
+        let first = &self.literals[0];
+        if self.literals.len() == 1 {
+            return (
+                first,
+                unwrap_string(&first.to_string()).map_or_else(
+                    || ParsedLiteral::InvalidToken(self.source.clone()),
+                    |unwrapped| ParsedLiteral::parse::<MERGE_CURLY>(&unwrapped, strip),
+                ),
+            );
+        }
+        let Some(contents) = self
+            .literals
+            .iter()
+            .map(|literal| unwrap_string(&literal.to_string()).map(escaped_contents))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return (first, ParsedLiteral::InvalidToken(self.source.clone()));
+        };
+        match parse(&contents.concat()) {
+            ParsedLiteral::InvalidString(e) => {
+                // the first fragment where parsing fails is reported
+                let failed = (1..contents.len())
+                    .find(|&i| {
+                        matches!(
+                            parse(&contents[..i].concat()),
+                            ParsedLiteral::InvalidString(_)
+                        )
+                    })
+                    .unwrap_or(contents.len());
+                (&self.literals[failed - 1], ParsedLiteral::InvalidString(e))
+            }
+            parsed => (first, parsed),
+        }
+    }
+}
+/// Returns the literal of a token, if it is one
+///
+/// Literals forwarded by `macro_rules!`, i.e. through `$lit:literal`,
+/// arrive wrapped in invisible groups, these are unwrapped.
+/// The literal keeps its own span, so errors point at where it was written
+fn literal_of(token: &TokenTree) -> Option<Literal> {
+    match token {
+        TokenTree::Literal(literal) => Some(literal.clone()),
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            let mut inner = group.stream().into_iter();
+            match (inner.next(), inner.next()) {
+                (Some(token), None) => literal_of(&token),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+/// Returns the contents of an [`UnwrappedLiteral`] as they would be within a str literal
+///
+/// Raw strings have their backslashes & quotes escaped
+fn escaped_contents(unwrapped: UnwrappedLiteral) -> String {
+    match unwrapped {
+        UnwrappedLiteral::String(s) => s.to_owned(),
+        UnwrappedLiteral::RawString(s, _) => s.replace('\\', r"\\").replace('"', r#"\""#),
+    }
+}
 /// A [`TokenStream`] split up into the needed parts
 struct StreamParts {
     /// The type of stream needed depending on the inputted [`MacroKind`]
     kind: StreamKind,
     /// The remaining tokens
-    tokens: IntoIter,
+    tokens: Peekable<IntoIter>,
 }
 impl StreamParts {
     /// Disassembles the given tokens
@@ -376,7 +761,8 @@ impl StreamParts {
     /// An `Err(TokenStream)` is returned when the inputted tokens are invalid
     /// in the context of the given [`MacroKind`]. This [`TokenStream`] is made
     /// up of the `TokenTrees` of the inputted tokens
-    fn from_parts(kind: MacroKind, mut tokens: IntoIter) -> Result<Self, TokenStream> {
+    fn from_parts(kind: MacroKind, tokens: IntoIter) -> Result<Self, TokenStream> {
+        let mut tokens = tokens.peekable();
         Ok(Self {
             kind: match StreamKind::from_kind(kind, &mut tokens) {
                 Ok(stream_kind) => stream_kind,
@@ -393,12 +779,12 @@ impl StreamParts {
 #[derive(Debug)]
 enum StreamKind {
     /// For one of
-    /// `EPrint | EPrintln | Format | FormatArgs | Print | Println | Sgr`
-    Standard(Literal),
+    /// `EPrint | EPrintln | Format | FormatArgs | Print | Println | Sgr | SgrStatic`
+    Standard(Fragments),
     /// For one of `Write | Writeln`
     ///
-    /// `1` will be `None` when either the [`Punct`] & [`Literal`] are not found
-    Writer(Vec<TokenTree>, Option<(Punct, Literal)>),
+    /// `1` will be `None` when either the [`Punct`] & [`Fragments`] are not found
+    Writer(Vec<TokenTree>, Option<(Punct, Fragments)>),
     /// For all variants of [`MacroKind`]
     Empty,
 }
@@ -413,19 +799,17 @@ impl StreamKind {
     /// in the context of the given [`MacroKind`]. This [`TokenStream`] is made
     /// up of the `TokenTrees` collected up until that point, any tokens remaining
     /// in `tokens` are ignored
-    fn from_kind(kind: MacroKind, tokens: &mut IntoIter) -> Result<Self, TokenStream> {
+    fn from_kind(kind: MacroKind, tokens: &mut Peekable<IntoIter>) -> Result<Self, TokenStream> {
         use MacroKind::*;
         use StreamKind::*;
         match kind {
-            EPrint | EPrintln | Format | FormatArgs | Print | Println | Sgr => {
-                match tokens.next() {
-                    Some(TokenTree::Literal(literal)) => Ok(Standard(literal)),
-                    Some(t) => Err(build_stream!(t)),
-                    None => Ok(Empty),
-                }
-            }
+            EPrint | EPrintln | Format | FormatArgs | FormatHtml | Print | Println | Sgr
+            | SgrStatic => Ok(Fragments::take(tokens)?.map_or(Empty, Standard)),
             Write | Writeln => {
-                fn find_punct(writer: &mut Vec<TokenTree>, tokens: &mut IntoIter) -> Option<Punct> {
+                fn find_punct(
+                    writer: &mut Vec<TokenTree>,
+                    tokens: &mut Peekable<IntoIter>,
+                ) -> Option<Punct> {
                     for token in tokens.by_ref() {
                         match token {
                             TokenTree::Punct(p) if p == ',' => {
@@ -438,12 +822,12 @@ impl StreamKind {
                 }
                 let mut writer = Vec::new();
                 let Some(punct) = find_punct(&mut writer, tokens) else {
-                    return Ok(Writer(writer, None))
+                    return Ok(Writer(writer, None));
                 };
-                match tokens.next() {
-                    Some(TokenTree::Literal(literal)) => Ok(Writer(writer, Some((punct, literal)))),
-                    Some(t) => Err(build_stream!(writer, punct, t)),
-                    None => Err(build_stream!(writer, punct)),
+                match Fragments::take(tokens) {
+                    Ok(Some(fragments)) => Ok(Writer(writer, Some((punct, fragments)))),
+                    Ok(None) => Err(build_stream!(writer, punct)),
+                    Err(t) => Err(build_stream!(writer, punct, t)),
                 }
             }
         }
@@ -463,12 +847,32 @@ pub(crate) fn create_macro(macro_call: &str, span: Span, stream: TokenStream) ->
 }
 /// creates a [`TokenStream`] of a [`std::compile_error`]
 /// with the given [`Span`] & message
+///
+/// Every token is given the span, so the error points at it
+/// rather than at the whole macro call
 pub(crate) fn compile_error(span: Span, message: &str) -> TokenStream {
     create_macro(
         "compile_error",
         span,
         build_stream!(Literal::string(message)),
     )
+    .into_iter()
+    .map(|mut token| {
+        if let TokenTree::Group(group) = &token {
+            let stream = group
+                .stream()
+                .into_iter()
+                .map(|mut token| {
+                    token.set_span(span);
+                    token
+                })
+                .collect();
+            token = TokenTree::Group(Group::new(group.delimiter(), stream));
+        }
+        token.set_span(span);
+        token
+    })
+    .collect()
 }
 impl Error {
     /// Turns self into a [`TokenStream`] of a [`compile_error`]
@@ -493,7 +897,25 @@ impl Error {
                 },
             ),
             MissingBracket => compile_error(span, "Missing a close bracket"),
+            UnclosedGroup => compile_error(span, "Unclosed style group, `{[>..]}` needs a `{[<]}`"),
+            UnopenedGroup => {
+                compile_error(span, "No style group to close, `{[<]}` needs a `{[>..]}`")
+            }
             InvalidColorLen => compile_error(span, "Incorrect number of digits found"),
+            InvalidHex => compile_error(span, "Invalid hex digit found"),
+            InvalidDynamic => compile_error(span, "Expected an expression within `(..)`"),
+            DynamicInGroup => compile_error(
+                span,
+                "Dynamic colors cannot be placed within a style group, `{[>..]}`",
+            ),
+            DynamicNotFormatted => compile_error(
+                span,
+                "Dynamic colors need formatting, use format! or format_args! in place of sgr!",
+            ),
+            DynamicInMerge => compile_error(
+                span,
+                "Dynamic colors cannot be merged into a capture, `{value&[..]}`",
+            ),
             CompilerPassOff => {
                 literal.map_or_else(|| build_stream!(), |literal| build_stream!(literal.clone()))
             }
@@ -556,6 +978,14 @@ impl StreamUnit for IntoIter {
         self.collect()
     }
 }
+impl StreamUnit for Peekable<IntoIter> {
+    fn extend_from_self(self, stream: &mut TokenStream) {
+        stream.extend(self);
+    }
+    fn to_stream(self) -> TokenStream {
+        self.collect()
+    }
+}
 impl StreamUnit for Vec<TokenTree> {
     fn extend_from_self(self, stream: &mut TokenStream) {
         stream.extend(self);
@@ -10,24 +10,40 @@ pub enum UnwrappedLiteral<'a> {
 }
 /// [Unwraps](UnwrappedLiteral) string, returning `None`
 /// when string is invalid
-pub fn unwrap_string(s: &str) -> Option<UnwrappedLiteral> {
+///
+/// For raw strings only the `#`s directly after the `r` are counted,
+/// the same number must follow the closing quote
+pub fn unwrap_string(s: &str) -> Option<UnwrappedLiteral<'_>> {
     use UnwrappedLiteral::*;
     match s.strip_prefix('r') {
         Some(s) => {
-            let len = s.as_bytes().len();
-            let s = s.trim_matches('#');
-            let diff = len - s.as_bytes().len();
-            if diff % 2 == 0 {
-                s.strip_prefix('"')?
-                    .strip_suffix('"')
-                    .map(|s| RawString(s, diff / 2))
-            } else {
-                None
+            let hashes = s.len() - s.trim_start_matches('#').len();
+            let s = s[hashes..].strip_prefix('"')?;
+            let (s, closing) = s.split_at_checked(s.len().checked_sub(hashes)?)?;
+            if !closing.bytes().all(|b| b == b'#') {
+                return None;
+            }
+            let s = s.strip_suffix('"')?;
+            // the content cannot contain the closing sequence
+            let terminator = format!("\"{closing}");
+            if s.contains(&terminator) {
+                return None;
             }
+            Some(RawString(s, hashes))
         }
         None => s.strip_prefix('"')?.strip_suffix('"').map(String),
     }
 }
+/// Returns the kind of a string literal that cannot hold SGR codes, if any
+///
+/// i.e. byte strings(`b".."`) or c strings(`c".."`)
+pub fn unsupported_literal(s: &str) -> Option<&'static str> {
+    match s.split_once(['"', '#'])?.0 {
+        "b" | "br" => Some("byte strings"),
+        "c" | "cr" => Some("c strings"),
+        _ => None,
+    }
+}
 /// Creates a raw string to be parsed and turned into a `TokenStream`
 pub fn create_raw_string(s: &str, i: usize) -> String {
     // add space for r#".."#
@@ -45,7 +61,14 @@ pub fn create_raw_string(s: &str, i: usize) -> String {
 pub enum Error {
     ParseInt(ParseIntError),
     MissingBracket,
+    UnclosedGroup,
+    UnopenedGroup,
     InvalidColorLen,
+    InvalidHex,
+    InvalidDynamic,
+    DynamicInGroup,
+    DynamicNotFormatted,
+    DynamicInMerge,
     CompilerPassOff,
 }
 impl From<ParseIntError> for Error {
@@ -63,17 +86,42 @@ impl From<ParseIntError> for Error {
 /// - Invalid escape
 /// - Unclosed bracket
 /// - Invalid keyword
+/// - Unclosed or unopened style group
 ///
-/// Invalid strings can also be occasionally returned with an Ok(), in
-/// these cases the string will continue being parsed> When returned the
+/// Invalid strings can also be occasionally returned with an `Ok(..)`, in
+/// these cases the string will continue being parsed. When returned the
 /// compiler is expected to deal with the error.
 /// The spots where these cases occur be annotated by the comment:
 /// `// INVALID HERE` or `INVALID RETURN` when continuing parsing is impossible
 pub fn sgr_string<F>(s: &str, check_curly: F) -> Result<String, Error>
+where
+    F: Fn(char) -> Option<&'static str>,
+{
+    parse_string(s, check_curly, false)
+}
+/// Removes escapes & SGR keywords, leaving a plain string
+///
+/// Keywords are still validated, so the same errors as
+/// [`sgr_string`] are returned
+///
+/// # Errors
+///
+/// See [`sgr_string`]
+pub fn stripped_string<F>(s: &str, check_curly: F) -> Result<String, Error>
+where
+    F: Fn(char) -> Option<&'static str>,
+{
+    parse_string(s, check_curly, true)
+}
+/// The implementation of [`sgr_string`] & [`stripped_string`]
+///
+/// `strip` indicates whether parsed keywords are to be left out
+fn parse_string<F>(s: &str, check_curly: F, strip: bool) -> Result<String, Error>
 where
     F: Fn(char) -> Option<&'static str>,
 {
     let mut buf = String::with_capacity(s.len());
+    let mut groups = Vec::new();
     let chars = &mut s.char_indices();
     let mut next: Option<(usize, char)> = chars.next();
 
@@ -83,7 +131,6 @@ where
             '\\' => {
                 if let Some(after_escape) = parse_escape(
                     chars.next().ok_or(Error::CompilerPassOff)?.1,
-                    s,
                     chars,
                     &mut buf,
                 )? {
@@ -91,7 +138,13 @@ where
                     continue;
                 }
             }
-            '{' => parse_param(chars.next(), s, chars, &mut buf, &check_curly)?,
+            '{' => parse_param(
+                chars.next(),
+                (s, chars),
+                (&mut buf, &mut groups),
+                &check_curly,
+                strip,
+            )?,
             '}' => match chars.next() {
                 Some((_, '}')) => buf.push_str("}}"),
                 // INVALID HERE
@@ -105,19 +158,22 @@ where
         }
         next = chars.next();
     }
-    Ok(buf)
+    if groups.is_empty() {
+        Ok(buf)
+    } else {
+        Err(Error::UnclosedGroup)
+    }
 }
 /// Checks the `char` after an escape
 ///
 /// # Returns
 ///
 /// - `Ok(Some(_))` when parsing has succeeded, returned value is
-/// meant to be used as the next `char` for parsing
+///   meant to be used as the next `char` for parsing
 /// - Ok(None) when parsing has succeeded, parsing to continue as normal
 /// - Err(Error) when an unrecoverable invalid string has been detected
 fn parse_escape(
     next_char: char,
-    s: &str,
     chars: &mut CharIndices,
     buf: &mut String,
 ) -> Result<Option<(usize, char)>, Error> {
@@ -126,14 +182,14 @@ fn parse_escape(
         '\'' => buf.push('\''),
         '"' => buf.push('"'),
         //ascii escapes
-        'x' => buf.push(parse_7bit(chars, s).ok_or(Error::CompilerPassOff)?),
+        'x' => buf.push(parse_7bit(chars).ok_or(Error::CompilerPassOff)?),
         'n' => buf.push('\n'),
         'r' => buf.push('\r'),
         't' => buf.push('\t'),
         '\\' => buf.push('\\'),
         '0' => buf.push('\0'),
         //unicode escape
-        'u' => buf.push(parse_24bit(chars, s).ok_or(Error::CompilerPassOff)?),
+        'u' => buf.push(parse_24bit(chars).ok_or(Error::CompilerPassOff)?),
         //whitespace ignore
         '\n' => {
             if let Some(non_whitespace) =
@@ -160,23 +216,25 @@ fn parse_escape(
 /// - `next_char`: the index, char pair after the opening brace
 /// - `s`: the full string to parse
 /// - `chars`: the string's `char_indices`,
-/// with `chars.next()` being the char after `next_char`
+///   with `chars.next()` being the char after `next_char`
 /// - `buf`: the string buf to append and return
+/// - `groups`: the style groups left open
 /// - `check_curly`: fn to check if char is curly
+/// - `strip`: whether to leave out parsed keywords
 ///
 /// `check_curly` is used since [`sgr`](super::sgr)
 /// follows different rules to the other macros
 ///
 fn parse_param(
     next_char: Option<(usize, char)>,
-    s: &str,
-    chars: &mut CharIndices,
-    buf: &mut String,
+    (s, chars): (&str, &mut CharIndices),
+    (buf, groups): (&mut String, &mut Vec<Group>),
     check_curly: impl Fn(char) -> Option<&'static str>,
+    strip: bool,
 ) -> Result<(), Error> {
     let Some((start, ch)) = next_char else {
         // INVALID HERE
-        buf.push( '{');
+        buf.push('{');
         return Ok(());
     };
     if let Some(s) = check_curly(ch) {
@@ -184,43 +242,406 @@ fn parse_param(
         return Ok(());
     }
 
+    // braces are balanced, so expressions may contain them
+    let mut depth = 1;
     // INVALID RETURN
-    let Some(end) = chars.find(|ch| ch.1 == '}') else {
-        buf.push_str( &s[start-1..]);
+    let Some(end) = chars.find(|&(_, ch)| {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => (),
+        }
+        depth == 0
+    }) else {
+        buf.push_str(&s[start - 1..]);
         return Ok(());
     };
     let end = end.0;
-    if ch == '[' {
-        buf.push_str("\x1b[");
-        for s in s[start + 1..end]
-            .strip_suffix(']')
-            .ok_or(Error::MissingBracket)?
-            .split_whitespace()
-        {
-            parse_sgr(s, buf)?;
-            buf.push(';');
+    // whitespace is allowed between the braces & brackets
+    if let Some(group) = s[start..end].trim().strip_prefix('[') {
+        let sgr_start = buf.len();
+        let group = keywords(group.strip_suffix(']').ok_or(Error::MissingBracket)?);
+        if group == "<" {
+            close_group(groups, buf)?;
+        } else if let Some(group) = group.strip_prefix('>') {
+            groups.push(open_group(group, buf)?);
+        } else {
+            buf.push_str("\x1b[");
+            for s in split_keywords(&group) {
+                if s.starts_with('(') {
+                    dynamic_color(s, buf, &check_curly)?;
+                } else {
+                    parse_sgr(s, buf)?;
+                }
+                buf.push(';');
+            }
+            // {[..]} if .. is empty it is parsed as reset
+            if buf.ends_with(';') {
+                buf.pop();
+            } else {
+                buf.push('0');
+            }
+            buf.push('m');
         }
-        // {[..]} if .. is empty it is parsed as reset
-        if buf.pop().unwrap() == '[' {
-            buf.push_str("[0");
+        // keywords are parsed regardless, so errors are still found
+        if strip {
+            buf.truncate(sgr_start);
         }
-        buf.push('m');
+    } else if let Some((expr, group)) = merged_capture(&s[start..end])
+        // only format strings, escaping `{` as `{{`, format params
+        .filter(|_| check_curly('{') == Some("{{"))
+    {
+        merge(expr, &keywords(group), buf, strip)?;
     } else {
         buf.push_str(&s[start - 1..=end]);
     }
     Ok(())
 }
+/// The path of the adapter a merged capture is formatted through
+pub const MERGE_ADAPTER: &str = "::easy_sgr::Merge::from_codes";
+/// Splits a merged capture, `expr&[..]`, into its expression & keywords
+///
+/// The `&[` must be outside of any brackets, its `]` ending the param
+fn merged_capture(param: &str) -> Option<(&str, &str)> {
+    let param = param.trim();
+    let group = param.strip_suffix(']')?;
+    let bytes = group.as_bytes();
+    let mut depth = 0usize;
+    let mut split = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth = depth.saturating_sub(1);
+                // the bracket after the split was closed before the end
+                if depth == 0 {
+                    split = None;
+                }
+            }
+            b'&' if depth == 0 && bytes.get(i + 1) == Some(&b'[') => split = Some(i),
+            _ => (),
+        }
+    }
+    let (expr, group) = group.split_at(split?);
+    let expr = expr.trim_end();
+    (!expr.is_empty() && !expr.ends_with('&')).then(|| (expr, &group[2..]))
+}
+/// Writes a merged capture as a format param of [`MERGE_ADAPTER`]
+///
+/// i.e. `{value&[bold red]}` becomes `{::easy_sgr::Merge::from_codes(&(value), &[1, 31])}`,
+/// or just `{value}` when stripping keywords
+fn merge(expr: &str, group: &str, buf: &mut String, strip: bool) -> Result<(), Error> {
+    let mut codes = String::new();
+    for s in split_keywords(group) {
+        if s.starts_with('(') {
+            return Err(Error::DynamicInMerge);
+        }
+        parse_sgr(s, &mut codes)?;
+        codes.push(';');
+    }
+    codes.pop();
+    buf.push('{');
+    if strip {
+        buf.push_str(expr);
+    } else {
+        buf.push_str(MERGE_ADAPTER);
+        buf.push_str("(&(");
+        buf.push_str(expr);
+        buf.push_str("), &[");
+        buf.push_str(&codes.replace(';', ", "));
+        buf.push_str("])");
+    }
+    buf.push('}');
+    Ok(())
+}
+/// The path of the adapter a dynamic color is formatted through
+const DYNAMIC_ADAPTER: &str = "::easy_sgr::DisplayParams";
+/// Writes a dynamic color, `(expr)`, as a format param of its parameters
+///
+/// i.e. `(color)` becomes `{::easy_sgr::DisplayParams(&(color))}`,
+/// the param later being lifted out by [`lift_captures`]
+fn dynamic_color(
+    s: &str,
+    buf: &mut String,
+    check_curly: impl Fn(char) -> Option<&'static str>,
+) -> Result<(), Error> {
+    // only format strings, escaping `{` as `{{`, can hold a param
+    if check_curly('{') != Some("{{") {
+        return Err(Error::DynamicNotFormatted);
+    }
+    if !s.ends_with(')') || s.len() == 2 {
+        return Err(Error::InvalidDynamic);
+    }
+    buf.push('{');
+    buf.push_str(DYNAMIC_ADAPTER);
+    buf.push_str("(&");
+    buf.push_str(s);
+    buf.push_str(")}");
+    Ok(())
+}
+/// The keywords of an open style group, each as its codes & the codes turning it off
+type Group = Vec<(String, &'static [u8])>;
+/// Writes the codes of a style group, `{[>..]}`, returning the opened group
+///
+/// Nothing is written when the group is empty
+fn open_group(group: &str, buf: &mut String) -> Result<Group, Error> {
+    let mut codes = String::new();
+    let mut opened = Vec::new();
+    for s in split_keywords(group) {
+        if s.starts_with('(') {
+            return Err(Error::DynamicInGroup);
+        }
+        let start = codes.len();
+        parse_sgr(s, &mut codes)?;
+        opened.push((codes[start..].to_string(), undo_codes(s)));
+        codes.push(';');
+    }
+    if codes.pop().is_some() {
+        buf.push_str("\x1b[");
+        buf.push_str(&codes);
+        buf.push('m');
+    }
+    Ok(opened)
+}
+/// Writes the codes turning off the innermost style group, `{[<]}`
+///
+/// Keywords of the outer groups that are turned off along with it are written again,
+/// such as the color of an outer group when the closed group changed it
+fn close_group(groups: &mut Vec<Group>, buf: &mut String) -> Result<(), Error> {
+    let closed = groups.pop().ok_or(Error::UnopenedGroup)?;
+    let mut undone: Vec<u8> = Vec::new();
+    for &code in closed.iter().flat_map(|(_, undo)| *undo) {
+        if !undone.contains(&code) {
+            undone.push(code);
+        }
+    }
+    let mut codes: Vec<String> = undone.iter().map(u8::to_string).collect();
+    for (placed, undo) in groups.iter().flatten() {
+        if undo.iter().any(|code| undone.contains(code)) {
+            codes.push(placed.clone());
+        }
+    }
+    if !codes.is_empty() {
+        buf.push_str("\x1b[");
+        buf.push_str(&codes.join(";"));
+        buf.push('m');
+    }
+    Ok(())
+}
+/// Returns the codes turning off a parsed keyword, empty when there are none
+///
+/// Keywords that already turn something off, such as `!bold` or `reset`, have none
+fn undo_codes(s: &str) -> &'static [u8] {
+    let Some(&(_, category, codes)) = KEYWORDS.iter().find(|(keyword, ..)| *keyword == s) else {
+        // complex colors
+        return if s.starts_with("on-") { &[49] } else { &[39] };
+    };
+    match (category, codes) {
+        (Category::Foreground, _) => &[39],
+        (Category::Background, _) => &[49],
+        (Category::Font, _) => &[10],
+        (Category::Style, [1 | 2]) => &[22],
+        (Category::Style, [3]) => &[23],
+        (Category::Style, [4]) => &[24],
+        (Category::Style, [5 | 6]) => &[25],
+        (Category::Style, [7]) => &[27],
+        (Category::Style, [8]) => &[28],
+        (Category::Style, [9]) => &[29],
+        (Category::Style, [51 | 52]) => &[54],
+        (Category::Style, [53]) => &[55],
+        #[cfg(feature = "extended")]
+        (Category::Extended, [26]) => &[50],
+        #[cfg(feature = "extended")]
+        (Category::Extended, [60..=64]) => &[65],
+        _ => &[],
+    }
+}
+/// The prefix of the named arguments created by [`lift_captures`]
+pub const CAPTURE_PREFIX: &str = "__easy_sgr_capture_";
+/// Lifts expressions out of format params, replacing them with named arguments
+///
+/// i.e. `{user.name():>8}` becomes `{__easy_sgr_capture_0:>8}`,
+/// with `user.name()` being returned.
+/// Params that are empty, an index or an identifier are left as is
+pub fn lift_captures(s: &str) -> (String, Vec<&str>) {
+    let mut buf = String::with_capacity(s.len());
+    let mut captures = Vec::new();
+    let mut rest = s;
+    while let Some(brace) = rest.find(['{', '}']) {
+        buf.push_str(&rest[..brace]);
+        rest = &rest[brace..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            buf.push_str(&rest[..2]);
+            rest = &rest[2..];
+            continue;
+        }
+        let Some(end) = matching_brace(rest) else {
+            break;
+        };
+        let (arg, spec) = split_spec(&rest[1..end]);
+        let arg = arg.trim();
+        if is_plain_arg(arg) {
+            buf.push_str(&rest[..=end]);
+        } else {
+            buf.push('{');
+            buf.push_str(CAPTURE_PREFIX);
+            buf.push_str(&captures.len().to_string());
+            buf.push_str(spec);
+            buf.push('}');
+            captures.push(arg);
+        }
+        rest = &rest[end + 1..];
+    }
+    buf.push_str(rest);
+    (buf, captures)
+}
+/// Turns a format string into the plain string it would format to
+///
+/// `{{` & `}}` become `{` & `}`
+///
+/// # Errors
+///
+/// The first format param is returned when one is found, i.e. `{}` or `{name}`
+pub fn static_string(s: &str) -> Result<String, &str> {
+    let mut buf = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(brace) = rest.find(['{', '}']) {
+        buf.push_str(&rest[..brace]);
+        rest = &rest[brace..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            buf.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        return Err(matching_brace(rest).map_or(rest, |end| &rest[..=end]));
+    }
+    buf.push_str(rest);
+    Ok(buf)
+}
+/// Finds the index of the brace closing the one at the start of `s`
+pub fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+/// Splits a format param into its argument & spec, the spec keeping its `:`
+///
+/// The spec starts at the first lone `:` outside of any brackets, so paths are kept
+pub fn split_spec(param: &str) -> (&str, &str) {
+    let bytes = param.as_bytes();
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b':' if depth == 0
+                && bytes.get(i + 1) != Some(&b':')
+                && (i == 0 || bytes[i - 1] != b':') =>
+            {
+                return param.split_at(i);
+            }
+            _ => (),
+        }
+    }
+    (param, "")
+}
+/// Whether a format argument can be used as is,
+/// i.e. it is empty, an index or an identifier
+pub fn is_plain_arg(arg: &str) -> bool {
+    let ident = arg.strip_prefix("r#").unwrap_or(arg);
+    arg.is_empty()
+        || arg.bytes().all(|b| b.is_ascii_digit())
+        || ident
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_alphabetic() || ch == '_')
+            && ident.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+}
+/// Splits the contents of a keyword group by whitespace,
+/// keeping dynamic colors, `(expr)`, whole
+fn split_keywords(group: &str) -> Vec<&str> {
+    let mut keywords = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, ch) in group.char_indices() {
+        match ch {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ch if ch.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    keywords.push(&group[start..i]);
+                }
+                continue;
+            }
+            _ => (),
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        keywords.push(&group[start..]);
+    }
+    keywords
+}
+/// Prepares the contents of a keyword group to be split by whitespace
+///
+/// Line continuations(`\` followed by a newline) become whitespace,
+/// and whitespace around commas is removed
+fn keywords(group: &str) -> String {
+    group
+        .replace("\\\r\n", " ")
+        .replace("\\\n", " ")
+        .split(',')
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(",")
+}
 /// Parses 7bit escape(`\x..`) into a char
-fn parse_7bit(chars: &mut CharIndices, s: &str) -> Option<char> {
-    let (end, _) = chars.nth(1)?;
-    let start = end - 1;
-    char::from_u32(u32::from_str_radix(&s[start..=end], 16).ok()?)
+///
+/// Returns `None` when the escape is cut short, contains
+/// non-hex digits or is above `\x7f`
+fn parse_7bit(chars: &mut CharIndices) -> Option<char> {
+    let high = chars.next()?.1.to_digit(16)?;
+    let low = chars.next()?.1.to_digit(16)?;
+    if high > 7 {
+        return None;
+    }
+    char::from_u32(high * 16 + low)
 }
-/// Parses 7bit escape(`\u{..}`) into a char
-fn parse_24bit(chars: &mut CharIndices, s: &str) -> Option<char> {
-    let (start, _) = chars.nth(1)?;
-    let (end, _) = chars.find(|ch| ch.1 == '}')?;
-    char::from_u32(u32::from_str_radix(&s[start..end], 16).ok()?)
+/// Parses 24bit escape(`\u{..}`) into a char
+///
+/// Returns `None` when the escape is cut short, is empty, has more than six digits,
+/// contains non-hex digits or is not a valid `char`, i.e. a surrogate
+fn parse_24bit(chars: &mut CharIndices) -> Option<char> {
+    if chars.next()?.1 != '{' {
+        return None;
+    }
+    let mut value = 0;
+    let mut digits = 0;
+    loop {
+        match chars.next()?.1 {
+            '}' if digits > 0 => break,
+            '_' if digits > 0 => (),
+            ch => {
+                value = value * 16 + ch.to_digit(16)?;
+                digits += 1;
+                if digits > 6 {
+                    return None;
+                }
+            }
+        }
+    }
+    char::from_u32(value)
 }
 /// Parses a SGR keyword from the inputted [`str`]
 ///
@@ -230,56 +651,167 @@ fn parse_24bit(chars: &mut CharIndices, s: &str) -> Option<char> {
 ///
 /// First [`parse_common`] is used, if it fails [`complex_color`] is used
 fn parse_sgr(s: &str, buf: &mut String) -> Result<(), Error> {
-    if let Some(n) = parse_common(s) {
-        n.append_to(buf);
+    if let Some(codes) = parse_common(s) {
+        for (i, n) in codes.iter().enumerate() {
+            if i != 0 {
+                buf.push(';');
+            }
+            n.append_to(buf);
+        }
         Ok(())
     } else {
         complex_color(s, buf)
     }
 }
-/// Parses common keywords
-fn parse_common(s: &str) -> Option<u8> {
-    match s {
+/// The category of a keyword, as listed by [`keyword_manifest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Style,
+    Font,
+    Undo,
+    Foreground,
+    Background,
+    #[cfg(feature = "extended")]
+    Extended,
+}
+impl Category {
+    /// Returns the name of the category within the manifest
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Style => "style",
+            Self::Font => "font",
+            Self::Undo => "undo",
+            Self::Foreground => "foreground",
+            Self::Background => "background",
+            #[cfg(feature = "extended")]
+            Self::Extended => "extended",
+        }
+    }
+}
+/// Every common keyword, its category & its codes
+///
+/// The only source of keywords, used by both [`parse_common`] & [`keyword_manifest`]
+pub const KEYWORDS: &[(&str, Category, &[u8])] = {
+    use Category::*;
+    &[
         // styles
-        "reset" => Some(0),
-        "bold" => Some(1),
-        "dim" => Some(2),
-        "italic" => Some(3),
-        "underline" => Some(4),
-        "blink" => Some(5),
-        "inverse" => Some(7),
-        "hide" => Some(8),
-        "strike" => Some(9),
+        ("reset", Style, &[0]),
+        ("bold", Style, &[1]),
+        ("dim", Style, &[2]),
+        ("italic", Style, &[3]),
+        ("underline", Style, &[4]),
+        ("blink", Style, &[5]),
+        ("rapid-blink", Style, &[6]),
+        ("inverse", Style, &[7]),
+        ("hide", Style, &[8]),
+        ("strike", Style, &[9]),
+        ("framed", Style, &[51]),
+        ("encircled", Style, &[52]),
+        ("overline", Style, &[53]),
+        // fonts
+        ("font0", Font, &[10]),
+        ("font1", Font, &[11]),
+        ("font2", Font, &[12]),
+        ("font3", Font, &[13]),
+        ("font4", Font, &[14]),
+        ("font5", Font, &[15]),
+        ("font6", Font, &[16]),
+        ("font7", Font, &[17]),
+        ("font8", Font, &[18]),
+        ("font9", Font, &[19]),
         // undo styles
-        "!bold" | "!dim" => Some(22),
-        "!italic" => Some(23),
-        "!underline" => Some(24),
-        "!blink" => Some(25),
-        "!inverse" => Some(27),
-        "!hide" => Some(28),
-        "!strike" => Some(29),
+        ("!bold", Undo, &[22]),
+        ("!dim", Undo, &[22]),
+        ("!italic", Undo, &[23]),
+        ("!underline", Undo, &[24]),
+        ("!blink", Undo, &[25]),
+        ("!inverse", Undo, &[27]),
+        ("!hide", Undo, &[28]),
+        ("!strike", Undo, &[29]),
+        ("!framed", Undo, &[54]),
+        ("!encircled", Undo, &[54]),
+        ("!overline", Undo, &[55]),
+        ("reset-colors", Undo, &[39, 49]),
+        ("reset-effects", Undo, &[22, 23, 24, 25, 27, 28, 29]),
         // foregrounds
-        "black" => Some(30),
-        "red" => Some(31),
-        "green" => Some(32),
-        "yellow" => Some(33),
-        "blue" => Some(34),
-        "magenta" => Some(35),
-        "cyan" => Some(36),
-        "white" => Some(37),
-        "default" => Some(39),
+        ("black", Foreground, &[30]),
+        ("red", Foreground, &[31]),
+        ("green", Foreground, &[32]),
+        ("yellow", Foreground, &[33]),
+        ("blue", Foreground, &[34]),
+        ("magenta", Foreground, &[35]),
+        ("cyan", Foreground, &[36]),
+        ("white", Foreground, &[37]),
+        ("default", Foreground, &[39]),
+        ("bright-black", Foreground, &[90]),
+        ("bright-red", Foreground, &[91]),
+        ("bright-green", Foreground, &[92]),
+        ("bright-yellow", Foreground, &[93]),
+        ("bright-blue", Foreground, &[94]),
+        ("bright-magenta", Foreground, &[95]),
+        ("bright-cyan", Foreground, &[96]),
+        ("bright-white", Foreground, &[97]),
         // backgrounds
-        "on-black" => Some(40),
-        "on-red" => Some(41),
-        "on-green" => Some(42),
-        "on-yellow" => Some(43),
-        "on-blue" => Some(44),
-        "on-magenta" => Some(45),
-        "on-cyan" => Some(46),
-        "on-white" => Some(47),
-        "on-default" => Some(49),
-        _ => None,
-    }
+        ("on-black", Background, &[40]),
+        ("on-red", Background, &[41]),
+        ("on-green", Background, &[42]),
+        ("on-yellow", Background, &[43]),
+        ("on-blue", Background, &[44]),
+        ("on-magenta", Background, &[45]),
+        ("on-cyan", Background, &[46]),
+        ("on-white", Background, &[47]),
+        ("on-default", Background, &[49]),
+        ("on-bright-black", Background, &[100]),
+        ("on-bright-red", Background, &[101]),
+        ("on-bright-green", Background, &[102]),
+        ("on-bright-yellow", Background, &[103]),
+        ("on-bright-blue", Background, &[104]),
+        ("on-bright-magenta", Background, &[105]),
+        ("on-bright-cyan", Background, &[106]),
+        ("on-bright-white", Background, &[107]),
+        // extended
+        #[cfg(feature = "extended")]
+        ("proportional", Extended, &[26]),
+        #[cfg(feature = "extended")]
+        ("!proportional", Extended, &[50]),
+        #[cfg(feature = "extended")]
+        ("ideogram-underline", Extended, &[60]),
+        #[cfg(feature = "extended")]
+        ("ideogram-double-underline", Extended, &[61]),
+        #[cfg(feature = "extended")]
+        ("ideogram-overline", Extended, &[62]),
+        #[cfg(feature = "extended")]
+        ("ideogram-double-overline", Extended, &[63]),
+        #[cfg(feature = "extended")]
+        ("ideogram-stress", Extended, &[64]),
+        #[cfg(feature = "extended")]
+        ("!ideogram", Extended, &[65]),
+    ]
+};
+/// Parses common keywords
+fn parse_common(s: &str) -> Option<&'static [u8]> {
+    KEYWORDS
+        .iter()
+        .find(|(keyword, ..)| *keyword == s)
+        .map(|&(.., codes)| codes)
+}
+/// Returns a JSON listing of every keyword within [`KEYWORDS`]
+///
+/// Formatted as `{"keywords":[{"keyword":"bold","category":"style","codes":[1]},..]}`,
+/// keywords never needing to be escaped
+pub fn keyword_manifest() -> String {
+    let entries: Vec<String> = KEYWORDS
+        .iter()
+        .map(|(keyword, category, codes)| {
+            let codes: Vec<String> = codes.iter().map(u8::to_string).collect();
+            format!(
+                r#"{{"keyword":"{keyword}","category":"{}","codes":[{}]}}"#,
+                category.name(),
+                codes.join(",")
+            )
+        })
+        .collect();
+    format!(r#"{{"keywords":[{}]}}"#, entries.join(","))
 }
 /// Parses more complex color configurations.
 ///
@@ -306,6 +838,10 @@ fn complex_color(s: &str, buf: &mut String) -> Result<(), Error> {
     buf.push_str(color_code);
 
     if let Some(s) = s.strip_prefix('#') {
+        // also ensures the digits can be sliced safely
+        if !s.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(Error::InvalidHex);
+        }
         match s.len() {
             2 => {
                 buf.push_str("5;");
@@ -1,4 +1,11 @@
-use crate::parse::{create_raw_string, sgr_string, unwrap_string, Error, UnwrappedLiteral};
+#![allow(
+    clippy::needless_raw_string_hashes,
+    clippy::literal_string_with_formatting_args
+)]
+use crate::parse::{
+    create_raw_string, keyword_manifest, lift_captures, sgr_string, static_string, stripped_string,
+    unsupported_literal, unwrap_string, Error, UnwrappedLiteral, KEYWORDS,
+};
 
 #[test]
 fn unwrap_str() {
@@ -18,6 +25,51 @@ fn unwrap_str() {
     }
 }
 
+#[test]
+fn unwrap_raw_str() {
+    use UnwrappedLiteral::*;
+    for (test, result) in [
+        (r#"r"""#, Some(RawString("", 0))),
+        (r#"r"text""#, Some(RawString("text", 0))),
+        (r##"r#"text"#"##, Some(RawString("text", 1))),
+        (r###"r##"text"##"###, Some(RawString("text", 2))),
+        // content beginning or ending with #
+        (r##"r"#RedFg text#""##, Some(RawString("#RedFg text#", 0))),
+        (r###"r#"#text#"#"###, Some(RawString("#text#", 1))),
+        (r####"r##"text#"##"####, Some(RawString("text#", 2))),
+        (r####"r##"##text"##"####, Some(RawString("##text", 2))),
+        // content containing a shorter closing sequence
+        (r####"r##"a"#b"##"####, Some(RawString(r##"a"#b"##, 2))),
+        (r###"r#"a"b"#"###, Some(RawString(r#"a"b"#, 1))),
+        // mismatched or invalid
+        (r###"r#"text"##"###, None),
+        (r###"r##"text"#"###, None),
+        (r##"r#"text""##, None),
+        (r##"r#"a"#b"#"##, None),
+        (r#"r""#, None),
+        ("r#", None),
+        ("r", None),
+    ] {
+        assert_eq!(unwrap_string(test), result, "{test}");
+    }
+}
+
+#[test]
+fn unsupported_literals() {
+    for (test, result) in [
+        (r#"b"bytes""#, Some("byte strings")),
+        (r##"br#"bytes"#"##, Some("byte strings")),
+        (r#"c"c string""#, Some("c strings")),
+        (r#"cr"c string""#, Some("c strings")),
+        (r#""text""#, None),
+        (r##"r#"text"#"##, None),
+        (r#"b'"'"#, None),
+        ("1", None),
+    ] {
+        assert_eq!(unsupported_literal(test), result, "{test}");
+    }
+}
+
 #[test]
 fn raw_string() {
     assert_eq!(create_raw_string("", 0), r#"r"""#);
@@ -40,7 +92,7 @@ fn sgr_string_general() {
         ("\x1b", Ok("\x1b")),
         ("\u{1f604} ☀ ☁ ☂", Ok("\u{1f604} ☀ ☁ ☂")),
     ] {
-        test_eq(test, result)
+        test_eq(test, result);
     }
 }
 
@@ -52,7 +104,7 @@ fn compiler_pass_off_undetected() {
         ("{with text", Ok("{with text")),
         ("with text{with text", Ok("with text{with text")),
     ] {
-        test_eq(test, result)
+        test_eq(test, result);
     }
 }
 #[test]
@@ -66,7 +118,7 @@ fn compiler_pass_off() {
         (r"\x", Err(CompilerPassOff)),
         (r"\x'", Err(CompilerPassOff)),
     ] {
-        test_eq(test, result)
+        test_eq(test, result);
     }
 }
 
@@ -94,7 +146,7 @@ fn skip_whitespace() {
             Ok("\n\r\t"),
         ),
     ] {
-        test_eq(test, result)
+        test_eq(test, result);
     }
 }
 
@@ -105,9 +157,50 @@ fn escapes() {
         "#,
         Ok("\' \" \x00 \n \r \t \\ \0 \u{0}\
         "),
-    )
+    );
 }
 
+#[test]
+fn escape_chars() {
+    for (test, result) in [
+        (r"\x41\x7f", "A\x7f"),
+        (r"\u{41}", "A"),
+        (r"\u{1F604}", "\u{1F604}"),
+        (r"\u{1_F6_04}", "\u{1F604}"),
+        (r"\u{10FFFF}", "\u{10FFFF}"),
+        (r"\u{00_0041}", "A"),
+    ] {
+        test_eq(test, Ok(result));
+    }
+}
+/// Malformed escapes, some of which used to panic
+#[test]
+fn malformed_escapes() {
+    for test in [
+        r"\x",
+        r"\x4",
+        r"\xg1",
+        r"\x80",
+        r"\xFF",
+        r"\xé1",
+        r"\x1é",
+        r"é\x",
+        r"\u",
+        r"\u{",
+        r"\u{}",
+        r"\u{_1}",
+        r"\u{12",
+        r"\u1234",
+        r"\u{zz}",
+        r"\u{é}",
+        r"\u{1234567}",
+        r"\u{D800}",
+        r"\u{DFFF}",
+        r"\u{110000}",
+    ] {
+        test_eq(test, Err(Error::CompilerPassOff));
+    }
+}
 #[test]
 fn curly_non_param() {
     test_eq("{{}} {{ { {", Ok("{{}} {{ { {"));
@@ -120,17 +213,279 @@ fn curly_var_param() {
     );
 }
 #[test]
+fn param_whitespace() {
+    for (test, result) in [
+        ("{ [bold] }", "\x1b[1m"),
+        ("{[  bold   red  ]}", "\x1b[1;31m"),
+        ("{\t[ bold ]\t}", "\x1b[1m"),
+        ("{[bold\n    red]}", "\x1b[1;31m"),
+        ("{[bold \\\n    red]}", "\x1b[1;31m"),
+        ("{[bold\\\r\n red]}", "\x1b[1;31m"),
+        ("{[ 0 , 0 ,0 on-1 ,2, 3 ]}", "\x1b[38;2;0;0;0;48;2;1;2;3m"),
+        ("{[0,\\\n 0,\n 0]}", "\x1b[38;2;0;0;0m"),
+        ("{ [] }", "\x1b[0m"),
+        // not keyword groups
+        ("{ }", "{ }"),
+        ("{ name }", "{ name }"),
+    ] {
+        test_eq(test, Ok(result));
+    }
+}
+#[test]
+fn captures() {
+    for (test, result, captures) in [
+        (
+            "{} {0} {name} {r#type} {:?} {name:>4}",
+            "{} {0} {name} {r#type} {:?} {name:>4}",
+            &[][..],
+        ),
+        ("{{user.name()}}", "{{user.name()}}", &[]),
+        ("{user.name()}", "{__easy_sgr_capture_0}", &["user.name()"]),
+        (
+            "{ a + b :>8} {v[0]}",
+            "{__easy_sgr_capture_0:>8} {__easy_sgr_capture_1}",
+            &["a + b", "v[0]"],
+        ),
+        (
+            "{Vec::<u8>::new().len()}",
+            "{__easy_sgr_capture_0}",
+            &["Vec::<u8>::new().len()"],
+        ),
+        (
+            "{(|| { 1 })():?}",
+            "{__easy_sgr_capture_0:?}",
+            &["(|| { 1 })()"],
+        ),
+        ("{a.b}{", "{__easy_sgr_capture_0}{", &["a.b"]),
+    ] {
+        let (lifted, lifted_captures) = lift_captures(test);
+        assert_eq!(lifted, result, "{test}");
+        assert_eq!(lifted_captures, captures, "{test}");
+    }
+}
+#[test]
+fn static_strings() {
+    for (test, result) in [
+        ("plain", Ok(String::from("plain"))),
+        ("{{a}} }}{{", Ok(String::from("{a} }{"))),
+        ("a {} b", Err("{}")),
+        ("{name:>4} {}", Err("{name:>4}")),
+        ("{(|| { 1 })()}", Err("{(|| { 1 })()}")),
+        ("trailing {", Err("{")),
+    ] {
+        assert_eq!(static_string(test), result, "{test}");
+    }
+}
+#[test]
+fn manifest() {
+    let manifest = keyword_manifest();
+    let listed: Vec<&str> = manifest
+        .split(r#"{"keyword":""#)
+        .skip(1)
+        .map(|entry| &entry[..entry.find('"').unwrap()])
+        .collect();
+    let keywords: Vec<&str> = KEYWORDS.iter().map(|(keyword, ..)| *keyword).collect();
+    assert_eq!(listed, keywords);
+    for (keyword, category, codes) in KEYWORDS {
+        let codes: Vec<String> = codes.iter().map(u8::to_string).collect();
+        // every listed keyword is parsed to its codes
+        test_eq(
+            &format!("{{[{keyword}]}}"),
+            Ok(&format!("\x1b[{}m", codes.join(";"))),
+        );
+        assert!(manifest.contains(&format!(
+            r#"{{"keyword":"{keyword}","category":"{}","codes":[{}]}}"#,
+            category.name(),
+            codes.join(",")
+        )));
+        assert_eq!(keywords.iter().filter(|k| *k == keyword).count(), 1);
+    }
+    // and every keyword parsed is listed
+    for keyword in ["bold", "!dim", "font9", "on-default", "strike"] {
+        assert!(listed.contains(&keyword), "{keyword}");
+    }
+    for not_keyword in ["strikethrough", "Bold", "on-bold", "font10"] {
+        assert!(!listed.contains(&not_keyword), "{not_keyword}");
+        assert!(sgr_string(&format!("{{[{not_keyword}]}}"), check_curly).is_err());
+    }
+}
+#[test]
 fn param_errors() {
     for test in [
         "{[not_a_var]}",
         "invalid len num{[#000]}",
         "no num{[#0]}",
+        "bad hex{[#0g]}",
+        "multibyte hex{[#0ă000]}",
         "comma error {[0,0]}",
         "bracket {[yeah}",
     ] {
         let result = sgr_string(test, check_curly);
-        assert!(result.is_err(), "Unexpected value: {result:#?}")
+        assert!(result.is_err(), "Unexpected value: {result:#?}");
+    }
+}
+#[test]
+fn groups() {
+    for (test, result) in [
+        // captures & literal text styled once
+        (
+            "{[>bold red]}{a} & {b}{[<]} plain",
+            "\x1b[1;31m{a} & {b}\x1b[22;39m plain",
+        ),
+        ("{[>italic on-15]}x{[ < ]}", "\x1b[3;48;5;15mx\x1b[23;49m"),
+        // outer attributes are placed again when turned off by the inner group
+        (
+            "{[>bold blue]}a{[>red dim]}b{[<]}c{[<]}",
+            "\x1b[1;34ma\x1b[31;2mb\x1b[39;22;1;34mc\x1b[22;39m",
+        ),
+        ("{[>underline]}a{[>]}b{[<]}c{[<]}", "\x1b[4mabc\x1b[24m"),
+        // keywords without an off code are not turned off
+        ("{[>!bold font3]}a{[<]}", "\x1b[22;13ma\x1b[10m"),
+        ("{[>reset]}a{[<]}", "\x1b[0ma"),
+    ] {
+        test_eq(test, Ok(result));
+    }
+    test_eq("{[>bold]}never closed", Err(Error::UnclosedGroup));
+    test_eq("{[>bold]}a{[>red]}b{[<]}", Err(Error::UnclosedGroup));
+    test_eq("{[<]}", Err(Error::UnopenedGroup));
+    test_eq("{[>bold]}a{[<]}{[<]}", Err(Error::UnopenedGroup));
+    assert_eq!(
+        stripped_string("{[>bold]}{a}{[<]}", check_curly),
+        Ok(String::from("{a}"))
+    );
+}
+#[test]
+fn dynamic_colors() {
+    for (test, result, captures) in [
+        (
+            "{[bold (color)]}x",
+            "\x1b[1;{__easy_sgr_capture_0}mx",
+            &["::easy_sgr::DisplayParams(&(color))"][..],
+        ),
+        (
+            "{[(colors[i % 2]) (pick(a, b))]}",
+            "\x1b[{__easy_sgr_capture_0};{__easy_sgr_capture_1}m",
+            &[
+                "::easy_sgr::DisplayParams(&(colors[i % 2]))",
+                "::easy_sgr::DisplayParams(&(pick(a,b)))",
+            ],
+        ),
+    ] {
+        let sgr = sgr_string(test, check_curly).unwrap();
+        let (lifted, lifted_captures) = lift_captures(&sgr);
+        assert_eq!(lifted, result, "{test}");
+        assert_eq!(lifted_captures, captures, "{test}");
     }
+    assert_eq!(
+        stripped_string("{[bold (color)]}x", check_curly),
+        Ok(String::from("x"))
+    );
+    test_eq("{[()]}", Err(Error::InvalidDynamic));
+    test_eq("{[(a)b]}", Err(Error::InvalidDynamic));
+    test_eq("{[>(color)]}x{[<]}", Err(Error::DynamicInGroup));
+    // sgr! creates a plain str, which cannot hold a param
+    assert_eq!(
+        sgr_string("{[(color)]}", |ch| (ch == '{').then_some("{")),
+        Err(Error::DynamicNotFormatted)
+    );
+}
+#[test]
+fn merged_captures() {
+    for (test, result, captures) in [
+        (
+            "{value&[bold red]}",
+            "{__easy_sgr_capture_0}",
+            &["::easy_sgr::Merge::from_codes(&(value), &[1, 31])"][..],
+        ),
+        (
+            "{ names[i]&[on-1, 2,3] }{a&[]}",
+            "{__easy_sgr_capture_0}{__easy_sgr_capture_1}",
+            &[
+                "::easy_sgr::Merge::from_codes(&(names[i]), &[48, 2, 1, 2, 3])",
+                "::easy_sgr::Merge::from_codes(&(a), &[])",
+            ],
+        ),
+        (
+            "{f(&[1])&[italic]}",
+            "{__easy_sgr_capture_0}",
+            &["::easy_sgr::Merge::from_codes(&(f(&[1])), &[3])"],
+        ),
+        // not merged, the `&[..]` does not end the param
+        ("{a&[1][0]}", "{__easy_sgr_capture_0}", &["a&[1][0]"]),
+    ] {
+        let sgr = sgr_string(test, check_curly).unwrap();
+        let (lifted, lifted_captures) = lift_captures(&sgr);
+        assert_eq!(lifted, result, "{test}");
+        assert_eq!(lifted_captures, captures, "{test}");
+    }
+    assert_eq!(
+        stripped_string("{value&[bold]}x", check_curly),
+        Ok(String::from("{value}x"))
+    );
+    test_eq("{a&[(color)]}", Err(Error::DynamicInMerge));
+    assert!(sgr_string("{a&[boldd]}", check_curly).is_err());
+    // sgr! leaves params as written
+    assert_eq!(
+        sgr_string("{a&[bold]}", |ch| (ch == '{').then_some("{")),
+        Ok(String::from("{a&[bold]}"))
+    );
+}
+#[test]
+fn html() {
+    let html = |s: &str| {
+        let sgr = sgr_string(s, check_curly).unwrap();
+        let (lifted, captures) = lift_captures(&sgr);
+        let captures: Vec<String> = captures.into_iter().map(String::from).collect();
+        crate::html::html_string(&lifted, &captures)
+    };
+    for (test, result, captures) in [
+        (
+            "{[bold red]}hi{[]}",
+            r#"<span style="color: rgb(128, 0, 0); font-weight: bold">hi</span>"#,
+            &[][..],
+        ),
+        ("a < b & {{c}}", "a &lt; b &amp; {{c}}", &[]),
+        (
+            "{[on-208 inverse]}{name:>5}{[!inverse underline]}{x.y()}",
+            concat!(
+                r#"<span style="color: rgb(255, 135, 0)">{__easy_sgr_capture_0}</span>"#,
+                r#"<span style="background-color: rgb(255, 135, 0); text-decoration: underline">"#,
+                "{__easy_sgr_capture_1}</span>"
+            ),
+            &[
+                r#"::easy_sgr::html::HtmlEscape(::std::format_args!("{:>5}", (name)))"#,
+                r#"::easy_sgr::html::HtmlEscape(::std::format_args!("{}", (x.y())))"#,
+            ],
+        ),
+        // attributes without CSS open no span
+        (
+            "{[blink]}a{[bold]}b",
+            r#"a<span style="font-weight: bold">b</span>"#,
+            &[],
+        ),
+        ("{[bold]}{[]}plain", "plain", &[]),
+    ] {
+        let (output, escaped) = html(test).unwrap();
+        assert_eq!(output, result, "{test}");
+        assert_eq!(escaped, captures, "{test}");
+    }
+    for test in ["{}", "{0}", "{:>5}", "{[(color)]}x", "{a&[bold]}"] {
+        assert!(html(test).is_err(), "{test}");
+    }
+}
+#[test]
+fn stripped() {
+    for (test, result) in [
+        ("{[]}", ""),
+        ("{[bold red]}text{[]} {var}", "text {var}"),
+        ("{{[]}} {[on-#0f73d7]}", "{{[]}} "),
+    ] {
+        assert_eq!(stripped_string(test, check_curly), Ok(result.to_string()));
+    }
+    assert_eq!(
+        stripped_string("{[#000]}", check_curly),
+        Err(Error::InvalidColorLen)
+    );
 }
 fn test_eq(test: &str, result: Result<&str, Error>) {
     match sgr_string(test, check_curly) {
@@ -139,7 +494,7 @@ fn test_eq(test: &str, result: Result<&str, Error>) {
             Err(result) => panic!("\"{test}\" does not eq {result:#?}"),
         },
         Err(test) => match result {
-            Ok(result) => panic!("{test:#?} does not eq {result}",),
+            Ok(result) => panic!("{test:#?} does not eq {result}"),
             Err(result) => assert_eq!(test, result),
         },
     }
@@ -152,3 +507,52 @@ fn check_curly(ch: char) -> Option<&'static str> {
         _ => None,
     }
 }
+/// Plain format params, passed through byte-for-byte
+mod format_specs {
+    use super::{check_curly, test_eq};
+    use crate::parse::{lift_captures, stripped_string};
+
+    const SPECS: [&str; 16] = [
+        "{}",
+        "{0}",
+        "{:>5}",
+        "{0:>8}",
+        "{name:<8}",
+        "{:+.3e}",
+        "{:#x}",
+        "{:#010b}",
+        "{:+}",
+        "{:#?}",
+        "{:width$}",
+        "{:width$.prec$}",
+        "{:1$}",
+        "{:.*}",
+        "{:[^9}",
+        "{:]>4}",
+    ];
+    #[test]
+    fn passthrough() {
+        for spec in SPECS {
+            test_eq(spec, Ok(spec));
+            let mixed = format!("{spec} {{[bold]}}x{spec}{{[]}}");
+            test_eq(&mixed, Ok(&format!("{spec} \x1b[1mx{spec}\x1b[0m")));
+            assert_eq!(
+                stripped_string(&mixed, check_curly).as_deref(),
+                Ok(&*format!("{spec} x{spec}"))
+            );
+        }
+    }
+    #[test]
+    fn not_lifted() {
+        for spec in SPECS {
+            assert_eq!(lift_captures(spec), (spec.to_string(), Vec::new()));
+        }
+        assert_eq!(
+            lift_captures("{v.len():>width$.prec$}"),
+            (
+                String::from("{__easy_sgr_capture_0:>width$.prec$}"),
+                vec!["v.len()"]
+            )
+        );
+    }
+}
@@ -6,9 +6,12 @@ python ./scripts/copy_readme.py
 cargo check --workspace --verbose
 cargo build --workspace --verbose
 cargo doc --workspace --verbose
-cargo test -F=macros --workspace --verbose
+cargo test -F=full --workspace --verbose
 cargo test -F=partial partial --verbose  
 cargo clippy --workspace --verbose
+for feature in partial from-str macros macro-only control osc log table regex testing proptest rand a11y binary extended report tokio query wasm-console palettes html theme terminfo; do
+  cargo test --no-default-features -F=$feature
+done
 
 cargo fmt --check --all --verbose
 
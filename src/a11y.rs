@@ -0,0 +1,143 @@
+use crate::Color;
+
+/// A type of color blindness, simulated by [`Color::simulate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorVisionDeficiency {
+    /// Missing long wavelength cones, red appearing dark
+    Protanopia,
+    /// Missing medium wavelength cones, red & green appearing alike
+    Deuteranopia,
+    /// Missing short wavelength cones, blue & green appearing alike
+    Tritanopia,
+}
+impl ColorVisionDeficiency {
+    /// Returns the matrix simulating the deficiency, applied to linear RGB
+    ///
+    /// The matrices of [Machado et al. (2009)](https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html)
+    /// at full severity
+    #[must_use]
+    pub const fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Self::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Self::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+}
+impl Color {
+    /// Returns the color as seen with the given [`ColorVisionDeficiency`]
+    ///
+    /// Named & byte colors are first turned into RGB through [`Color::rgb`],
+    /// the result is always an RGB color of the same layer.
+    /// [`Color::DefaultFg`] & [`Color::DefaultBg`] are returned as is
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{a11y::ColorVisionDeficiency::*, Color::*};
+    ///
+    ///assert_eq!(RgbFg(255, 0, 0).simulate(Deuteranopia), RgbFg(163, 144, 0));
+    ///assert_eq!(GreenBg.simulate(Deuteranopia), RgbBg(119, 106, 24));
+    ///```
+    #[must_use]
+    pub fn simulate(&self, deficiency: ColorVisionDeficiency) -> Self {
+        let Some(rgb) = self.rgb() else {
+            return self.clone();
+        };
+        let (r, g, b) = simulate_rgb(rgb, deficiency);
+        if matches!(self.undo(), Self::DefaultFg) {
+            Self::RgbFg(r, g, b)
+        } else {
+            Self::RgbBg(r, g, b)
+        }
+    }
+}
+/// Returns the red, green & blue values as seen with the given [`ColorVisionDeficiency`]
+///
+/// Refer to [`Color::simulate`]
+#[must_use]
+pub fn simulate_rgb((r, g, b): (u8, u8, u8), deficiency: ColorVisionDeficiency) -> (u8, u8, u8) {
+    let linear = [to_linear(r), to_linear(g), to_linear(b)];
+    deficiency
+        .matrix()
+        .map(|row| {
+            from_linear(row[0].mul_add(linear[0], row[1].mul_add(linear[1], row[2] * linear[2])))
+        })
+        .into()
+}
+/// Black, from the palette of [`colorblind_safe`]
+pub const BLACK: Color = Color::RgbFg(0, 0, 0);
+/// Orange, from the palette of [`colorblind_safe`]
+pub const ORANGE: Color = Color::RgbFg(230, 159, 0);
+/// Sky blue, from the palette of [`colorblind_safe`]
+pub const SKY_BLUE: Color = Color::RgbFg(86, 180, 233);
+/// Bluish green, from the palette of [`colorblind_safe`]
+pub const BLUISH_GREEN: Color = Color::RgbFg(0, 158, 115);
+/// Yellow, from the palette of [`colorblind_safe`]
+pub const YELLOW: Color = Color::RgbFg(240, 228, 66);
+/// Blue, from the palette of [`colorblind_safe`]
+pub const BLUE: Color = Color::RgbFg(0, 114, 178);
+/// Vermillion, from the palette of [`colorblind_safe`]
+pub const VERMILLION: Color = Color::RgbFg(213, 94, 0);
+/// Reddish purple, from the palette of [`colorblind_safe`]
+pub const REDDISH_PURPLE: Color = Color::RgbFg(204, 121, 167);
+/// Returns eight foregrounds that stay distinguishable with any [`ColorVisionDeficiency`]
+///
+/// The [Okabe-Ito](https://jfly.uni-koeln.de/color/) palette,
+/// each color is also available as a constant, i.e. [`ORANGE`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{a11y::*, EasySGR};
+///
+///assert_eq!(colorblind_safe().len(), 8);
+///println!("{} {}", "ok".color(BLUISH_GREEN), "error".color(VERMILLION));
+///```
+#[must_use]
+pub const fn colorblind_safe() -> [Color; 8] {
+    [
+        BLACK,
+        ORANGE,
+        SKY_BLUE,
+        BLUISH_GREEN,
+        YELLOW,
+        BLUE,
+        VERMILLION,
+        REDDISH_PURPLE,
+    ]
+}
+/// Converts an sRGB channel to linear light, `0.0` to `1.0`
+fn to_linear(c: u8) -> f64 {
+    let c = f64::from(c) / 255.0;
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+/// Converts linear light to an sRGB channel, clamping out of gamut values
+fn from_linear(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055f64.mul_add(c.powf(1.0 / 2.4), -0.055)
+    };
+    // clamped to 0.0..=255.0 before rounding
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let c = (c * 255.0).round() as u8;
+    c
+}
@@ -0,0 +1,201 @@
+use std::io;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    writing::{format_param, END, ESCAPE},
+    DiscreteSGR, EasyWrite, SGRBuilder, SGRString,
+};
+
+/// A writer capable of asynchronously writing a [`str`]
+///
+/// The async counterpart of [`CapableWriter`](crate::CapableWriter),
+/// which cannot be reused as its methods are blocking
+pub trait AsyncCapableWriter {
+    /// The error returned by [`AsyncCapableWriter::write`]
+    ///
+    /// Always convertible into the crate's [`Error`](crate::Error)
+    type Error: std::error::Error + Into<crate::Error>;
+    /// Writes a [`str`] to the inner writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`AsyncCapableWriter::Error`]
+    fn write(&mut self, s: &str) -> impl std::future::Future<Output = Result<(), Self::Error>>;
+}
+/// A writer built on top of a [`tokio::io::AsyncWrite`]
+///
+/// Writes the same bytes as [`SGRWriter`](crate::SGRWriter) would for the same input
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{AsyncIoWriter, EasySGR, Style::*};
+///
+///# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+///let mut writer = AsyncIoWriter(Vec::new());
+///"text".style(Bold).write_async(&mut writer).await.unwrap();
+///
+///assert_eq!(writer.0, b"\x1b[1mtext");
+///# });
+///```
+#[derive(Debug, Clone)]
+pub struct AsyncIoWriter<W: AsyncWrite + Unpin>(pub W);
+impl<W: AsyncWrite + Unpin> AsyncCapableWriter for AsyncIoWriter<W> {
+    type Error = io::Error;
+
+    #[inline]
+    async fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.write_all(s.as_bytes()).await
+    }
+}
+impl<W: AsyncWrite + Unpin> AsyncIoWriter<W> {
+    /// Writes a [`str`] to the inner writer
+    ///
+    /// A shortcut to [`AsyncCapableWriter::write`] without having to import it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    #[inline]
+    pub async fn write_inner(&mut self, s: &str) -> io::Result<()> {
+        self.write(s).await
+    }
+    /// Returns the internal writer
+    #[inline]
+    pub fn internal(self) -> W {
+        self.0
+    }
+    /// Writes the contained SGR codes to the writer through calling [`SGRString::place_all`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    #[inline]
+    pub async fn place_sgr(&mut self, sgr: &SGRString) -> io::Result<()> {
+        let mut builder = SGRBuilder::default();
+        sgr.place_all(&mut builder);
+        self.write_builder(&builder).await
+    }
+    /// Writes the contained SGR codes to the writer through calling [`SGRString::clean_all`]
+    ///
+    /// Supposed to reverse the effects made by [`SGRString::place_all`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    #[inline]
+    pub async fn clean_sgr(&mut self, sgr: &SGRString) -> io::Result<()> {
+        let mut builder = SGRBuilder::default();
+        sgr.clean_all(&mut builder);
+        self.write_builder(&builder).await
+    }
+    /// Writes the contained SGR codes to the writer through calling [`DiscreteSGR::write`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    #[inline]
+    pub async fn inline_sgr(&mut self, sgr: &(impl DiscreteSGR + Sync)) -> io::Result<()> {
+        let mut builder = SGRBuilder::default();
+        sgr.write(&mut builder);
+        self.write_builder(&builder).await
+    }
+    /// Writes the contained SGR codes to the writer
+    ///
+    /// Uses [`EasyWrite`] so the it can be used for both
+    /// [`SGRString`] and [`DiscreteSGR`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    pub async fn sgr(&mut self, sgr: &(impl EasyWrite + Sync)) -> io::Result<()> {
+        let mut builder = SGRBuilder::default();
+        sgr.sgr(&mut builder);
+        self.write_builder(&builder).await
+    }
+    /// Writes the codes buffered in an [`SGRBuilder`] to the writer
+    ///
+    /// Writes nothing if the builder is empty
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    pub async fn write_builder(&mut self, builder: &SGRBuilder) -> io::Result<()> {
+        if builder.is_empty() {
+            return Ok(());
+        }
+        self.write(ESCAPE).await?;
+        self.write_params(builder.codes()).await?;
+        self.write(END).await
+    }
+    /// Writes `\x1b[0m`, resetting every attribute
+    ///
+    /// Refer to [`SGRWriter::write_reset`](crate::SGRWriter::write_reset)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    pub async fn write_reset(&mut self) -> io::Result<()> {
+        self.write(ESCAPE).await?;
+        self.write("0").await?;
+        self.write(END).await
+    }
+    /// Writes the codes buffered in an [`SGRBuilder`] to the writer
+    ///
+    /// Does not write the escape or end sequences
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    #[inline]
+    pub async fn write_partial(&mut self, builder: &SGRBuilder) -> io::Result<()> {
+        self.write_params(builder.codes()).await
+    }
+    /// Writes a numeric parameter in decimal, without allocating
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    #[inline]
+    pub async fn write_param(&mut self, param: u16) -> io::Result<()> {
+        self.write(format_param(param, &mut [0; 5])).await
+    }
+    /// Writes codes separated by `;`
+    async fn write_params(&mut self, codes: &[u8]) -> io::Result<()> {
+        let Some((first, rest)) = codes.split_first() else {
+            return Ok(());
+        };
+        self.write_param((*first).into()).await?;
+
+        for code in rest {
+            self.write(";").await?;
+            self.write_param((*code).into()).await?;
+        }
+        Ok(())
+    }
+}
+impl<W: AsyncWrite + Unpin> From<W> for AsyncIoWriter<W> {
+    fn from(value: W) -> Self {
+        Self(value)
+    }
+}
+impl SGRString {
+    /// Asynchronously writes the [`SGRString`] to an [`AsyncIoWriter`]
+    ///
+    /// Writes the same bytes as its [`Display`](std::fmt::Display) impl,
+    /// without formatting into an intermediate [`String`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    pub async fn write_async<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut AsyncIoWriter<W>,
+    ) -> io::Result<()> {
+        writer.place_sgr(self).await?;
+        writer.write_inner(&self.text).await?;
+        writer.clean_sgr(self).await
+    }
+}
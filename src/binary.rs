@@ -0,0 +1,316 @@
+use crate::{CleanKind, ColorKind, ParseError, SGRString, StyleKind};
+
+/// The version written as the first byte, the only one [`from_bytes`] accepts
+pub const VERSION: u8 = 1;
+
+/// Ends the attributes of a fragment
+const END: u8 = 0;
+/// [`SGRString::clean`], followed by `1` for [`CleanKind::Reset`] or `2` for [`CleanKind::Reverse`]
+const CLEAN: u8 = 1;
+/// [`SGRString::reset`] being `true`
+const RESET: u8 = 2;
+/// [`SGRString::custom_places`], followed by a length & the codes
+const CUSTOM_PLACES: u8 = 3;
+/// [`SGRString::custom_cleans`], followed by a length & the codes
+const CUSTOM_CLEANS: u8 = 4;
+/// [`SGRString::foreground`], followed by a color
+const FOREGROUND: u8 = 5;
+/// [`SGRString::background`], followed by a color
+const BACKGROUND: u8 = 6;
+/// The first style, [`SGRString::bold`], followed by `1` for [`StyleKind::Place`] or `2` for [`StyleKind::Clean`]
+///
+/// The other styles follow in the order of their fields
+const STYLES: u8 = 16;
+/// The last style, [`SGRString::overline`]
+const LAST_STYLE: u8 = STYLES + 11;
+
+/// Encodes styled fragments into the compact binary form
+///
+/// Refer to the [module](self) for the format
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{binary, Color::*, EasySGR, Style::*};
+///
+///let line = ["error".style(Bold).color(RedFg), ": not found".into()];
+///let bytes = binary::to_bytes(&line);
+///let decoded = binary::from_bytes(&bytes).unwrap();
+///assert_eq!(decoded.len(), 2);
+///assert_eq!(decoded[0].to_string(), line[0].to_string());
+///```
+#[must_use]
+pub fn to_bytes(fragments: &[SGRString]) -> Vec<u8> {
+    let mut out = vec![VERSION];
+    for sgr in fragments {
+        encode(sgr, &mut out);
+    }
+    out
+}
+/// Decodes the fragments encoded by [`to_bytes`]
+///
+/// # Errors
+///
+/// Returns an error if the version is not [`VERSION`],
+/// the input is truncated or otherwise invalid
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<SGRString>, ParseError> {
+    let mut reader = Reader(bytes);
+    match reader.byte()? {
+        VERSION => (),
+        version => {
+            return Err(ParseError::new(format!(
+                "Unsupported binary version `{version}`, expected `{VERSION}`"
+            )))
+        }
+    }
+    let mut fragments = Vec::new();
+    while !reader.0.is_empty() {
+        fragments.push(reader.fragment()?);
+    }
+    Ok(fragments)
+}
+impl SGRString {
+    /// Encodes self into the compact binary form, as a single fragment
+    ///
+    /// Refer to [`binary`](crate::binary)
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR, SGRString, Style::*};
+    ///
+    ///let sgr = "text".style(Italic).color(ByteBg(208));
+    ///let decoded = SGRString::from_bytes(&sgr.to_bytes()).unwrap();
+    ///assert_eq!(decoded.to_string(), sgr.to_string());
+    ///```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(std::slice::from_ref(self))
+    }
+    /// Decodes an [`SGRString`] encoded by [`SGRString::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decoding fails, refer to [`from_bytes`],
+    /// or there is not exactly one fragment
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let fragments = from_bytes(bytes)?;
+        let count = fragments.len();
+        let [sgr] = <[Self; 1]>::try_from(fragments)
+            .map_err(|_| ParseError::new(format!("Expected a single fragment, found {count}")))?;
+        Ok(sgr)
+    }
+}
+/// Appends the encoding of a single fragment
+fn encode(sgr: &SGRString, out: &mut Vec<u8>) {
+    match sgr.clean {
+        CleanKind::None => (),
+        CleanKind::Reset => out.extend([CLEAN, 1]),
+        CleanKind::Reverse => out.extend([CLEAN, 2]),
+    }
+    if sgr.reset {
+        out.push(RESET);
+    }
+    for (tag, codes) in [
+        (CUSTOM_PLACES, &sgr.custom_places),
+        (CUSTOM_CLEANS, &sgr.custom_cleans),
+    ] {
+        if !codes.is_empty() {
+            out.push(tag);
+            length(codes.len(), out);
+            out.extend_from_slice(codes);
+        }
+    }
+    for (tag, color) in [(FOREGROUND, &sgr.foreground), (BACKGROUND, &sgr.background)] {
+        use ColorKind::*;
+        let (kind, payload): (u8, &[u8]) = match color {
+            None => continue,
+            Black => (1, &[]),
+            Red => (2, &[]),
+            Green => (3, &[]),
+            Yellow => (4, &[]),
+            Blue => (5, &[]),
+            Magenta => (6, &[]),
+            Cyan => (7, &[]),
+            White => (8, &[]),
+            Byte(n) => (9, &[*n]),
+            Rgb(r, g, b) => (10, &[*r, *g, *b]),
+            Default => (11, &[]),
+            BrightBlack => (12, &[]),
+            BrightRed => (13, &[]),
+            BrightGreen => (14, &[]),
+            BrightYellow => (15, &[]),
+            BrightBlue => (16, &[]),
+            BrightMagenta => (17, &[]),
+            BrightCyan => (18, &[]),
+            BrightWhite => (19, &[]),
+        };
+        out.extend([tag, kind]);
+        out.extend_from_slice(payload);
+    }
+    for (tag, kind) in (STYLES..).zip(styles(sgr)) {
+        match kind {
+            StyleKind::None => (),
+            StyleKind::Place => out.extend([tag, 1]),
+            StyleKind::Clean => out.extend([tag, 2]),
+        }
+    }
+    out.push(END);
+    length(sgr.text.len(), out);
+    out.extend_from_slice(sgr.text.as_bytes());
+}
+/// Appends a length, seven bits a byte with the high bit marking that more follow
+fn length(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        // only the low seven bits are kept
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+/// Returns the styles of an [`SGRString`], in the order of their fields
+const fn styles(sgr: &SGRString) -> [&StyleKind; 12] {
+    [
+        &sgr.bold,
+        &sgr.dim,
+        &sgr.italic,
+        &sgr.underline,
+        &sgr.blinking,
+        &sgr.rapid_blinking,
+        &sgr.inverse,
+        &sgr.hidden,
+        &sgr.strikethrough,
+        &sgr.framed,
+        &sgr.encircled,
+        &sgr.overline,
+    ]
+}
+/// Reads the encoded form, the slice holding what is left
+struct Reader<'a>(&'a [u8]);
+impl<'a> Reader<'a> {
+    /// Takes the next `n` bytes
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        let Some((taken, rest)) = self.0.split_at_checked(n) else {
+            return Err(ParseError::new("Truncated binary input"));
+        };
+        self.0 = rest;
+        Ok(taken)
+    }
+    /// Takes the next byte
+    fn byte(&mut self) -> Result<u8, ParseError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+    /// Takes a length written by [`length`]
+    fn length(&mut self) -> Result<usize, ParseError> {
+        let mut len = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            len |= usize::from(byte & 0x7f)
+                .checked_shl(shift)
+                .ok_or_else(|| ParseError::new("Binary length overflows"))?;
+            if byte & 0x80 == 0 {
+                return Ok(len);
+            }
+        }
+        Err(ParseError::new("Binary length overflows"))
+    }
+    /// Takes a single fragment written by [`encode`]
+    fn fragment(&mut self) -> Result<SGRString, ParseError> {
+        let mut sgr = SGRString::default();
+        loop {
+            match self.byte()? {
+                END => break,
+                CLEAN => {
+                    sgr.clean = match self.byte()? {
+                        1 => CleanKind::Reset,
+                        2 => CleanKind::Reverse,
+                        kind => return Err(invalid("clean kind", kind)),
+                    }
+                }
+                RESET => sgr.reset = true,
+                tag @ (CUSTOM_PLACES | CUSTOM_CLEANS) => {
+                    let len = self.length()?;
+                    let codes = self.take(len)?.to_vec();
+                    if tag == CUSTOM_PLACES {
+                        sgr.custom_places = codes;
+                    } else {
+                        sgr.custom_cleans = codes;
+                    }
+                }
+                tag @ (FOREGROUND | BACKGROUND) => {
+                    let color = self.color()?;
+                    if tag == FOREGROUND {
+                        sgr.foreground = color;
+                    } else {
+                        sgr.background = color;
+                    }
+                }
+                tag @ STYLES..=LAST_STYLE => {
+                    let kind = match self.byte()? {
+                        1 => StyleKind::Place,
+                        2 => StyleKind::Clean,
+                        kind => return Err(invalid("style kind", kind)),
+                    };
+                    *styles_mut(&mut sgr)[usize::from(tag - STYLES)] = kind;
+                }
+                tag => return Err(invalid("attribute tag", tag)),
+            }
+        }
+        let len = self.length()?;
+        sgr.text = String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| ParseError::new("Invalid UTF-8 within binary text"))?;
+        Ok(sgr)
+    }
+    /// Takes a color written by [`encode`]
+    fn color(&mut self) -> Result<ColorKind, ParseError> {
+        use ColorKind::*;
+        Ok(match self.byte()? {
+            1 => Black,
+            2 => Red,
+            3 => Green,
+            4 => Yellow,
+            5 => Blue,
+            6 => Magenta,
+            7 => Cyan,
+            8 => White,
+            9 => Byte(self.byte()?),
+            10 => Rgb(self.byte()?, self.byte()?, self.byte()?),
+            11 => Default,
+            12 => BrightBlack,
+            13 => BrightRed,
+            14 => BrightGreen,
+            15 => BrightYellow,
+            16 => BrightBlue,
+            17 => BrightMagenta,
+            18 => BrightCyan,
+            19 => BrightWhite,
+            kind => return Err(invalid("color kind", kind)),
+        })
+    }
+}
+/// Returns the styles of an [`SGRString`] mutably, in the order of their fields
+const fn styles_mut(sgr: &mut SGRString) -> [&mut StyleKind; 12] {
+    [
+        &mut sgr.bold,
+        &mut sgr.dim,
+        &mut sgr.italic,
+        &mut sgr.underline,
+        &mut sgr.blinking,
+        &mut sgr.rapid_blinking,
+        &mut sgr.inverse,
+        &mut sgr.hidden,
+        &mut sgr.strikethrough,
+        &mut sgr.framed,
+        &mut sgr.encircled,
+        &mut sgr.overline,
+    ]
+}
+/// Returns the error for an unknown byte
+fn invalid(what: &str, byte: u8) -> ParseError {
+    ParseError::new(format!("Unknown binary {what} `{byte}`"))
+}
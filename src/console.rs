@@ -0,0 +1,106 @@
+pub use crate::html::css;
+use crate::{
+    escapes::{apply_params, find_sgr},
+    CapableWriter, SGRString, SGRWriter, StyleSet,
+};
+
+/// Collects styled text as the arguments of the browser's `console.log`
+///
+/// Rather than writing escapes, the attributes in effect are tracked: each time they change
+/// before more text, `%c` is placed in the format string & their [CSS](css) pushed to the styles.
+/// Sequences within the written text are applied as well, so already styled text can be written.
+/// `%` is escaped as `%%`, so the text is shown as is
+///
+/// [`CapableWriter::get_writer`] returns the format string & the styles,
+/// to be passed to `console.log(format, ...styles)`
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{console::ConsoleWriter, CapableWriter, SGRWriter};
+///
+///let mut writer = SGRWriter::from(ConsoleWriter::default());
+///writer.write_inner("100% \x1b[1;31mdone\x1b[0m!")?;
+///let (format, styles) = writer.internal();
+///assert_eq!(format, "100%% %cdone%c!");
+///assert_eq!(styles, ["color: rgb(128, 0, 0); font-weight: bold", ""]);
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConsoleWriter {
+    format: String,
+    styles: Vec<String>,
+    state: StyleSet,
+    written: StyleSet,
+}
+impl ConsoleWriter {
+    /// Returns the format string written so far
+    #[must_use]
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+    /// Returns the styles written so far, one for each `%c`
+    #[must_use]
+    pub fn styles(&self) -> &[String] {
+        &self.styles
+    }
+    /// Adds visible text, placing the styles in effect first if they changed
+    fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.state != self.written {
+            self.format.push_str("%c");
+            self.styles.push(css(&self.state));
+            self.written.clone_from(&self.state);
+        }
+        self.format.push_str(&text.replace('%', "%%"));
+    }
+}
+impl CapableWriter for ConsoleWriter {
+    type Writer = (String, Vec<String>);
+    type Error = std::fmt::Error;
+
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        let mut rest = s;
+        while let Some((start, params, end)) = find_sgr(rest) {
+            self.text(&rest[..start]);
+            apply_params(&mut self.state, params);
+            rest = &rest[end..];
+        }
+        self.text(rest);
+        Ok(())
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.state.apply_codes(codes);
+        Ok(())
+    }
+    fn get_writer(self) -> Self::Writer {
+        (self.format, self.styles)
+    }
+}
+impl SGRString {
+    /// Returns the arguments of the browser's `console.log` showing this styled text
+    ///
+    /// The format string, its `%c`s placing each of the styles.
+    /// Refer to [`ConsoleWriter`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{EasySGR, Style::*};
+    ///
+    ///let (format, styles) = "hi".style(Bold).to_console_args();
+    ///assert_eq!(format, "%chi");
+    ///assert_eq!(styles, ["font-weight: bold"]);
+    ///```
+    #[must_use]
+    pub fn to_console_args(&self) -> (String, Vec<String>) {
+        let mut writer = SGRWriter::from(ConsoleWriter::default());
+        // writing to a ConsoleWriter never fails
+        let _ = writer.place_sgr(self);
+        let _ = writer.write_inner(&self.text);
+        let _ = writer.clean_sgr(self);
+        writer.internal()
+    }
+}
@@ -0,0 +1,80 @@
+/// Defines each sequence constant & its code in [`codes`]
+///
+/// Both are created from the same literal, so they can not drift apart
+macro_rules! sequences {
+    ($($(#[$doc:meta])* $name:ident = $code:literal),+ $(,)?) => {
+        $(
+            $(#[$doc])*
+            #[doc = concat!("\n\nThe sequence `\\x1b[", $code, "m`")]
+            pub const $name: &str = concat!("\x1b[", $code, "m");
+        )+
+        /// The bare codes of the sequences in [`consts`](super)
+        ///
+        /// # Examples
+        ///
+        ///```rust
+        ///use easy_sgr::consts::codes;
+        ///
+        ///assert_eq!(codes::BOLD, 1);
+        ///assert_eq!(codes::RED_FG, 31);
+        ///```
+        pub mod codes {
+            $(
+                $(#[$doc])*
+                pub const $name: u8 = $code;
+            )+
+        }
+    };
+}
+sequences!(
+    /// Resets all attributes, see [`Style::Reset`](crate::Style::Reset)
+    RESET = 0,
+    /// Bold, see [`Style::Bold`](crate::Style::Bold)
+    BOLD = 1,
+    /// Dim, see [`Style::Dim`](crate::Style::Dim)
+    DIM = 2,
+    /// Italic, see [`Style::Italic`](crate::Style::Italic)
+    ITALIC = 3,
+    /// Underline, see [`Style::Underline`](crate::Style::Underline)
+    UNDERLINE = 4,
+    /// A black foreground, see [`Color::BlackFg`](crate::Color::BlackFg)
+    BLACK_FG = 30,
+    /// A red foreground, see [`Color::RedFg`](crate::Color::RedFg)
+    RED_FG = 31,
+    /// A green foreground, see [`Color::GreenFg`](crate::Color::GreenFg)
+    GREEN_FG = 32,
+    /// A yellow foreground, see [`Color::YellowFg`](crate::Color::YellowFg)
+    YELLOW_FG = 33,
+    /// A blue foreground, see [`Color::BlueFg`](crate::Color::BlueFg)
+    BLUE_FG = 34,
+    /// A magenta foreground, see [`Color::MagentaFg`](crate::Color::MagentaFg)
+    MAGENTA_FG = 35,
+    /// A cyan foreground, see [`Color::CyanFg`](crate::Color::CyanFg)
+    CYAN_FG = 36,
+    /// A white foreground, see [`Color::WhiteFg`](crate::Color::WhiteFg)
+    WHITE_FG = 37,
+    /// The default foreground, see [`Color::DefaultFg`](crate::Color::DefaultFg)
+    DEFAULT_FG = 39,
+    /// A black background, see [`Color::BlackBg`](crate::Color::BlackBg)
+    BLACK_BG = 40,
+    /// A red background, see [`Color::RedBg`](crate::Color::RedBg)
+    RED_BG = 41,
+    /// A green background, see [`Color::GreenBg`](crate::Color::GreenBg)
+    GREEN_BG = 42,
+    /// A yellow background, see [`Color::YellowBg`](crate::Color::YellowBg)
+    YELLOW_BG = 43,
+    /// A blue background, see [`Color::BlueBg`](crate::Color::BlueBg)
+    BLUE_BG = 44,
+    /// A magenta background, see [`Color::MagentaBg`](crate::Color::MagentaBg)
+    MAGENTA_BG = 45,
+    /// A cyan background, see [`Color::CyanBg`](crate::Color::CyanBg)
+    CYAN_BG = 46,
+    /// A white background, see [`Color::WhiteBg`](crate::Color::WhiteBg)
+    WHITE_BG = 47,
+    /// The default background, see [`Color::DefaultBg`](crate::Color::DefaultBg)
+    DEFAULT_BG = 49,
+);
+/// Turns off every attribute without `0`, see [`ResetStrategy::ExplicitOffCodes`](crate::ResetStrategy::ExplicitOffCodes)
+///
+/// The sequence `\x1b[22;23;24;25;27;28;29;39;49;54;55m`
+pub const RESET_EXPLICIT: &str = "\x1b[22;23;24;25;27;28;29;39;49;54;55m";
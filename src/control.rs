@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use crate::{writing::write_csi, CapableWriter, SGRWriter};
+
+/// A control sequence that is not an SGR sequence
+///
+/// Parameters are written as is, so a value of `0` may be treated
+/// as `1` by the terminal
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Control::*, EraseMode, Style::*};
+///
+///print!("{}{}{Bold}Overwritten!{Reset}", CursorToColumn(1), EraseLine(EraseMode::All));
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Control {
+    /// Represents the sequence `\x1b[<n>A`
+    ///
+    /// Moves the cursor up `<n>` lines
+    CursorUp(u16),
+    /// Represents the sequence `\x1b[<n>B`
+    ///
+    /// Moves the cursor down `<n>` lines
+    CursorDown(u16),
+    /// Represents the sequence `\x1b[<n>C`
+    ///
+    /// Moves the cursor forward `<n>` columns
+    CursorForward(u16),
+    /// Represents the sequence `\x1b[<n>D`
+    ///
+    /// Moves the cursor back `<n>` columns
+    CursorBack(u16),
+    /// Represents the sequence `\x1b[<n>G`
+    ///
+    /// Moves the cursor to column `<n>`, where the first column is `1`
+    CursorToColumn(u16),
+    /// Represents the sequence `\x1b[<n>K`
+    ///
+    /// Where `<n>` is decided by the [`EraseMode`]
+    EraseLine(EraseMode),
+    /// Represents the sequence `\x1b[<n>J`
+    ///
+    /// Where `<n>` is decided by the [`EraseMode`]
+    EraseScreen(EraseMode),
+    /// Represents the sequence `\x1b[s`
+    SaveCursor,
+    /// Represents the sequence `\x1b[u`
+    RestoreCursor,
+}
+impl Control {
+    /// Writes the control sequence to the given writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        use Control::*;
+        match self {
+            CursorUp(n) => write_csi(writer, 'A', &[*n]),
+            CursorDown(n) => write_csi(writer, 'B', &[*n]),
+            CursorForward(n) => write_csi(writer, 'C', &[*n]),
+            CursorBack(n) => write_csi(writer, 'D', &[*n]),
+            CursorToColumn(n) => write_csi(writer, 'G', &[*n]),
+            EraseLine(mode) => write_csi(writer, 'K', &[mode.code()]),
+            EraseScreen(mode) => write_csi(writer, 'J', &[mode.code()]),
+            SaveCursor => write_csi(writer, 's', &[]),
+            RestoreCursor => write_csi(writer, 'u', &[]),
+        }
+    }
+}
+impl Display for Control {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(&mut SGRWriter::from(f))
+    }
+}
+/// Component of [`Control`]; what part of a line or screen to erase
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EraseMode {
+    /// Erases from the cursor to the end, code `0`
+    #[default]
+    ToEnd,
+    /// Erases from the start to the cursor, code `1`
+    ToStart,
+    /// Erases everything, code `2`
+    All,
+}
+impl EraseMode {
+    /// Returns the parameter written for this mode
+    #[must_use]
+    pub const fn code(&self) -> u16 {
+        match self {
+            Self::ToEnd => 0,
+            Self::ToStart => 1,
+            Self::All => 2,
+        }
+    }
+}
@@ -16,6 +16,12 @@ impl FromStr for Seq {
 /// An error encountered while trying to parse a string into a [`Seq`]
 #[derive(Debug, PartialEq, Eq)]
 pub struct ParseSeqError;
+impl Display for ParseSeqError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Invalid Seq, expected `Esc` or `End`")
+    }
+}
+impl Error for ParseSeqError {}
 impl FromStr for Style {
     type Err = ParseStyleError;
 
@@ -27,9 +33,13 @@ impl FromStr for Style {
             "Italic" => Ok(Self::Italic),
             "Underline" => Ok(Self::Underline),
             "Blinking" => Ok(Self::Blinking),
+            "RapidBlinking" => Ok(Self::RapidBlinking),
             "Inverse" => Ok(Self::Inverse),
             "Hidden" => Ok(Self::Hidden),
             "Strikethrough" => Ok(Self::Strikethrough),
+            "Framed" => Ok(Self::Framed),
+            "Encircled" => Ok(Self::Encircled),
+            "Overline" => Ok(Self::Overline),
             "NotBold" => Ok(Self::NotBold),
             "NotDim" => Ok(Self::NotDim),
             "NotItalic" => Ok(Self::NotItalic),
@@ -38,6 +48,8 @@ impl FromStr for Style {
             "NotInverse" => Ok(Self::NotInverse),
             "NotHidden" => Ok(Self::NotHidden),
             "NotStrikethrough" => Ok(Self::NotStrikethrough),
+            "NotFramedEncircled" => Ok(Self::NotFramedEncircled),
+            "NotOverline" => Ok(Self::NotOverline),
             _ => Err(ParseStyleError),
         }
     }
@@ -45,6 +57,12 @@ impl FromStr for Style {
 /// An error encountered while trying to parse a string into a [`Style`]
 #[derive(Debug, PartialEq, Eq)]
 pub struct ParseStyleError;
+impl Display for ParseStyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Invalid Style, expected a variant name such as `Bold` or `NotBold`")
+    }
+}
+impl Error for ParseStyleError {}
 impl FromStr for Color {
     type Err = ParseColorError;
 
@@ -60,6 +78,14 @@ impl FromStr for Color {
             "CyanFg" => Ok(CyanFg),
             "WhiteFg" => Ok(WhiteFg),
             "DefaultFg" => Ok(DefaultFg),
+            "BrightBlackFg" => Ok(BrightBlackFg),
+            "BrightRedFg" => Ok(BrightRedFg),
+            "BrightGreenFg" => Ok(BrightGreenFg),
+            "BrightYellowFg" => Ok(BrightYellowFg),
+            "BrightBlueFg" => Ok(BrightBlueFg),
+            "BrightMagentaFg" => Ok(BrightMagentaFg),
+            "BrightCyanFg" => Ok(BrightCyanFg),
+            "BrightWhiteFg" => Ok(BrightWhiteFg),
             "BlackBg" => Ok(BlackBg),
             "RedBg" => Ok(RedBg),
             "GreenBg" => Ok(GreenBg),
@@ -69,6 +95,14 @@ impl FromStr for Color {
             "CyanBg" => Ok(CyanBg),
             "WhiteBg" => Ok(WhiteBg),
             "DefaultBg" => Ok(DefaultBg),
+            "BrightBlackBg" => Ok(BrightBlackBg),
+            "BrightRedBg" => Ok(BrightRedBg),
+            "BrightGreenBg" => Ok(BrightGreenBg),
+            "BrightYellowBg" => Ok(BrightYellowBg),
+            "BrightBlueBg" => Ok(BrightBlueBg),
+            "BrightMagentaBg" => Ok(BrightMagentaBg),
+            "BrightCyanBg" => Ok(BrightCyanBg),
+            "BrightWhiteBg" => Ok(BrightWhiteBg),
             _ => match s.get(..5) {
                 Some("RgbFg") => {
                     let parts = resolve_rgb(s)?;
@@ -120,12 +154,19 @@ impl Display for ParseColorError {
             }
             Self::ParseIntError(e) => write!(f, "Error parsing int: {e}"),
             Self::Len(n) => {
-                write!(f, "Found wrong number of items in string: {n}. Needed 2")
+                write!(f, "Found wrong number of items in string: {n}. Needed 3")
             }
         }
     }
 }
-impl Error for ParseColorError {}
+impl Error for ParseColorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ParseIntError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 fn resolve_byte(s: &str) -> Result<u8, ParseColorError> {
     s.get(6..)
         .ok_or_else(|| ParseColorError::MissingNum(s.to_string()))
@@ -153,11 +194,42 @@ fn resolve_rgb(s: &str) -> Result<(u8, u8, u8), ParseColorError> {
         .strip_suffix(')')
         .ok_or_else(|| ParseColorError::Brace(s.to_string()))?
         .split(',')
-        .flat_map(|s| s.parse().map_err(ParseColorError::ParseIntError))
-        .collect();
+        .map(|s| s.parse().map_err(ParseColorError::ParseIntError))
+        .collect::<Result<_, _>>()?;
 
     match &parts[..] {
         &[n1, n2, n3] => Ok((n1, n2, n3)),
         _ => Err(ParseColorError::Len(parts.len())),
     }
 }
+#[cfg(feature = "extended")]
+impl FromStr for crate::Extended {
+    type Err = ParseExtendedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use crate::Extended::*;
+        match s {
+            "ProportionalSpacing" => Ok(ProportionalSpacing),
+            "NotProportionalSpacing" => Ok(NotProportionalSpacing),
+            "IdeogramUnderline" => Ok(IdeogramUnderline),
+            "IdeogramDoubleUnderline" => Ok(IdeogramDoubleUnderline),
+            "IdeogramOverline" => Ok(IdeogramOverline),
+            "IdeogramDoubleOverline" => Ok(IdeogramDoubleOverline),
+            "IdeogramStress" => Ok(IdeogramStress),
+            "NotIdeogram" => Ok(NotIdeogram),
+            _ => Err(ParseExtendedError),
+        }
+    }
+}
+/// An error encountered while trying to parse a string into an [`Extended`](crate::Extended)
+#[cfg(feature = "extended")]
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseExtendedError;
+#[cfg(feature = "extended")]
+impl Display for ParseExtendedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Invalid Extended, expected a variant name such as `IdeogramStress`")
+    }
+}
+#[cfg(feature = "extended")]
+impl Error for ParseExtendedError {}
@@ -1,11 +1,29 @@
 use std::fmt::Display;
 
-use crate::{EasySGR, SGRBuilder, SGRWriter};
+use crate::{palette256::clamp, EasySGR, SGRBuilder, SGRWriter, END, ESCAPE};
 
 /// Implements [`FromStr`](std::str::FromStr) for the [`discrete`](crate::discrete) module
 #[cfg(feature = "from-str")]
 pub mod from_str;
 
+/// Creates the `&'static str` written when a [`DiscreteSGR`] is displayed
+///
+/// With `partial` enabled the escape & end strings are left out
+#[cfg(not(feature = "partial"))]
+macro_rules! seq {
+    ($codes:literal) => {
+        concat!("\x1b[", $codes, "m")
+    };
+}
+/// Creates the `&'static str` written when a [`DiscreteSGR`] is displayed
+///
+/// With `partial` enabled the escape & end strings are left out
+#[cfg(feature = "partial")]
+macro_rules! seq {
+    ($codes:literal) => {
+        concat!($codes)
+    };
+}
 /// An SGR style code's end & escape
 ///
 /// Intended use case is when the `partial` feature is enable
@@ -21,16 +39,16 @@ pub mod from_str;
 ///```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Seq {
-    /// The sequence escape string, `\x1b[`
+    /// The sequence escape string, [`ESCAPE`]
     Esc,
-    /// The sequence end string, `m`
+    /// The sequence end string, [`END`]
     End,
 }
 impl Display for Seq {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
-            Self::Esc => "\x1b[",
-            Self::End => "m",
+            Self::Esc => ESCAPE,
+            Self::End => END,
         })
     }
 }
@@ -65,12 +83,22 @@ pub enum Style {
     Underline,
     /// Represents the SGR code `5`
     Blinking,
+    /// Represents the SGR code `6`
+    ///
+    /// Turned off by [`Style::NotBlinking`]
+    RapidBlinking,
     /// Represents the SGR code `7`
     Inverse,
     /// Represents the SGR code `8`
     Hidden,
     /// Represents the SGR code `9`
     Strikethrough,
+    /// Represents the SGR code `51`
+    Framed,
+    /// Represents the SGR code `52`
+    Encircled,
+    /// Represents the SGR code `53`
+    Overline,
     /// Represents the SGR code `22`
     ///
     /// Is equivalent to [`Style::NotDim`]
@@ -84,6 +112,8 @@ pub enum Style {
     /// Represents the SGR code `24`
     NotUnderline,
     /// Represents the SGR code `25`
+    ///
+    /// Turns off both [`Style::Blinking`] & [`Style::RapidBlinking`]
     NotBlinking,
     /// Represents the SGR code `27`
     NotInverse,
@@ -91,25 +121,141 @@ pub enum Style {
     NotHidden,
     /// Represents the SGR code `29`
     NotStrikethrough,
+    /// Represents the SGR code `54`
+    ///
+    /// Turns off both [`Style::Framed`] & [`Style::Encircled`]
+    NotFramedEncircled,
+    /// Represents the SGR code `55`
+    NotOverline,
+}
+impl Style {
+    /// Returns what displays only the parameters of this style, i.e. `1`
+    ///
+    /// For embedding within a sequence written by hand, refer to [`DisplayParams`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::Style::*;
+    ///
+    ///assert_eq!(format!("\x1b[{};{}m", Bold.params_display(), Italic.params_display()), "\x1b[1;3m");
+    ///```
+    #[must_use]
+    pub const fn params_display(&self) -> DisplayParams<'_, Self> {
+        DisplayParams(self)
+    }
+    /// Returns the [`Style`] that turns off this style
+    ///
+    /// i.e. [`Style::Bold`] becomes [`Style::NotBold`].
+    ///
+    /// [`Style::Reset`] and the variants prefixed with `Not` are
+    /// already turning something off, so they are returned as is.
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::Style::*;
+    ///
+    ///assert_eq!(Italic.undo(), NotItalic);
+    ///assert_eq!(NotItalic.undo(), NotItalic);
+    ///```
+    #[must_use]
+    pub const fn undo(&self) -> Self {
+        use Style::*;
+        match self {
+            Bold | NotBold => NotBold,
+            Dim | NotDim => NotDim,
+            Italic | NotItalic => NotItalic,
+            Underline | NotUnderline => NotUnderline,
+            Blinking | RapidBlinking | NotBlinking => NotBlinking,
+            Inverse | NotInverse => NotInverse,
+            Hidden | NotHidden => NotHidden,
+            Strikethrough | NotStrikethrough => NotStrikethrough,
+            Framed | Encircled | NotFramedEncircled => NotFramedEncircled,
+            Overline | NotOverline => NotOverline,
+            Reset => Reset,
+        }
+    }
+    /// Returns the sequence turning off every effect while leaving colors, `22;23;24;25;27;28;29`
+    ///
+    /// Unlike [`Style::Reset`], foreground & background colors stay in effect
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{DiscreteSGR, Style};
+    ///
+    ///assert_eq!(Style::reset_effects().sgr_params(), [22, 23, 24, 25, 27, 28, 29]);
+    ///```
+    #[must_use]
+    pub const fn reset_effects() -> ResetEffects {
+        use Style::*;
+        Combined(
+            Combined(
+                Combined(
+                    Combined(
+                        Combined(Combined(NotBold, NotItalic), NotUnderline),
+                        NotBlinking,
+                    ),
+                    NotInverse,
+                ),
+                NotHidden,
+            ),
+            NotStrikethrough,
+        )
+    }
 }
 impl Display for Style {
+    /// Writes a precomputed sequence,
+    /// equivalent to [`DiscreteSGR::standard_display`]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.standard_display(f)
+        use Style::*;
+        f.write_str(match self {
+            Reset => seq!(0),
+            Bold => seq!(1),
+            Dim => seq!(2),
+            Italic => seq!(3),
+            Underline => seq!(4),
+            Blinking => seq!(5),
+            RapidBlinking => seq!(6),
+            Inverse => seq!(7),
+            Hidden => seq!(8),
+            Strikethrough => seq!(9),
+            Framed => seq!(51),
+            Encircled => seq!(52),
+            Overline => seq!(53),
+            NotBold | NotDim => seq!(22),
+            NotItalic => seq!(23),
+            NotUnderline => seq!(24),
+            NotBlinking => seq!(25),
+            NotInverse => seq!(27),
+            NotHidden => seq!(28),
+            NotStrikethrough => seq!(29),
+            NotFramedEncircled => seq!(54),
+            NotOverline => seq!(55),
+        })
     }
 }
 impl DiscreteSGR for Style {
     fn write(&self, builder: &mut SGRBuilder) {
+        builder.write_codes(&self.codes());
+    }
+    fn codes(&self) -> Codes {
         use Style::*;
-        builder.write_code(match self {
+        Codes::from(match self {
             Reset => 0,
             Bold => 1,
             Dim => 2,
             Italic => 3,
             Underline => 4,
             Blinking => 5,
+            RapidBlinking => 6,
             Inverse => 7,
             Hidden => 8,
             Strikethrough => 9,
+            Framed => 51,
+            Encircled => 52,
+            Overline => 53,
             NotBold | NotDim => 22,
             NotItalic => 23,
             NotUnderline => 24,
@@ -117,7 +263,9 @@ impl DiscreteSGR for Style {
             NotInverse => 27,
             NotHidden => 28,
             NotStrikethrough => 29,
-        });
+            NotFramedEncircled => 54,
+            NotOverline => 55,
+        })
     }
 }
 /// An SGR color code
@@ -160,6 +308,22 @@ pub enum Color {
     RgbFg(u8, u8, u8),
     /// Represents the SGR code `39`
     DefaultFg,
+    /// Represents the SGR code `90`
+    BrightBlackFg,
+    /// Represents the SGR code `91`
+    BrightRedFg,
+    /// Represents the SGR code `92`
+    BrightGreenFg,
+    /// Represents the SGR code `93`
+    BrightYellowFg,
+    /// Represents the SGR code `94`
+    BrightBlueFg,
+    /// Represents the SGR code `95`
+    BrightMagentaFg,
+    /// Represents the SGR code `96`
+    BrightCyanFg,
+    /// Represents the SGR code `97`
+    BrightWhiteFg,
 
     /// Represents the SGR code `40`
     BlackBg,
@@ -181,59 +345,742 @@ pub enum Color {
     ///
     /// Where `<n>` is an 8 bit color
     ByteBg(u8),
-    /// Represents the SGR codes `38;2;<n1>;<n2>;<n3>`
+    /// Represents the SGR codes `48;2;<n1>;<n2>;<n3>`
     ///
     /// Where `<n1>`,`<n2>`,`<n3>` are 8 bit colors
     RgbBg(u8, u8, u8),
     /// Represents the SGR code `49`
     DefaultBg,
+    /// Represents the SGR code `100`
+    BrightBlackBg,
+    /// Represents the SGR code `101`
+    BrightRedBg,
+    /// Represents the SGR code `102`
+    BrightGreenBg,
+    /// Represents the SGR code `103`
+    BrightYellowBg,
+    /// Represents the SGR code `104`
+    BrightBlueBg,
+    /// Represents the SGR code `105`
+    BrightMagentaBg,
+    /// Represents the SGR code `106`
+    BrightCyanBg,
+    /// Represents the SGR code `107`
+    BrightWhiteBg,
+}
+impl Color {
+    /// Returns what displays only the parameters of this color, i.e. `38;2;1;2;3`
+    ///
+    /// For embedding within a sequence written by hand, refer to [`DisplayParams`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::Color::*;
+    ///
+    ///assert_eq!(format!("\x1b[1;{}m", RgbFg(1, 2, 3).params_display()), "\x1b[1;38;2;1;2;3m");
+    ///```
+    #[must_use]
+    pub const fn params_display(&self) -> DisplayParams<'_, Self> {
+        DisplayParams(self)
+    }
+    /// Returns the [`Color`] that turns off this color
+    ///
+    /// Foreground colors become [`Color::DefaultFg`],
+    /// background colors become [`Color::DefaultBg`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::Color::*;
+    ///
+    ///assert_eq!(RgbFg(0, 0, 0).undo(), DefaultFg);
+    ///assert_eq!(RedBg.undo(), DefaultBg);
+    ///```
+    #[must_use]
+    pub const fn undo(&self) -> Self {
+        use Color::*;
+        match self {
+            BlackFg | RedFg | GreenFg | YellowFg | BlueFg | MagentaFg | CyanFg | WhiteFg
+            | ByteFg(_) | RgbFg(..) | DefaultFg | BrightBlackFg | BrightRedFg | BrightGreenFg
+            | BrightYellowFg | BrightBlueFg | BrightMagentaFg | BrightCyanFg | BrightWhiteFg => {
+                DefaultFg
+            }
+            BlackBg | RedBg | GreenBg | YellowBg | BlueBg | MagentaBg | CyanBg | WhiteBg
+            | ByteBg(_) | RgbBg(..) | DefaultBg | BrightBlackBg | BrightRedBg | BrightGreenBg
+            | BrightYellowBg | BrightBlueBg | BrightMagentaBg | BrightCyanBg | BrightWhiteBg => {
+                DefaultBg
+            }
+        }
+    }
+    /// Returns the sequence resetting both colors while leaving effects, `39;49`
+    ///
+    /// Unlike [`Style::Reset`], effects such as [`Style::Bold`] stay in effect
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color, DiscreteSGR};
+    ///
+    ///assert_eq!(Color::reset_all().sgr_params(), [39, 49]);
+    ///```
+    #[must_use]
+    pub const fn reset_all() -> ResetColors {
+        Combined(Self::DefaultFg, Self::DefaultBg)
+    }
+    /// Returns the red, green & blue values of the color
+    ///
+    /// Named, bright & byte colors use the standard xterm palette, see [`palette256::to_rgb`](crate::palette256::to_rgb).
+    /// Returns [`None`] for [`Color::DefaultFg`] & [`Color::DefaultBg`], as they are decided by the terminal
+    #[must_use]
+    pub const fn rgb(&self) -> Option<(u8, u8, u8)> {
+        use Color::*;
+        let index = match self {
+            BlackFg | BlackBg => 0,
+            RedFg | RedBg => 1,
+            GreenFg | GreenBg => 2,
+            YellowFg | YellowBg => 3,
+            BlueFg | BlueBg => 4,
+            MagentaFg | MagentaBg => 5,
+            CyanFg | CyanBg => 6,
+            WhiteFg | WhiteBg => 7,
+            BrightBlackFg | BrightBlackBg => 8,
+            BrightRedFg | BrightRedBg => 9,
+            BrightGreenFg | BrightGreenBg => 10,
+            BrightYellowFg | BrightYellowBg => 11,
+            BrightBlueFg | BrightBlueBg => 12,
+            BrightMagentaFg | BrightMagentaBg => 13,
+            BrightCyanFg | BrightCyanBg => 14,
+            BrightWhiteFg | BrightWhiteBg => 15,
+            ByteFg(n) | ByteBg(n) => *n,
+            RgbFg(r, g, b) | RgbBg(r, g, b) => return Some((*r, *g, *b)),
+            DefaultFg | DefaultBg => return None,
+        };
+        Some(crate::palette256::to_rgb(index))
+    }
+    /// Returns [`Color::BlackFg`] or [`Color::WhiteFg`], whichever is more legible on this color
+    ///
+    /// Refer to [`Color::contrasting_fg_with`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::{self, *}, EasySGR};
+    ///
+    ///// badges with user chosen colors stay legible
+    ///for background in [YellowBg, BlueBg, RgbBg(250, 220, 240)] {
+    ///    let badge = " tag ".color(background.clone()).color(background.contrasting_fg());
+    ///    println!("{badge}{}", DefaultBg);
+    ///}
+    ///assert_eq!(YellowBg.contrasting_fg(), BlackFg);
+    ///assert_eq!(BlueBg.contrasting_fg(), WhiteFg);
+    ///```
+    #[must_use]
+    pub fn contrasting_fg(&self) -> Self {
+        self.contrasting_fg_with(Self::BlackFg, Self::WhiteFg)
+    }
+    /// Returns `dark` or `light`, whichever is more legible on this color
+    ///
+    /// Picks the one with the higher contrast ratio against the [relative luminance](crate::palette256::relative_luminance)
+    /// of [`Color::rgb`], assuming `dark` is black & `light` is white.
+    /// Default colors are unknown, so [`Color::DefaultFg`] is returned
+    #[must_use]
+    pub fn contrasting_fg_with(&self, dark: Self, light: Self) -> Self {
+        self.rgb().map_or(Self::DefaultFg, |rgb| {
+            let luminance = crate::palette256::relative_luminance(rgb);
+            // contrast against black is (L + 0.05) / 0.05, against white 1.05 / (L + 0.05)
+            if (luminance + 0.05).powi(2) > 0.05 * 1.05 {
+                dark
+            } else {
+                light
+            }
+        })
+    }
+    /// Returns a foreground chosen by hashing the key
+    ///
+    /// Picks from the [readable](crate::palette256::is_readable) colors of the 256 color cube,
+    /// so distinct keys, such as thread names or IDs, tend to get distinct colors.
+    /// The hash does not depend on the platform or on [`std`]'s hasher,
+    /// so a key is given the same color across runs & builds
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color, EasySGR};
+    ///
+    ///for service in ["web", "db", "cache"] {
+    ///    println!("{}", service.color(Color::hashed_fg(service)));
+    ///}
+    ///assert_eq!(Color::hashed_fg("web"), Color::hashed_fg("web"));
+    ///```
+    #[must_use]
+    pub fn hashed_fg(key: impl std::hash::Hash) -> Self {
+        let mut hasher = Fnv::default();
+        key.hash(&mut hasher);
+        let count = readable().count() as u64;
+        // the count is far less than usize::MAX
+        #[allow(clippy::cast_possible_truncation)]
+        let index = (std::hash::Hasher::finish(&hasher) % count) as usize;
+        Self::ByteFg(readable().nth(index).unwrap_or(16))
+    }
+    /// Returns a random foreground
+    ///
+    /// Picks from the same [readable](crate::palette256::is_readable) colors as [`Color::hashed_fg`]
+    #[cfg(feature = "rand")]
+    #[must_use]
+    pub fn random_fg(rng: &mut impl rand::Rng) -> Self {
+        let index = rng.random_range(0..readable().count());
+        Self::ByteFg(readable().nth(index).unwrap_or(16))
+    }
+    /// Returns a foreground from the 256 color cube
+    ///
+    /// Components greater than `5` are clamped, refer to [`palette256::cube`](crate::palette256::cube)
+    #[must_use]
+    pub const fn cube_fg(r: u8, g: u8, b: u8) -> Self {
+        Self::ByteFg(cube_clamped(r, g, b))
+    }
+    /// Returns a background from the 256 color cube
+    ///
+    /// Components greater than `5` are clamped, refer to [`palette256::cube`](crate::palette256::cube)
+    #[must_use]
+    pub const fn cube_bg(r: u8, g: u8, b: u8) -> Self {
+        Self::ByteBg(cube_clamped(r, g, b))
+    }
+    /// Returns a foreground from the 256 color grayscale ramp
+    ///
+    /// Levels greater than `23` are clamped, refer to [`palette256::gray`](crate::palette256::gray)
+    #[must_use]
+    pub const fn gray_fg(level: u8) -> Self {
+        Self::ByteFg(crate::palette256::gray(clamp(level, 23)))
+    }
+    /// Returns a background from the 256 color grayscale ramp
+    ///
+    /// Levels greater than `23` are clamped, refer to [`palette256::gray`](crate::palette256::gray)
+    #[must_use]
+    pub const fn gray_bg(level: u8) -> Self {
+        Self::ByteBg(crate::palette256::gray(clamp(level, 23)))
+    }
 }
 impl Display for Color {
+    /// Writes a precomputed sequence, or formats the parameters directly,
+    /// equivalent to [`DiscreteSGR::standard_display`]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.standard_display(f)
+        use Color::*;
+        f.write_str(match self {
+            BlackFg => seq!(30),
+            RedFg => seq!(31),
+            GreenFg => seq!(32),
+            YellowFg => seq!(33),
+            BlueFg => seq!(34),
+            MagentaFg => seq!(35),
+            CyanFg => seq!(36),
+            WhiteFg => seq!(37),
+            ByteFg(n) => return write!(f, seq!("38;5;{}"), n),
+            RgbFg(r, g, b) => return write!(f, seq!("38;2;{};{};{}"), r, g, b),
+            DefaultFg => seq!(39),
+            BrightBlackFg => seq!(90),
+            BrightRedFg => seq!(91),
+            BrightGreenFg => seq!(92),
+            BrightYellowFg => seq!(93),
+            BrightBlueFg => seq!(94),
+            BrightMagentaFg => seq!(95),
+            BrightCyanFg => seq!(96),
+            BrightWhiteFg => seq!(97),
+
+            BlackBg => seq!(40),
+            RedBg => seq!(41),
+            GreenBg => seq!(42),
+            YellowBg => seq!(43),
+            BlueBg => seq!(44),
+            MagentaBg => seq!(45),
+            CyanBg => seq!(46),
+            WhiteBg => seq!(47),
+            ByteBg(n) => return write!(f, seq!("48;5;{}"), n),
+            RgbBg(r, g, b) => return write!(f, seq!("48;2;{};{};{}"), r, g, b),
+            DefaultBg => seq!(49),
+            BrightBlackBg => seq!(100),
+            BrightRedBg => seq!(101),
+            BrightGreenBg => seq!(102),
+            BrightYellowBg => seq!(103),
+            BrightBlueBg => seq!(104),
+            BrightMagentaBg => seq!(105),
+            BrightCyanBg => seq!(106),
+            BrightWhiteBg => seq!(107),
+        })
     }
 }
 impl DiscreteSGR for Color {
     fn write(&self, builder: &mut SGRBuilder) {
+        builder.write_codes(&self.codes());
+    }
+    fn codes(&self) -> Codes {
         use Color::*;
         match self {
-            BlackFg => builder.write_code(30),
-            RedFg => builder.write_code(31),
-            GreenFg => builder.write_code(32),
-            YellowFg => builder.write_code(33),
-            BlueFg => builder.write_code(34),
-            MagentaFg => builder.write_code(35),
-            CyanFg => builder.write_code(36),
-            WhiteFg => builder.write_code(37),
-            ByteFg(n) => builder.write_codes(&[38, 5, *n]),
-            RgbFg(r, g, b) => builder.write_codes(&[38, 2, *r, *g, *b]),
-            DefaultFg => builder.write_code(39),
+            BlackFg => Codes::from(30),
+            RedFg => Codes::from(31),
+            GreenFg => Codes::from(32),
+            YellowFg => Codes::from(33),
+            BlueFg => Codes::from(34),
+            MagentaFg => Codes::from(35),
+            CyanFg => Codes::from(36),
+            WhiteFg => Codes::from(37),
+            ByteFg(n) => Codes::from_slice(&[38, 5, *n]),
+            RgbFg(r, g, b) => Codes::from_slice(&[38, 2, *r, *g, *b]),
+            DefaultFg => Codes::from(39),
+            BrightBlackFg => Codes::from(90),
+            BrightRedFg => Codes::from(91),
+            BrightGreenFg => Codes::from(92),
+            BrightYellowFg => Codes::from(93),
+            BrightBlueFg => Codes::from(94),
+            BrightMagentaFg => Codes::from(95),
+            BrightCyanFg => Codes::from(96),
+            BrightWhiteFg => Codes::from(97),
 
-            BlackBg => builder.write_code(40),
-            RedBg => builder.write_code(41),
-            GreenBg => builder.write_code(42),
-            YellowBg => builder.write_code(43),
-            BlueBg => builder.write_code(44),
-            MagentaBg => builder.write_code(45),
-            CyanBg => builder.write_code(46),
-            WhiteBg => builder.write_code(47),
-            ByteBg(n) => builder.write_codes(&[48, 5, *n]),
-            RgbBg(r, g, b) => builder.write_codes(&[48, 2, *r, *g, *b]),
-            DefaultBg => builder.write_code(49),
+            BlackBg => Codes::from(40),
+            RedBg => Codes::from(41),
+            GreenBg => Codes::from(42),
+            YellowBg => Codes::from(43),
+            BlueBg => Codes::from(44),
+            MagentaBg => Codes::from(45),
+            CyanBg => Codes::from(46),
+            WhiteBg => Codes::from(47),
+            ByteBg(n) => Codes::from_slice(&[48, 5, *n]),
+            RgbBg(r, g, b) => Codes::from_slice(&[48, 2, *r, *g, *b]),
+            DefaultBg => Codes::from(49),
+            BrightBlackBg => Codes::from(100),
+            BrightRedBg => Codes::from(101),
+            BrightGreenBg => Codes::from(102),
+            BrightYellowBg => Codes::from(103),
+            BrightBlueBg => Codes::from(104),
+            BrightMagentaBg => Codes::from(105),
+            BrightCyanBg => Codes::from(106),
+            BrightWhiteBg => Codes::from(107),
         }
     }
 }
+/// An RGB color not yet applied to the foreground or background
+///
+/// Lets a color be stored, i.e. within a theme, and its layer decided when applied.
+/// Converts from `(r, g, b)` tuples & `0xRRGGBB` integers
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color, EasySGR, Rgb};
+///
+///const ACCENT: Rgb = Rgb::from_hex(0xff8800);
+///assert_eq!(ACCENT.fg(), Color::RgbFg(255, 136, 0));
+///assert_eq!(ACCENT.bg(), Color::RgbBg(255, 136, 0));
+///assert_eq!(
+///    "text".fg(ACCENT).bg((0, 0, 0)).to_string(),
+///    "\x1b[38;2;255;136;0;48;2;0;0;0mtext"
+///);
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+impl Rgb {
+    /// Creates an [`Rgb`] from a `0xRRGGBB` integer, the highest byte is ignored
+    #[must_use]
+    pub const fn from_hex(hex: u32) -> Self {
+        let [_, r, g, b] = hex.to_be_bytes();
+        Self(r, g, b)
+    }
+    /// Returns the color applied to the foreground, a [`Color::RgbFg`]
+    #[must_use]
+    pub const fn fg(self) -> Color {
+        Color::RgbFg(self.0, self.1, self.2)
+    }
+    /// Returns the color applied to the background, a [`Color::RgbBg`]
+    #[must_use]
+    pub const fn bg(self) -> Color {
+        Color::RgbBg(self.0, self.1, self.2)
+    }
+}
+impl From<(u8, u8, u8)> for Rgb {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self(r, g, b)
+    }
+}
+impl From<u32> for Rgb {
+    /// Refer to [`Rgb::from_hex`]
+    fn from(value: u32) -> Self {
+        Self::from_hex(value)
+    }
+}
+/// An SGR font code, selecting the primary or one of nine alternative fonts
+///
+/// Font `0` is the primary font, written as the code `10`,
+/// fonts `1` to `9` are written as the codes `11` to `19`.
+/// Indices outside `0` to `9` are rejected by [`Font::new`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{DiscreteSGR, Font};
+///
+///let font = Font::new(3).unwrap();
+///assert_eq!(&*font.codes(), [13]);
+///assert_eq!(font.undo(), Font::PRIMARY);
+///assert!(Font::new(10).is_err());
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Font(u8);
+impl Font {
+    /// The primary font, represents the SGR code `10`
+    pub const PRIMARY: Self = Self(0);
+    /// Creates a [`Font`] from its index
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index is greater than `9`
+    pub const fn new(index: u8) -> Result<Self, InvalidFont> {
+        if index <= 9 {
+            Ok(Self(index))
+        } else {
+            Err(InvalidFont(index))
+        }
+    }
+    /// Returns the index of the font, from `0` to `9`
+    #[must_use]
+    pub const fn index(&self) -> u8 {
+        self.0
+    }
+    /// Returns the [`Font`] that turns off this font, [`Font::PRIMARY`]
+    #[must_use]
+    pub const fn undo(&self) -> Self {
+        Self::PRIMARY
+    }
+}
+impl Display for Font {
+    /// Formats the code directly,
+    /// equivalent to [`DiscreteSGR::standard_display`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, seq!("{}"), 10 + self.0)
+    }
+}
+impl DiscreteSGR for Font {
+    fn write(&self, builder: &mut SGRBuilder) {
+        builder.write_code(10 + self.0);
+    }
+    fn codes(&self) -> Codes {
+        Codes::from(10 + self.0)
+    }
+}
+/// The error returned by [`Font::new`] when given an index greater than `9`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidFont(pub u8);
+impl Display for InvalidFont {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid font {}, expected an index from 0 to 9", self.0)
+    }
+}
+impl std::error::Error for InvalidFont {}
+/// The rarely supported SGR codes: proportional spacing & ideograms
+///
+/// Kept apart from [`Style`] as few terminals implement them
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{DiscreteSGR, Extended::*};
+///
+///assert_eq!(&*IdeogramStress.codes(), [64]);
+///assert_eq!(IdeogramStress.undo(), NotIdeogram);
+///```
+#[cfg(feature = "extended")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extended {
+    /// Represents the SGR code `26`
+    ProportionalSpacing,
+    /// Represents the SGR code `50`
+    NotProportionalSpacing,
+    /// Represents the SGR code `60`
+    IdeogramUnderline,
+    /// Represents the SGR code `61`
+    IdeogramDoubleUnderline,
+    /// Represents the SGR code `62`
+    IdeogramOverline,
+    /// Represents the SGR code `63`
+    IdeogramDoubleOverline,
+    /// Represents the SGR code `64`
+    IdeogramStress,
+    /// Represents the SGR code `65`
+    ///
+    /// Turns off all ideogram attributes
+    NotIdeogram,
+}
+#[cfg(feature = "extended")]
+impl Extended {
+    /// Returns the [`Extended`] that turns off this attribute
+    ///
+    /// i.e. [`Extended::IdeogramStress`] becomes [`Extended::NotIdeogram`]
+    #[must_use]
+    pub const fn undo(&self) -> Self {
+        use Extended::*;
+        match self {
+            ProportionalSpacing | NotProportionalSpacing => NotProportionalSpacing,
+            IdeogramUnderline
+            | IdeogramDoubleUnderline
+            | IdeogramOverline
+            | IdeogramDoubleOverline
+            | IdeogramStress
+            | NotIdeogram => NotIdeogram,
+        }
+    }
+}
+#[cfg(feature = "extended")]
+impl Display for Extended {
+    /// Writes a precomputed sequence,
+    /// equivalent to [`DiscreteSGR::standard_display`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Extended::*;
+        f.write_str(match self {
+            ProportionalSpacing => seq!(26),
+            NotProportionalSpacing => seq!(50),
+            IdeogramUnderline => seq!(60),
+            IdeogramDoubleUnderline => seq!(61),
+            IdeogramOverline => seq!(62),
+            IdeogramDoubleOverline => seq!(63),
+            IdeogramStress => seq!(64),
+            NotIdeogram => seq!(65),
+        })
+    }
+}
+#[cfg(feature = "extended")]
+impl DiscreteSGR for Extended {
+    fn write(&self, builder: &mut SGRBuilder) {
+        builder.write_codes(&self.codes());
+    }
+    fn codes(&self) -> Codes {
+        use Extended::*;
+        Codes::from(match self {
+            ProportionalSpacing => 26,
+            NotProportionalSpacing => 50,
+            IdeogramUnderline => 60,
+            IdeogramDoubleUnderline => 61,
+            IdeogramOverline => 62,
+            IdeogramDoubleOverline => 63,
+            IdeogramStress => 64,
+            NotIdeogram => 65,
+        })
+    }
+}
+/// The readable indices of the 256 color cube
+fn readable() -> impl Iterator<Item = u8> {
+    (16..=231).filter(|&index| crate::palette256::is_readable(index))
+}
+/// A 64 bit FNV-1a hasher, with integers written as little endian
+///
+/// Unlike [`DefaultHasher`](std::hash::DefaultHasher), the output is stable
+#[derive(Debug)]
+struct Fnv(u64);
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+impl std::hash::Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+/// Returns the cube index with each component clamped to `5`
+const fn cube_clamped(r: u8, g: u8, b: u8) -> u8 {
+    crate::palette256::cube(clamp(r, 5), clamp(g, 5), clamp(b, 5))
+}
+/// The SGR codes of a [`DiscreteSGR`], returned by [`DiscreteSGR::codes`]
+///
+/// Up to [`Codes::CAPACITY`] codes, the length of the longest sequence in this module:
+/// an RGB color, are stored without allocating.
+/// Longer sequences, such as those of a [`Combined`], are moved to the heap
+///
+/// Dereferences to a slice of the contained codes
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, DiscreteSGR, Style::*};
+///
+///assert_eq!(&[38, 5, 208], &*ByteFg(208).codes());
+///assert_eq!(&[1, 38, 2, 1, 2, 3], &*Bold.and(RgbFg(1, 2, 3)).codes());
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Codes {
+    buf: [u8; Self::CAPACITY],
+    len: usize,
+    spilled: Vec<u8>,
+}
+impl Codes {
+    /// The number of codes stored without allocating
+    pub const CAPACITY: usize = 5;
+    /// Creates [`Codes`] from a slice of codes, moved to the heap if they do not fit inline
+    pub(crate) fn from_slice(codes: &[u8]) -> Self {
+        let mut buf = [0; Self::CAPACITY];
+        if codes.len() > Self::CAPACITY {
+            return Self {
+                buf,
+                len: 0,
+                spilled: codes.to_vec(),
+            };
+        }
+        buf[..codes.len()].copy_from_slice(codes);
+        Self {
+            buf,
+            len: codes.len(),
+            spilled: Vec::new(),
+        }
+    }
+}
+impl From<u8> for Codes {
+    fn from(code: u8) -> Self {
+        Self::from_slice(&[code])
+    }
+}
+impl std::ops::Deref for Codes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        if self.spilled.is_empty() {
+            &self.buf[..self.len]
+        } else {
+            &self.spilled
+        }
+    }
+}
+impl IntoIterator for Codes {
+    type Item = u8;
+    type IntoIter = std::iter::Chain<
+        std::iter::Take<std::array::IntoIter<u8, { Self::CAPACITY }>>,
+        std::vec::IntoIter<u8>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buf.into_iter().take(self.len).chain(self.spilled)
+    }
+}
+/// Two [`DiscreteSGR`]s written as a single sequence
+///
+/// Created through [`DiscreteSGR::and`], the codes of the first are written before the second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Combined<A, B>(pub A, pub B);
+/// The sequence returned by [`Color::reset_all`], `39;49`
+pub type ResetColors = Combined<Color, Color>;
+/// The sequence returned by [`Style::reset_effects`], `22;23;24;25;27;28;29`
+#[allow(clippy::type_complexity)]
+pub type ResetEffects = Combined<
+    Combined<Combined<Combined<Combined<Combined<Style, Style>, Style>, Style>, Style>, Style>,
+    Style,
+>;
+impl<A: DiscreteSGR, B: DiscreteSGR> Display for Combined<A, B> {
+    /// Writes one merged sequence through [`DiscreteSGR::standard_display`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.standard_display(f)
+    }
+}
+impl<A: DiscreteSGR, B: DiscreteSGR> DiscreteSGR for Combined<A, B> {
+    fn write(&self, builder: &mut SGRBuilder) {
+        self.0.write(builder);
+        self.1.write(builder);
+    }
+}
+/// Displays only the SGR parameters of a [`DiscreteSGR`], separated by `;`
+///
+/// The escape & end strings are never written, regardless of the `partial` feature,
+/// nor are formatting options applied. Also created through [`Style::params_display`]
+/// & [`Color::params_display`]. Used by the macros to place a color chosen at runtime, `{[bold (color)]}`,
+/// within a sequence created at compile time
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, DisplayParams};
+///
+///assert_eq!(DisplayParams(&ByteFg(208)).to_string(), "38;5;208");
+///assert_eq!(format!("\x1b[1;{}m", DisplayParams(&DefaultFg)), "\x1b[1;39m");
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayParams<'a, T>(pub &'a T);
+impl<T: DiscreteSGR> Display for DisplayParams<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        SGRWriter::from(f).partial_sgr(self.0)
+    }
+}
 /// Represents SGR sequences that can be used discretely.
 ///
 /// This means it doesn't exist in terms of a [`SGRString`](crate::SGRString),
 /// though it can be used in conjunction with one
 #[allow(clippy::module_name_repetitions)]
 pub trait DiscreteSGR: Sized + Display + EasySGR {
-    /// Writes a set of SGR codes to the given [`SGRWriter`]
+    /// Writes a set of SGR codes to the given [`SGRBuilder`]
     ///
     /// Writing is not an IO operation, instead writing
-    /// pushes codes to the [`SGRBuilder`]'s buffer
+    /// pushes codes to the [`SGRBuilder`]'s buffer, so it cannot fail
     fn write(&self, writer: &mut SGRBuilder);
+    /// Returns the SGR codes written by [`DiscreteSGR::write`]
+    ///
+    /// By default this writes to a new [`SGRBuilder`], capturing every code written
+    #[must_use]
+    fn codes(&self) -> Codes {
+        let mut builder = SGRBuilder::default();
+        self.write(&mut builder);
+        Codes::from_slice(builder.codes())
+    }
+    /// Returns the SGR parameters written by [`DiscreteSGR::write`] as a [`Vec`]
+    ///
+    /// Unlike [`DiscreteSGR::codes`], the codes are always allocated
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, DiscreteSGR};
+    ///
+    ///assert_eq!(RgbBg(1, 2, 3).sgr_params(), vec![48, 2, 1, 2, 3]);
+    ///```
+    #[must_use]
+    fn sgr_params(&self) -> Vec<u8> {
+        let mut builder = SGRBuilder::default();
+        self.write(&mut builder);
+        builder.codes().to_vec()
+    }
+    /// Combines self with another [`DiscreteSGR`], written as a single sequence
+    ///
+    /// Chaining nests the [`Combined`] values, so nothing is allocated
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, DiscreteSGR, Style::*};
+    ///
+    ///# #[cfg(not(feature = "partial"))]
+    ///# {
+    ///assert_eq!(format!("{}text", Bold.and(RedFg)), "\x1b[1;31mtext");
+    ///assert_eq!(format!("{}{}text", Bold, RedFg), "\x1b[1m\x1b[31mtext");
+    ///# }
+    ///```
+    #[must_use]
+    fn and<O: DiscreteSGR>(self, other: O) -> Combined<Self, O> {
+        Combined(self, other)
+    }
     /// Writes an SGR sequence to the given [`Formatter`](std::fmt::Formatter)
     ///
     /// # Errors
@@ -258,3 +1105,85 @@ pub trait DiscreteSGR: Sized + Display + EasySGR {
         SGRWriter::from(f).partial_sgr(self)
     }
 }
+/// An object safe [`DiscreteSGR`], so differing types can be stored together
+///
+/// Implemented for every [`DiscreteSGR`], usable with [`SGRWriter::sgr`] & [`SGRWriter::partial_sgr`].
+/// Slices & [`Vec`]s of boxed [`DynSGR`]s are also [`DiscreteSGRs`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, DynSGR, SGRWriter, Style::*};
+///
+///let theme: Vec<Box<dyn DynSGR>> = vec![Box::new(Bold), Box::new(RedFg)];
+///let mut writer = SGRWriter::from(String::new());
+///writer.inline_sgr_all(&theme)?;
+///writer.sgr(&*theme[0])?;
+///assert_eq!(writer.internal(), "\x1b[1;31m\x1b[1m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+pub trait DynSGR: Display {
+    /// Writes a set of SGR codes to the given [`SGRBuilder`]
+    ///
+    /// Refer to [`DiscreteSGR::write`]
+    fn write_dyn(&self, builder: &mut SGRBuilder);
+}
+impl<S: DiscreteSGR> DynSGR for S {
+    #[inline]
+    fn write_dyn(&self, builder: &mut SGRBuilder) {
+        self.write(builder);
+    }
+}
+impl DiscreteSGRs for [Box<dyn DynSGR>] {
+    fn write_each(&self, builder: &mut SGRBuilder) {
+        for sgr in self {
+            sgr.write_dyn(builder);
+        }
+    }
+}
+impl DiscreteSGRs for Vec<Box<dyn DynSGR>> {
+    fn write_each(&self, builder: &mut SGRBuilder) {
+        self.as_slice().write_each(builder);
+    }
+}
+/// A group of [`DiscreteSGR`]s, possibly of different types, written as one sequence
+///
+/// Implemented for tuples of up to five [`DiscreteSGR`]s,
+/// & for arrays, slices & [`Vec`]s of a single type.
+/// Used by [`SGRWriter::inline_sgr_all`]
+pub trait DiscreteSGRs {
+    /// Writes the codes of every item to the given [`SGRBuilder`], in order
+    fn write_each(&self, builder: &mut SGRBuilder);
+}
+impl<S: DiscreteSGR> DiscreteSGRs for [S] {
+    fn write_each(&self, builder: &mut SGRBuilder) {
+        for sgr in self {
+            sgr.write(builder);
+        }
+    }
+}
+impl<S: DiscreteSGR, const N: usize> DiscreteSGRs for [S; N] {
+    fn write_each(&self, builder: &mut SGRBuilder) {
+        self.as_slice().write_each(builder);
+    }
+}
+impl<S: DiscreteSGR> DiscreteSGRs for Vec<S> {
+    fn write_each(&self, builder: &mut SGRBuilder) {
+        self.as_slice().write_each(builder);
+    }
+}
+/// Implements [`DiscreteSGRs`] for tuples of [`DiscreteSGR`]s, written from first to last
+macro_rules! tuple_discrete_sgrs {
+    ($(($($ty:ident),+)),+) => {
+        $(
+            impl<$($ty: DiscreteSGR),+> DiscreteSGRs for ($($ty,)+) {
+                #[allow(non_snake_case)]
+                fn write_each(&self, builder: &mut SGRBuilder) {
+                    let ($($ty,)+) = self;
+                    $($ty.write(builder);)+
+                }
+            }
+        )+
+    };
+}
+tuple_discrete_sgrs!((A), (A, B), (A, B, C), (A, B, C, D), (A, B, C, D, E));
@@ -0,0 +1,149 @@
+use core::fmt;
+
+use crate::{DiscreteSGR, SGRBuilder, END, ESCAPE};
+
+/// The error returned when a buffer can not hold the sequence being encoded
+///
+/// Nothing is written to the buffer when this is returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferTooSmall {
+    needed: usize,
+}
+impl BufferTooSmall {
+    /// Returns the number of bytes the sequence needs
+    #[must_use]
+    pub const fn needed(&self) -> usize {
+        self.needed
+    }
+}
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Buffer too small, {} bytes needed", self.needed)
+    }
+}
+impl core::error::Error for BufferTooSmall {}
+
+/// Returns the number of bytes [`encode_into`] writes for the codes
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::encode::encoded_len;
+///
+///assert_eq!(encoded_len(&[1, 31]), "\x1b[1;31m".len());
+///assert_eq!(encoded_len(&[]), 0);
+///```
+#[must_use]
+pub const fn encoded_len(codes: &[u8]) -> usize {
+    if codes.is_empty() {
+        return 0;
+    }
+    // the escape, the end & a separator between each code
+    let mut len = ESCAPE.len() + END.len() + codes.len() - 1;
+    let mut i = 0;
+    while i < codes.len() {
+        len += param_len(codes[i] as u16);
+        i += 1;
+    }
+    len
+}
+/// Writes the SGR sequence of the codes into the buffer, returning the number of bytes written
+///
+/// Writes the same bytes as [`CapableWriter::write_sgr`](crate::CapableWriter::write_sgr),
+/// nothing if there are no codes.
+/// Usable in const contexts, along with the codes of [`consts::codes`](crate::consts::codes)
+///
+/// # Errors
+///
+/// Returns [`BufferTooSmall`] if the sequence does not fit, leaving the buffer untouched
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{consts::codes, encode::encode_into};
+///
+///let mut buf = [0; 16];
+///let len = encode_into(&[codes::BOLD, codes::RED_FG], &mut buf)?;
+///assert_eq!(&buf[..len], b"\x1b[1;31m");
+///assert!(encode_into(&[38, 5, 208], &mut [0; 8]).is_err());
+///# Ok::<(), easy_sgr::encode::BufferTooSmall>(())
+///```
+pub const fn encode_into(codes: &[u8], buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+    let needed = encoded_len(codes);
+    if buf.len() < needed {
+        return Err(BufferTooSmall { needed });
+    }
+    if needed == 0 {
+        return Ok(0);
+    }
+    buf[0] = ESCAPE.as_bytes()[0];
+    buf[1] = ESCAPE.as_bytes()[1];
+    let mut at = ESCAPE.len();
+    let mut i = 0;
+    while i < codes.len() {
+        if i > 0 {
+            buf[at] = b';';
+            at += 1;
+        }
+        at += write_param(codes[i] as u16, buf, at);
+        i += 1;
+    }
+    buf[at] = END.as_bytes()[0];
+    Ok(at + 1)
+}
+/// Writes the attributes as a single SGR sequence into the buffer, returning the number of bytes written
+///
+/// The codes of each attribute are [written](DiscreteSGR::write) in order to an [`SGRBuilder`],
+/// then encoded by [`encode_into`]. Nothing is allocated for up to
+/// [`SGRBuilder::INLINE_CAPACITY`] codes
+///
+/// # Errors
+///
+/// Returns [`BufferTooSmall`] if the sequence does not fit, leaving the buffer untouched
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{encode::encode_attrs_into, Color::*};
+///
+///let mut buf = [0; 32];
+///let len = encode_attrs_into(&[RedFg, RgbBg(1, 2, 3)], &mut buf)?;
+///assert_eq!(&buf[..len], b"\x1b[31;48;2;1;2;3m");
+///# Ok::<(), easy_sgr::encode::BufferTooSmall>(())
+///```
+pub fn encode_attrs_into<T: DiscreteSGR>(
+    attrs: &[T],
+    buf: &mut [u8],
+) -> Result<usize, BufferTooSmall> {
+    let mut builder = SGRBuilder::default();
+    for attr in attrs {
+        attr.write(&mut builder);
+    }
+    encode_into(builder.codes(), buf)
+}
+/// Returns the number of decimal digits of a parameter
+pub(crate) const fn param_len(param: u16) -> usize {
+    match param {
+        0..=9 => 1,
+        10..=99 => 2,
+        100..=999 => 3,
+        1000..=9999 => 4,
+        _ => 5,
+    }
+}
+/// Writes a parameter in decimal at the offset, returning the number of digits written
+///
+/// The buffer must have room for [`param_len`] bytes from the offset
+pub(crate) const fn write_param(mut param: u16, buf: &mut [u8], at: usize) -> usize {
+    let len = param_len(param);
+    let mut i = len;
+    while i > 0 {
+        i -= 1;
+        // a single digit always fits
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (param % 10) as u8;
+        buf[at + i] = b'0' + digit;
+        param /= 10;
+    }
+    len
+}
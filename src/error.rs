@@ -0,0 +1,120 @@
+use std::{fmt, io};
+
+/// An error encountered while writing
+///
+/// Unifies the error types of the [`CapableWriter`](crate::CapableWriter)s
+/// of this crate, so generic code can return a single error type
+#[derive(Debug)]
+pub enum Error {
+    /// An error from writing to a [`std::fmt::Write`]
+    Fmt(fmt::Error),
+    /// An error from writing to a [`std::io::Write`]
+    Io(io::Error),
+    /// An error from parsing a string
+    Parse(ParseError),
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fmt(e) => write!(f, "Error writing to formatter: {e}"),
+            Self::Io(e) => write!(f, "Error writing to io: {e}"),
+            Self::Parse(e) => write!(f, "Error parsing: {e}"),
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fmt(e) => Some(e),
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+/// Prevents [`WriteError`] from being implemented outside this crate
+mod sealed {
+    pub trait Sealed {}
+}
+/// The error of a writer wrapped by an [`UnderlyingWrite`](crate::UnderlyingWrite),
+/// [`fmt::Error`] or [`io::Error`]
+///
+/// Bounds [`UnderlyingWrite::Writer`](crate::UnderlyingWrite::Writer), so `?` converts it
+/// into an [`Error`] within generic code. This trait is sealed
+pub trait WriteError: sealed::Sealed {
+    /// Wraps self in the matching variant of [`Error`]
+    fn into_error(self) -> Error;
+}
+impl sealed::Sealed for fmt::Error {}
+impl WriteError for fmt::Error {
+    fn into_error(self) -> Error {
+        Error::Fmt(self)
+    }
+}
+impl sealed::Sealed for io::Error {}
+impl WriteError for io::Error {
+    fn into_error(self) -> Error {
+        Error::Io(self)
+    }
+}
+impl<E: WriteError> From<E> for Error {
+    /// Refer to [`WriteError::into_error`]
+    fn from(value: E) -> Self {
+        value.into_error()
+    }
+}
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        Self::Parse(value)
+    }
+}
+impl From<Error> for io::Error {
+    /// [`Error::Io`] is unwrapped, other variants are wrapped using [`io::Error::other`]
+    fn from(value: Error) -> Self {
+        match value {
+            Error::Io(e) => e,
+            e => Self::other(e),
+        }
+    }
+}
+/// An error encountered while parsing a string
+///
+/// Returned within [`Error::Parse`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+}
+impl ParseError {
+    /// Creates a new [`ParseError`] describing what went wrong
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+impl std::error::Error for ParseError {}
+/// Implements [`From`] for [`Error`], converting into an [`Error::Parse`]
+#[cfg(feature = "from-str")]
+macro_rules! from_parse_error {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for Error {
+                fn from(value: $ty) -> Self {
+                    Self::Parse(ParseError::new(value.to_string()))
+                }
+            }
+        )+
+    };
+}
+#[cfg(feature = "from-str")]
+from_parse_error!(
+    crate::discrete::from_str::ParseSeqError,
+    crate::discrete::from_str::ParseStyleError,
+    crate::discrete::from_str::ParseColorError
+);
+#[cfg(all(feature = "from-str", feature = "extended"))]
+from_parse_error!(crate::discrete::from_str::ParseExtendedError);
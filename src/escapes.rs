@@ -0,0 +1,991 @@
+use std::{
+    borrow::{Borrow, Cow},
+    fmt::{Debug, Display, Write},
+    io::{self, BufRead},
+};
+
+use crate::{
+    encode::encoded_len, writing::extended, CapableWriter, CleanKind, FmtWriter, IoWriter,
+    SGRBuilder, SGRString, StyleKind, StyleSet,
+};
+
+/// Options for [`debug_escapes_with`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EscapeOptions {
+    /// Writes the escape character as `␛` rather than `\x1b`
+    pub symbol: bool,
+    /// Replaces SGR sequences with the keywords of their parameters,
+    /// so `\x1b[1;31m` becomes `<bold red>`
+    ///
+    /// Keywords are those of the macros, unknown codes are written as `?n`
+    pub annotate: bool,
+}
+/// Returns the text with each escape character written as `\x1b`
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::debug_escapes;
+///
+///assert_eq!(debug_escapes("\x1b[1mbold"), r"\x1b[1mbold");
+///```
+#[must_use]
+pub fn debug_escapes(text: &str) -> String {
+    debug_escapes_with(text, EscapeOptions::default())
+}
+/// Returns the text with each escape character made visible, using the given options
+///
+/// Sequences other than SGR are never annotated
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{debug_escapes_with, EscapeOptions};
+///
+///let options = EscapeOptions {
+///    symbol: true,
+///    annotate: true,
+///};
+///assert_eq!(
+///    debug_escapes_with("\x1b[1;31mred\x1b[39;22m\x1b[2K", options),
+///    "<bold red>red<default !bold>␛[2K"
+///);
+///```
+#[must_use]
+pub fn debug_escapes_with(text: &str, options: EscapeOptions) -> String {
+    let escape = if options.symbol { "␛" } else { r"\x1b" };
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(['\x1b', '\u{9b}']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let sgr = introducer_len(rest).and_then(|len| {
+            let seq = &rest[len..];
+            let end = seq.find(|ch: char| !ch.is_ascii_digit() && ch != ';')?;
+            (seq.as_bytes()[end] == b'm').then_some((len, end))
+        });
+        match sgr {
+            Some((len, end)) if options.annotate => {
+                out.push('<');
+                annotate(&mut out, &rest[len..len + end]);
+                out.push('>');
+                rest = &rest[len + end + 1..];
+            }
+            // the C1 introducer is always escaped, as it has no symbol
+            _ if rest.starts_with('\u{9b}') => {
+                out.push_str(r"\u{9b}");
+                rest = &rest['\u{9b}'.len_utf8()..];
+            }
+            _ => {
+                out.push_str(escape);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+/// Returns the attributes still in effect at the end of the text
+///
+/// Tracks SGR sequences through [`StyleSet::apply_codes`],
+/// the same as `sgr_segments` of the `testing` feature without collecting any segments.
+/// An [empty](StyleSet::is_empty) result means text appended afterwards is unstyled
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{open_state, Color::*, StyleSet};
+///
+///assert_eq!(open_state("\x1b[1mbold\x1b[22m \x1b[31mred"), StyleSet::from(RedFg));
+///assert!(open_state("plain").is_empty());
+///```
+#[must_use]
+pub fn open_state(text: &str) -> StyleSet {
+    let mut state = StyleSet::default();
+    let mut rest = text;
+    while let Some((_, params, end)) = find_sgr(rest) {
+        apply_params(&mut state, params);
+        rest = &rest[end..];
+    }
+    state
+}
+/// Returns `true` if nothing is left styled at the end of the text
+///
+/// So no reset is needed before appending unstyled text, refer to [`open_state`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::ends_with_reset;
+///
+///assert!(ends_with_reset("\x1b[1mbold\x1b[0m"));
+///assert!(!ends_with_reset("\x1b[0;31mred"));
+///```
+#[must_use]
+pub fn ends_with_reset(text: &str) -> bool {
+    open_state(text).is_empty()
+}
+/// Removes the SGR sequences of the text that do not change the attributes in effect,
+/// merging those that remain between each piece of text into one
+///
+/// The attributes are tracked as by a [`StatefulWriter`](crate::StatefulWriter):
+/// before each piece of text the [transition](StyleSet::transition) into its attributes is written,
+/// or a reset followed by them when that is shorter. So re-applying a color already in effect,
+/// resetting twice or resetting unstyled text writes nothing.
+/// Other escape sequences are kept as text.
+///
+/// Text without SGR sequences, or whose sequences are all needed already, is borrowed,
+/// as is text with a parameter that is not a code, such as `300`, its effect not being known
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::optimize;
+///
+///assert_eq!(optimize("\x1b[31mfoo\x1b[31mbar\x1b[0m\x1b[0m"), "\x1b[31mfoobar\x1b[0m");
+///assert_eq!(optimize("\x1b[0m\x1b[1m\x1b[33mhi"), "\x1b[33;1mhi");
+///assert_eq!(optimize("\x1b[31mfoo\x1b[32mbar"), "\x1b[31mfoo\x1b[32mbar");
+///```
+#[must_use]
+pub fn optimize(text: &str) -> Cow<'_, str> {
+    let mut out = String::with_capacity(text.len());
+    let (mut read, mut written) = (StyleSet::default(), StyleSet::default());
+    let mut rest = text;
+    loop {
+        let (visible, sequence) = match find_sgr(rest) {
+            Some((start, params, end)) => (&rest[..start], Some((params, end))),
+            None => (rest, None),
+        };
+        if !visible.is_empty() || sequence.is_none() {
+            push_transition(&mut out, &written, &read);
+            out.push_str(visible);
+            written.clone_from(&read);
+        }
+        let Some((params, end)) = sequence else {
+            break;
+        };
+        let unknown = |param: &str| !param.is_empty() && param.parse::<u8>().is_err();
+        if params.split(';').any(unknown) {
+            return Cow::Borrowed(text);
+        }
+        apply_params(&mut read, params);
+        rest = &rest[end..];
+    }
+    if out == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(out)
+    }
+}
+/// Returns the [`SGRString`] with [inverse](crate::Style::Inverse) toggled over all of it,
+/// such as to highlight a selection
+///
+/// Inverse is placed before the text, or turned off if the string places it.
+/// Within the text `7` & `27` are swapped & inverse is placed again after each reset,
+/// so what was shown inverse is shown plain & the other way around.
+/// After the text the inverse is restored as it would have been, so inverting twice cancels out.
+/// Text before the string is taken to not be inverse.
+/// Sequences with a parameter that is not a code, such as `300`, are kept as is
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{invert_region, EasySGR};
+///
+///let selected = invert_region(&"to \x1b[7mdo\x1b[27m list".to_sgr());
+///assert_eq!(selected.text, "\x1b[7mto \x1b[27mdo\x1b[7m list\x1b[27m");
+///```
+#[must_use]
+pub fn invert_region(sgr: &SGRString) -> SGRString {
+    // whether the original is inverse, what is written being the opposite
+    let mut inverse = sgr.inverse == StyleKind::Place;
+    let mut text = String::with_capacity(sgr.text.len() + 12);
+    push_inverse(&mut text, !inverse);
+    let mut rest = sgr.text.as_str();
+    while let Some((start, params, end)) = find_sgr(rest) {
+        text.push_str(&rest[..start]);
+        let codes: Option<Vec<u8>> = params
+            .split(';')
+            .map(|param| {
+                if param.is_empty() {
+                    Some(0)
+                } else {
+                    param.parse().ok()
+                }
+            })
+            .collect();
+        match codes {
+            Some(codes) => {
+                let mut builder = SGRBuilder::default();
+                let mut codes = codes.into_iter();
+                while let Some(code) = codes.next() {
+                    match code {
+                        0 => {
+                            inverse = false;
+                            builder.write_codes(&[0, 7]);
+                        }
+                        7 | 27 => {
+                            inverse = code == 7;
+                            builder.write_code(if inverse { 27 } else { 7 });
+                        }
+                        38 | 48 | 58 => {
+                            builder.write_code(code);
+                            extended(&mut codes, &mut builder);
+                        }
+                        code => builder.write_code(code),
+                    }
+                }
+                // writing to a String never fails
+                let _ = builder.write_to(&mut FmtWriter(&mut text));
+            }
+            None => text.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+    // the inverse the original is left with once cleaned, what is written being the opposite
+    let restored = match (&sgr.clean, &sgr.inverse) {
+        (CleanKind::Reset, _) => None,
+        (CleanKind::Reverse, StyleKind::Place) => Some(false),
+        (CleanKind::Reverse, StyleKind::Clean) => Some(true),
+        _ => Some(inverse),
+    };
+    if let Some(restored) = restored.filter(|&restored| restored == inverse) {
+        push_inverse(&mut text, restored);
+    }
+    SGRString {
+        text,
+        inverse: StyleKind::None,
+        ..sgr.clone()
+    }
+}
+/// Writes the sequence placing inverse or turning it off
+fn push_inverse(out: &mut String, inverse: bool) {
+    out.push_str(if inverse { "\x1b[7m" } else { "\x1b[27m" });
+}
+/// Writes the shorter of the transition from `prev` into `next` & a reset placing `next`
+fn push_transition(out: &mut String, prev: &StyleSet, next: &StyleSet) {
+    // writing to a String never fails
+    let _ = shortest_transition(prev, next).write_to(&mut FmtWriter(out));
+}
+/// Returns the shorter of the transition from `prev` into `next` & a reset placing `next`
+///
+/// Custom codes can only be turned off by a reset,
+/// so one is always returned when `prev` has any that `next` does not
+pub(crate) fn shortest_transition(prev: &StyleSet, next: &StyleSet) -> SGRBuilder {
+    let mut transition = SGRBuilder::default();
+    prev.transition(next, &mut transition);
+    let mut reset = SGRBuilder::default();
+    reset.write_code(0);
+    StyleSet::default().transition(next, &mut reset);
+    let removes_custom = prev
+        .custom_places
+        .iter()
+        .any(|code| !next.custom_places.contains(code));
+    if removes_custom || encoded_len(reset.codes()) < encoded_len(transition.codes()) {
+        reset
+    } else {
+        transition
+    }
+}
+/// How [`sanitize`] & [`SanitizingWriter`](crate::SanitizingWriter) treat escape sequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SanitizePolicy {
+    /// Removes every escape sequence & C1 control
+    ///
+    /// Text [hidden](crate::Style::Hidden) by a removed sequence would no longer be hidden,
+    /// so each of its [`char`]s but `\n` is replaced with `*`
+    StripAll,
+    /// Removes every escape sequence & C1 control, except SGR sequences, i.e. `\x1b[1;31m`
+    ///
+    /// Only SGR sequences beginning with `\x1b[` are kept, not those beginning with the C1 CSI
+    StripNonSGR,
+    /// Replaces the escape character & C1 controls with `␛`,
+    /// so sequences are shown rather than interpreted
+    Neutralize,
+}
+/// Removes or neutralizes the escape sequences of untrusted text, according to a [`SanitizePolicy`]
+///
+/// Recognizes control sequences, `\x1b[..`, strings such as OSC, `\x1b]..`,
+/// ended by BEL or ST, & two character escapes, along with their C1 forms, i.e. `\u{9b}`.
+/// A sequence left unfinished at the end of the text is removed.
+/// Text without escapes or C1 controls is borrowed
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{sanitize, SanitizePolicy::*};
+///
+///let name = "\x1b]0;pwned\x07\x1b[1mfile\x1b[2J";
+///assert_eq!(sanitize(name, StripAll), "file");
+///assert_eq!(sanitize(name, StripNonSGR), "\x1b[1mfile");
+///assert_eq!(sanitize(name, Neutralize), "␛]0;pwned\x07␛[1mfile␛[2J");
+///```
+#[must_use]
+pub fn sanitize(text: &str, policy: SanitizePolicy) -> Cow<'_, str> {
+    if !text.contains(is_escape) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    Sanitizer::new(policy).feed(text, &mut out);
+    Cow::Owned(out)
+}
+/// Writes the keywords of the `;` separated parameters, separated by spaces
+pub(crate) fn annotate(out: &mut String, params: &str) {
+    let mut codes = params
+        .split(';')
+        .map(|param| (param, param.parse::<u8>().ok()));
+    let mut first = true;
+    while let Some((param, code)) = codes.next() {
+        if !std::mem::take(&mut first) {
+            out.push(' ');
+        }
+        let Some(code) = code else {
+            // an empty parameter is a reset
+            if param.is_empty() {
+                out.push_str("reset");
+            } else {
+                let _ = write!(out, "?{param}");
+            }
+            continue;
+        };
+        let keyword = match code {
+            0 => "reset",
+            1 => "bold",
+            2 => "dim",
+            3 => "italic",
+            4 => "underline",
+            5 => "blink",
+            6 => "rapid-blink",
+            7 => "inverse",
+            8 => "hide",
+            9 => "strike",
+            10..=19 => {
+                let _ = write!(out, "font{}", code - 10);
+                continue;
+            }
+            22 => "!bold",
+            23 => "!italic",
+            24 => "!underline",
+            25 => "!blink",
+            26 => "proportional",
+            27 => "!inverse",
+            28 => "!hide",
+            29 => "!strike",
+            38 | 48 => {
+                if code == 48 {
+                    out.push_str("on-");
+                }
+                let mut next = || codes.next().and_then(|(_, code)| code);
+                match next() {
+                    Some(5) => match next() {
+                        Some(n) => {
+                            let _ = write!(out, "{n}");
+                        }
+                        None => out.push_str("?5"),
+                    },
+                    Some(2) => match (next(), next(), next()) {
+                        (Some(r), Some(g), Some(b)) => {
+                            let _ = write!(out, "{r},{g},{b}");
+                        }
+                        _ => out.push_str("?2"),
+                    },
+                    _ => {
+                        let _ = write!(out, "?{code}");
+                    }
+                }
+                continue;
+            }
+            30..=37 | 40..=47 | 90..=97 | 100..=107 => {
+                if matches!(code, 40..=47 | 100..=107) {
+                    out.push_str("on-");
+                }
+                if code >= 90 {
+                    out.push_str("bright-");
+                }
+                [
+                    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+                ][usize::from(code % 10)]
+            }
+            39 => "default",
+            49 => "on-default",
+            50 => "!proportional",
+            51 => "framed",
+            52 => "encircled",
+            53 => "overline",
+            54 => "!framed",
+            55 => "!overline",
+            60 => "ideogram-underline",
+            61 => "ideogram-double-underline",
+            62 => "ideogram-overline",
+            63 => "ideogram-double-overline",
+            64 => "ideogram-stress",
+            65 => "!ideogram",
+            code => {
+                let _ = write!(out, "?{code}");
+                continue;
+            }
+        };
+        out.push_str(keyword);
+    }
+}
+/// Displays the inner value with its SGR sequences annotated
+///
+/// Refer to [`EscapeOptions::annotate`].
+/// [`Debug`] prints the same, quoted, so failed assertions show readable styles
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, DisplayEscaped, EasySGR, Style::*};
+///
+///let text = "text".color(RedFg).style(Bold);
+///assert_eq!(DisplayEscaped(&text).to_string(), "<red bold>text");
+///assert_eq!(DisplayEscaped(&text), DisplayEscaped("\x1b[31;1mtext"));
+///```
+#[derive(Clone, Copy)]
+pub struct DisplayEscaped<T>(pub T);
+impl<T: Display> Display for DisplayEscaped<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let options = EscapeOptions {
+            symbol: false,
+            annotate: true,
+        };
+        f.write_str(&debug_escapes_with(&self.0.to_string(), options))
+    }
+}
+impl<T: Display> Debug for DisplayEscaped<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{self}\"")
+    }
+}
+impl<T: Display, U: Display> PartialEq<DisplayEscaped<U>> for DisplayEscaped<T> {
+    /// Compares the displayed text, escapes included
+    fn eq(&self, other: &DisplayEscaped<U>) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+/// Displays segments of text & the attributes in effect, as returned by `sgr_segments` of the `testing` feature
+///
+/// Only the attributes changed between consecutive segments are written, refer to [`StyleSet::transition`],
+/// so segments styled the same have no sequence between them.
+/// A reset is written at the end only if the last segment is styled.
+/// Accepts anything iterated more than once, such as a slice or a reference to a [`Vec`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, DisplaySegments, Style::*, StyleSet};
+///
+///let segments = [
+///    (String::from("a"), StyleSet::from(Bold)),
+///    (String::from("b"), StyleSet::from((Bold, RedFg))),
+///    (String::from("c"), StyleSet::default()),
+///];
+///assert_eq!(DisplaySegments(&segments).to_string(), "\x1b[1ma\x1b[31mb\x1b[39;22mc");
+///```
+#[derive(Debug, Clone, Copy)]
+pub struct DisplaySegments<I>(pub I);
+impl<I> DisplaySegments<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Borrow<(String, StyleSet)>,
+{
+    /// Writes the segments to the given writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        let mut state = StyleSet::default();
+        let mut builder = SGRBuilder::default();
+        for segment in self.0.clone() {
+            let (text, style) = segment.borrow();
+            builder.clear();
+            state.transition(style, &mut builder);
+            builder.write_to(writer)?;
+            writer.write(text)?;
+            state.clone_from(style);
+        }
+        if state.is_empty() {
+            Ok(())
+        } else {
+            writer.write_sgr(&[0])
+        }
+    }
+}
+impl<I> Display for DisplaySegments<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Borrow<(String, StyleSet)>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(&mut FmtWriter(f))
+    }
+}
+/// Displays a value already styled by SGR sequences, with more attributes placed beneath its own
+///
+/// Wrapping an [`SGRString`](crate::SGRString) in another would write two sets of sequences,
+/// the inner clean undoing the outer attributes before the text is done.
+/// Instead the value is formatted, its sequences are read, & the text is written once
+/// with the attributes of both, those of the value winning where both set one.
+/// The attributes are turned off at the end in a single sequence,
+/// only what was written being turned off.
+/// Created by the macros through `{value&[keywords]}`
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{CleanKind, Color::*, EasySGR, Merge, Style::*};
+///
+///let red = "red".color(RedFg).clean(CleanKind::Reset);
+///assert_eq!(Merge::new(&red, Bold).to_string(), "\x1b[31;1mred\x1b[39;22m");
+///assert_eq!(Merge::from_codes("plain", &[4]).to_string(), "\x1b[4mplain\x1b[24m");
+///```
+#[derive(Debug, Clone)]
+pub struct Merge<T>(pub T, pub StyleSet);
+impl<T> Merge<T> {
+    /// Creates a [`Merge`] placing the attributes beneath those of the value
+    pub fn new(value: T, style: impl Into<StyleSet>) -> Self {
+        Self(value, style.into())
+    }
+    /// Creates a [`Merge`] placing the attributes the codes turn on, refer to [`StyleSet::apply_codes`]
+    #[must_use]
+    pub fn from_codes(value: T, codes: &[u8]) -> Self {
+        let mut style = StyleSet::default();
+        style.apply_codes(codes);
+        Self(value, style)
+    }
+}
+impl<T: Display> Merge<T> {
+    /// Writes the value to the given writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        let text = self.0.to_string();
+        let (mut read, mut written) = (StyleSet::default(), StyleSet::default());
+        let mut builder = SGRBuilder::default();
+        let mut rest = &*text;
+        loop {
+            let (visible, sequence) = match find_sgr(rest) {
+                Some((start, params, end)) => (&rest[..start], Some((params, end))),
+                None => (rest, None),
+            };
+            if !visible.is_empty() {
+                let style = self.1.clone().layered(&read);
+                builder.clear();
+                written.transition(&style, &mut builder);
+                builder.write_to(writer)?;
+                writer.write(visible)?;
+                written = style;
+            }
+            let Some((params, end)) = sequence else {
+                break;
+            };
+            apply_params(&mut read, params);
+            rest = &rest[end..];
+        }
+        builder.clear();
+        written.transition(&StyleSet::default(), &mut builder);
+        builder.write_to(writer)
+    }
+}
+impl<T: Display> Display for Merge<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(&mut FmtWriter(f))
+    }
+}
+/// Recolors styled text read line by line, writing each line once its attributes are replaced
+///
+/// The text of each line is split where the attributes in effect change, as by `sgr_segments` of
+/// the `testing` feature, the callback returning the attributes to write each piece with.
+/// Attributes are carried across lines, both those read & those written,
+/// so a color opened on one line & closed on another is seen on each line between.
+/// Only the changes between pieces are written, a reset at the end if anything is left styled.
+/// The writer is flushed after every line, so the filter can be used within a pipeline.
+/// Invalid UTF-8 is replaced, refer to [`String::from_utf8_lossy`]
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{recolor_lines, ColorKind, StyleSet};
+///
+///let input = "\x1b[31mred\nstill red\x1b[39m plain\n";
+///let mut output = Vec::new();
+///recolor_lines(input.as_bytes(), &mut output, |_, style| StyleSet {
+///    foreground: match style.foreground {
+///        ColorKind::Red => ColorKind::Blue,
+///        ref color => color.clone(),
+///    },
+///    ..style.clone()
+///})?;
+///assert_eq!(output, b"\x1b[34mred\nstill red\x1b[39m plain\n");
+///# Ok::<(), std::io::Error>(())
+///```
+pub fn recolor_lines(
+    mut reader: impl BufRead,
+    writer: impl io::Write,
+    mut recolor: impl FnMut(&str, &StyleSet) -> StyleSet,
+) -> io::Result<()> {
+    let mut writer = IoWriter(writer);
+    let (mut read, mut written) = (StyleSet::default(), StyleSet::default());
+    let mut builder = SGRBuilder::default();
+    let mut buf = Vec::new();
+    while reader.read_until(b'\n', &mut buf)? != 0 {
+        let line = String::from_utf8_lossy(&buf);
+        let mut rest = &*line;
+        loop {
+            let (visible, sequence) = match find_sgr(rest) {
+                Some((start, params, end)) => (&rest[..start], Some((params, end))),
+                None => (rest, None),
+            };
+            if !visible.is_empty() {
+                let style = recolor(visible, &read);
+                builder.clear();
+                written.transition(&style, &mut builder);
+                builder.write_to(&mut writer)?;
+                writer.write(visible)?;
+                written = style;
+            }
+            let Some((params, end)) = sequence else {
+                break;
+            };
+            apply_params(&mut read, params);
+            rest = &rest[end..];
+        }
+        writer.0.flush()?;
+        buf.clear();
+    }
+    if !written.is_empty() {
+        writer.write_sgr(&[0])?;
+    }
+    writer.0.flush()
+}
+/// Maps between the visible columns of escaped text & its byte offsets
+///
+/// Escape sequences, `\x1b[..` or `\u{9b}..`, are zero width, every other [`char`] one column,
+/// wide [`char`]s such as CJK ideographs included.
+/// Stored as runs of [`char`]s of the same length in bytes, each after the escapes preceding it,
+/// so a long line takes an entry for each escape rather than for each byte.
+/// Created through [`offset_map`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OffsetMap {
+    runs: Vec<OffsetRun>,
+    /// The bytes of escape sequences after the last run
+    trailing: usize,
+}
+/// A run of [`char`]s of an [`OffsetMap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct OffsetRun {
+    /// The bytes of escape sequences before the run
+    skipped: usize,
+    /// The number of [`char`]s in the run
+    chars: usize,
+    /// The length in bytes of each [`char`]
+    char_len: u8,
+}
+impl OffsetMap {
+    /// Returns the number of visible columns
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.runs.iter().map(|run| run.chars).sum()
+    }
+    /// Returns the length of the text in bytes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.runs
+            .iter()
+            .map(|run| run.skipped + run.chars * usize::from(run.char_len))
+            .sum::<usize>()
+            + self.trailing
+    }
+    /// Returns `true` if the text is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.runs.is_empty() && self.trailing == 0
+    }
+    /// Returns the byte offset of the visible column
+    ///
+    /// That is the start of its [`char`], after any escapes before it.
+    /// The column after the last is the end of the text, further columns [`None`]
+    #[must_use]
+    pub fn visible_to_byte(&self, column: usize) -> Option<usize> {
+        let (mut byte, mut start) = (0, 0);
+        for run in &self.runs {
+            byte += run.skipped;
+            if column < start + run.chars {
+                return Some(byte + (column - start) * usize::from(run.char_len));
+            }
+            byte += run.chars * usize::from(run.char_len);
+            start += run.chars;
+        }
+        (column == start).then_some(byte + self.trailing)
+    }
+    /// Returns the visible column of the byte offset
+    ///
+    /// An offset within a [`char`] is its column,
+    /// one within an escape sequence the column of the [`char`] after it.
+    /// The end of the text is the column after the last, offsets past it [`None`]
+    #[must_use]
+    pub fn byte_to_visible(&self, offset: usize) -> Option<usize> {
+        let (mut byte, mut column) = (0, 0);
+        for run in &self.runs {
+            byte += run.skipped;
+            if offset < byte {
+                return Some(column);
+            }
+            let end = byte + run.chars * usize::from(run.char_len);
+            if offset < end {
+                return Some(column + (offset - byte) / usize::from(run.char_len));
+            }
+            byte = end;
+            column += run.chars;
+        }
+        (offset <= byte + self.trailing).then_some(column)
+    }
+}
+/// Maps the visible columns of the text to its byte offsets, in one pass
+///
+/// Refer to [`OffsetMap`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::offset_map;
+///
+///let map = offset_map("a\x1b[31mé\x1b[0m");
+///assert_eq!(map.width(), 2);
+///assert_eq!(map.visible_to_byte(1), Some(6));
+///assert_eq!(map.byte_to_visible(7), Some(1));
+///assert_eq!(map.byte_to_visible(3), Some(1));
+///assert_eq!(map.visible_to_byte(2), Some(12));
+///```
+#[must_use]
+pub fn offset_map(text: &str) -> OffsetMap {
+    let mut runs: Vec<OffsetRun> = Vec::new();
+    let mut skipped = 0;
+    for piece in visible_chars(text) {
+        let ch = match piece {
+            Visible::Sequence(sequence) => {
+                skipped += sequence.len();
+                continue;
+            }
+            Visible::Char(ch) => ch,
+        };
+        // a char is at most 4 bytes
+        #[allow(clippy::cast_possible_truncation)]
+        let char_len = ch.len_utf8() as u8;
+        match runs.last_mut() {
+            Some(run) if skipped == 0 && run.char_len == char_len => run.chars += 1,
+            _ => runs.push(OffsetRun {
+                skipped,
+                chars: 1,
+                char_len,
+            }),
+        }
+        skipped = 0;
+    }
+    OffsetMap {
+        runs,
+        trailing: skipped,
+    }
+}
+/// Finds the next SGR sequence, returning its start, parameters & end
+///
+/// Sequences beginning with either [`ESCAPE`](crate::ESCAPE) or [`C1_ESCAPE`](crate::C1_ESCAPE) are found
+pub(crate) fn find_sgr(text: &str) -> Option<(usize, &str, usize)> {
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find(['\x1b', '\u{9b}']) {
+        let start = offset + start;
+        let Some(len) = introducer_len(&text[start..]) else {
+            offset = start + 1;
+            continue;
+        };
+        let seq = &text[start + len..];
+        let end = seq.find(|ch: char| !ch.is_ascii_digit() && ch != ';');
+        match end {
+            Some(end) if seq.as_bytes()[end] == b'm' => {
+                return Some((start, &seq[..end], start + len + end + 1));
+            }
+            _ => offset = start + len,
+        }
+    }
+    None
+}
+/// Returns the length of the control sequence introducer the text begins with, if any
+///
+/// Either [`ESCAPE`](crate::ESCAPE) or [`C1_ESCAPE`](crate::C1_ESCAPE)
+pub(crate) fn introducer_len(text: &str) -> Option<usize> {
+    if text.starts_with("\x1b[") || text.starts_with('\u{9b}') {
+        Some(2)
+    } else {
+        None
+    }
+}
+/// Returns the length of the control sequence the text begins with, if any
+///
+/// The sequence begins with an [introducer](introducer_len) & runs up to and including
+/// its final byte, `@` to `~`, or to the end of the text when it has none
+pub(crate) fn sequence_len(text: &str) -> Option<usize> {
+    let len = introducer_len(text)?;
+    let rest = &text[len..];
+    Some(
+        len + rest
+            .find(|ch| matches!(ch, '@'..='~'))
+            .map_or(rest.len(), |end| end + 1),
+    )
+}
+/// A piece of text split by [`visible_chars`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Visible<'a> {
+    /// A [`char`] shown by a terminal, taking one column
+    Char(char),
+    /// A control sequence, taking none, refer to [`sequence_len`]
+    Sequence(&'a str),
+}
+/// Splits the text into its visible [`char`]s & the control sequences between them
+///
+/// Every column of the text is measured through here,
+/// so [`offset_map`] & `visible_width` of the `table` feature agree
+pub(crate) fn visible_chars(text: &str) -> impl Iterator<Item = Visible<'_>> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if let Some(len) = sequence_len(rest) {
+            let (sequence, after) = rest.split_at(len);
+            rest = after;
+            return Some(Visible::Sequence(sequence));
+        }
+        let ch = rest.chars().next()?;
+        rest = &rest[ch.len_utf8()..];
+        Some(Visible::Char(ch))
+    })
+}
+/// Applies the parameters of an SGR sequence to the attributes in effect
+///
+/// Parameters that are not numbers are read as `0`
+pub(crate) fn apply_params(state: &mut StyleSet, params: &str) {
+    let codes: Vec<u8> = params
+        .split(';')
+        .map(|param| param.parse().unwrap_or(0))
+        .collect();
+    state.apply_codes(&codes);
+}
+/// Returns `true` for the escape character & C1 controls
+const fn is_escape(ch: char) -> bool {
+    matches!(ch, '\x1b' | '\u{80}'..='\u{9f}')
+}
+/// Where a [`Sanitizer`] is within an escape sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SanitizeState {
+    /// Within plain text
+    Text,
+    /// After the escape character
+    Escape,
+    /// After the intermediate bytes of a two character escape, i.e. `\x1b(`
+    Intermediate,
+    /// Within a control sequence, `\x1b[`
+    Csi,
+    /// Within a string ended by BEL or ST, such as OSC, `\x1b]`
+    String,
+}
+/// Applies a [`SanitizePolicy`] to text, keeping its state across calls
+///
+/// Sequences may be split across calls to [`Sanitizer::feed`],
+/// a control sequence being held back until it is known whether it is an SGR sequence
+#[derive(Debug, Clone)]
+pub(crate) struct Sanitizer {
+    policy: SanitizePolicy,
+    state: SanitizeState,
+    pending: String,
+    hidden: bool,
+}
+impl Sanitizer {
+    /// Creates a [`Sanitizer`] within plain text
+    pub(crate) const fn new(policy: SanitizePolicy) -> Self {
+        Self {
+            policy,
+            state: SanitizeState::Text,
+            pending: String::new(),
+            hidden: false,
+        }
+    }
+    /// Returns the [`SanitizePolicy`] in use
+    pub(crate) const fn policy(&self) -> SanitizePolicy {
+        self.policy
+    }
+    /// Appends the sanitized text to `out`
+    pub(crate) fn feed(&mut self, text: &str, out: &mut String) {
+        if self.policy == SanitizePolicy::Neutralize {
+            out.extend(text.chars().map(|ch| if is_escape(ch) { '␛' } else { ch }));
+            return;
+        }
+        for ch in text.chars() {
+            self.push(ch, out);
+        }
+    }
+    /// Handles a single [`char`]
+    fn push(&mut self, ch: char, out: &mut String) {
+        use SanitizeState::*;
+        self.state = match (self.state, ch) {
+            (Text | Escape | Intermediate | Csi | String, '\x1b') => {
+                self.pending.clear();
+                self.pending.push(ch);
+                Escape
+            }
+            (Text | Escape | Intermediate | Csi | String, '\u{9b}') => {
+                self.pending.clear();
+                self.pending.push(ch);
+                Csi
+            }
+            (Text | Escape | Intermediate | Csi, '\u{90}' | '\u{98}' | '\u{9d}'..='\u{9f}') => {
+                String
+            }
+            // unknown C1 controls & ST
+            (Text | Escape | Intermediate | Csi | String, '\u{80}'..='\u{9f}')
+            | (String, '\x07') => Text,
+            (String, _) | (Escape, ']' | 'P' | 'X' | '^' | '_') => String,
+            (Escape, '[') | (Csi, ' '..='?') => {
+                self.pending.push(ch);
+                Csi
+            }
+            (Escape | Intermediate, ' '..='/') => Intermediate,
+            (Escape | Intermediate, '0'..='~') => Text,
+            (Csi, '@'..='~') => {
+                let params = &self.pending[self.pending.len().min(2)..];
+                let sgr = ch == 'm' && params.bytes().all(|b| b.is_ascii_digit() || b == b';');
+                if self.policy == SanitizePolicy::StripNonSGR
+                    && sgr
+                    && self.pending.starts_with("\x1b[")
+                {
+                    out.push_str(&self.pending);
+                    out.push(ch);
+                } else if self.policy == SanitizePolicy::StripAll && sgr {
+                    let mut stripped = StyleSet {
+                        hidden: if self.hidden {
+                            StyleKind::Place
+                        } else {
+                            StyleKind::None
+                        },
+                        ..StyleSet::default()
+                    };
+                    apply_params(&mut stripped, params);
+                    self.hidden = stripped.hidden == StyleKind::Place;
+                }
+                Text
+            }
+            // an unfinished sequence is dropped, the char being plain text
+            (Text | Escape | Intermediate | Csi, _) => {
+                out.push(if self.hidden && ch != '\n' { '*' } else { ch });
+                Text
+            }
+        };
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt::Write, io};
+
+use crate::{Control, EraseMode, FmtWriter, SGRString, SGRWriter};
+
+/// An in-memory frame of styled output, written to the terminal all at once
+///
+/// Everything written is recorded until [`FrameBuffer::present`],
+/// which writes the frame in a single [`write_all`](io::Write::write_all) call so it never flickers.
+/// Buffers are reused across frames, so drawing frames of a similar size does not allocate
+///
+/// When the previous frame is [retained](FrameBuffer::retained), only the lines that changed
+/// are written, the cursor moving between them. Each line should then be styled on its own,
+/// attributes not carrying over to the next line
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, Control, EasySGR, EraseMode, FrameBuffer};
+///
+///let mut frame = FrameBuffer::new().before([Control::EraseScreen(EraseMode::All)]);
+///frame.writer().place_sgr(&GreenFg.to_sgr()).unwrap();
+///frame.writer().write_inner("ready").unwrap();
+///assert_eq!(frame.as_bytes(), b"\x1b[32mready");
+///
+///let mut out = Vec::new();
+///frame.present(&mut out).unwrap();
+///assert_eq!(out, b"\x1b[2J\x1b[32mready");
+///assert!(frame.is_empty());
+///```
+#[derive(Debug, Clone, Default)]
+pub struct FrameBuffer {
+    frame: String,
+    previous: String,
+    output: String,
+    before: Vec<Control>,
+    retain: bool,
+    presented: bool,
+}
+impl FrameBuffer {
+    /// Creates an empty [`FrameBuffer`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets the control sequences written before every full frame,
+    /// i.e. [`Control::EraseScreen`]
+    #[must_use]
+    pub fn before(self, controls: impl IntoIterator<Item = Control>) -> Self {
+        Self {
+            before: controls.into_iter().collect(),
+            ..self
+        }
+    }
+    /// Sets whether the previous frame is kept, so only the lines that changed are written
+    ///
+    /// The first frame & the one after [`FrameBuffer::invalidate`] are always written in full
+    #[must_use]
+    pub fn retained(self, retain: bool) -> Self {
+        Self { retain, ..self }
+    }
+    /// Returns a writer recording into the current frame
+    pub fn writer(&mut self) -> SGRWriter<FmtWriter<&mut String>> {
+        SGRWriter::from(&mut self.frame)
+    }
+    /// Renders the styled text into the current frame
+    ///
+    /// The frame is grown once, by [`SGRString::rendered_len`], before it is written
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR, FrameBuffer};
+    ///
+    ///let mut frame = FrameBuffer::new();
+    ///frame.push(&"ready".color(GreenFg));
+    ///assert_eq!(frame.as_bytes(), b"\x1b[32mready");
+    ///```
+    pub fn push(&mut self, sgr: &SGRString) {
+        self.frame.reserve(sgr.rendered_len());
+        let mut writer = self.writer();
+        // writing to a String never fails
+        let _ = writer.place_sgr(sgr);
+        let _ = writer.write_inner(&sgr.text);
+        let _ = writer.clean_sgr(sgr);
+    }
+    /// Returns the number of bytes written to the current frame
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.frame.len()
+    }
+    /// Returns `true` if nothing has been written to the current frame
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.frame.is_empty()
+    }
+    /// Returns the bytes written to the current frame
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.frame.as_bytes()
+    }
+    /// Discards the current frame
+    pub fn clear(&mut self) {
+        self.frame.clear();
+    }
+    /// Forgets the previous frame, so the next is written in full
+    pub const fn invalidate(&mut self) {
+        self.presented = false;
+    }
+    /// Writes the current frame to `out` in a single call, then starts a new frame
+    ///
+    /// Writes the whole frame preceded by the [`before`](FrameBuffer::before) sequences,
+    /// or only the changed lines when the previous frame is [retained](FrameBuffer::retained)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails,
+    /// the frame is then written in full the next time
+    pub fn present(&mut self, out: &mut impl io::Write) -> io::Result<()> {
+        self.output.clear();
+        if self.retain && self.presented {
+            diff_lines(&self.previous, &self.frame, &mut self.output);
+        } else {
+            for control in &self.before {
+                let _ = write!(self.output, "{control}");
+            }
+            self.output.push_str(&self.frame);
+        }
+        let written = out.write_all(self.output.as_bytes());
+        self.presented = written.is_ok();
+        std::mem::swap(&mut self.previous, &mut self.frame);
+        self.frame.clear();
+        written
+    }
+}
+impl Write for FrameBuffer {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.frame.push_str(s);
+        Ok(())
+    }
+}
+/// Writes the lines of `next` that differ from `prev`
+///
+/// The cursor is expected on the last line of `prev`,
+/// and is left on the last line of `next`
+fn diff_lines(prev: &str, next: &str, out: &mut String) {
+    let prev_count = prev.matches('\n').count() + 1;
+    let mut prev_lines = prev.split('\n');
+    let mut at = prev_count - 1;
+    let mut count = 0;
+    for (i, line) in next.split('\n').enumerate() {
+        count = i + 1;
+        match prev_lines.next() {
+            Some(prev) if prev == line => continue,
+            Some(_) => move_to(out, &mut at, i),
+            None => {
+                // new lines are created by writing past the end
+                move_to(out, &mut at, i - 1);
+                out.push('\n');
+                at = i;
+            }
+        }
+        let _ = write!(
+            out,
+            "{}{line}{}",
+            Control::CursorToColumn(1),
+            Control::EraseLine(EraseMode::ToEnd)
+        );
+    }
+    // lines no longer drawn are erased
+    for i in count..prev_count {
+        move_to(out, &mut at, i);
+        let _ = write!(out, "{}", Control::EraseLine(EraseMode::All));
+    }
+    move_to(out, &mut at, count - 1);
+}
+/// Moves the cursor from the line `at` to `line`
+fn move_to(out: &mut String, at: &mut usize, line: usize) {
+    let _ = match line.cmp(at) {
+        std::cmp::Ordering::Less => write!(out, "{}", Control::CursorUp(lines(*at - line))),
+        std::cmp::Ordering::Greater => write!(out, "{}", Control::CursorDown(lines(line - *at))),
+        std::cmp::Ordering::Equal => Ok(()),
+    };
+    *at = line;
+}
+/// Converts a number of lines or columns into a parameter, saturating at [`u16::MAX`]
+fn lines(n: usize) -> u16 {
+    u16::try_from(n).unwrap_or(u16::MAX)
+}
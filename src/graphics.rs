@@ -1,6 +1,12 @@
-use std::fmt::{Debug, Display};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+};
 
-use crate::{Color, SGRBuilder, SGRWriter, Style};
+use crate::{
+    encode::encoded_len, CapableWriter, Color, DiscreteSGR, Font, ParseError, SGRBuilder,
+    SGRWriter, Style,
+};
 
 /// A String encapsulating the usage of SGR codes
 ///
@@ -69,13 +75,208 @@ pub struct SGRString {
     /// Refer to [`StyleKind`]
     pub blinking: StyleKind,
     /// Refer to [`StyleKind`]
+    pub rapid_blinking: StyleKind,
+    /// Refer to [`StyleKind`]
     pub inverse: StyleKind,
     /// Refer to [`StyleKind`]
     pub hidden: StyleKind,
     /// Refer to [`StyleKind`]
     pub strikethrough: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub framed: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub encircled: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub overline: StyleKind,
 }
 impl SGRString {
+    /// Returns the text, the same as [`SGRString::text`]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+    /// Returns the text mutably, leaving the attributes as they are
+    pub const fn text_mut(&mut self) -> &mut String {
+        &mut self.text
+    }
+    /// Replaces the text with the result of `f`, leaving the attributes as they are
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///let header = "name".color(RedFg).map_text(|text| text.to_uppercase());
+    ///assert_eq!(header.to_string(), "\x1b[31mNAME");
+    ///```
+    #[must_use]
+    pub fn map_text(self, f: impl FnOnce(String) -> String) -> Self {
+        Self {
+            text: f(self.text),
+            ..self
+        }
+    }
+    /// Returns `true` if the text is empty, regardless of the attributes
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+    /// Returns the exact number of bytes [`Display`] writes, escapes included
+    ///
+    /// The place & clean sequences are measured without being written,
+    /// so it is cheap enough to size a buffer before rendering into it
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{CleanKind, Color::*, EasySGR};
+    ///
+    ///let sgr = "text".color(RgbFg(255, 0, 10)).clean(CleanKind::Reverse);
+    ///assert_eq!(sgr.rendered_len(), sgr.to_string().len());
+    ///```
+    #[must_use]
+    pub fn rendered_len(&self) -> usize {
+        let mut builder = SGRBuilder::default();
+        self.place_all(&mut builder);
+        let place = encoded_len(builder.codes());
+        builder.clear();
+        self.clean_all(&mut builder);
+        place + self.text.len() + encoded_len(builder.codes())
+    }
+    /// Renders into a [`String`] allocated once, of [`SGRString::rendered_len`] bytes
+    ///
+    /// Returns the same as [`ToString::to_string`], which grows its buffer as it is written
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///let sgr = "text".color(RedFg);
+    ///assert_eq!(sgr.render_to_string(), sgr.to_string());
+    ///```
+    #[must_use]
+    pub fn render_to_string(&self) -> String {
+        let mut writer = SGRWriter::from(String::with_capacity(self.rendered_len()));
+        // writing to a String never fails
+        let _ = writer.place_sgr(self);
+        let _ = writer.write_inner(&self.text);
+        let _ = writer.clean_sgr(self);
+        writer.internal()
+    }
+    /// Removes every attribute, custom codes & the [`CleanKind`] included, leaving the text
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR, Style::*};
+    ///
+    ///let mut sgr = "plain".color(RedFg).style(Bold);
+    ///sgr.clear_styles();
+    ///assert_eq!(sgr.to_string(), "plain");
+    ///```
+    pub fn clear_styles(&mut self) {
+        *self = Self {
+            text: std::mem::take(&mut self.text),
+            ..Self::default()
+        };
+    }
+    /// Returns the foreground, [`None`] if it is not set
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///assert_eq!("text".color(RedFg).color(BlueFg).foreground(), Some(BlueFg));
+    ///assert_eq!("text".to_sgr().foreground(), None);
+    ///```
+    #[must_use]
+    pub const fn foreground(&self) -> Option<Color> {
+        self.foreground.as_fg()
+    }
+    /// Returns the background, [`None`] if it is not set
+    #[must_use]
+    pub const fn background(&self) -> Option<Color> {
+        self.background.as_bg()
+    }
+    /// Returns an iterator over the styles placed & cleaned, in the order of their fields
+    ///
+    /// [`Style::Reset`] comes first when [`SGRString::reset`] is set,
+    /// cleaned styles are returned as their undoing style, i.e. [`Style::NotBold`].
+    /// Custom codes are within [`SGRString::custom_places`] & [`SGRString::custom_cleans`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{EasySGR, Style::*};
+    ///
+    ///let header = "error".style(Bold).style(NotItalic);
+    ///assert!(header.styles().eq([Bold, NotItalic]));
+    ///```
+    pub fn styles(&self) -> impl Iterator<Item = Style> + '_ {
+        use Style::*;
+        let styles = [
+            (&self.bold, Bold, NotBold),
+            (&self.dim, Dim, NotDim),
+            (&self.italic, Italic, NotItalic),
+            (&self.underline, Underline, NotUnderline),
+            (&self.blinking, Blinking, NotBlinking),
+            (&self.rapid_blinking, RapidBlinking, NotBlinking),
+            (&self.inverse, Inverse, NotInverse),
+            (&self.hidden, Hidden, NotHidden),
+            (&self.strikethrough, Strikethrough, NotStrikethrough),
+            (&self.framed, Framed, NotFramedEncircled),
+            (&self.encircled, Encircled, NotFramedEncircled),
+            (&self.overline, Overline, NotOverline),
+        ];
+        self.reset
+            .then_some(Reset)
+            .into_iter()
+            .chain(
+                styles
+                    .into_iter()
+                    .filter_map(|(kind, place, clean)| match kind {
+                        StyleKind::None => None,
+                        StyleKind::Place => Some(place),
+                        StyleKind::Clean => Some(clean),
+                    }),
+            )
+    }
+    /// Returns `true` if the style is among [`SGRString::styles`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR, Style::*};
+    ///
+    ///let header = "error".style(Bold).color(RedFg);
+    ///assert!(header.has_style(&Bold) && header.foreground() == Some(RedFg));
+    ///assert!(!header.has_style(&Italic));
+    ///```
+    #[must_use]
+    pub fn has_style(&self, style: &Style) -> bool {
+        self.styles().any(|s| s == *style)
+    }
+    /// Sets the foreground to whichever of black or white is more legible on the background
+    ///
+    /// Does nothing without a background, refer to [`Color::contrasting_fg`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///let badge = " tag ".color(YellowBg).auto_fg();
+    ///assert_eq!(badge.to_string(), "\x1b[30;43m tag ");
+    ///```
+    #[must_use]
+    pub fn auto_fg(self) -> Self {
+        let Some(background) = self.background.as_bg() else {
+            return self;
+        };
+        self.color(background.contrasting_fg())
+    }
     /// Writes all contained SGR codes to the given [`SGRBuilder`]
     ///
     /// Does not perform any IO operations
@@ -104,8 +305,16 @@ impl SGRString {
             Byte(n) => builder.write_codes(&[38, 5, n]),
             Rgb(r, g, b) => builder.write_codes(&[38, 2, r, g, b]),
             Default => builder.write_code(39),
+            BrightBlack => builder.write_code(90),
+            BrightRed => builder.write_code(91),
+            BrightGreen => builder.write_code(92),
+            BrightYellow => builder.write_code(93),
+            BrightBlue => builder.write_code(94),
+            BrightMagenta => builder.write_code(95),
+            BrightCyan => builder.write_code(96),
+            BrightWhite => builder.write_code(97),
             ColorKind::None => (),
-        };
+        }
         match self.background {
             Black => builder.write_code(40),
             Red => builder.write_code(41),
@@ -118,28 +327,46 @@ impl SGRString {
             Byte(n) => builder.write_codes(&[48, 5, n]),
             Rgb(r, g, b) => builder.write_codes(&[48, 2, r, g, b]),
             Default => builder.write_code(49),
+            BrightBlack => builder.write_code(100),
+            BrightRed => builder.write_code(101),
+            BrightGreen => builder.write_code(102),
+            BrightYellow => builder.write_code(103),
+            BrightBlue => builder.write_code(104),
+            BrightMagenta => builder.write_code(105),
+            BrightCyan => builder.write_code(106),
+            BrightWhite => builder.write_code(107),
             ColorKind::None => (),
-        };
+        }
     }
     /// Writes SGR style codes to the given [`SGRWriter`]
     ///
+    /// Styles turned off are written before those placed,
+    /// so a code shared by two styles, such as `22`, never undoes one placed alongside it
+    ///
     /// Does not perform any IO operations
     pub fn place_styles(&self, builder: &mut SGRBuilder) {
-        use StyleKind::*;
-        for (kind, place, not) in [
+        let styles = [
             (&self.bold, 1, 22),
             (&self.dim, 2, 22),
             (&self.italic, 3, 23),
             (&self.underline, 4, 24),
             (&self.blinking, 5, 25),
+            (&self.rapid_blinking, 6, 25),
             (&self.inverse, 7, 27),
             (&self.hidden, 8, 28),
             (&self.strikethrough, 9, 29),
-        ] {
-            match kind {
-                None => (),
-                Place => builder.write_code(place),
-                Clean => builder.write_code(not),
+            (&self.framed, 51, 54),
+            (&self.encircled, 52, 54),
+            (&self.overline, 53, 55),
+        ];
+        for (kind, _, not) in styles {
+            if *kind == StyleKind::Clean {
+                builder.write_code(not);
+            }
+        }
+        for (kind, place, _) in styles {
+            if *kind == StyleKind::Place {
+                builder.write_code(place);
             }
         }
     }
@@ -165,6 +392,45 @@ impl SGRString {
         }
         self.clean_custom(builder);
     }
+    /// Writes the codes of [`SGRString::place_all`] to the writer, without the text
+    ///
+    /// Together with [`SGRString::write_clean`] this styles content written separately,
+    /// such as a large body streamed piece by piece, without buffering it into the [`SGRString`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{CleanKind, Color::*, EasySGR, SGRWriter};
+    ///
+    ///let sgr = "".color(RedFg).clean(CleanKind::Reverse);
+    ///let mut writer = SGRWriter::from(String::new());
+    ///sgr.write_place(&mut writer)?;
+    ///for line in ["streamed ", "body"] {
+    ///    writer.write_inner(line)?;
+    ///}
+    ///sgr.write_clean(&mut writer)?;
+    ///assert_eq!(writer.internal(), "\x1b[31mstreamed body\x1b[39m");
+    ///# Ok::<(), std::fmt::Error>(())
+    ///```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_place<W: CapableWriter>(&self, writer: &mut SGRWriter<W>) -> Result<(), W::Error> {
+        writer.place_sgr(self)
+    }
+    /// Writes the codes of [`SGRString::clean_all`] to the writer, reversing [`SGRString::write_place`]
+    ///
+    /// Follows the [`CleanKind`] as rendering the [`SGRString`] would
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_clean<W: CapableWriter>(&self, writer: &mut SGRWriter<W>) -> Result<(), W::Error> {
+        writer.clean_sgr(self)
+    }
     /// Writes SGR color codes to the given [`SGRWriter`]
     ///
     /// Reverses the effects of [`SGRString::place_colors`]
@@ -180,24 +446,72 @@ impl SGRString {
     }
     /// Writes SGR style codes to the given [`SGRWriter`]
     ///
-    /// Reverses the effects of [`SGRString::place_styles`]
+    /// Reverses the effects of [`SGRString::place_styles`],
+    /// styles turned back on being written after those turned off
     ///
     /// Does not perform any IO operations
     pub fn clean_styles(&self, builder: &mut SGRBuilder) {
-        for (kind, place, not) in [
+        let styles = [
             (&self.bold, 22, 1),
             (&self.dim, 22, 2),
             (&self.italic, 23, 3),
             (&self.underline, 24, 4),
             (&self.blinking, 25, 5),
+            (&self.rapid_blinking, 25, 6),
             (&self.inverse, 27, 7),
             (&self.hidden, 28, 8),
             (&self.strikethrough, 29, 9),
+            (&self.framed, 54, 51),
+            (&self.encircled, 54, 52),
+            (&self.overline, 55, 53),
+        ];
+        for (kind, place, _) in styles {
+            if *kind == StyleKind::Place {
+                builder.write_code(place);
+            }
+        }
+        for (kind, _, not) in styles {
+            if *kind == StyleKind::Clean {
+                builder.write_code(not);
+            }
+        }
+    }
+    /// Writes the SGR codes that turn off everything placed by [`SGRString::place_all`]
+    ///
+    /// Unlike [`SGRString::clean_styles`], styles set to [`StyleKind::Clean`]
+    /// are left alone, since they are already off.
+    /// Styles are turned off using [`Style::undo`], writing each code at most once.
+    /// Neither the reset code `0` nor custom codes are written.
+    ///
+    /// Does not perform any IO operations
+    pub fn undo_all(&self, builder: &mut SGRBuilder) {
+        if self.foreground != ColorKind::None {
+            Color::DefaultFg.write(builder);
+        }
+        if self.background != ColorKind::None {
+            Color::DefaultBg.write(builder);
+        }
+        // bold & dim share the same undo code, as do both blinking styles
+        // and framed & encircled
+        let mut last = None;
+        for (kind, style) in [
+            (&self.bold, Style::Bold),
+            (&self.dim, Style::Dim),
+            (&self.italic, Style::Italic),
+            (&self.underline, Style::Underline),
+            (&self.blinking, Style::Blinking),
+            (&self.rapid_blinking, Style::RapidBlinking),
+            (&self.inverse, Style::Inverse),
+            (&self.hidden, Style::Hidden),
+            (&self.strikethrough, Style::Strikethrough),
+            (&self.framed, Style::Framed),
+            (&self.encircled, Style::Encircled),
+            (&self.overline, Style::Overline),
         ] {
-            match kind {
-                StyleKind::None => (),
-                StyleKind::Place => builder.write_code(place),
-                StyleKind::Clean => builder.write_code(not),
+            let undo = style.undo();
+            if *kind == StyleKind::Place && last != Some(undo.codes()) {
+                undo.write(builder);
+                last = Some(undo.codes());
             }
         }
     }
@@ -220,6 +534,33 @@ impl From<Style> for SGRString {
         Self::default().style(value)
     }
 }
+impl From<Font> for SGRString {
+    /// Fonts are written as a custom code, as an [`SGRString`] has no font of its own
+    fn from(value: Font) -> Self {
+        Self::default().custom(value.codes()[0])
+    }
+}
+#[cfg(feature = "extended")]
+impl From<crate::Extended> for SGRString {
+    /// Written as a custom code, as an [`SGRString`] has no field for it
+    fn from(value: crate::Extended) -> Self {
+        Self::default().custom(value.codes()[0])
+    }
+}
+impl<A: DiscreteSGR, B: DiscreteSGR> From<crate::Combined<A, B>> for SGRString {
+    /// Layers the second over the first, keeping the custom codes of both
+    fn from(crate::Combined(first, second): crate::Combined<A, B>) -> Self {
+        let second: Self = second.into();
+        let mut over = StyleSet::from(&second);
+        let custom_places = std::mem::take(&mut over.custom_places);
+        let custom_cleans = std::mem::take(&mut over.custom_cleans);
+        let mut sgr = first.apply(over);
+        sgr.custom_places.extend(custom_places);
+        sgr.custom_cleans.extend(custom_cleans);
+        sgr.reset |= second.reset;
+        sgr
+    }
+}
 impl From<&str> for SGRString {
     fn from(value: &str) -> Self {
         Self {
@@ -252,6 +593,881 @@ impl Display for SGRString {
         fmt.clean_sgr(self)
     }
 }
+/// A borrowed [`str`] styled by a [`StyleSet`]
+///
+/// The borrowing counterpart of [`SGRString`], so styling literals or slices
+/// of a larger buffer does not allocate. Created through [`BorrowSGR::borrow_sgr`] or [`SGRStr::new`],
+/// as [`EasySGR`] on a [`str`] returns an [`SGRString`] for existing code to keep compiling.
+/// The two convert through [`From`] & [`SGRStr::into_owned`].
+/// [`SGRStr::color`], [`SGRStr::style`] & [`SGRStr::apply`] keep the text borrowed,
+/// while the methods of [`EasySGR`] turn it into an [`SGRString`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{BorrowSGR, Color::*, EasySGR, Style::*};
+///
+///let line = String::from("name: value");
+///let key = line[..4].borrow_sgr().color(BlueFg).style(Bold);
+///assert_eq!(key.to_string(), "\x1b[34;1mname");
+///assert_eq!(key.to_string(), "name".color(BlueFg).style(Bold).to_string());
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SGRStr<'a> {
+    /// The borrowed text
+    pub text: &'a str,
+    /// The attributes placed before the text, refer to [`StyleSet`]
+    pub styles: StyleSet,
+    /// Refer to [`SGRString::clean`]
+    pub clean: CleanKind,
+    /// Refer to [`SGRString::reset`]
+    pub reset: bool,
+}
+impl<'a> SGRStr<'a> {
+    /// Creates an unstyled [`SGRStr`] borrowing `text`
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            ..Self::default()
+        }
+    }
+    /// Adds a style, refer to [`EasySGR::style`]
+    #[must_use]
+    pub fn style(self, style: impl Into<Style>) -> Self {
+        self.map_sgr(|sgr| sgr.style(style))
+    }
+    /// Adds a color, refer to [`EasySGR::color`]
+    #[must_use]
+    pub fn color(self, color: impl Into<Color>) -> Self {
+        self.map_sgr(|sgr| sgr.color(color))
+    }
+    /// Applies a whole set of attributes, refer to [`EasySGR::apply`]
+    #[must_use]
+    pub fn apply(self, styles: impl Into<StyleSet>) -> Self {
+        Self {
+            styles: self.styles.layered(&styles.into()),
+            ..self
+        }
+    }
+    /// Sets the [`CleanKind`], refer to [`EasySGR::clean`]
+    #[must_use]
+    pub fn clean(self, clean: impl Into<CleanKind>) -> Self {
+        Self {
+            clean: clean.into(),
+            ..self
+        }
+    }
+    /// Returns an [`SGRString`] owning a copy of the text
+    #[must_use]
+    pub fn into_owned(self) -> SGRString {
+        let text = self.text.to_string();
+        SGRString {
+            text,
+            ..self.into_attributes()
+        }
+    }
+    /// Returns an [`SGRString`] of the attributes, its text left empty
+    fn into_attributes(self) -> SGRString {
+        let mut sgr = SGRString {
+            clean: self.clean,
+            reset: self.reset,
+            ..SGRString::default()
+        };
+        self.styles.apply_to(&mut sgr);
+        sgr
+    }
+    /// Changes the attributes through the methods of [`EasySGR`], keeping the text borrowed
+    fn map_sgr(self, f: impl FnOnce(SGRString) -> SGRString) -> Self {
+        let text = self.text;
+        let sgr = f(self.into_attributes());
+        Self {
+            text,
+            styles: StyleSet::from(&sgr),
+            clean: sgr.clean,
+            reset: sgr.reset,
+        }
+    }
+}
+/// Styles a [`str`] without allocating, returning an [`SGRStr`] borrowing it
+///
+/// The borrowing counterpart of [`EasySGR`], which returns an owned [`SGRString`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{BorrowSGR, Color::*, EasySGR, Style::*};
+///
+///let styled = "x".borrow_sgr().color(RedFg).style(Bold);
+///assert_eq!(styled.to_string(), "x".color(RedFg).style(Bold).to_string());
+///```
+pub trait BorrowSGR {
+    /// Returns an unstyled [`SGRStr`] borrowing self
+    fn borrow_sgr(&self) -> SGRStr<'_>;
+}
+impl BorrowSGR for str {
+    #[inline]
+    fn borrow_sgr(&self) -> SGRStr<'_> {
+        SGRStr::new(self)
+    }
+}
+impl<'a> From<&'a str> for SGRStr<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a> From<&'a SGRString> for SGRStr<'a> {
+    /// Borrows the text, cloning the attributes
+    fn from(value: &'a SGRString) -> Self {
+        Self {
+            text: &value.text,
+            styles: StyleSet::from(value),
+            clean: value.clean.clone(),
+            reset: value.reset,
+        }
+    }
+}
+impl From<SGRStr<'_>> for SGRString {
+    fn from(value: SGRStr<'_>) -> Self {
+        value.into_owned()
+    }
+}
+impl AsRef<str> for SGRStr<'_> {
+    /// Returns the unstyled text
+    fn as_ref(&self) -> &str {
+        self.text
+    }
+}
+impl Display for SGRStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // nothing is allocated unless there are custom codes to clone
+        let sgr = self.clone().into_attributes();
+        let mut fmt = SGRWriter::from(f);
+        fmt.place_sgr(&sgr)?;
+        fmt.write_inner(self.text)?;
+        fmt.clean_sgr(&sgr)
+    }
+}
+/// The attributes of an [`SGRString`], without its text, reset or clean
+///
+/// Used where text is styled piece by piece, such as by [`SGRRuns`](crate::SGRRuns)
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, EasySGR, SGRBuilder, Style::*, StyleSet};
+///
+///let red = StyleSet::from(RedFg.style(Bold));
+///let blue = StyleSet::from(BlueFg.style(Bold));
+///
+///let mut builder = SGRBuilder::default();
+///red.transition(&blue, &mut builder);
+///assert_eq!(builder.codes(), [34]);
+///```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct StyleSet {
+    /// Refer to [`SGRString::custom_places`]
+    pub custom_places: Vec<u8>,
+    /// Refer to [`SGRString::custom_cleans`]
+    pub custom_cleans: Vec<u8>,
+    /// Refer to [`SGRString::foreground`]
+    pub foreground: ColorKind,
+    /// Refer to [`SGRString::background`]
+    pub background: ColorKind,
+    /// Refer to [`StyleKind`]
+    pub bold: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub dim: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub italic: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub underline: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub blinking: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub rapid_blinking: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub inverse: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub hidden: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub strikethrough: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub framed: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub encircled: StyleKind,
+    /// Refer to [`StyleKind`]
+    pub overline: StyleKind,
+}
+/// Options for [`StyleSet::transition_with`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransitionOptions {
+    /// Writes the colors of the next [`StyleSet`] again after turning off inverse video, `27`
+    ///
+    /// Terminals differ in how colors changed while inverse video is on are shown once it is
+    /// turned off, some keeping the colors swapped or stale until they are set again.
+    /// Unset colors are written as their defaults, `39` & `49`.
+    /// Off by default, as most terminals do not need it
+    pub reapply_colors_after_inverse_toggle: bool,
+}
+impl StyleSet {
+    /// Returns true if no attribute is set
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+    /// Sets every attribute that is set in `over`, leaving the rest
+    ///
+    /// Custom codes are replaced as a whole when `over` has any
+    pub fn layer(&mut self, over: &Self) {
+        if over.foreground != ColorKind::None {
+            self.foreground = over.foreground.clone();
+        }
+        if over.background != ColorKind::None {
+            self.background = over.background.clone();
+        }
+        for (kind, over) in self.styles_mut().into_iter().zip(over.styles()) {
+            if *over != StyleKind::None {
+                *kind = over.clone();
+            }
+        }
+        if !over.custom_places.is_empty() || !over.custom_cleans.is_empty() {
+            self.custom_places.clone_from(&over.custom_places);
+            self.custom_cleans.clone_from(&over.custom_cleans);
+        }
+    }
+    /// Returns self with `over` [layered](StyleSet::layer) on top
+    #[must_use]
+    pub fn layered(mut self, over: &Self) -> Self {
+        self.layer(over);
+        self
+    }
+    /// Updates the attributes as a terminal would once the codes are written
+    ///
+    /// Attributes turned off & default colors are left unset, `0` clears everything.
+    /// Codes without a typed attribute are added to the [custom places](StyleSet::custom_places)
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, Style::*, StyleSet};
+    ///
+    ///let mut state = StyleSet::default();
+    ///state.apply_codes(&[1, 31]);
+    ///assert_eq!(state, StyleSet::from((Bold, RedFg)));
+    ///state.apply_codes(&[22, 39]);
+    ///assert!(state.is_empty());
+    ///```
+    pub fn apply_codes(&mut self, codes: &[u8]) {
+        use StyleKind::*;
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            let (kinds, value): (&mut [&mut StyleKind], _) = match code {
+                0 => {
+                    *self = Self::default();
+                    continue;
+                }
+                1 => (&mut [&mut self.bold], Place),
+                2 => (&mut [&mut self.dim], Place),
+                3 => (&mut [&mut self.italic], Place),
+                4 => (&mut [&mut self.underline], Place),
+                5 => (&mut [&mut self.blinking], Place),
+                6 => (&mut [&mut self.rapid_blinking], Place),
+                7 => (&mut [&mut self.inverse], Place),
+                8 => (&mut [&mut self.hidden], Place),
+                9 => (&mut [&mut self.strikethrough], Place),
+                51 => (&mut [&mut self.framed], Place),
+                52 => (&mut [&mut self.encircled], Place),
+                53 => (&mut [&mut self.overline], Place),
+                22 => (&mut [&mut self.bold, &mut self.dim], None),
+                23 => (&mut [&mut self.italic], None),
+                24 => (&mut [&mut self.underline], None),
+                25 => (&mut [&mut self.blinking, &mut self.rapid_blinking], None),
+                27 => (&mut [&mut self.inverse], None),
+                28 => (&mut [&mut self.hidden], None),
+                29 => (&mut [&mut self.strikethrough], None),
+                54 => (&mut [&mut self.framed, &mut self.encircled], None),
+                55 => (&mut [&mut self.overline], None),
+                30..=49 => {
+                    let color = match code % 10 {
+                        0 => ColorKind::Black,
+                        1 => ColorKind::Red,
+                        2 => ColorKind::Green,
+                        3 => ColorKind::Yellow,
+                        4 => ColorKind::Blue,
+                        5 => ColorKind::Magenta,
+                        6 => ColorKind::Cyan,
+                        7 => ColorKind::White,
+                        8 => {
+                            let mut next = || codes.next().unwrap_or(0);
+                            match next() {
+                                5 => ColorKind::Byte(next()),
+                                2 => ColorKind::Rgb(next(), next(), next()),
+                                _ => continue,
+                            }
+                        }
+                        _ => ColorKind::None,
+                    };
+                    if code < 40 {
+                        self.foreground = color;
+                    } else {
+                        self.background = color;
+                    }
+                    continue;
+                }
+                90..=97 => {
+                    self.foreground = bright(code - 90);
+                    continue;
+                }
+                100..=107 => {
+                    self.background = bright(code - 100);
+                    continue;
+                }
+                code => {
+                    if !self.custom_places.contains(&code) {
+                        self.custom_places.push(code);
+                    }
+                    continue;
+                }
+            };
+            for kind in kinds {
+                **kind = value.clone();
+            }
+        }
+    }
+    /// Writes the codes that change the attributes of self into those of `next`
+    ///
+    /// Only changed attributes are written, attributes no longer set are turned off.
+    /// Styles sharing an off code, such as bold & dim, are placed again when another is turned off
+    ///
+    /// Does not perform any IO operations
+    pub fn transition(&self, next: &Self, builder: &mut SGRBuilder) {
+        self.transition_with(next, builder, TransitionOptions::default());
+    }
+    /// Writes the codes that change the attributes of self into those of `next`,
+    /// using the given options
+    ///
+    /// Refer to [`StyleSet::transition`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR, SGRBuilder, Style::*, StyleSet, TransitionOptions};
+    ///
+    ///let inverse = StyleSet::from(RedFg.style(Inverse));
+    ///let red = StyleSet::from(RedFg);
+    ///let options = TransitionOptions {
+    ///    reapply_colors_after_inverse_toggle: true,
+    ///};
+    ///
+    ///let mut builder = SGRBuilder::default();
+    ///inverse.transition_with(&red, &mut builder, options);
+    ///assert_eq!(builder.codes(), [27, 31, 49]);
+    ///```
+    pub fn transition_with(
+        &self,
+        next: &Self,
+        builder: &mut SGRBuilder,
+        options: TransitionOptions,
+    ) {
+        use StyleKind::*;
+        /// The place & off code of each style
+        const CODES: [(u8, u8); 12] = [
+            (1, 22),
+            (2, 22),
+            (3, 23),
+            (4, 24),
+            (5, 25),
+            (6, 25),
+            (7, 27),
+            (8, 28),
+            (9, 29),
+            (51, 54),
+            (52, 54),
+            (53, 55),
+        ];
+
+        for (prev, next, off, fg) in [
+            (&self.foreground, &next.foreground, 39, true),
+            (&self.background, &next.background, 49, false),
+        ] {
+            if prev == next {
+                continue;
+            }
+            if *next == ColorKind::None {
+                builder.write_code(off);
+            } else {
+                let mut sgr = SGRString::default();
+                if fg {
+                    sgr.foreground = next.clone();
+                } else {
+                    sgr.background = next.clone();
+                }
+                sgr.place_colors(builder);
+            }
+        }
+        let (styles, next_styles) = (self.styles(), next.styles());
+        let all: [_; 12] =
+            std::array::from_fn(|i| (styles[i], next_styles[i], CODES[i].0, CODES[i].1));
+        // styles sharing an off code are turned off together
+        for group in all.chunk_by(|a, b| a.3 == b.3) {
+            let turned_off = group
+                .iter()
+                .any(|(prev, next, ..)| prev != next && (**prev == Place || **next == Clean));
+            if turned_off {
+                builder.write_code(group[0].3);
+            }
+            for (prev, next, place, _) in group {
+                if **next == Place && (turned_off || prev != next) {
+                    builder.write_code(*place);
+                }
+            }
+        }
+        let inverse_off = self.inverse == Place && next.inverse != Place;
+        if options.reapply_colors_after_inverse_toggle && inverse_off {
+            for (color, off, fg) in [(&next.foreground, 39, true), (&next.background, 49, false)] {
+                if *color == ColorKind::None {
+                    builder.write_code(off);
+                } else if fg {
+                    SGRString::default().fg(color.clone()).place_colors(builder);
+                } else {
+                    SGRString::default().bg(color.clone()).place_colors(builder);
+                }
+            }
+        }
+        if self.custom_places != next.custom_places || self.custom_cleans != next.custom_cleans {
+            builder.write_codes(&self.custom_cleans);
+            builder.write_codes(&next.custom_places);
+        }
+    }
+    const fn styles(&self) -> [&StyleKind; 12] {
+        [
+            &self.bold,
+            &self.dim,
+            &self.italic,
+            &self.underline,
+            &self.blinking,
+            &self.rapid_blinking,
+            &self.inverse,
+            &self.hidden,
+            &self.strikethrough,
+            &self.framed,
+            &self.encircled,
+            &self.overline,
+        ]
+    }
+    const fn styles_mut(&mut self) -> [&mut StyleKind; 12] {
+        [
+            &mut self.bold,
+            &mut self.dim,
+            &mut self.italic,
+            &mut self.underline,
+            &mut self.blinking,
+            &mut self.rapid_blinking,
+            &mut self.inverse,
+            &mut self.hidden,
+            &mut self.strikethrough,
+            &mut self.framed,
+            &mut self.encircled,
+            &mut self.overline,
+        ]
+    }
+    /// Sets the attributes of the [`SGRString`] to those of self
+    fn apply_to(self, sgr: &mut SGRString) {
+        Self {
+            custom_places: sgr.custom_places,
+            custom_cleans: sgr.custom_cleans,
+            foreground: sgr.foreground,
+            background: sgr.background,
+            bold: sgr.bold,
+            dim: sgr.dim,
+            italic: sgr.italic,
+            underline: sgr.underline,
+            blinking: sgr.blinking,
+            rapid_blinking: sgr.rapid_blinking,
+            inverse: sgr.inverse,
+            hidden: sgr.hidden,
+            strikethrough: sgr.strikethrough,
+            framed: sgr.framed,
+            encircled: sgr.encircled,
+            overline: sgr.overline,
+        } = self;
+    }
+}
+impl StyleSet {
+    /// Parses a `;` separated list of SGR parameters, such as `01;31` or `38;5;208`
+    ///
+    /// The extended color forms `38;5;n`, `38;2;r;g;b` & their background counterparts are supported.
+    /// As in [`StyleSet::apply_codes`], `0` clears what came before it & off codes shared by styles,
+    /// such as `22` for bold & dim, clean each of them.
+    /// Codes without a typed attribute, such as `73`, are kept as
+    /// [custom places](StyleSet::custom_places) rather than being errors.
+    /// An empty list is an empty [`StyleSet`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter is not a number from `0` to `255`,
+    /// or an extended color is incomplete
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{ColorKind, StyleKind, StyleSet};
+    ///
+    ///let set = StyleSet::from_sgr_params("01;38;5;208").unwrap();
+    ///assert_eq!(set.bold, StyleKind::Place);
+    ///assert_eq!(set.foreground, ColorKind::Byte(208));
+    ///```
+    pub fn from_sgr_params(params: &str) -> Result<Self, ParseError> {
+        use StyleKind::*;
+        let mut set = Self::default();
+        if params.is_empty() {
+            return Ok(set);
+        }
+        let mut codes = params
+            .split(';')
+            .map(|param| {
+                param
+                    .trim()
+                    .parse::<u8>()
+                    .map_err(|_| ParseError::new(format!("Invalid SGR parameter `{param}`")))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+        while let Some(code) = codes.next() {
+            let (kinds, value): (&mut [&mut StyleKind], _) = match code {
+                0 => {
+                    set = Self::default();
+                    continue;
+                }
+                1 => (&mut [&mut set.bold], Place),
+                2 => (&mut [&mut set.dim], Place),
+                3 => (&mut [&mut set.italic], Place),
+                4 => (&mut [&mut set.underline], Place),
+                5 => (&mut [&mut set.blinking], Place),
+                6 => (&mut [&mut set.rapid_blinking], Place),
+                7 => (&mut [&mut set.inverse], Place),
+                8 => (&mut [&mut set.hidden], Place),
+                9 => (&mut [&mut set.strikethrough], Place),
+                51 => (&mut [&mut set.framed], Place),
+                52 => (&mut [&mut set.encircled], Place),
+                53 => (&mut [&mut set.overline], Place),
+                22 => (&mut [&mut set.bold, &mut set.dim], Clean),
+                23 => (&mut [&mut set.italic], Clean),
+                24 => (&mut [&mut set.underline], Clean),
+                25 => (&mut [&mut set.blinking, &mut set.rapid_blinking], Clean),
+                27 => (&mut [&mut set.inverse], Clean),
+                28 => (&mut [&mut set.hidden], Clean),
+                29 => (&mut [&mut set.strikethrough], Clean),
+                54 => (&mut [&mut set.framed, &mut set.encircled], Clean),
+                55 => (&mut [&mut set.overline], Clean),
+                30..=49 => {
+                    let color = match code % 10 {
+                        0 => ColorKind::Black,
+                        1 => ColorKind::Red,
+                        2 => ColorKind::Green,
+                        3 => ColorKind::Yellow,
+                        4 => ColorKind::Blue,
+                        5 => ColorKind::Magenta,
+                        6 => ColorKind::Cyan,
+                        7 => ColorKind::White,
+                        8 => extended_color(&mut codes, params)?,
+                        _ => ColorKind::Default,
+                    };
+                    if code < 40 {
+                        set.foreground = color;
+                    } else {
+                        set.background = color;
+                    }
+                    continue;
+                }
+                90..=97 => {
+                    set.foreground = bright(code - 90);
+                    continue;
+                }
+                100..=107 => {
+                    set.background = bright(code - 100);
+                    continue;
+                }
+                code => {
+                    set.custom_places.push(code);
+                    continue;
+                }
+            };
+            for kind in kinds {
+                **kind = value.clone();
+            }
+        }
+        Ok(set)
+    }
+}
+/// Returns the bright color at the given offset, `0` for [`ColorKind::BrightBlack`]
+const fn bright(offset: u8) -> ColorKind {
+    match offset {
+        0 => ColorKind::BrightBlack,
+        1 => ColorKind::BrightRed,
+        2 => ColorKind::BrightGreen,
+        3 => ColorKind::BrightYellow,
+        4 => ColorKind::BrightBlue,
+        5 => ColorKind::BrightMagenta,
+        6 => ColorKind::BrightCyan,
+        _ => ColorKind::BrightWhite,
+    }
+}
+/// Takes the rest of an extended color, `5;n` or `2;r;g;b`
+fn extended_color(
+    codes: &mut impl Iterator<Item = u8>,
+    params: &str,
+) -> Result<ColorKind, ParseError> {
+    let mut next = || {
+        codes
+            .next()
+            .ok_or_else(|| ParseError::new(format!("Incomplete extended color in `{params}`")))
+    };
+    match next()? {
+        5 => Ok(ColorKind::Byte(next()?)),
+        2 => Ok(ColorKind::Rgb(next()?, next()?, next()?)),
+        kind => Err(ParseError::new(format!(
+            "Invalid extended color kind `{kind}` in `{params}`, expected `5` or `2`"
+        ))),
+    }
+}
+/// Parses a `key=value:key=value` list of SGR parameters, as used by `LS_COLORS` & `GREP_COLORS`
+///
+/// Each value is parsed through [`StyleSet::from_sgr_params`].
+/// Empty entries & entries without a `=`, such as the `ne` flag of `GREP_COLORS`, are skipped
+///
+/// # Errors
+///
+/// Returns an error if a value fails to parse
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{parse_color_spec, ColorKind};
+///
+///let spec = parse_color_spec("di=01;34:*.tar=01;31").unwrap();
+///assert_eq!(spec["di"].foreground, ColorKind::Blue);
+///assert_eq!(spec["*.tar"].foreground, ColorKind::Red);
+///```
+pub fn parse_color_spec(spec: &str) -> Result<HashMap<String, StyleSet>, ParseError> {
+    spec.split(':')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(key, value)| Ok((key.to_string(), StyleSet::from_sgr_params(value)?)))
+        .collect()
+}
+impl From<&SGRString> for StyleSet {
+    fn from(value: &SGRString) -> Self {
+        Self {
+            custom_places: value.custom_places.clone(),
+            custom_cleans: value.custom_cleans.clone(),
+            foreground: value.foreground.clone(),
+            background: value.background.clone(),
+            bold: value.bold.clone(),
+            dim: value.dim.clone(),
+            italic: value.italic.clone(),
+            underline: value.underline.clone(),
+            blinking: value.blinking.clone(),
+            rapid_blinking: value.rapid_blinking.clone(),
+            inverse: value.inverse.clone(),
+            hidden: value.hidden.clone(),
+            strikethrough: value.strikethrough.clone(),
+            framed: value.framed.clone(),
+            encircled: value.encircled.clone(),
+            overline: value.overline.clone(),
+        }
+    }
+}
+impl<I: Into<SGRString>> From<I> for StyleSet {
+    fn from(value: I) -> Self {
+        Self::from(&value.into())
+    }
+}
+/// Implements [`From`] for tuples of [`StyleSet`]s, layered from first to last
+macro_rules! tuple_style_set {
+    ($(($($ty:ident),+)),+) => {
+        $(
+            impl<$($ty: Into<StyleSet>),+> From<($($ty,)+)> for StyleSet {
+                #[allow(non_snake_case)]
+                fn from(($($ty,)+): ($($ty,)+)) -> Self {
+                    let mut set = Self::default();
+                    $(set.layer(&$ty.into());)+
+                    set
+                }
+            }
+        )+
+    };
+}
+tuple_style_set!((A, B), (A, B, C), (A, B, C, D), (A, B, C, D, E));
+impl<S: Into<Self>, const N: usize> From<[S; N]> for StyleSet {
+    /// Layers each attribute from first to last
+    fn from(value: [S; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+impl<S: Clone + Into<Self>> From<&[S]> for StyleSet {
+    /// Layers each attribute from first to last
+    fn from(value: &[S]) -> Self {
+        value.iter().cloned().collect()
+    }
+}
+impl<S: Into<Self>> FromIterator<S> for StyleSet {
+    /// Layers each attribute from first to last,
+    /// so with two foregrounds the last is kept
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::default(), |mut set, over| {
+            set.layer(&over.into());
+            set
+        })
+    }
+}
+/// A [`char`] repeated a number of times, styled by a [`SGRString`]
+///
+/// Written using [`SGRWriter::fill`], so the repeated text is never allocated
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{fill, Style::*};
+///
+///println!("{}", fill('─', 8).styled(Dim));
+///```
+#[derive(Debug, Clone)]
+pub struct Fill {
+    /// The codes written before and after the repeated [`char`]
+    ///
+    /// [`SGRString::text`] is not written
+    pub sgr: SGRString,
+    /// The [`char`] to repeat
+    pub ch: char,
+    /// The number of times to write [`Fill::ch`]
+    pub len: usize,
+}
+impl Fill {
+    /// Sets the [`SGRString`] used to style the repeated [`char`]
+    #[must_use]
+    pub fn styled(self, sgr: impl Into<SGRString>) -> Self {
+        Self {
+            sgr: sgr.into(),
+            ..self
+        }
+    }
+}
+impl Display for Fill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        SGRWriter::from(f).fill(&self.sgr, self.ch, self.len)
+    }
+}
+/// Creates an unstyled [`Fill`] of `ch` repeated `len` times
+#[must_use]
+pub fn fill(ch: char, len: usize) -> Fill {
+    Fill {
+        sgr: SGRString::default(),
+        ch,
+        len,
+    }
+}
+/// Creates the unstyled [`SGRString`] of `mask` repeated `text_len` times
+///
+/// Shown in place of a secret, such as a passphrase being typed,
+/// rather than relying on [`Style::Hidden`], which some terminals ignore.
+/// Refer to [`MaskingWriter`](crate::MaskingWriter) for masking what is written hidden
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::masked;
+///
+///let typed = "hunter2";
+///assert_eq!(masked(typed.chars().count(), '*').to_string(), "*******");
+///```
+#[must_use]
+pub fn masked(text_len: usize, mask: char) -> SGRString {
+    SGRString::from(std::iter::repeat_n(mask, text_len).collect::<String>())
+}
+/// A progress bar made up of two [`Fill`]s
+///
+/// Each part is cleaned using [`CleanKind::Reverse`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, ProgressBar};
+///
+///let bar = ProgressBar::new(20, 0.25)
+///    .filled('█', GreenFg)
+///    .empty('░', BlackFg);
+///println!("{bar}");
+///```
+#[derive(Debug, Clone)]
+pub struct ProgressBar {
+    /// The total number of chars written
+    pub width: usize,
+    /// How much of the bar is filled, clamped between `0.0` & `1.0`
+    pub ratio: f64,
+    /// The [`char`] & [`Color`] of the filled part
+    pub filled: (char, Color),
+    /// The [`char`] & [`Color`] of the empty part
+    pub empty: (char, Color),
+}
+impl ProgressBar {
+    /// Creates a new [`ProgressBar`]
+    ///
+    /// By default the filled part is written as `#` and empty as `-`,
+    /// both using [`Color::DefaultFg`]
+    #[must_use]
+    pub const fn new(width: usize, ratio: f64) -> Self {
+        Self {
+            width,
+            ratio,
+            filled: ('#', Color::DefaultFg),
+            empty: ('-', Color::DefaultFg),
+        }
+    }
+    /// Sets the [`char`] & [`Color`] of the filled part
+    #[must_use]
+    pub const fn filled(mut self, ch: char, color: Color) -> Self {
+        self.filled = (ch, color);
+        self
+    }
+    /// Sets the [`char`] & [`Color`] of the empty part
+    #[must_use]
+    pub const fn empty(mut self, ch: char, color: Color) -> Self {
+        self.empty = (ch, color);
+        self
+    }
+    /// Returns the number of filled chars, rounded to the nearest
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn filled_len(&self) -> usize {
+        let ratio = if self.ratio.is_nan() {
+            0.0
+        } else {
+            self.ratio.clamp(0.0, 1.0)
+        };
+        ((self.width as f64 * ratio).round() as usize).min(self.width)
+    }
+}
+impl Display for ProgressBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let filled = self.filled_len();
+        let mut fmt = SGRWriter::from(f);
+        fmt.fill(
+            &self.filled.1.clone().clean(CleanKind::Reverse),
+            self.filled.0,
+            filled,
+        )?;
+        fmt.fill(
+            &self.empty.1.clone().clean(CleanKind::Reverse),
+            self.empty.0,
+            self.width - filled,
+        )
+    }
+}
 /// Component of [`SGRString`]; the type of clean
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum CleanKind {
@@ -297,11 +1513,155 @@ pub enum ColorKind {
     Rgb(u8, u8, u8),
     /// Applies the default `SGR` color
     Default,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+impl ColorKind {
+    /// Returns the color applied to the foreground, [`None`] for [`ColorKind::None`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color, ColorKind};
+    ///
+    ///assert_eq!(ColorKind::Byte(208).as_fg(), Some(Color::ByteFg(208)));
+    ///assert_eq!(ColorKind::None.as_fg(), None);
+    ///```
+    #[must_use]
+    pub const fn as_fg(&self) -> Option<Color> {
+        use Color::*;
+        Some(match *self {
+            Self::None => return None,
+            Self::Black => BlackFg,
+            Self::Red => RedFg,
+            Self::Green => GreenFg,
+            Self::Yellow => YellowFg,
+            Self::Blue => BlueFg,
+            Self::Magenta => MagentaFg,
+            Self::Cyan => CyanFg,
+            Self::White => WhiteFg,
+            Self::Byte(n) => ByteFg(n),
+            Self::Rgb(r, g, b) => RgbFg(r, g, b),
+            Self::Default => DefaultFg,
+            Self::BrightBlack => BrightBlackFg,
+            Self::BrightRed => BrightRedFg,
+            Self::BrightGreen => BrightGreenFg,
+            Self::BrightYellow => BrightYellowFg,
+            Self::BrightBlue => BrightBlueFg,
+            Self::BrightMagenta => BrightMagentaFg,
+            Self::BrightCyan => BrightCyanFg,
+            Self::BrightWhite => BrightWhiteFg,
+        })
+    }
+    /// Returns the color applied to the background, [`None`] for [`ColorKind::None`]
+    #[must_use]
+    pub const fn as_bg(&self) -> Option<Color> {
+        use Color::*;
+        Some(match *self {
+            Self::None => return None,
+            Self::Black => BlackBg,
+            Self::Red => RedBg,
+            Self::Green => GreenBg,
+            Self::Yellow => YellowBg,
+            Self::Blue => BlueBg,
+            Self::Magenta => MagentaBg,
+            Self::Cyan => CyanBg,
+            Self::White => WhiteBg,
+            Self::Byte(n) => ByteBg(n),
+            Self::Rgb(r, g, b) => RgbBg(r, g, b),
+            Self::Default => DefaultBg,
+            Self::BrightBlack => BrightBlackBg,
+            Self::BrightRed => BrightRedBg,
+            Self::BrightGreen => BrightGreenBg,
+            Self::BrightYellow => BrightYellowBg,
+            Self::BrightBlue => BrightBlueBg,
+            Self::BrightMagenta => BrightMagentaBg,
+            Self::BrightCyan => BrightCyanBg,
+            Self::BrightWhite => BrightWhiteBg,
+        })
+    }
+}
+impl From<Color> for ColorKind {
+    /// Drops the layer of the color, so [`Color::RedFg`] & [`Color::RedBg`] both become
+    /// [`ColorKind::Red`]
+    fn from(value: Color) -> Self {
+        use Color::*;
+        match value {
+            BlackFg | BlackBg => Self::Black,
+            RedFg | RedBg => Self::Red,
+            GreenFg | GreenBg => Self::Green,
+            YellowFg | YellowBg => Self::Yellow,
+            BlueFg | BlueBg => Self::Blue,
+            MagentaFg | MagentaBg => Self::Magenta,
+            CyanFg | CyanBg => Self::Cyan,
+            WhiteFg | WhiteBg => Self::White,
+            ByteFg(n) | ByteBg(n) => Self::Byte(n),
+            RgbFg(r, g, b) | RgbBg(r, g, b) => Self::Rgb(r, g, b),
+            DefaultFg | DefaultBg => Self::Default,
+            BrightBlackFg | BrightBlackBg => Self::BrightBlack,
+            BrightRedFg | BrightRedBg => Self::BrightRed,
+            BrightGreenFg | BrightGreenBg => Self::BrightGreen,
+            BrightYellowFg | BrightYellowBg => Self::BrightYellow,
+            BrightBlueFg | BrightBlueBg => Self::BrightBlue,
+            BrightMagentaFg | BrightMagentaBg => Self::BrightMagenta,
+            BrightCyanFg | BrightCyanBg => Self::BrightCyan,
+            BrightWhiteFg | BrightWhiteBg => Self::BrightWhite,
+        }
+    }
+}
+impl From<crate::Rgb> for ColorKind {
+    fn from(crate::Rgb(r, g, b): crate::Rgb) -> Self {
+        Self::Rgb(r, g, b)
+    }
+}
+impl From<(u8, u8, u8)> for ColorKind {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self::Rgb(r, g, b)
+    }
+}
+impl From<u32> for ColorKind {
+    /// Refer to [`Rgb::from_hex`](crate::Rgb::from_hex)
+    fn from(value: u32) -> Self {
+        crate::Rgb::from_hex(value).into()
+    }
+}
+/// Returns the kind of a style after another turns it off through the code they share
+///
+/// A placed style is left unset, as the code of the other writes the turning off
+const fn off(kind: StyleKind) -> StyleKind {
+    match kind {
+        StyleKind::Place => StyleKind::None,
+        kind => kind,
+    }
 }
 impl<I: Into<SGRString>> EasySGR for I {}
 /// Allows for chaining SGR sequence types
 ///
 /// Methods return a [`SGRString`]
+///
+/// # Order
+///
+/// Each attribute is stored once, so a later attribute overrides an earlier one of the same kind:
+/// a second foreground replaces the first & [`Style::NotBold`] after [`Style::Bold`] turns bold off.
+/// Turning off a style also overrides any other style sharing its code,
+/// i.e. [`Style::NotBold`] turns off a placed [`Style::Dim`] as `22` does.
+/// [`Style::Reset`] drops every attribute placed before it, keeping the [`CleanKind`].
+///
+/// The codes are written in a fixed order rather than the order of application:
+/// the reset, colors, styles turned off, styles placed, then custom codes
+///
+///```rust
+///use easy_sgr::{Color::*, EasySGR, Style::*};
+///
+///assert_eq!("x".style(Bold).color(RedFg).style(NotBold).to_string(), "\x1b[31;22mx");
+///assert_eq!("x".style(Italic).style(Reset).style(Bold).to_string(), "\x1b[0;1mx");
+///```
 pub trait EasySGR: Into<SGRString> {
     /// Turns self into [`SGRString`]
     ///
@@ -337,24 +1697,39 @@ pub trait EasySGR: Into<SGRString> {
 
         let mut this = self.into();
         match style.into() {
-            Reset => this.reset = true,
+            Reset => {
+                this = SGRString {
+                    text: this.text,
+                    clean: this.clean,
+                    reset: true,
+                    custom_cleans: this.custom_cleans,
+                    ..SGRString::default()
+                };
+            }
             Bold => this.bold = Place,
             Dim => this.dim = Place,
             Italic => this.italic = Place,
             Underline => this.underline = Place,
             Blinking => this.blinking = Place,
+            RapidBlinking => this.rapid_blinking = Place,
             Inverse => this.inverse = Place,
             Hidden => this.hidden = Place,
             Strikethrough => this.strikethrough = Place,
+            Framed => this.framed = Place,
+            Encircled => this.encircled = Place,
+            Overline => this.overline = Place,
 
-            NotBold => this.bold = Clean,
-            NotDim => this.dim = Clean,
+            // the code is shared, turning off both styles
+            NotBold => (this.bold, this.dim) = (Clean, off(this.dim)),
+            NotDim => (this.bold, this.dim) = (off(this.bold), Clean),
             NotItalic => this.italic = Clean,
             NotUnderline => this.underline = Clean,
-            NotBlinking => this.blinking = Clean,
+            NotBlinking => (this.blinking, this.rapid_blinking) = (Clean, off(this.rapid_blinking)),
             NotInverse => this.inverse = Clean,
             NotHidden => this.hidden = Clean,
             NotStrikethrough => this.strikethrough = Clean,
+            NotFramedEncircled => (this.framed, this.encircled) = (Clean, off(this.encircled)),
+            NotOverline => this.overline = Clean,
         }
         this
     }
@@ -378,6 +1753,14 @@ pub trait EasySGR: Into<SGRString> {
             ByteFg(n) => (Byte(n), this.background),
             RgbFg(r, g, b) => (Rgb(r, g, b), this.background),
             DefaultFg => (Default, this.background),
+            BrightBlackFg => (BrightBlack, this.background),
+            BrightRedFg => (BrightRed, this.background),
+            BrightGreenFg => (BrightGreen, this.background),
+            BrightYellowFg => (BrightYellow, this.background),
+            BrightBlueFg => (BrightBlue, this.background),
+            BrightMagentaFg => (BrightMagenta, this.background),
+            BrightCyanFg => (BrightCyan, this.background),
+            BrightWhiteFg => (BrightWhite, this.background),
 
             BlackBg => (this.foreground, Black),
             RedBg => (this.foreground, Red),
@@ -390,9 +1773,73 @@ pub trait EasySGR: Into<SGRString> {
             ByteBg(n) => (this.foreground, Byte(n)),
             RgbBg(r, g, b) => (this.foreground, Rgb(r, g, b)),
             DefaultBg => (this.foreground, Default),
+            BrightBlackBg => (this.foreground, BrightBlack),
+            BrightRedBg => (this.foreground, BrightRed),
+            BrightGreenBg => (this.foreground, BrightGreen),
+            BrightYellowBg => (this.foreground, BrightYellow),
+            BrightBlueBg => (this.foreground, BrightBlue),
+            BrightMagentaBg => (this.foreground, BrightMagenta),
+            BrightCyanBg => (this.foreground, BrightCyan),
+            BrightWhiteBg => (this.foreground, BrightWhite),
         };
         this
     }
+    /// Sets the foreground of the returned [`SGRString`]
+    ///
+    /// Takes any layer-agnostic color, such as an [`Rgb`](crate::Rgb),
+    /// an `(r, g, b)` tuple or a `0xRRGGBB` integer.
+    /// A [`Color`] is applied to the foreground whatever its layer
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///assert_eq!("x".fg((255, 0, 0)).to_string(), "\x1b[38;2;255;0;0mx");
+    ///assert_eq!("x".fg(0x00ff00).to_string(), "\x1b[38;2;0;255;0mx");
+    ///assert_eq!("x".fg(RedBg).to_string(), "\x1b[31mx");
+    ///```
+    #[must_use]
+    #[inline]
+    fn fg(self, color: impl Into<ColorKind>) -> SGRString {
+        SGRString {
+            foreground: color.into(),
+            ..self.into()
+        }
+    }
+    /// Sets the background of the returned [`SGRString`]
+    ///
+    /// Refer to [`EasySGR::fg`]
+    #[must_use]
+    #[inline]
+    fn bg(self, color: impl Into<ColorKind>) -> SGRString {
+        SGRString {
+            background: color.into(),
+            ..self.into()
+        }
+    }
+    /// Applies a whole set of attributes to the returned [`SGRString`]
+    ///
+    /// Attributes are [layered](StyleSet::layer) over those already set,
+    /// so within a tuple or array the last color of each kind wins
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR, Style::*};
+    ///
+    ///let text = "text".apply((RedFg, Bold, Italic));
+    ///assert_eq!(text.to_string(), "\x1b[31;1;3mtext");
+    ///```
+    #[must_use]
+    #[inline]
+    fn apply(self, styles: impl Into<StyleSet>) -> SGRString {
+        let mut this = self.into();
+        StyleSet::from(&this)
+            .layered(&styles.into())
+            .apply_to(&mut this);
+        this
+    }
     /// Adds a custom code to the returned [`SGRString`]
     ///
     /// Code is used in [`SGRString::place_all`]
@@ -428,3 +1875,34 @@ pub trait EasySGR: Into<SGRString> {
         this
     }
 }
+/// Creates an [`SGRString`] from [`format!`] arguments, styled by the attributes before the `;`
+///
+/// Each attribute is an expression [applied](EasySGR::apply) in order,
+/// so the last color of each kind wins.
+/// Variants of [`Style`] & [`Color`] can be named without importing them,
+/// any other expression, such as a variable holding a [`Color`], being used as is.
+/// The text is not parsed for keywords, unlike the macros of `easy-sgr-macros`.
+/// Cleaned using [`CleanKind::Reverse`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::style;
+///
+///let n = 3;
+///let count = style!(Bold, RedFg; "count = {}", n);
+///assert_eq!(count.to_string(), "\x1b[31;1mcount = 3\x1b[39;22m");
+///```
+#[macro_export]
+macro_rules! style {
+    ($($attr:expr),+ $(,)?; $($args:tt)+) => {{
+        #[allow(unused_imports)]
+        use $crate::{Color::*, Style::*};
+        let sgr = $crate::EasySGR::clean(
+            ::std::format!($($args)+),
+            $crate::CleanKind::Reverse,
+        );
+        $(let sgr = $crate::EasySGR::apply(sgr, $attr);)+
+        sgr
+    }};
+}
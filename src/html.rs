@@ -0,0 +1,248 @@
+use std::fmt::{self, Display, Write};
+
+use crate::{
+    escapes::{apply_params, find_sgr},
+    palette256::to_rgb,
+    CapableWriter, ColorKind, SGRString, SGRWriter, StyleKind, StyleSet,
+};
+
+/// Displays the inner value with the characters special to HTML escaped
+///
+/// `&`, `<`, `>`, `"` & `'` are written as their character references,
+/// so the value can be placed within text or an attribute.
+/// The `format_html` macro wraps every format param in one
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::html::HtmlEscape;
+///
+///assert_eq!(HtmlEscape("a < b && c").to_string(), "a &lt; b &amp;&amp; c");
+///assert_eq!(HtmlEscape(1).to_string(), "1");
+///```
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlEscape<T>(pub T);
+impl<T: Display> Display for HtmlEscape<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(Escaper(f), "{}", self.0)
+    }
+}
+/// Escapes what is written, before passing it on
+struct Escaper<W>(W);
+impl<W: Write> Write for Escaper<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(at) = rest.find(['&', '<', '>', '"', '\'']) {
+            self.0.write_str(&rest[..at])?;
+            self.0.write_str(match rest.as_bytes()[at] {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&#39;",
+            })?;
+            rest = &rest[at + 1..];
+        }
+        self.0.write_str(rest)
+    }
+}
+/// Writes styled text as HTML, each change of the attributes opening a `<span>`
+///
+/// Rather than writing escapes, the attributes in effect are tracked: each time they change
+/// before more text, the open `<span>` is closed & another opened with their [CSS](css)
+/// in its `style` attribute, none being opened while nothing is set.
+/// Sequences within the written text are applied as well, so already styled text can be written.
+/// The text is [escaped](HtmlEscape)
+///
+/// [`CapableWriter::get_writer`] closes the last `<span>`, returning the HTML
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{html::HtmlWriter, CapableWriter, SGRWriter};
+///
+///let mut writer = SGRWriter::from(HtmlWriter::default());
+///writer.write_inner("a <\x1b[1mb\x1b[0m")?;
+///assert_eq!(
+///    writer.internal(),
+///    r#"a &lt;<span style="font-weight: bold">b</span>"#
+///);
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HtmlWriter {
+    html: String,
+    state: StyleSet,
+    written: StyleSet,
+    /// The CSS of the open `<span>`, empty when none is open
+    open: String,
+}
+impl HtmlWriter {
+    /// Returns the HTML written so far, the last `<span>` left open
+    #[must_use]
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+    /// Adds visible text, opening a `<span>` first if the attributes changed
+    fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.state != self.written {
+            let css = css(&self.state);
+            // attributes without CSS, such as blinking, change no span
+            if css != self.open {
+                if !self.open.is_empty() {
+                    self.html.push_str("</span>");
+                }
+                if !css.is_empty() {
+                    let _ = write!(self.html, "<span style=\"{}\">", HtmlEscape(&css));
+                }
+                self.open = css;
+            }
+            self.written.clone_from(&self.state);
+        }
+        let _ = write!(self.html, "{}", HtmlEscape(text));
+    }
+}
+impl CapableWriter for HtmlWriter {
+    type Writer = String;
+    type Error = fmt::Error;
+
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        let mut rest = s;
+        while let Some((start, params, end)) = find_sgr(rest) {
+            self.text(&rest[..start]);
+            apply_params(&mut self.state, params);
+            rest = &rest[end..];
+        }
+        self.text(rest);
+        Ok(())
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.state.apply_codes(codes);
+        Ok(())
+    }
+    fn get_writer(mut self) -> Self::Writer {
+        if !self.open.is_empty() {
+            self.html.push_str("</span>");
+        }
+        self.html
+    }
+}
+/// Returns the CSS of the attributes, as placed in a `style` attribute or by `%c` within `console.log`
+///
+/// Colors are written as `rgb(..)`, the 16 system colors & the 256 color palette
+/// taking the values of the standard xterm palette.
+/// Inverse swaps the colors that are set, hidden text is made transparent.
+/// Blinking & custom codes have no equivalent, so are left out
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{html::css, Color::*, Style::*, StyleSet};
+///
+///assert_eq!(
+///    css(&StyleSet::from((Italic, RgbBg(1, 2, 3)))),
+///    "background-color: rgb(1, 2, 3); font-style: italic"
+///);
+///assert_eq!(css(&StyleSet::default()), "");
+///```
+#[must_use]
+pub fn css(style: &StyleSet) -> String {
+    let mut declarations: Vec<String> = Vec::new();
+    let (foreground, background) = if style.inverse == StyleKind::Place {
+        (&style.background, &style.foreground)
+    } else {
+        (&style.foreground, &style.background)
+    };
+    let placed = |kind: &StyleKind| *kind == StyleKind::Place;
+    if placed(&style.hidden) {
+        declarations.push(String::from("color: transparent"));
+    } else if let Some(color) = css_color(foreground) {
+        declarations.push(format!("color: {color}"));
+    }
+    if let Some(color) = css_color(background) {
+        declarations.push(format!("background-color: {color}"));
+    }
+    if placed(&style.bold) {
+        declarations.push(String::from("font-weight: bold"));
+    }
+    if placed(&style.dim) {
+        declarations.push(String::from("opacity: 0.5"));
+    }
+    if placed(&style.italic) {
+        declarations.push(String::from("font-style: italic"));
+    }
+    let lines: Vec<&str> = [
+        (&style.underline, "underline"),
+        (&style.strikethrough, "line-through"),
+        (&style.overline, "overline"),
+    ]
+    .into_iter()
+    .filter(|(kind, _)| placed(kind))
+    .map(|(_, line)| line)
+    .collect();
+    if !lines.is_empty() {
+        declarations.push(format!("text-decoration: {}", lines.join(" ")));
+    }
+    if placed(&style.framed) {
+        declarations.push(String::from("border: 1px solid"));
+    } else if placed(&style.encircled) {
+        declarations.push(String::from("border: 1px solid; border-radius: 1em"));
+    }
+    declarations.join("; ")
+}
+/// Returns the CSS of a color, [`None`] when it is unset or the default
+fn css_color(color: &ColorKind) -> Option<String> {
+    use ColorKind::*;
+    let (r, g, b) = match *color {
+        None | Default => return Option::None,
+        Rgb(r, g, b) => (r, g, b),
+        Byte(index) => to_rgb(index),
+        Black => to_rgb(0),
+        Red => to_rgb(1),
+        Green => to_rgb(2),
+        Yellow => to_rgb(3),
+        Blue => to_rgb(4),
+        Magenta => to_rgb(5),
+        Cyan => to_rgb(6),
+        White => to_rgb(7),
+        BrightBlack => to_rgb(8),
+        BrightRed => to_rgb(9),
+        BrightGreen => to_rgb(10),
+        BrightYellow => to_rgb(11),
+        BrightBlue => to_rgb(12),
+        BrightMagenta => to_rgb(13),
+        BrightCyan => to_rgb(14),
+        BrightWhite => to_rgb(15),
+    };
+    Some(format!("rgb({r}, {g}, {b})"))
+}
+impl SGRString {
+    /// Returns the HTML showing this styled text
+    ///
+    /// The text is [escaped](HtmlEscape) within a `<span>` styled by its [CSS](css),
+    /// refer to [`HtmlWriter`]
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///assert_eq!(
+    ///    "<hi>".color(RedFg).to_html(),
+    ///    r#"<span style="color: rgb(128, 0, 0)">&lt;hi&gt;</span>"#
+    ///);
+    ///assert_eq!("plain".to_sgr().to_html(), "plain");
+    ///```
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut writer = SGRWriter::from(HtmlWriter::default());
+        // writing to an HtmlWriter never fails
+        let _ = writer.place_sgr(self);
+        let _ = writer.write_inner(&self.text);
+        let _ = writer.clean_sgr(self);
+        writer.internal()
+    }
+}
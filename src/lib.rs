@@ -32,9 +32,12 @@
 //! And its usage is very simple:
 //!
 //! ```rust
+//! # #[cfg(feature = "macros")]
+//! # {
 //! use easy_sgr::println;
 //!
 //! println!("{[italic red]}This should be italic & red!{[]}");
+//! # }
 //! ```
 //!
 //! `{[]}` is interpreted as a reset here.
@@ -49,9 +52,12 @@
 //! `println!`, `writeln!` or `format!`:
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use easy_sgr::{Color::*, Style::*};
 //!
 //! println!("{Italic}{RedFg}This should be italic & red!{Reset}");
+//! # }
 //! ```
 //!
 //! `Color` and `Style` are both enums that implement `Display`: when they
@@ -70,17 +76,20 @@
 //! ### `EasySGR` trait
 //!
 //! This is similar to the method above but uses the `EasySGR` trait.
-//! This trait is implemented by anything that implements Into\<AnsiString\> including Style and Color.
+//! This trait is implemented by anything that implements `Into<SGRString>` including Style and Color.
 //! Its main purpose is to provide functions for chaining [`SGR`][SGR] codes.
 //!
 //! The example above can be achieved using it as such:
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use easy_sgr::{ Color::*, EasySGR, Style::*};
 //!
 //! let sgr = Italic.color(RedFg);
 //!
 //! println!("{sgr}This should be italic & red!{Reset}");
+//! # }
 //! ```
 //!
 //! Now the output would look something like this:
@@ -93,6 +102,21 @@
 //!
 //! Doing this avoids the issue of rewriting the Escape and End sequences,
 //! though is more expensive to use as it allocates an `SGRString`.
+//! The `BorrowSGR` trait styles a `str` without allocating, `"x".borrow_sgr().color(RedFg)`
+//! returning an `SGRStr` borrowing the text.
+//!
+//! The `style!` macro formats its arguments into an `SGRString` styled this way,
+//! without importing `Color` or `Style` & without parsing the text for keywords:
+//!
+//! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
+//! use easy_sgr::style;
+//!
+//! let n = 3;
+//! println!("{}", style!(Italic, RedFg; "{n} should be italic & red!"));
+//! # }
+//! ```
 //!
 //! ### `SGRString` struct
 //!
@@ -100,6 +124,8 @@
 //! possible [`SGR`][SGR] sequences. You can use it to reproduce the previous examples as such:
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use easy_sgr::{Color::*, EasySGR, Style::*};
 //!
 //! let text = "This should be italic & red!"
@@ -107,6 +133,7 @@
 //!     .style(Italic)
 //!     .color(RedFg);
 //! println!("{text}");
+//! # }
 //! ```
 //!
 //! You can forgo `.to_sgr()` as `.style(..)`, `.color(..)` and all other `EasySGR` functions
@@ -115,6 +142,8 @@
 //! The method above still uses the `EasySGR` trait, you can go without it like here:
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use easy_sgr::{ColorKind, SGRString, StyleKind};
 //!
 //! let mut text = SGRString::from("This should be italic & red!");
@@ -122,6 +151,7 @@
 //! text.foreground = ColorKind::Red;
 //!
 //! println!("{text}")
+//! # }
 //! ```
 //!
 //! ### `SGRWriter` struct
@@ -129,6 +159,8 @@
 //! The writer can also be used directly, instead of using the above methods:
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use std::io::{stdout, Write};
 //! use easy_sgr::{Color::*, EasySGR, SGRWriter, Style::*};
 //!
@@ -136,11 +168,14 @@
 //! writer.sgr(&Italic.color(RedFg)).unwrap();
 //! writer.write_inner("This should be italic & red!").unwrap();
 //! writer.sgr(&Reset).unwrap();
+//! # }
 //! ```
 //!
 //! or, when writing to a String
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use easy_sgr::{Color::*, EasySGR, SGRWriter, Style::*};
 //!
 //! let stylized_string = {
@@ -150,10 +185,34 @@
 //!     writer.sgr(&Reset).unwrap();
 //!     writer.internal()
 //! };
+//! # }
 //! ```
 //!
 //! ## Features
 //!
+//! ### `full`
+//!
+//! Enables every feature adding to the crate, all but `partial` & `macro-only`,
+//! which change what the crate is rather than adding to it, & the development only `bench-compare`:
+//!
+//! ```toml
+//! [dependencies]
+//! easy-sgr = { version = "0.1.1", features = ["full"] }
+//! ```
+//!
+//! ### `from-str`
+//!
+//! Implements `FromStr` for `Seq`, `Style` & `Color`, parsing the names of their variants:
+//!
+//! ```rust
+//! # #[cfg(feature = "from-str")]
+//! # {
+//! use easy_sgr::Style;
+//!
+//! assert_eq!("Bold".parse::<Style>(), Ok(Style::Bold));
+//! # }
+//! ```
+//!
 //! ### `partial`
 //!
 //! This feature changes the way that the `discrete` module works,
@@ -162,9 +221,12 @@
 //! This means to achieve the same affect as above you must do this:
 //!
 //! ```rust
+//! # #[cfg(not(feature = "macro-only"))]
+//! # {
 //! use easy_sgr::{Color::*, Seq::*, Style::*};
 //!
 //! println!("{Esc}{Italic};{RedFg}{End}This should be italic & red!{Esc}{Reset}{End}");
+//! # }
 //! ```
 //!
 //! resulting in the string:
@@ -175,9 +237,325 @@
 //!
 //! This feature exchanges ease of use for verbosity, resulting in more control.
 //!
+//! Without it, `params_display()` gives the same for a single `Style` or `Color`,
+//! i.e. `format!("\x1b[{}m", RedFg.params_display())`.
+//!
+//! ### `control`
+//!
+//! Enables the `control` module, containing non-[`SGR`][SGR] control sequences
+//! such as cursor movement & erasing, written using the same writers:
+//!
+//! ```rust
+//! # #[cfg(feature = "control")]
+//! # {
+//! use easy_sgr::{Control::*, EraseMode, Style::*};
+//!
+//! print!("{}{}{Bold}Overwritten!{Reset}", CursorToColumn(1), EraseLine(EraseMode::All));
+//! # }
+//! ```
+//!
+//! Along with the `frame` module, whose `FrameBuffer` records a frame of output
+//! and writes it all at once, optionally only the lines that changed.
+//!
+//! ### `osc`
+//!
+//! Enables the `osc` module, with the operating system commands setting the window title
+//! & copying to the clipboard, terminated by `ESC \`:
+//!
+//! ```rust
+//! # #[cfg(feature = "osc")]
+//! # {
+//! use easy_sgr::osc::{CopyToClipboard, SetTitle};
+//!
+//! print!("{}{}", SetTitle("easy-sgr"), CopyToClipboard(b"copied"));
+//! # }
+//! ```
+//!
+//! ### `tokio`
+//!
+//! Enables the `asynchronous` module, writing to a tokio `AsyncWrite`
+//! without rendering into an intermediate `String` first:
+//!
+//! ```rust
+//! # #[cfg(feature = "tokio")]
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! use easy_sgr::{AsyncIoWriter, EasySGR, Style::*};
+//!
+//! let mut writer = AsyncIoWriter(Vec::new());
+//! "Async!".style(Bold).write_async(&mut writer).await.unwrap();
+//! # });
+//! ```
+//!
+//! ### `report`
+//!
+//! Enables the `report` module, rendering an error & its sources
+//! with dimmed labels and a bold red message:
+//!
+//! ```rust
+//! # #[cfg(feature = "report")]
+//! # {
+//! let err = "-1".parse::<u8>().unwrap_err();
+//! eprintln!("{}", easy_sgr::report(&err));
+//! # }
+//! ```
+//!
+//! ### `table`
+//!
+//! Enables the `table` module, aligning rows of styled cells by their visible width:
+//!
+//! ```rust
+//! # #[cfg(feature = "table")]
+//! # {
+//! use easy_sgr::{Color::*, EasySGR, Row, Table};
+//!
+//! let table = Table::new()
+//!     .separator(" | ")
+//!     .row(Row::from_iter(["key", "value"]))
+//!     .row(Row::from_iter(["status".to_sgr(), "ok".color(GreenFg)]));
+//! print!("{table}");
+//! # }
+//! ```
+//!
+//! ### `testing`
+//!
+//! Enables the `testing` module & `assert_sgr_eq!`, comparing styled output by its
+//! visible text & the attributes in effect rather than byte for byte.
+//! Failures are reported by `diff`, naming the text & attributes that differ:
+//!
+//! ```rust
+//! # #[cfg(feature = "testing")]
+//! # {
+//! use easy_sgr::{assert_sgr_eq, Color::*, EasySGR, Style::*};
+//!
+//! assert_sgr_eq!("X".color(RedFg).style(Bold), "\x1b[1m\x1b[31mX");
+//! # }
+//! ```
+//!
+//! ### `proptest`
+//!
+//! Enables the `proptest` module, with strategies & `Arbitrary` impls for
+//! `Style`, `Color`, `StyleSet` & `SGRString`:
+//!
+//! ```rust
+//! # #[cfg(feature = "proptest")]
+//! # {
+//! use easy_sgr::proptest::any_color;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(color in any_color())| {
+//!     prop_assert!(!color.to_string().is_empty());
+//! });
+//! # }
+//! ```
+//!
+//! ### `rand`
+//!
+//! Adds `Color::random_fg`, picking a random readable color from the 256 color cube.
+//! The stable `Color::hashed_fg` is always available:
+//!
+//! ```rust
+//! # #[cfg(feature = "rand")]
+//! # {
+//! use easy_sgr::{Color, EasySGR};
+//!
+//! println!("{}", "random".color(Color::random_fg(&mut rand::rng())));
+//! # }
+//! ```
+//!
+//! ### `a11y`
+//!
+//! Adds the `a11y` module, simulating color blindness through `Color::simulate`
+//! along with a palette of colors that stay distinguishable regardless:
+//!
+//! ```rust
+//! # #[cfg(feature = "a11y")]
+//! # {
+//! use easy_sgr::{a11y::{ColorVisionDeficiency::*, *}, EasySGR};
+//!
+//! let (ok, error) = (BLUISH_GREEN, VERMILLION);
+//! assert_ne!(ok.simulate(Deuteranopia), error.simulate(Deuteranopia));
+//! println!("{} {}", "ok".color(ok), "error".color(error));
+//! # }
+//! ```
+//!
+//! ### `binary`
+//!
+//! Adds the `binary` module, encoding `SGRString`s into a compact binary form
+//! to send between processes without losing their attributes:
+//!
+//! ```rust
+//! # #[cfg(feature = "binary")]
+//! # {
+//! use easy_sgr::{Color::*, EasySGR, SGRString};
+//!
+//! let sgr = "styled".color(RedFg);
+//! let decoded = SGRString::from_bytes(&sgr.to_bytes()).unwrap();
+//! assert_eq!(decoded.to_string(), sgr.to_string());
+//! # }
+//! ```
+//!
+//! ### `extended`
+//!
+//! Adds `Extended`, for the rarely supported proportional spacing & ideogram codes,
+//! along with their keywords in the macros:
+//!
+//! ```rust
+//! # #[cfg(feature = "extended")]
+//! # {
+//! use easy_sgr::{Extended::*, SGRString};
+//!
+//! println!("{}ideogram{}", IdeogramStress, NotIdeogram);
+//! let text = SGRString::from(ProportionalSpacing);
+//! # }
+//! ```
+//!
+//! ### `regex`
+//!
+//! Adds `highlight_regex`, styling every match of a regex like `highlight_matches`:
+//!
+//! ```rust
+//! # #[cfg(feature = "regex")]
+//! # {
+//! use easy_sgr::{highlight_regex, Style::*};
+//!
+//! let regex = regex::Regex::new(r"\d+").unwrap();
+//! println!("{}", highlight_regex("took 12ms", &regex, Bold));
+//! # }
+//! ```
+//!
+//! ### `query`
+//!
+//! Enables the `query` module, asking the terminal for its background color
+//! so light & dark themes can be told apart. Falls back to `COLORFGBG`
+//! when the terminal does not answer. Only queries on unix:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "query")]
+//! # {
+//! use easy_sgr::query::{detect_background, Luma};
+//!
+//! let theme = match detect_background() {
+//!     Some(Luma::Light) => "light",
+//!     Some(Luma::Dark) | None => "dark",
+//! };
+//! # }
+//! ```
+//!
+//! ### `wasm-console`
+//!
+//! Enables the `console` module, writing styled text as the arguments of the browser's
+//! `console.log`: a format string placing each style with `%c`, & the CSS of each style.
+//! Nothing is bound to JavaScript, the caller passes both on, i.e. through `wasm-bindgen`:
+//!
+//! ```rust
+//! # #[cfg(feature = "wasm-console")]
+//! # {
+//! use easy_sgr::{EasySGR, Style::*};
+//!
+//! let (format, styles) = "ready".style(Bold).to_console_args();
+//! assert_eq!((&*format, &*styles), ("%cready", &[String::from("font-weight: bold")][..]));
+//! # }
+//! ```
+//!
+//! ### `palettes`
+//!
+//! Adds the built-in color schemes `SOLARIZED`, `DRACULA` & `GRUVBOX` to the `palette256` module,
+//! each a `PaletteMap` for a `RemapWriter` to write the basic colors as:
+//!
+//! ```rust
+//! # #[cfg(feature = "palettes")]
+//! # {
+//! use easy_sgr::{palette256::DRACULA, Color::*, FmtWriter, RemapWriter, SGRWriter};
+//!
+//! let mut writer = SGRWriter::from(RemapWriter::new(FmtWriter(String::new()), DRACULA));
+//! writer.inline_sgr(&RedFg).unwrap();
+//! assert_eq!(writer.internal(), "\x1b[38;2;255;85;85m");
+//! # }
+//! ```
+//!
+//! ### `html`
+//!
+//! Enables the `html` module, writing styled text as HTML: each style a `<span>` with its CSS,
+//! the text escaped. With `macros`, `format_html!` does the same at compile time:
+//!
+//! ```rust
+//! # #[cfg(feature = "html")]
+//! # {
+//! use easy_sgr::{EasySGR, Color::*};
+//!
+//! let html = "<ok>".color(GreenFg).to_html();
+//! assert_eq!(html, r#"<span style="color: rgb(0, 128, 0)">&lt;ok&gt;</span>"#);
+//! # }
+//! ```
+//!
+//! ### `theme`
+//!
+//! Enables the `theme` module: a `Theme` of the styles of errors, warnings & the like,
+//! set for the whole process with `set_global` & overridden on a thread by `with_scoped`,
+//! such as within a test or a subcommand. `error_style()` & the others look up the theme in effect:
+//!
+//! ```rust
+//! # #[cfg(feature = "theme")]
+//! # {
+//! use easy_sgr::{theme::{self, Theme}, Color::*, EasySGR};
+//!
+//! let plain = Theme { error: RedFg.to_sgr(), ..Theme::default() };
+//! let message = theme::with_scoped(plain, || theme::error_style().text("failed"));
+//! assert_eq!(message.to_string(), "\x1b[31mfailed");
+//! # }
+//! ```
+//!
+//! ### `terminfo`
+//!
+//! Enables the `terminfo` module, reading the terminfo database rather than guessing from `TERM`
+//! & `COLORTERM`. `terminfo::detect()` gives the `Capabilities` of the terminal:
+//! its colors & whether italics & strikethrough are displayed, for a `DowngradeWriter`
+//! rewriting what is not, i.e. italics as underline:
+//!
+//! ```rust
+//! # #[cfg(feature = "terminfo")]
+//! # {
+//! use easy_sgr::{terminfo, DowngradeWriter, IoWriter, SGRWriter, Style, StyleFallback};
+//!
+//! let downgrade = DowngradeWriter::new(IoWriter(std::io::stdout()), terminfo::detect())
+//!     .italic_fallback(StyleFallback::Substitute(Style::Underline));
+//! let mut writer = SGRWriter::from(downgrade);
+//! writer.inline_sgr(&Style::Italic).unwrap();
+//! # }
+//! ```
+//!
+//! ### `log`
+//!
+//! Enables the `logging` module, formatting `log` records with a colored level,
+//! including a format closure for `env_logger`.
+//! No codes are written when `NO_COLOR` is set:
+//!
+//! ```rust
+//! # #[cfg(feature = "log")]
+//! # {
+//! use easy_sgr::{format_record, SGRWriter};
+//!
+//! let record = log::Record::builder()
+//!     .level(log::Level::Info)
+//!     .target("app")
+//!     .args(format_args!("started"))
+//!     .build();
+//! let mut writer = SGRWriter::from(String::new());
+//! format_record(&record, &mut writer).unwrap();
+//! # }
+//! ```
+//!
+//! ### `bench-compare`
+//!
+//! Adds the benches comparing this crate to `colored` & `owo-colors`, only used during development:
+//!
+//! ```sh
+//! cargo bench -F bench-compare
+//! ```
+//!
 //! ## Structure
 //!
-//! easy-sgr is split into three modules:
+//! easy-sgr is split into seven modules:
 //!
 //! - discrete
 //!     - Contains types that can be used inline of a string literal
@@ -196,15 +574,44 @@
 //!         - `&str`
 //!         - `String`
 //!         - `&String`
+//! - runs
+//!     - Implements `SGRRuns`, text styled piece by piece through `StyleSet`s
+//!     - Created through `SGRString::style_range`, `map_chars` & `map_words`
+//! - span
+//!     - Implements `Span`, a tree of styled text, inner spans layered over their parents
+//! - status
+//!     - Implements `StatusLine`, a line rewritten in place with `\r`, such as progress
+//!     - Stale content wider than the new is erased, `\x1b[K` being used with `control`
+//! - palette256
+//!     - Indices & RGB values of the 256 color palette
+//!     - Used through `Color::cube_fg`, `Color::gray_bg` & the like
 //! - writing
 //!     - Implements `SGRWriter` & `SGRBuilder`
 //!     - Used by other modules to do writing
+//! - escapes
+//!     - `debug_escapes` & `DisplayEscaped` make escape sequences readable
+//!     - SGR sequences can be annotated with their keywords, such as `<bold red>`
+//!     - `offset_map` maps visible columns to byte offsets & back
+//!     - `optimize` removes SGR sequences that change nothing, merging the rest
+//!     - `invert_region` toggles inverse over styled text, such as a selection, then restores it
+//! - error
+//!     - Contains `Error`, unifying the errors of the writers
 //!
 //! Though no modules really will be seen in usage,
 //! as all the types they contain are reexported.
 //!
 //! [SGR]: https://en.wikipedia.org/wiki/ANSI_escape_code#SGR
 //!
+//! ## Benchmarks
+//!
+//! The benches in `benches/render.rs` are ran by `cargo bench`, covering styling a single word,
+//! highlighted lines of many segments, macro created strings & stripping large inputs.
+//! They are timed by criterion, each run showing the change since the last, so regressions are seen.
+//! Use `cargo bench -F full` to include the benches of the macros & the `table` feature.
+//!
+//! The benches in `benches/compare.rs` render the same text through `colored` & `owo-colors`,
+//! giving relative numbers. They are ran by `cargo bench -F bench-compare`
+//!
 //! ## TODO for `1.0.0` release
 //!
 //! - [ ] Add examples to docs
@@ -228,23 +635,151 @@
 )]
 #![warn(missing_debug_implementations)]
 #![allow(clippy::enum_glob_use)]
+/// Pre-rendered sequences & their codes, for splicing into text without a writer
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::consts::{BOLD, RESET};
+///
+///let text = format!("{BOLD}third-party text{RESET}");
+///assert_eq!(text, "\x1b[1mthird-party text\x1b[0m");
+///```
+#[cfg(not(feature = "macro-only"))]
+pub mod consts;
 /// Implements SGR types that can be used standalone of a [`SGRString`]
 ///
 /// These types exist outside the context of a [`SGRString`], but
 /// can be used in conjunction of one through the use of [`EasySGR`]
 #[cfg(not(feature = "macro-only"))]
 pub mod discrete;
+/// Encodes SGR sequences into caller-provided buffers, using nothing beyond [`core`]
+///
+/// Neither allocates nor touches [`String`] or [`Vec`], so it suits environments without `alloc`.
+/// The writers of this crate format parameters through the same code
+#[cfg(not(feature = "macro-only"))]
+pub mod encode;
+/// Contains the crate's [`Error`] type
+#[cfg(not(feature = "macro-only"))]
+pub mod error;
+/// Makes escape sequences visible & reports the attributes they leave in effect
+#[cfg(not(feature = "macro-only"))]
+pub mod escapes;
 /// Contains the standard SGR implementations.
 ///
 /// Makes use of the [`writers`](writing) to write `SGR` codes to a writer
 #[cfg(not(feature = "macro-only"))]
 pub mod graphics;
+/// Indices of the 256 color palette, used by [`Color::ByteFg`] & [`Color::ByteBg`]
+#[cfg(not(feature = "macro-only"))]
+pub mod palette256;
+/// Implements [`SGRRuns`], text styled piece by piece
+#[cfg(not(feature = "macro-only"))]
+pub mod runs;
+/// Implements [`Span`], a tree of styled text
+#[cfg(not(feature = "macro-only"))]
+pub mod span;
+/// Implements [`StatusLine`], a line of output rewritten in place
+#[cfg(not(feature = "macro-only"))]
+pub mod status;
 /// Contains various structs and traits to help in writing `SGR` codes
 #[cfg(not(feature = "macro-only"))]
 pub mod writing;
 
+/// Simulates color blindness & provides a colorblind safe palette
+#[cfg(all(feature = "a11y", not(feature = "macro-only")))]
+pub mod a11y;
+/// Implements async writing through [`tokio`]'s `AsyncWrite`
+#[cfg(all(feature = "tokio", not(feature = "macro-only")))]
+pub mod asynchronous;
+/// Encodes [`SGRString`]s into a compact binary form, for sending between processes
+///
+/// The first byte is the [version](binary::VERSION), followed by each fragment in order.
+/// A fragment is its attributes, each a tag byte & its payload, ended by a `0` byte,
+/// then the length of its text & the UTF-8 text itself.
+/// Lengths are written seven bits a byte, the high bit set on every byte but the last.
+/// Attributes left unset are not written:
+///
+/// | Tag | Attribute | Payload |
+/// |-----|-----------|---------|
+/// | `1` | [`SGRString::clean`] | `1` reset, `2` reverse |
+/// | `2` | [`SGRString::reset`] | none |
+/// | `3` | [`SGRString::custom_places`] | a length & the codes |
+/// | `4` | [`SGRString::custom_cleans`] | a length & the codes |
+/// | `5` | [`SGRString::foreground`] | a color |
+/// | `6` | [`SGRString::background`] | a color |
+/// | `16` to `27` | [`SGRString::bold`] to [`SGRString::overline`], in field order | `1` place, `2` clean |
+///
+/// A color is `1` to `8` for black to white, `9` & an index for a byte color,
+/// `10` & three bytes for an RGB color, `11` for the default color
+/// or `12` to `19` for bright black to bright white
+#[cfg(all(feature = "binary", not(feature = "macro-only")))]
+pub mod binary;
+/// Writes styled text as the `%c` format string & styles of the browser's `console.log`
+#[cfg(all(feature = "wasm-console", not(feature = "macro-only")))]
+pub mod console;
+/// Implements non-SGR control sequences, such as cursor movement & erasing
+///
+/// Written using the same writers as the rest of the crate
+#[cfg(all(feature = "control", not(feature = "macro-only")))]
+pub mod control;
+/// Implements [`FrameBuffer`], styled output written to the terminal a frame at a time
+#[cfg(all(feature = "control", not(feature = "macro-only")))]
+pub mod frame;
+/// Writes styled text as HTML, each style a `<span>` with its CSS
+#[cfg(all(feature = "html", not(feature = "macro-only")))]
+pub mod html;
+/// Formats [`log`] records with colored levels
+#[cfg(all(feature = "log", not(feature = "macro-only")))]
+pub mod logging;
+/// Implements the operating system commands setting the window title & copying to the clipboard
+///
+/// Written using the same writers as the rest of the crate
+#[cfg(all(feature = "osc", not(feature = "macro-only")))]
+pub mod osc;
+/// Implements [`proptest`](::proptest) strategies for the crate's types
+#[cfg(all(feature = "proptest", not(feature = "macro-only")))]
+pub mod proptest;
+/// Queries the terminal's background color, to choose colors for light or dark themes
+#[cfg(all(feature = "query", not(feature = "macro-only")))]
+pub mod query;
+/// Renders errors & their sources
+#[cfg(all(feature = "report", not(feature = "macro-only")))]
+pub mod report;
+/// Lays out rows of cells by their visible width
+#[cfg(all(feature = "table", not(feature = "macro-only")))]
+pub mod table;
+/// Implements [`Terminfo`](terminfo::Terminfo), a parser of compiled terminfo entries
+#[cfg(all(feature = "terminfo", not(feature = "macro-only")))]
+pub mod terminfo;
+/// Compares styled text by its visible text & attributes, for tests
+#[cfg(all(feature = "testing", not(feature = "macro-only")))]
+pub mod testing;
+/// Implements [`Theme`](theme::Theme), a global theme overridable within a scope
+#[cfg(all(feature = "theme", not(feature = "macro-only")))]
+pub mod theme;
+
 #[cfg(not(feature = "macro-only"))]
-pub use self::{discrete::*, graphics::*, writing::*};
+pub use self::{
+    discrete::*, error::*, escapes::*, graphics::*, runs::*, span::*, status::*, writing::*,
+};
+
+#[cfg(all(feature = "control", not(feature = "macro-only")))]
+pub use self::{control::*, frame::*};
+
+#[cfg(all(feature = "log", not(feature = "macro-only")))]
+pub use self::logging::*;
+
+#[cfg(all(feature = "report", not(feature = "macro-only")))]
+pub use self::report::*;
+
+#[cfg(all(feature = "table", not(feature = "macro-only")))]
+pub use self::table::*;
+#[cfg(all(feature = "testing", not(feature = "macro-only")))]
+pub use self::testing::*;
+
+#[cfg(all(feature = "tokio", not(feature = "macro-only")))]
+pub use self::asynchronous::*;
 
 #[cfg(feature = "macros")]
 pub use easy_sgr_macros::*;
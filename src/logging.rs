@@ -0,0 +1,157 @@
+use std::io;
+
+use log::{Level, Record};
+
+use crate::{CapableWriter, CleanKind, Color, EasySGR, IoWriter, SGRString, SGRWriter};
+
+/// The styles used when formatting a [`Record`]
+///
+/// Each style is an [`SGRString`] whose codes are applied to the text,
+/// its own text is ignored
+#[derive(Debug, Clone)]
+pub struct LevelStyle {
+    /// Applied to [`Level::Error`]
+    pub error: SGRString,
+    /// Applied to [`Level::Warn`]
+    pub warn: SGRString,
+    /// Applied to [`Level::Info`]
+    pub info: SGRString,
+    /// Applied to [`Level::Debug`]
+    pub debug: SGRString,
+    /// Applied to [`Level::Trace`]
+    pub trace: SGRString,
+    /// Applied to the record's target
+    pub target: SGRString,
+    /// Whether any codes are written at all
+    ///
+    /// Defaults to the crate-wide switch, [`enabled`](crate::enabled)
+    pub enabled: bool,
+}
+impl LevelStyle {
+    /// Returns the style applied to the given [`Level`]
+    #[must_use]
+    pub const fn level(&self, level: Level) -> &SGRString {
+        match level {
+            Level::Error => &self.error,
+            Level::Warn => &self.warn,
+            Level::Info => &self.info,
+            Level::Debug => &self.debug,
+            Level::Trace => &self.trace,
+        }
+    }
+}
+impl Default for LevelStyle {
+    /// Error as bold red, warn as yellow, info as green,
+    /// debug as cyan, trace & targets as dim
+    ///
+    /// With the `theme` feature, error, warn & info are those of the
+    /// [current](crate::theme::current) theme, trace & targets its hint
+    #[cfg(feature = "theme")]
+    fn default() -> Self {
+        let theme = crate::theme::current();
+        Self {
+            error: theme.error.clone(),
+            warn: theme.warn.clone(),
+            info: theme.info.clone(),
+            debug: Color::CyanFg.clean(CleanKind::Reverse),
+            trace: theme.hint.clone(),
+            target: theme.hint.clone(),
+            enabled: crate::enabled(),
+        }
+    }
+    /// Error as bold red, warn as yellow, info as green,
+    /// debug as cyan, trace & targets as dim
+    #[cfg(not(feature = "theme"))]
+    fn default() -> Self {
+        use crate::Style;
+        Self {
+            error: Style::Bold.color(Color::RedFg).clean(CleanKind::Reverse),
+            warn: Color::YellowFg.clean(CleanKind::Reverse),
+            info: Color::GreenFg.clean(CleanKind::Reverse),
+            debug: Color::CyanFg.clean(CleanKind::Reverse),
+            trace: Style::Dim.clean(CleanKind::Reverse),
+            target: Style::Dim.clean(CleanKind::Reverse),
+            enabled: crate::enabled(),
+        }
+    }
+}
+/// Formats a [`Record`] as `LEVEL target: message` using the default [`LevelStyle`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+/// Error type specified by [`CapableWriter::Error`]
+pub fn format_record<W: CapableWriter>(
+    record: &Record<'_>,
+    writer: &mut SGRWriter<W>,
+) -> Result<(), W::Error> {
+    format_record_with(record, writer, &LevelStyle::default())
+}
+/// Formats a [`Record`] as `LEVEL target: message` using the given [`LevelStyle`]
+///
+/// The level is padded to five characters, the padding is left unstyled.
+/// No trailing newline is written
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+/// Error type specified by [`CapableWriter::Error`]
+pub fn format_record_with<W: CapableWriter>(
+    record: &Record<'_>,
+    writer: &mut SGRWriter<W>,
+    style: &LevelStyle,
+) -> Result<(), W::Error> {
+    let level = record.level().as_str();
+    write_styled(writer, level, style.level(record.level()), style.enabled)?;
+    for _ in level.len()..5 {
+        writer.write_inner(" ")?;
+    }
+    writer.write_inner(" ")?;
+    write_styled(writer, record.target(), &style.target, style.enabled)?;
+    writer.write_inner(": ")?;
+
+    match record.args().as_str() {
+        Some(s) => writer.write_inner(s),
+        None => writer.write_inner(&record.args().to_string()),
+    }
+}
+/// Returns a closure for use with `env_logger::Builder::format`
+///
+/// Formats each record using [`format_record_with`], followed by a newline
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{env_logger_format, LevelStyle};
+///
+///env_logger::Builder::new()
+///    .format(env_logger_format(LevelStyle::default()))
+///    .init();
+///
+///log::info!("Hello!");
+///```
+pub fn env_logger_format<W: io::Write>(
+    style: LevelStyle,
+) -> impl Fn(&mut W, &Record<'_>) -> io::Result<()> + Send + Sync + 'static {
+    move |buf, record| {
+        let mut writer = SGRWriter::from(IoWriter(&mut *buf));
+        format_record_with(record, &mut writer, &style)?;
+        writer.write_inner("\n")
+    }
+}
+/// Writes the text with its codes, or only the text if not enabled
+fn write_styled<W: CapableWriter>(
+    writer: &mut SGRWriter<W>,
+    text: &str,
+    style: &SGRString,
+    enabled: bool,
+) -> Result<(), W::Error> {
+    if enabled {
+        writer.place_sgr(style)?;
+    }
+    writer.write_inner(text)?;
+    if enabled {
+        writer.clean_sgr(style)?;
+    }
+    Ok(())
+}
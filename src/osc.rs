@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+use crate::{CapableWriter, SGRWriter};
+
+/// The string that begins an operating system command, `\x1b]`
+pub const OSC: &str = "\x1b]";
+/// The string terminator ending an operating system command, `\x1b\`
+pub const ST: &str = "\x1b\\";
+
+/// Sets the title of the window, the sequence `\x1b]2;<title>\x1b\`
+///
+/// Control characters within the title, which could end the sequence early or
+/// begin another, are left out. Text that is not ASCII is written as is
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::osc::SetTitle;
+///
+///assert_eq!(SetTitle("build: ok").to_string(), "\x1b]2;build: ok\x1b\\");
+///assert_eq!(SetTitle("a\x1b]0;b\x07").to_string(), "\x1b]2;a]0;b\x1b\\");
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetTitle<'a>(pub &'a str);
+impl SetTitle<'_> {
+    /// Writes the sequence to the given writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write(OSC)?;
+        writer.write("2;")?;
+        for part in self.0.split(char::is_control) {
+            if !part.is_empty() {
+                writer.write(part)?;
+            }
+        }
+        writer.write(ST)
+    }
+}
+impl Display for SetTitle<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(&mut SGRWriter::from(f))
+    }
+}
+/// Copies bytes to the clipboard, the sequence `\x1b]52;c;<base64>\x1b\`
+///
+/// The bytes are base64 encoded as written, without allocating.
+/// Terminals may limit the size of the payload or ignore the sequence entirely
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::osc::CopyToClipboard;
+///
+///assert_eq!(CopyToClipboard(b"hello").to_string(), "\x1b]52;c;aGVsbG8=\x1b\\");
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyToClipboard<'a>(pub &'a [u8]);
+impl CopyToClipboard<'_> {
+    /// Writes the sequence to the given writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write(OSC)?;
+        writer.write("52;c;")?;
+        let mut buf = [0; 64];
+        for chunk in self.0.chunks(48) {
+            writer.write(base64(chunk, &mut buf))?;
+        }
+        writer.write(ST)
+    }
+}
+impl Display for CopyToClipboard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(&mut SGRWriter::from(f))
+    }
+}
+/// The characters of the standard base64 alphabet
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Encodes up to 48 bytes as padded base64 into `buf`
+fn base64<'a>(bytes: &[u8], buf: &'a mut [u8; 64]) -> &'a str {
+    let mut len = 0;
+    for group in bytes.chunks(3) {
+        let n = group
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            buf[len + i] = if i <= group.len() {
+                ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]
+            } else {
+                b'='
+            };
+        }
+        len += 4;
+    }
+    // only characters of the alphabet & padding were written
+    std::str::from_utf8(&buf[..len]).unwrap_or_default()
+}
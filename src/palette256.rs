@@ -0,0 +1,417 @@
+/// The 16 system colors, as given by xterm
+const SYSTEM: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+/// The value of each of the six steps of the color cube
+const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the index of a color within the 6×6×6 color cube, `16` to `231`
+///
+/// # Panics
+///
+/// Panics if any of the components is greater than `5`,
+/// a compile error when evaluated in a const context
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::cube;
+///
+///const ORANGE: u8 = cube(5, 2, 0);
+///assert_eq!(ORANGE, 208);
+///```
+///
+///Out of range components fail to compile in a const context:
+///
+///```rust,compile_fail
+///use easy_sgr::palette256::cube;
+///
+///let index = const { cube(6, 0, 0) };
+///```
+#[must_use]
+pub const fn cube(r: u8, g: u8, b: u8) -> u8 {
+    assert!(
+        r <= 5 && g <= 5 && b <= 5,
+        "cube components range from 0 to 5"
+    );
+    16 + 36 * r + 6 * g + b
+}
+/// Returns the index of a level of the grayscale ramp, `232` to `255`
+///
+/// # Panics
+///
+/// Panics if the level is greater than `23`,
+/// a compile error when evaluated in a const context
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::gray;
+///
+///assert_eq!(gray(0), 232);
+///assert_eq!(gray(23), 255);
+///```
+#[must_use]
+pub const fn gray(level: u8) -> u8 {
+    assert!(level <= 23, "gray levels range from 0 to 23");
+    232 + level
+}
+/// Returns the red, green & blue values of an index, matching the standard xterm palette
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::to_rgb;
+///
+///assert_eq!(to_rgb(9), (255, 0, 0));
+///assert_eq!(to_rgb(208), (255, 135, 0));
+///assert_eq!(to_rgb(232), (8, 8, 8));
+///```
+#[must_use]
+pub const fn to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => SYSTEM[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_STEPS[(i / 36) as usize],
+                CUBE_STEPS[(i / 6 % 6) as usize],
+                CUBE_STEPS[(i % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let v = 8 + 10 * (index - 232);
+            (v, v, v)
+        }
+    }
+}
+/// Returns true if the index is within the color cube & neither very dark nor very light
+///
+/// These colors are legible on both dark & light backgrounds,
+/// their [relative luminance](relative_luminance) being from `0.05` to `0.6`
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::{cube, is_readable};
+///
+///assert!(is_readable(cube(5, 2, 0)));
+///assert!(!is_readable(cube(0, 0, 0)));
+///assert!(!is_readable(cube(5, 5, 5)));
+///```
+#[must_use]
+pub fn is_readable(index: u8) -> bool {
+    (16..=231).contains(&index) && (0.05..=0.6).contains(&relative_luminance(to_rgb(index)))
+}
+/// Returns the index of the color cube or grayscale ramp closest to the given color
+///
+/// Compares the squared distance to each of `16` to `255`, the first closest is returned.
+/// The system colors are skipped, as terminals theme them differently.
+/// Refer to [`NearestCache`] when converting the same colors repeatedly
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::{cube, gray, nearest};
+///
+///assert_eq!(nearest((250, 130, 10)), cube(5, 2, 0));
+///assert_eq!(nearest((100, 100, 100)), gray(9));
+///```
+#[must_use]
+pub fn nearest(rgb: (u8, u8, u8)) -> u8 {
+    (16..=255)
+        .min_by_key(|&index| distance(rgb, to_rgb(index)))
+        .unwrap_or(16)
+}
+/// Returns the index of the system color closest to the given color, `0` to `15`
+///
+/// Compares the squared distance to each of the colors given by xterm,
+/// the first closest is returned
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::{cube, nearest_system, to_rgb};
+///
+///assert_eq!(nearest_system((250, 20, 10)), 9);
+///assert_eq!(nearest_system(to_rgb(cube(0, 0, 2))), 4);
+///```
+#[must_use]
+pub fn nearest_system(rgb: (u8, u8, u8)) -> u8 {
+    (0..16)
+        .min_by_key(|&index| distance(rgb, to_rgb(index)))
+        .unwrap_or(0)
+}
+/// Returns the squared distance between two colors
+fn distance((r, g, b): (u8, u8, u8), (or, og, ob): (u8, u8, u8)) -> u32 {
+    [(r, or), (g, og), (b, ob)]
+        .into_iter()
+        .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+        .sum()
+}
+/// A fixed size cache of [`nearest`], for converting the same colors repeatedly
+///
+/// Holds up to [`NearestCache::CAPACITY`] colors in an open-addressed array,
+/// replacing older entries once its probes are full, so it never allocates.
+/// Results are always those of [`nearest`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::{nearest, NearestCache};
+///
+///let mut cache = NearestCache::new();
+///for _ in 0..3 {
+///    assert_eq!(cache.get((250, 130, 10)), nearest((250, 130, 10)));
+///}
+///assert_eq!((cache.hits(), cache.misses()), (2, 1));
+///```
+#[derive(Debug, Clone)]
+pub struct NearestCache {
+    entries: [(u32, u8); Self::CAPACITY],
+    hits: u64,
+    misses: u64,
+}
+impl NearestCache {
+    /// The number of colors held at once
+    pub const CAPACITY: usize = 64;
+    /// The number of slots looked at from a color's home slot
+    const PROBES: usize = 4;
+    /// Marks an entry as filled, so black is told apart from an empty slot
+    const FILLED: u32 = 1 << 24;
+    /// Creates an empty [`NearestCache`]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [(0, 0); Self::CAPACITY],
+            hits: 0,
+            misses: 0,
+        }
+    }
+    /// Returns [`nearest`] of the color, computed only if it is not cached
+    pub fn get(&mut self, (r, g, b): (u8, u8, u8)) -> u8 {
+        let key = Self::FILLED | u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b);
+        // the highest six bits of a Fibonacci hash, within the capacity
+        let home = (key.wrapping_mul(0x9e37_79b9) >> 26) as usize;
+        let mut free = None;
+        for probe in 0..Self::PROBES {
+            let slot = (home + probe) % Self::CAPACITY;
+            match self.entries[slot] {
+                (filled, index) if filled == key => {
+                    self.hits += 1;
+                    return index;
+                }
+                (0, _) => {
+                    free.get_or_insert(slot);
+                }
+                _ => (),
+            }
+        }
+        self.misses += 1;
+        let index = nearest((r, g, b));
+        self.entries[free.unwrap_or(home)] = (key, index);
+        index
+    }
+    /// Returns the number of colors found cached
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+    /// Returns the number of colors computed
+    #[must_use]
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+    /// Removes every cached color, keeping the counts
+    pub const fn clear(&mut self) {
+        self.entries = [(0, 0); Self::CAPACITY];
+    }
+}
+impl Default for NearestCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// The colors written in place of the 16 system colors, & optionally any of the 256 palette
+///
+/// Used by [`RemapWriter`](crate::RemapWriter) to write a color scheme as RGB colors,
+/// so it is shown the same whatever the terminal's own scheme.
+/// [`PaletteMap::default`] holds the standard xterm colors.
+/// Built-in schemes, such as [`SOLARIZED`], are enabled by the `palettes` feature
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::PaletteMap;
+///
+///let map = PaletteMap::default().with_color(1, (220, 50, 47)).with_override(208, (255, 160, 0));
+///assert_eq!(map.color(1), (220, 50, 47));
+///assert_eq!(map.get(1), Some((220, 50, 47)));
+///assert_eq!(map.get(208), Some((255, 160, 0)));
+///assert_eq!(map.get(209), None);
+///```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PaletteMap {
+    colors: [(u8, u8, u8); 16],
+    overrides: [Option<(u8, u8, u8)>; 256],
+}
+impl PaletteMap {
+    /// Creates a [`PaletteMap`] of the 16 system colors, black to white then bright black to bright white
+    #[must_use]
+    pub const fn new(colors: [(u8, u8, u8); 16]) -> Self {
+        Self {
+            colors,
+            overrides: [None; 256],
+        }
+    }
+    /// Sets one of the 16 system colors
+    ///
+    /// Indices greater than `15` are clamped
+    #[must_use]
+    pub const fn with_color(mut self, index: u8, rgb: (u8, u8, u8)) -> Self {
+        self.colors[clamp(index, 15) as usize] = rgb;
+        self
+    }
+    /// Sets the color written for an index of the 256 color palette, `38;5;<index>`
+    ///
+    /// Takes precedence over the system colors for indices up to `15`
+    #[must_use]
+    pub const fn with_override(mut self, index: u8, rgb: (u8, u8, u8)) -> Self {
+        self.overrides[index as usize] = Some(rgb);
+        self
+    }
+    /// Returns one of the 16 system colors
+    ///
+    /// Indices greater than `15` are clamped
+    #[must_use]
+    pub const fn color(&self, index: u8) -> (u8, u8, u8) {
+        self.colors[clamp(index, 15) as usize]
+    }
+    /// Returns the color written for an index of the 256 color palette
+    ///
+    /// The override if set, else the system color for indices up to `15`,
+    /// [`None`] for the other indices, which are left as they are
+    #[must_use]
+    pub const fn get(&self, index: u8) -> Option<(u8, u8, u8)> {
+        match self.overrides[index as usize] {
+            Some(rgb) => Some(rgb),
+            None if index < 16 => Some(self.colors[index as usize]),
+            None => None,
+        }
+    }
+}
+impl Default for PaletteMap {
+    fn default() -> Self {
+        Self::new(SYSTEM)
+    }
+}
+/// The dark [Solarized](https://ethanschoonover.com/solarized/) terminal colors
+#[cfg(feature = "palettes")]
+pub const SOLARIZED: PaletteMap = PaletteMap::new([
+    (7, 54, 66),
+    (220, 50, 47),
+    (133, 153, 0),
+    (181, 137, 0),
+    (38, 139, 210),
+    (211, 54, 130),
+    (42, 161, 152),
+    (238, 232, 213),
+    (0, 43, 54),
+    (203, 75, 22),
+    (88, 110, 117),
+    (101, 123, 131),
+    (131, 148, 150),
+    (108, 113, 196),
+    (147, 161, 161),
+    (253, 246, 227),
+]);
+/// The [Dracula](https://draculatheme.com) terminal colors
+#[cfg(feature = "palettes")]
+pub const DRACULA: PaletteMap = PaletteMap::new([
+    (33, 34, 44),
+    (255, 85, 85),
+    (80, 250, 123),
+    (241, 250, 140),
+    (189, 147, 249),
+    (255, 121, 198),
+    (139, 233, 253),
+    (248, 248, 242),
+    (98, 114, 164),
+    (255, 110, 110),
+    (105, 255, 148),
+    (255, 255, 165),
+    (214, 172, 255),
+    (255, 146, 223),
+    (164, 255, 255),
+    (255, 255, 255),
+]);
+/// The dark [Gruvbox](https://github.com/morhetz/gruvbox) terminal colors
+#[cfg(feature = "palettes")]
+pub const GRUVBOX: PaletteMap = PaletteMap::new([
+    (40, 40, 40),
+    (204, 36, 29),
+    (152, 151, 26),
+    (215, 153, 33),
+    (69, 133, 136),
+    (177, 98, 134),
+    (104, 157, 106),
+    (168, 153, 132),
+    (146, 131, 116),
+    (251, 73, 52),
+    (184, 187, 38),
+    (250, 189, 47),
+    (131, 165, 152),
+    (211, 134, 155),
+    (142, 192, 124),
+    (235, 219, 178),
+]);
+/// Clamps a component to the given maximum
+pub(crate) const fn clamp(value: u8, max: u8) -> u8 {
+    if value > max {
+        max
+    } else {
+        value
+    }
+}
+/// Returns the relative luminance of a color, from `0.0` for black to `1.0` for white
+///
+/// Uses the [WCAG](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance) formula
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::palette256::relative_luminance;
+///
+///assert_eq!(relative_luminance((0, 0, 0)), 0.0);
+///assert_eq!(relative_luminance((255, 255, 255)), 1.0);
+///```
+#[must_use]
+pub fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.039_28 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.0722f64.mul_add(
+        channel(b),
+        0.2126f64.mul_add(channel(r), 0.7152 * channel(g)),
+    )
+}
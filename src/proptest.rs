@@ -0,0 +1,210 @@
+use ::proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    prelude::{any, prop_oneof, BoxedStrategy, Just, Strategy},
+    sample::select,
+};
+
+use crate::{CleanKind, Color, ColorKind, EasySGR, SGRString, Style, StyleKind, StyleSet};
+
+/// The maximum number of [`char`]s in the text of [`any_sgr_string`]
+pub const MAX_TEXT_LEN: usize = 32;
+
+/// Returns a strategy producing every [`Style`]
+pub fn any_style() -> impl Strategy<Value = Style> {
+    use Style::*;
+    select(vec![
+        Reset,
+        Bold,
+        Dim,
+        Italic,
+        Underline,
+        Blinking,
+        RapidBlinking,
+        Inverse,
+        Hidden,
+        Strikethrough,
+        Framed,
+        Encircled,
+        Overline,
+        NotBold,
+        NotDim,
+        NotItalic,
+        NotUnderline,
+        NotBlinking,
+        NotInverse,
+        NotHidden,
+        NotStrikethrough,
+        NotFramedEncircled,
+        NotOverline,
+    ])
+}
+/// Returns a strategy producing every [`Color`], byte & RGB colors over the full [`u8`] range
+pub fn any_color() -> impl Strategy<Value = Color> {
+    use Color::*;
+    prop_oneof![
+        select(vec![
+            BlackFg,
+            RedFg,
+            GreenFg,
+            YellowFg,
+            BlueFg,
+            MagentaFg,
+            CyanFg,
+            WhiteFg,
+            DefaultFg,
+            BlackBg,
+            RedBg,
+            GreenBg,
+            YellowBg,
+            BlueBg,
+            MagentaBg,
+            CyanBg,
+            WhiteBg,
+            DefaultBg,
+            BrightBlackFg,
+            BrightRedFg,
+            BrightGreenFg,
+            BrightYellowFg,
+            BrightBlueFg,
+            BrightMagentaFg,
+            BrightCyanFg,
+            BrightWhiteFg,
+            BrightBlackBg,
+            BrightRedBg,
+            BrightGreenBg,
+            BrightYellowBg,
+            BrightBlueBg,
+            BrightMagentaBg,
+            BrightCyanBg,
+            BrightWhiteBg,
+        ]),
+        any::<u8>().prop_map(ByteFg),
+        any::<u8>().prop_map(ByteBg),
+        any::<(u8, u8, u8)>().prop_map(|(r, g, b)| RgbFg(r, g, b)),
+        any::<(u8, u8, u8)>().prop_map(|(r, g, b)| RgbBg(r, g, b)),
+    ]
+}
+/// Returns a strategy producing every [`StyleKind`]
+pub fn any_style_kind() -> impl Strategy<Value = StyleKind> {
+    select(vec![StyleKind::None, StyleKind::Place, StyleKind::Clean])
+}
+/// Returns a strategy producing every [`ColorKind`]
+pub fn any_color_kind() -> impl Strategy<Value = ColorKind> {
+    use ColorKind::*;
+    prop_oneof![
+        select(vec![
+            None,
+            Black,
+            Red,
+            Green,
+            Yellow,
+            Blue,
+            Magenta,
+            Cyan,
+            White,
+            Default,
+            BrightBlack,
+            BrightRed,
+            BrightGreen,
+            BrightYellow,
+            BrightBlue,
+            BrightMagenta,
+            BrightCyan,
+            BrightWhite,
+        ]),
+        any::<u8>().prop_map(Byte),
+        any::<(u8, u8, u8)>().prop_map(|(r, g, b)| Rgb(r, g, b)),
+    ]
+}
+/// Returns a strategy producing [`StyleSet`]s, with up to four custom codes of each kind
+pub fn any_style_set() -> impl Strategy<Value = StyleSet> {
+    (
+        vec(any::<u8>(), 0..=4),
+        vec(any::<u8>(), 0..=4),
+        any_color_kind(),
+        any_color_kind(),
+        ::proptest::array::uniform12(any_style_kind()),
+    )
+        .prop_map(
+            |(custom_places, custom_cleans, foreground, background, styles)| {
+                let [
+                    bold,
+                    dim,
+                    italic,
+                    underline,
+                    blinking,
+                    rapid_blinking,
+                    inverse,
+                    hidden,
+                    strikethrough,
+                    framed,
+                    encircled,
+                    overline,
+                ] = styles;
+                StyleSet {
+                    custom_places,
+                    custom_cleans,
+                    foreground,
+                    background,
+                    bold,
+                    dim,
+                    italic,
+                    underline,
+                    blinking,
+                    rapid_blinking,
+                    inverse,
+                    hidden,
+                    strikethrough,
+                    framed,
+                    encircled,
+                    overline,
+                }
+            },
+        )
+}
+/// Returns a strategy producing [`SGRString`]s, with up to [`MAX_TEXT_LEN`] [`char`]s of text
+pub fn any_sgr_string() -> impl Strategy<Value = SGRString> {
+    (
+        vec(any::<char>(), 0..=MAX_TEXT_LEN),
+        any::<bool>(),
+        prop_oneof![
+            Just(CleanKind::None),
+            Just(CleanKind::Reset),
+            Just(CleanKind::Reverse)
+        ],
+        any_style_set(),
+    )
+        .prop_map(|(text, reset, clean, set)| {
+            SGRString {
+                text: text.into_iter().collect(),
+                reset,
+                clean,
+                ..SGRString::default()
+            }
+            .apply(set)
+        })
+}
+/// Implements [`Arbitrary`] through the given strategy
+macro_rules! arbitrary {
+    ($($ty:ty => $strategy:ident),+) => {
+        $(
+            impl Arbitrary for $ty {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                    $strategy().boxed()
+                }
+            }
+        )+
+    };
+}
+arbitrary!(
+    Style => any_style,
+    Color => any_color,
+    StyleKind => any_style_kind,
+    ColorKind => any_color_kind,
+    StyleSet => any_style_set,
+    SGRString => any_sgr_string
+);
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use crate::palette256::relative_luminance;
+
+/// The query for the background color, `\x1b]11;?\x1b\`
+pub const BACKGROUND_QUERY: &str = "\x1b]11;?\x1b\\";
+/// The query for the primary device attributes, `\x1b[c`
+///
+/// Sent after [`BACKGROUND_QUERY`], as every terminal answers it,
+/// so terminals not answering the background query are not waited on
+pub const ATTRIBUTES_QUERY: &str = "\x1b[c";
+
+/// Whether a background is light or dark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Luma {
+    /// A light background, to be written on with dark colors
+    Light,
+    /// A dark background, to be written on with light colors
+    Dark,
+}
+impl Luma {
+    /// Classifies a color with 16 bits a channel, as reported by [`query_background`]
+    ///
+    /// A color is light when black contrasts with it more than white does,
+    /// that is when its [relative luminance](relative_luminance) is above `0.179`
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::query::Luma;
+    ///
+    ///assert_eq!(Luma::from_rgb16((0xffff, 0xffff, 0xffff)), Luma::Light);
+    ///assert_eq!(Luma::from_rgb16((0x1e1e, 0x1e1e, 0x1e1e)), Luma::Dark);
+    ///```
+    #[must_use]
+    pub fn from_rgb16(rgb: (u16, u16, u16)) -> Self {
+        let rgb = <[u16; 3]>::from(rgb).map(|c| c.to_be_bytes()[0]);
+        // (L + 0.05) / 0.05 > 1.05 / (L + 0.05)
+        if relative_luminance(rgb.into()) > 0.179 {
+            Self::Light
+        } else {
+            Self::Dark
+        }
+    }
+    /// Classifies the value of the `COLORFGBG` environment variable, i.e. `15;0`
+    ///
+    /// The last field is the background, an index of the 16 system colors:
+    /// `7` & `9` to `15` are light, the others dark.
+    /// Returns [`None`] when it is not an index, such as `default`
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::query::Luma;
+    ///
+    ///assert_eq!(Luma::from_colorfgbg("15;0"), Some(Luma::Dark));
+    ///assert_eq!(Luma::from_colorfgbg("0;default;15"), Some(Luma::Light));
+    ///assert_eq!(Luma::from_colorfgbg("default;default"), None);
+    ///```
+    #[must_use]
+    pub fn from_colorfgbg(value: &str) -> Option<Self> {
+        match value.rsplit(';').next()?.trim().parse::<u8>().ok()? {
+            0..=6 | 8 => Some(Self::Dark),
+            7 | 9..=15 => Some(Self::Light),
+            _ => None,
+        }
+    }
+}
+/// Parses the reply to [`BACKGROUND_QUERY`], returning the color with 16 bits a channel
+///
+/// The reply is `\x1b]11;rgb:rrrr/gggg/bbbb` ended by `\x1b\` or `\x07`,
+/// each channel being 1 to 4 hex digits, scaled to 16 bits.
+/// An alpha channel, `rgba:rrrr/gggg/bbbb/aaaa`, is ignored.
+/// Anything before or after the reply, such as the answer to [`ATTRIBUTES_QUERY`], is skipped
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::query::parse_background_reply;
+///
+///assert_eq!(
+///    parse_background_reply(b"\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\"),
+///    Some((0x1e1e, 0x1e1e, 0x2e2e))
+///);
+///assert_eq!(parse_background_reply(b"\x1b]11;rgb:ff/80/00\x07"), Some((0xffff, 0x8080, 0)));
+///assert_eq!(parse_background_reply(b"\x1b[?62;22c"), None);
+///```
+#[must_use]
+pub fn parse_background_reply(reply: &[u8]) -> Option<(u16, u16, u16)> {
+    let start = reply.windows(5).position(|w| w == b"\x1b]11;")? + 5;
+    let reply = &reply[start..];
+    let end = reply.iter().position(|&b| b == b'\x1b' || b == b'\x07')?;
+    let reply = std::str::from_utf8(&reply[..end]).ok()?;
+    let (channels, alpha) = match reply.split_once(':')? {
+        ("rgb", channels) => (channels, false),
+        ("rgba", channels) => (channels, true),
+        _ => return None,
+    };
+    let mut channels = channels.split('/').map(channel);
+    let rgb = (channels.next()??, channels.next()??, channels.next()??);
+    if alpha {
+        channels.next()??;
+    }
+    channels.next().is_none().then_some(rgb)
+}
+/// Parses a channel of 1 to 4 hex digits, scaled to 16 bits
+fn channel(hex: &str) -> Option<u16> {
+    if !(1..=4).contains(&hex.len()) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    let max = (1 << (4 * hex.len())) - 1;
+    u16::try_from(value * 0xffff / max).ok()
+}
+/// Returns whether the answer to [`ATTRIBUTES_QUERY`], `\x1b[?..c`, has been read
+fn answered(reply: &[u8]) -> bool {
+    reply
+        .windows(3)
+        .position(|w| w == b"\x1b[?")
+        .is_some_and(|start| reply[start..].contains(&b'c'))
+}
+/// Sends [`BACKGROUND_QUERY`] to the controlling terminal, returning the parsed reply
+///
+/// The terminal is put in a mode without echo or line buffering while waiting,
+/// being restored before returning, whether or not a reply arrives.
+/// [`ATTRIBUTES_QUERY`] is sent after, so waiting stops once it is answered
+/// rather than after the full `timeout` when the background query is not supported.
+///
+/// Returns [`None`] when there is no controlling terminal, as when ran by a pipeline or service,
+/// when the reply does not arrive in time or cannot be parsed.
+/// Always [`None`] outside of unix
+#[must_use]
+#[cfg(unix)]
+pub fn query_background(timeout: Duration) -> Option<(u16, u16, u16)> {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{Read, Write},
+        time::Instant,
+    };
+
+    use rustix::{
+        event::{poll, PollFd, PollFlags, Timespec},
+        termios::{tcgetattr, tcsetattr, LocalModes, OptionalActions, SpecialCodeIndex, Termios},
+    };
+
+    /// Restores the mode of the terminal when dropped
+    struct Restore<'a>(&'a File, Termios);
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            let _ = tcsetattr(self.0, OptionalActions::Now, &self.1);
+        }
+    }
+
+    let tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let original = tcgetattr(&tty).ok()?;
+    let mut mode = original.clone();
+    mode.local_modes
+        .remove(LocalModes::ICANON | LocalModes::ECHO);
+    mode.special_codes[SpecialCodeIndex::VMIN] = 0;
+    mode.special_codes[SpecialCodeIndex::VTIME] = 0;
+    tcsetattr(&tty, OptionalActions::Now, &mode).ok()?;
+    let _restore = Restore(&tty, original);
+
+    (&tty)
+        .write_all(format!("{BACKGROUND_QUERY}{ATTRIBUTES_QUERY}").as_bytes())
+        .ok()?;
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    let mut buf = [0; 64];
+    while !answered(&reply) {
+        let remaining =
+            Timespec::try_from(deadline.saturating_duration_since(Instant::now())).ok()?;
+        let mut fds = [PollFd::new(&tty, PollFlags::IN)];
+        if poll(&mut fds, Some(&remaining)).ok()? == 0 {
+            break;
+        }
+        match (&tty).read(&mut buf).ok()? {
+            0 => break,
+            len => reply.extend_from_slice(&buf[..len]),
+        }
+    }
+    parse_background_reply(&reply)
+}
+/// Sends [`BACKGROUND_QUERY`] to the controlling terminal, returning the parsed reply
+///
+/// Always [`None`] outside of unix
+#[must_use]
+#[cfg(not(unix))]
+pub const fn query_background(_timeout: Duration) -> Option<(u16, u16, u16)> {
+    None
+}
+/// Returns whether the terminal's background is light or dark
+///
+/// The terminal is [queried](query_background), waiting up to 100 milliseconds.
+/// When that fails the `COLORFGBG` environment variable is [used](Luma::from_colorfgbg),
+/// [`None`] being returned when neither is available
+///
+/// # Examples
+///
+///```rust,no_run
+///use easy_sgr::{query::{detect_background, Luma}, Color::*};
+///
+///let accent = match detect_background() {
+///    Some(Luma::Light) => BlueFg,
+///    Some(Luma::Dark) | None => BrightCyanFg,
+///};
+///```
+#[must_use]
+pub fn detect_background() -> Option<Luma> {
+    query_background(Duration::from_millis(100)).map_or_else(
+        || Luma::from_colorfgbg(&std::env::var("COLORFGBG").ok()?),
+        |rgb| Some(Luma::from_rgb16(rgb)),
+    )
+}
@@ -0,0 +1,94 @@
+use std::{error::Error, fmt::Write};
+
+use crate::{CleanKind, Color, EasySGR, SGRString, Style};
+
+/// The styles used by [`report_with`]
+///
+/// Each style is an [`SGRString`] whose codes are applied to the text,
+/// its own text is ignored
+#[derive(Debug, Clone)]
+pub struct ReportStyle {
+    /// Applied to the `error:` & `caused by:` labels
+    pub label: SGRString,
+    /// Applied to the message of the reported error
+    pub message: SGRString,
+    /// Applied to the messages of the error's sources
+    pub source: SGRString,
+    /// The number of spaces each source is indented by
+    pub indent: usize,
+}
+impl Default for ReportStyle {
+    /// Dimmed labels, a bold red message, and red sources indented by two spaces
+    fn default() -> Self {
+        Self {
+            label: Style::Dim.clean(CleanKind::Reverse),
+            message: Style::Bold.color(Color::RedFg).clean(CleanKind::Reverse),
+            source: Color::RedFg.clean(CleanKind::Reverse),
+            indent: 2,
+        }
+    }
+}
+/// Renders an error & its chain of [sources](Error::source) using the default [`ReportStyle`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::report;
+///
+///let err = "not a number".parse::<u8>().unwrap_err();
+///eprintln!("{}", report(&err));
+///```
+#[must_use]
+pub fn report(err: &dyn Error) -> SGRString {
+    report_with(err, &ReportStyle::default())
+}
+/// Renders an error & its chain of [sources](Error::source) using the given [`ReportStyle`]
+///
+/// The error is written as `error: <message>`, then each source is written on
+/// a new line as `caused by: <message>`, indented one level further than the last.
+/// Lines after the first of a multi-line message are aligned with its first line.
+///
+/// The returned [`SGRString`] holds the styled text, without codes of its own
+#[must_use]
+pub fn report_with(err: &dyn Error, style: &ReportStyle) -> SGRString {
+    let mut text = String::new();
+    let mut current = Some(err);
+    let mut depth = 0;
+
+    while let Some(err) = current {
+        let (label, message_style) = if depth == 0 {
+            ("error:", &style.message)
+        } else {
+            text.push('\n');
+            ("caused by:", &style.source)
+        };
+        let indent = depth * style.indent;
+        push_styled(&mut text, label, &style.label, indent);
+        text.push(' ');
+
+        let message = err.to_string();
+        for (i, line) in message.lines().enumerate() {
+            if i != 0 {
+                text.push('\n');
+                text.extend(std::iter::repeat_n(' ', indent + label.len() + 1));
+            }
+            push_styled(&mut text, line, message_style, 0);
+        }
+        current = err.source();
+        depth += 1;
+    }
+    SGRString::from(text)
+}
+/// Pushes the text indented by `indent` spaces, with only the text styled
+fn push_styled(buf: &mut String, text: &str, style: &SGRString, indent: usize) {
+    buf.extend(std::iter::repeat_n(' ', indent));
+    if text.is_empty() {
+        return;
+    }
+    let styled = SGRString {
+        text: text.to_string(),
+        ..style.clone()
+    };
+    // writing to a String cannot fail
+    let _ = write!(buf, "{styled}");
+}
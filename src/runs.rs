@@ -0,0 +1,399 @@
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{
+    CapableWriter, CleanKind, SGRBuilder, SGRString, SGRWriter, StyleSet, TransitionOptions,
+};
+
+/// Text made up of runs, each styled by its own [`StyleSet`]
+///
+/// Created from an [`SGRString`], whose attributes become the base every run is layered on.
+/// Indices are of visible [`char`]s, adjacent runs of the same style are merged
+/// so only the codes that change between runs are written.
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, EasySGR, SGRString, Style::*};
+///
+///let text = SGRString::from("a match here").style_range(2..7, Bold.color(RedFg));
+///assert_eq!(text.to_string(), "a \x1b[31;1mmatch\x1b[39;22m here");
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SGRRuns {
+    runs: Vec<(String, StyleSet)>,
+    base: StyleSet,
+    reset: bool,
+    clean: CleanKind,
+    options: TransitionOptions,
+}
+impl SGRRuns {
+    /// Returns the runs, each a piece of text & its style
+    #[must_use]
+    pub fn runs(&self) -> &[(String, StyleSet)] {
+        &self.runs
+    }
+    /// Returns the visible text of all runs
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.runs.iter().map(|(text, _)| text.as_str()).collect()
+    }
+    /// Returns the visible text of all runs, borrowed when there are less than two
+    ///
+    /// Refer to [`SGRRuns::pieces`] to go through the text of each run without allocating
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, EasySGR};
+    ///
+    ///let runs = "a b".to_sgr().style_range(2.., RedFg);
+    ///assert_eq!(runs.as_str(), "a b");
+    ///assert_eq!(runs.pieces().collect::<Vec<_>>(), ["a ", "b"]);
+    ///```
+    #[must_use]
+    pub fn as_str(&self) -> Cow<'_, str> {
+        match self.runs.as_slice() {
+            [] => Cow::Borrowed(""),
+            [(text, _)] => Cow::Borrowed(text),
+            _ => Cow::Owned(self.text()),
+        }
+    }
+    /// Returns an iterator over the text of each run, in order
+    pub fn pieces(&self) -> impl Iterator<Item = &str> {
+        self.runs.iter().map(|(text, _)| text.as_str())
+    }
+    /// Returns true if there is no visible text
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.runs.iter().all(|(text, _)| text.is_empty())
+    }
+    /// Replaces the text of each run with the result of `f`, leaving its style as it is
+    ///
+    /// Runs left empty are removed
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{EasySGR, Style::*};
+    ///
+    ///let runs = "ab".to_sgr().style_range(1.., Bold).map_text(str::to_uppercase);
+    ///assert_eq!(runs.to_string(), "A\x1b[1mB\x1b[22m");
+    ///```
+    #[must_use]
+    pub fn map_text(self, mut f: impl FnMut(&str) -> String) -> Self {
+        let runs = self
+            .runs
+            .into_iter()
+            .map(|(text, style)| (f(&text), style))
+            .filter(|(text, _)| !text.is_empty())
+            .collect();
+        Self { runs, ..self }
+    }
+    /// Layers a style over the [`char`]s within the range
+    ///
+    /// Attributes set by `style` win over those already applied,
+    /// a range past the end of the text is clamped
+    #[must_use]
+    pub fn style_range(self, range: impl RangeBounds<usize>, style: impl Into<StyleSet>) -> Self {
+        let style = style.into();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => usize::MAX,
+        };
+        self.restyle(|i, _| (start..end).contains(&i).then(|| style.clone()))
+    }
+    /// Layers the style returned for each [`char`] & its index over it
+    #[must_use]
+    pub fn map_chars<S: Into<StyleSet>>(self, mut f: impl FnMut(usize, char) -> S) -> Self {
+        self.restyle(|i, ch| Some(f(i, ch).into()))
+    }
+    /// Layers the style returned for each word & its index over it
+    ///
+    /// Words are separated by whitespace, which is left as is
+    #[must_use]
+    pub fn map_words<S: Into<StyleSet>>(self, mut f: impl FnMut(usize, &str) -> S) -> Self {
+        let text = self.text();
+        let mut styles = Vec::new();
+        let mut word_of = Vec::new();
+        let mut start = None;
+        let end = std::iter::once((text.len(), ' '));
+        for (i, (byte, ch)) in text.char_indices().chain(end).enumerate() {
+            match (ch.is_whitespace(), start) {
+                (true, Some((char_start, byte_start))) => {
+                    word_of.resize(char_start, None);
+                    word_of.resize(i, Some(styles.len()));
+                    styles.push(f(styles.len(), &text[byte_start..byte]).into());
+                    start = None;
+                }
+                (false, None) => start = Some((i, byte)),
+                _ => (),
+            }
+        }
+        self.restyle(|i, _| {
+            word_of
+                .get(i)
+                .copied()
+                .flatten()
+                .map(|word| styles[word].clone())
+        })
+    }
+    /// Sets the options used to write the codes between runs,
+    /// refer to [`StyleSet::transition_with`]
+    #[must_use]
+    pub fn transition_options(self, options: TransitionOptions) -> Self {
+        Self { options, ..self }
+    }
+    /// Writes the runs to the writer
+    ///
+    /// Ends by returning to the base style, then cleans it as the [`SGRString`] would
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write<W: CapableWriter>(&self, writer: &mut SGRWriter<W>) -> Result<(), W::Error> {
+        let empty = StyleSet::default();
+        let mut builder = SGRBuilder::default();
+        if self.reset {
+            builder.write_code(0);
+        }
+        let mut prev = &empty;
+        for (text, style) in &self.runs {
+            prev.transition_with(style, &mut builder, self.options);
+            writer.write_builder(&builder)?;
+            builder.clear();
+            writer.write_inner(text)?;
+            prev = style;
+        }
+        if self.clean == CleanKind::Reset {
+            builder.write_code(0);
+        } else {
+            prev.transition_with(&self.base, &mut builder, self.options);
+            if self.clean == CleanKind::Reverse {
+                self.base
+                    .transition_with(&empty, &mut builder, self.options);
+            }
+        }
+        writer.write_builder(&builder)
+    }
+    /// Rebuilds the runs, layering the returned style over each [`char`]
+    fn restyle(self, mut f: impl FnMut(usize, char) -> Option<StyleSet>) -> Self {
+        let mut runs: Vec<(String, StyleSet)> = Vec::with_capacity(self.runs.len());
+        let mut i = 0;
+        for (text, style) in &self.runs {
+            for ch in text.chars() {
+                let style =
+                    f(i, ch).map_or_else(|| style.clone(), |over| style.clone().layered(&over));
+                match runs.last_mut() {
+                    Some((text, last)) if *last == style => text.push(ch),
+                    _ => runs.push((ch.to_string(), style)),
+                }
+                i += 1;
+            }
+        }
+        Self { runs, ..self }
+    }
+}
+/// Options for [`highlight_matches_with`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HighlightOptions {
+    /// Matches without regard to case, comparing lowercase [`char`]s
+    pub case_insensitive: bool,
+}
+/// Returns the haystack with every occurrence of the needle styled
+///
+/// Matches do not overlap, adjacent matches are merged into a single run.
+/// An empty needle matches nothing
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{highlight_matches, Style::*};
+///
+///let text = highlight_matches("a cat, a cat", "cat", Bold);
+///assert_eq!(text.to_string(), "a \x1b[1mcat\x1b[22m, a \x1b[1mcat\x1b[22m");
+///```
+pub fn highlight_matches(haystack: &str, needle: &str, style: impl Into<StyleSet>) -> SGRRuns {
+    highlight_matches_with(haystack, needle, style, HighlightOptions::default())
+}
+/// Returns the haystack with every occurrence of the needle styled, using the given options
+///
+/// Refer to [`highlight_matches`]
+pub fn highlight_matches_with(
+    haystack: &str,
+    needle: &str,
+    style: impl Into<StyleSet>,
+    options: HighlightOptions,
+) -> SGRRuns {
+    let haystack_chars: Vec<char> = haystack.chars().collect();
+    let needle: Vec<char> = needle.chars().collect();
+    let mut matched = vec![false; haystack_chars.len()];
+    let eq = |a: char, b: char| {
+        a == b || (options.case_insensitive && a.to_lowercase().eq(b.to_lowercase()))
+    };
+    let mut i = 0;
+    while !needle.is_empty() && i + needle.len() <= haystack_chars.len() {
+        let window = &haystack_chars[i..i + needle.len()];
+        if window.iter().zip(&needle).all(|(&a, &b)| eq(a, b)) {
+            matched[i..i + needle.len()].fill(true);
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    highlight(haystack, &matched, &style.into())
+}
+/// Returns the haystack with every match of the regex styled
+///
+/// Adjacent matches are merged into a single run
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{highlight_regex, Color::*};
+///
+///let regex = regex::Regex::new(r"\d+").unwrap();
+///let text = highlight_regex("took 12ms", &regex, GreenFg);
+///assert_eq!(text.to_string(), "took \x1b[32m12\x1b[39mms");
+///```
+#[cfg(feature = "regex")]
+pub fn highlight_regex(
+    haystack: &str,
+    regex: &regex::Regex,
+    style: impl Into<StyleSet>,
+) -> SGRRuns {
+    let mut matched = Vec::with_capacity(haystack.len());
+    let mut last = 0;
+    for found in regex.find_iter(haystack) {
+        matched.extend(haystack[last..found.start()].chars().map(|_| false));
+        matched.extend(found.as_str().chars().map(|_| true));
+        last = found.end();
+    }
+    matched.extend(haystack[last..].chars().map(|_| false));
+    highlight(haystack, &matched, &style.into())
+}
+/// Returns the fragments joined by an unstyled separator
+///
+/// Refer to [`join_styled`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{join, Color::*, EasySGR};
+///
+///let text = join(["a".color(RedFg), "b".color(RedFg)], ", ");
+///assert_eq!(text.to_string(), "\x1b[31ma\x1b[39m, \x1b[31mb\x1b[39m");
+///assert_eq!(join(Vec::<&str>::new(), ", ").to_string(), "");
+///```
+pub fn join<S: Into<SGRString>>(
+    fragments: impl IntoIterator<Item = S>,
+    separator: &str,
+) -> SGRRuns {
+    join_styled(fragments, separator)
+}
+/// Returns the fragments joined by the separator, each a run styled on its own
+///
+/// The styles of fragments & the separator never carry over into each other,
+/// so the separator is styled only by itself even between two fragments styled alike.
+/// The [`reset`](SGRString::reset) & [`clean`](SGRString::clean) of each are left out,
+/// the codes between runs already keep them apart
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{join_styled, EasySGR, Style::*};
+///
+///let text = join_styled(["a", "b"], " | ".style(Dim));
+///assert_eq!(text.to_string(), "a\x1b[2m | \x1b[22mb");
+///```
+pub fn join_styled<S: Into<SGRString>>(
+    fragments: impl IntoIterator<Item = S>,
+    separator: impl Into<SGRString>,
+) -> SGRRuns {
+    let separator = separator.into();
+    let separator = (StyleSet::from(&separator), separator.text);
+    let mut runs: Vec<(String, StyleSet)> = Vec::new();
+    let mut push = |text: &str, style: &StyleSet| {
+        if text.is_empty() {
+            return;
+        }
+        match runs.last_mut() {
+            Some((last_text, last)) if last == style => last_text.push_str(text),
+            _ => runs.push((text.to_string(), style.clone())),
+        }
+    };
+    for (i, fragment) in fragments.into_iter().enumerate() {
+        if i != 0 {
+            push(&separator.1, &separator.0);
+        }
+        let fragment = fragment.into();
+        push(&fragment.text, &StyleSet::from(&fragment));
+    }
+    SGRRuns {
+        runs,
+        ..SGRRuns::default()
+    }
+}
+/// Styles the [`char`]s marked as matched
+fn highlight(haystack: &str, matched: &[bool], style: &StyleSet) -> SGRRuns {
+    SGRRuns::from(SGRString::from(haystack)).restyle(|i, _| matched[i].then(|| style.clone()))
+}
+impl From<SGRString> for SGRRuns {
+    fn from(value: SGRString) -> Self {
+        let base = StyleSet::from(&value);
+        let runs = if value.text.is_empty() {
+            Vec::new()
+        } else {
+            vec![(value.text, base.clone())]
+        };
+        Self {
+            runs,
+            base,
+            reset: value.reset,
+            clean: value.clean,
+            options: TransitionOptions::default(),
+        }
+    }
+}
+impl Display for SGRRuns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(&mut SGRWriter::from(f))
+    }
+}
+impl SGRString {
+    /// Layers a style over the [`char`]s within the range
+    ///
+    /// Refer to [`SGRRuns::style_range`]
+    #[must_use]
+    pub fn style_range(
+        self,
+        range: impl RangeBounds<usize>,
+        style: impl Into<StyleSet>,
+    ) -> SGRRuns {
+        SGRRuns::from(self).style_range(range, style)
+    }
+    /// Layers the style returned for each [`char`] & its index over it
+    ///
+    /// Refer to [`SGRRuns::map_chars`]
+    #[must_use]
+    pub fn map_chars<S: Into<StyleSet>>(self, f: impl FnMut(usize, char) -> S) -> SGRRuns {
+        SGRRuns::from(self).map_chars(f)
+    }
+    /// Layers the style returned for each word & its index over it
+    ///
+    /// Refer to [`SGRRuns::map_words`]
+    #[must_use]
+    pub fn map_words<S: Into<StyleSet>>(self, f: impl FnMut(usize, &str) -> S) -> SGRRuns {
+        SGRRuns::from(self).map_words(f)
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt::Display;
+
+use crate::{CapableWriter, FmtWriter, SGRBuilder, SGRString, StyleSet};
+
+/// A tree of styled text, each span styling its children on top of its parent
+///
+/// A span's [`StyleSet`] is [layered](StyleSet::layer) over those of the spans containing it,
+/// so inner spans keep the attributes they do not set & override those they do.
+/// Before each piece of text only the [transition](StyleSet::transition) into its attributes
+/// is written, so the text after a child is styled by the parent again rather than reset.
+/// Everything is turned off after the last text. Text is written as is
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, Span, Style::*};
+///
+///let title = Span::new(Bold).child("Usage: ").child(Span::new(Italic).child("cmd"));
+///let help = Span::new(BlueFg).child(title).child(" [options]");
+///assert_eq!(
+///    help.to_string(),
+///    "\x1b[34;1mUsage: \x1b[3mcmd\x1b[22;23m [options]\x1b[39m"
+///);
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span {
+    /// The attributes layered over the parent's
+    pub style: StyleSet,
+    /// The text & spans within, in order
+    pub children: Vec<SpanChild>,
+}
+/// Component of [`Span`]; a piece of text or a nested span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpanChild {
+    /// Text styled by the span containing it
+    Text(String),
+    /// A span nested within
+    Span(Span),
+}
+impl Span {
+    /// Creates an empty [`Span`] styled by `style`
+    #[must_use]
+    pub fn new(style: impl Into<StyleSet>) -> Self {
+        Self {
+            style: style.into(),
+            children: Vec::new(),
+        }
+    }
+    /// Adds text or a nested span after the other children
+    #[must_use]
+    pub fn child(mut self, child: impl Into<SpanChild>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+    /// Returns the text of every span, without any styling
+    #[must_use]
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        self.push_text(&mut text);
+        text
+    }
+    fn push_text(&self, out: &mut String) {
+        for child in &self.children {
+            match child {
+                SpanChild::Text(text) => out.push_str(text),
+                SpanChild::Span(span) => span.push_text(out),
+            }
+        }
+    }
+    /// Writes the styled text to the writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        let mut written = StyleSet::default();
+        let mut builder = SGRBuilder::default();
+        self.write_within(&StyleSet::default(), &mut written, &mut builder, writer)?;
+        builder.clear();
+        written.transition(&StyleSet::default(), &mut builder);
+        builder.write_to(writer)
+    }
+    /// Writes the children, styled by `parent` with this span's style layered on top
+    fn write_within<W: CapableWriter>(
+        &self,
+        parent: &StyleSet,
+        written: &mut StyleSet,
+        builder: &mut SGRBuilder,
+        writer: &mut W,
+    ) -> Result<(), W::Error> {
+        let style = parent.clone().layered(&self.style);
+        for child in &self.children {
+            match child {
+                SpanChild::Text(text) if text.is_empty() => (),
+                SpanChild::Text(text) => {
+                    builder.clear();
+                    written.transition(&style, builder);
+                    builder.write_to(writer)?;
+                    writer.write(text)?;
+                    written.clone_from(&style);
+                }
+                SpanChild::Span(span) => span.write_within(&style, written, builder, writer)?,
+            }
+        }
+        Ok(())
+    }
+}
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(&mut FmtWriter(f))
+    }
+}
+impl From<SGRString> for Span {
+    /// A span styled by the attributes of the [`SGRString`], its text the only child
+    ///
+    /// The [`CleanKind`](crate::CleanKind) is not kept, everything being turned off after the text
+    fn from(value: SGRString) -> Self {
+        let style = StyleSet::from(&value);
+        Self::new(style).child(value.text)
+    }
+}
+impl From<Span> for SpanChild {
+    fn from(value: Span) -> Self {
+        Self::Span(value)
+    }
+}
+impl From<SGRString> for SpanChild {
+    fn from(value: SGRString) -> Self {
+        Self::Span(value.into())
+    }
+}
+impl From<String> for SpanChild {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+impl From<&str> for SpanChild {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
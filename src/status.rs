@@ -0,0 +1,138 @@
+use std::io;
+
+use crate::{offset_map, SGRString, SGRWriter};
+
+/// A line of styled output rewritten in place, such as a progress message
+///
+/// Each [`set`](StatusLine::set) returns to the start of the line with `\r` & writes the new content,
+/// erasing what is left of the previous content when it was wider.
+/// Widths are counted in visible [`char`]s, escape sequences excluded.
+/// Each update is written in a single [`write_all`](io::Write::write_all) call, then flushed
+///
+/// Without the `control` feature the previous content is covered by spaces,
+/// which are styled by anything the content leaves in effect.
+/// With it, `\x1b[K` erases to the end of the line instead
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{EasySGR, StatusLine};
+///
+///let mut status = StatusLine::new(Vec::new());
+///status.set(&"10%".to_sgr())?;
+///status.set(&"10%".to_sgr())?;
+///status.set(&"done".to_sgr())?;
+///status.finish()?;
+///assert_eq!(status.into_inner(), b"\r10%\rdone\x1b[0m\n");
+///# Ok::<(), std::io::Error>(())
+///```
+#[derive(Debug, Default, Clone)]
+pub struct StatusLine<W: io::Write> {
+    out: W,
+    shown: String,
+    width: usize,
+    buf: String,
+}
+impl<W: io::Write> StatusLine<W> {
+    /// Creates a [`StatusLine`] writing to `out`, nothing yet shown
+    #[must_use]
+    pub const fn new(out: W) -> Self {
+        Self {
+            out,
+            shown: String::new(),
+            width: 0,
+            buf: String::new(),
+        }
+    }
+    /// Returns the writer written to
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.out
+    }
+    /// Returns the writer written to
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+    /// Returns the visible width of the content shown
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+    /// Replaces the content shown, writing nothing when it is unchanged
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails,
+    /// the previous content then being considered shown
+    pub fn set(&mut self, sgr: &SGRString) -> io::Result<()> {
+        let rendered = sgr.render_to_string();
+        if rendered == self.shown {
+            return Ok(());
+        }
+        let width = offset_map(&rendered).width();
+        self.buf.clear();
+        self.buf.push('\r');
+        self.buf.push_str(&rendered);
+        self.erase_after(width);
+        self.flush()?;
+        self.shown = rendered;
+        self.width = width;
+        Ok(())
+    }
+    /// Erases the content shown, leaving the cursor at the start of the line
+    ///
+    /// Writes nothing when nothing is shown
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    pub fn clear(&mut self) -> io::Result<()> {
+        if self.shown.is_empty() {
+            return Ok(());
+        }
+        self.buf.clear();
+        self.buf.push('\r');
+        self.erase_after(0);
+        if cfg!(not(feature = "control")) {
+            self.buf.push('\r');
+        }
+        self.flush()?;
+        self.shown.clear();
+        self.width = 0;
+        Ok(())
+    }
+    /// Ends the line, writing a reset & a newline
+    ///
+    /// The content shown is kept on its line, the next [`set`](StatusLine::set) starting a new one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.buf.clear();
+        // writing to a String never fails
+        let _ = SGRWriter::from(&mut self.buf).write_reset();
+        self.buf.push('\n');
+        self.flush()?;
+        self.shown.clear();
+        self.width = 0;
+        Ok(())
+    }
+    /// Adds to the update what erases the previous content past `width`
+    fn erase_after(&mut self, width: usize) {
+        if self.width <= width {
+            return;
+        }
+        if cfg!(feature = "control") {
+            let _ = SGRWriter::from(&mut self.buf).write_raw_csi('K', &[]);
+        } else {
+            self.buf
+                .extend(std::iter::repeat_n(' ', self.width - width));
+        }
+    }
+    /// Writes the update in a single call, then flushes
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.write_all(self.buf.as_bytes())?;
+        self.out.flush()
+    }
+}
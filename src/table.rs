@@ -0,0 +1,244 @@
+use std::{borrow::Cow, fmt::Display};
+
+use crate::{
+    escapes::{visible_chars, Visible},
+    CapableWriter, CleanKind, SGRBuilder, SGRString, SGRWriter,
+};
+
+/// The alignment of a [`Column`]'s cells
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    /// Pads on the right
+    #[default]
+    Left,
+    /// Pads on the left
+    Right,
+    /// Pads on both sides, with extra padding on the right
+    Center,
+}
+/// The layout of a column of a [`Table`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Column {
+    /// The minimum visible width of the column
+    pub min: usize,
+    /// The maximum visible width of the column, cells wider are truncated
+    pub max: Option<usize>,
+    /// How cells are aligned within the column
+    pub align: Align,
+}
+impl Column {
+    /// Sets the minimum width
+    #[must_use]
+    pub const fn min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+    /// Sets the maximum width
+    #[must_use]
+    pub const fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+    /// Sets the alignment
+    #[must_use]
+    pub const fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+}
+/// A row of [`SGRString`] cells
+#[derive(Debug, Clone, Default)]
+pub struct Row(pub Vec<SGRString>);
+impl From<Vec<SGRString>> for Row {
+    fn from(value: Vec<SGRString>) -> Self {
+        Self(value)
+    }
+}
+impl<I: Into<SGRString>> FromIterator<I> for Row {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+/// Rows of cells aligned by their visible width
+///
+/// The SGR codes of a cell are not counted towards its width,
+/// padding is written outside of them & left unstyled.
+/// Cells using [`CleanKind::None`] are cleaned through [`SGRString::undo_all`],
+/// so their codes cannot bleed into the padding.
+/// Widths are measured by [`visible_width`], so columns holding wide [`char`]s,
+/// such as CJK ideographs, are misaligned in terminals showing them as two columns
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Align, Color::*, Column, EasySGR, Row, Table};
+///
+///let table = Table::new()
+///    .column(Column::default().min(6))
+///    .column(Column::default().align(Align::Right))
+///    .row(Row::from_iter(["name", "size"]))
+///    .row(Row::from_iter(["a.rs".color(GreenFg), "12".to_sgr()]));
+///
+///assert_eq!(
+///    table.to_string(),
+///    "name   size\n\x1b[32ma.rs\x1b[39m     12\n"
+///);
+///```
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// The layout of each column, columns without one use [`Column::default`]
+    pub columns: Vec<Column>,
+    /// The rows of the table
+    pub rows: Vec<Row>,
+    /// The string written between columns
+    pub separator: String,
+}
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Table {
+    /// Creates an empty [`Table`] with columns separated by a space
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            separator: String::from(" "),
+        }
+    }
+    /// Adds the layout of the next column
+    #[must_use]
+    pub fn column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+    /// Adds a row
+    #[must_use]
+    pub fn row(mut self, row: impl Into<Row>) -> Self {
+        self.rows.push(row.into());
+        self
+    }
+    /// Sets the string written between columns
+    #[must_use]
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+    /// Returns the visible width of each column
+    #[must_use]
+    pub fn widths(&self) -> Vec<usize> {
+        let count = self.rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+        (0..count)
+            .map(|i| {
+                let column = self.layout(i);
+                let widest = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.0.get(i))
+                    .map(|cell| visible_width(&cell.text))
+                    .max()
+                    .unwrap_or(0)
+                    .max(column.min);
+                column.max.map_or(widest, |max| widest.min(max))
+            })
+            .collect()
+    }
+    /// Writes the table to the writer, each row followed by a newline
+    ///
+    /// Trailing padding is not written for left aligned last columns
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn write<W: CapableWriter>(&self, writer: &mut SGRWriter<W>) -> Result<(), W::Error> {
+        let widths = self.widths();
+        let blank = SGRString::default();
+        for row in &self.rows {
+            for (i, &width) in widths.iter().enumerate() {
+                if i != 0 {
+                    writer.write_inner(&self.separator)?;
+                }
+                let column = self.layout(i);
+                let cell = row.0.get(i).unwrap_or(&blank);
+                let text = truncate_visible(&cell.text, width);
+                let pad = width - visible_width(&text);
+                let (before, after) = match column.align {
+                    Align::Left => (0, pad),
+                    Align::Right => (pad, 0),
+                    Align::Center => (pad / 2, pad - pad / 2),
+                };
+                let after = if i + 1 == widths.len() && column.align == Align::Left {
+                    0
+                } else {
+                    after
+                };
+                writer.fill(&blank, ' ', before)?;
+                writer.place_sgr(cell)?;
+                writer.write_inner(&text)?;
+                if cell.clean == CleanKind::None {
+                    let mut builder = SGRBuilder::default();
+                    cell.undo_all(&mut builder);
+                    writer.write_builder(&builder)?;
+                } else {
+                    writer.clean_sgr(cell)?;
+                }
+                writer.fill(&blank, ' ', after)?;
+            }
+            writer.write_inner("\n")?;
+        }
+        Ok(())
+    }
+    fn layout(&self, column: usize) -> Column {
+        self.columns.get(column).copied().unwrap_or_default()
+    }
+}
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(&mut SGRWriter::from(f))
+    }
+}
+/// Returns the number of [`char`]s in the text, not counting escape sequences
+///
+/// An escape sequence is `\x1b[` or `\u{9b}` followed by anything up to and including its final byte.
+/// Every [`char`] is counted as one column, as by [`offset_map`](crate::offset_map),
+/// so wide [`char`]s such as CJK ideographs or emoji, taking two columns in most terminals,
+/// are under counted
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::visible_width;
+///
+///assert_eq!(visible_width("\x1b[31mred\x1b[0m"), 3);
+///assert_eq!(visible_width("日本"), 2);
+///```
+#[must_use]
+pub fn visible_width(text: &str) -> usize {
+    visible_chars(text)
+        .filter(|piece| matches!(piece, Visible::Char(_)))
+        .count()
+}
+/// Returns the text cut after `max` visible [`char`]s
+///
+/// Escape sequences after the cut are kept, so a trailing reset is never lost
+fn truncate_visible(text: &str, max: usize) -> Cow<'_, str> {
+    if visible_width(text) <= max {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::new();
+    let mut width = 0;
+    for piece in visible_chars(text) {
+        match piece {
+            Visible::Sequence(sequence) => out.push_str(sequence),
+            Visible::Char(ch) if width < max => {
+                width += 1;
+                out.push(ch);
+            }
+            Visible::Char(_) => (),
+        }
+    }
+    Cow::Owned(out)
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{enabled, Capabilities, ColorSupport, Error, ParseError};
+
+/// The index of `colors` within the numbers of an entry
+const MAX_COLORS: usize = 13;
+/// The index of `sitm`, entering italics, within the strings of an entry
+const ENTER_ITALICS: usize = 311;
+/// The magic number of entries with 16 bit numbers
+const MAGIC_16: u16 = 0o432;
+/// The magic number of entries with 32 bit numbers
+const MAGIC_32: u16 = 0o1036;
+
+/// A compiled terminfo entry, describing what a terminal supports
+///
+/// Parsed from the format written by `tic`, with numbers of either 16 or 32 bits,
+/// including the extended capabilities that follow the standard ones
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{terminfo::Terminfo, ColorSupport};
+///
+///if let Ok(entry) = Terminfo::load("xterm-256color") {
+///    assert_eq!(entry.capabilities().colors, ColorSupport::Palette256);
+///}
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Terminfo {
+    names: Vec<String>,
+    flags: Vec<bool>,
+    numbers: Vec<Option<i32>>,
+    strings: Vec<Option<Vec<u8>>>,
+    extended_flags: BTreeMap<String, bool>,
+    extended_numbers: BTreeMap<String, i32>,
+    extended_strings: BTreeMap<String, Vec<u8>>,
+}
+impl Terminfo {
+    /// Parses a compiled terminfo entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry is truncated or not of a known format
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader { bytes, pos: 0 };
+        let number_width = match reader.u16()? {
+            MAGIC_16 => 2,
+            MAGIC_32 => 4,
+            magic => {
+                return Err(ParseError::new(format!(
+                    "unknown terminfo magic {magic:#o}"
+                )))
+            }
+        };
+        let [names_len, flags_len, numbers_len, strings_len, table_len] = reader.counts()?;
+        let names = reader.take(names_len)?;
+        let names = String::from_utf8_lossy(names.split(|&b| b == 0).next().unwrap_or_default())
+            .split('|')
+            .map(String::from)
+            .collect();
+        let flags = reader.take(flags_len)?.iter().map(|&b| b == 1).collect();
+        reader.align();
+        let numbers = (0..numbers_len)
+            .map(|_| reader.number(number_width))
+            .collect::<Result<_, _>>()?;
+        let offsets = (0..strings_len)
+            .map(|_| reader.i16())
+            .collect::<Result<Vec<_>, _>>()?;
+        let table = reader.take(table_len)?;
+        let strings = offsets
+            .iter()
+            .map(|&offset| string_at(table, offset))
+            .collect::<Result<_, _>>()?;
+        let mut entry = Self {
+            names,
+            flags,
+            numbers,
+            strings,
+            ..Self::default()
+        };
+        reader.align();
+        if reader.pos < reader.bytes.len() {
+            entry.parse_extended(&mut reader, number_width)?;
+        }
+        Ok(entry)
+    }
+    /// Parses the extended capabilities, named within the entry
+    fn parse_extended(
+        &mut self,
+        reader: &mut Reader<'_>,
+        number_width: usize,
+    ) -> Result<(), ParseError> {
+        let [flags_len, numbers_len, strings_len, _, table_len] = reader.counts()?;
+        let flags: Vec<bool> = reader.take(flags_len)?.iter().map(|&b| b == 1).collect();
+        reader.align();
+        let numbers = (0..numbers_len)
+            .map(|_| reader.number(number_width))
+            .collect::<Result<Vec<_>, _>>()?;
+        let offsets = (0..strings_len)
+            .map(|_| reader.i16())
+            .collect::<Result<Vec<_>, _>>()?;
+        let name_offsets = (0..flags_len + numbers_len + strings_len)
+            .map(|_| reader.i16())
+            .collect::<Result<Vec<_>, _>>()?;
+        let table = reader.take(table_len)?;
+        let strings = offsets
+            .iter()
+            .map(|&offset| string_at(table, offset))
+            .collect::<Result<Vec<_>, _>>()?;
+        // the names follow the last of the strings
+        let names_start = offsets
+            .iter()
+            .zip(&strings)
+            .filter_map(|(&offset, string)| {
+                Some(usize::try_from(offset).ok()? + string.as_ref()?.len() + 1)
+            })
+            .max()
+            .unwrap_or(0);
+        let names = name_offsets
+            .iter()
+            .map(|&offset| {
+                let name = string_at(&table[names_start.min(table.len())..], offset)?;
+                Ok(String::from_utf8_lossy(&name.unwrap_or_default()).into_owned())
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        let (flag_names, rest) = names.split_at(flags_len);
+        let (number_names, string_names) = rest.split_at(numbers_len);
+        self.extended_flags = flag_names.iter().cloned().zip(flags).collect();
+        self.extended_numbers = number_names
+            .iter()
+            .cloned()
+            .zip(numbers)
+            .filter_map(|(name, number)| Some((name, number?)))
+            .collect();
+        self.extended_strings = string_names
+            .iter()
+            .cloned()
+            .zip(strings)
+            .filter_map(|(name, string)| Some((name, string?)))
+            .collect();
+        Ok(())
+    }
+    /// Loads the entry of a terminal from the terminfo database
+    ///
+    /// Searches `$TERMINFO`, `~/.terminfo`, each of `$TERMINFO_DIRS`,
+    /// then `/etc/terminfo`, `/lib/terminfo` & `/usr/share/terminfo`,
+    /// within directories named by either the first letter or its hexadecimal code
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if no entry is found or it cannot be read,
+    /// [`Error::Parse`] if it cannot be parsed
+    pub fn load(term: &str) -> Result<Self, Error> {
+        let first = term
+            .chars()
+            .next()
+            .filter(|_| !term.contains(['/', '\\']) && term != "..")
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid terminal name"))?;
+        let subdirs = [first.to_string(), format!("{:x}", u32::from(first))];
+        for dir in search_dirs() {
+            for subdir in &subdirs {
+                match fs::read(dir.join(subdir).join(term)) {
+                    Ok(bytes) => return Ok(Self::parse(&bytes)?),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no terminfo entry for {term}"),
+        )
+        .into())
+    }
+    /// Loads the entry of the terminal named by the `TERM` environment variable
+    ///
+    /// Refer to [`Terminfo::load`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if `TERM` is not set, no entry is found or it cannot be read,
+    /// [`Error::Parse`] if it cannot be parsed
+    pub fn from_env() -> Result<Self, Error> {
+        let term = env::var("TERM")
+            .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "TERM is not set"))?;
+        Self::load(&term)
+    }
+    /// Returns the names of the terminal, the last usually being a description
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+    /// Returns whether the flag at the index is set
+    #[must_use]
+    pub fn flag(&self, index: usize) -> bool {
+        self.flags.get(index).copied().unwrap_or(false)
+    }
+    /// Returns the number at the index, if present
+    #[must_use]
+    pub fn number(&self, index: usize) -> Option<i32> {
+        self.numbers.get(index).copied().flatten()
+    }
+    /// Returns the string at the index, if present
+    #[must_use]
+    pub fn string(&self, index: usize) -> Option<&[u8]> {
+        self.strings.get(index)?.as_deref()
+    }
+    /// Returns whether the extended flag of the name is set
+    #[must_use]
+    pub fn extended_flag(&self, name: &str) -> bool {
+        self.extended_flags.get(name).copied().unwrap_or(false)
+    }
+    /// Returns the extended number of the name, if present
+    #[must_use]
+    pub fn extended_number(&self, name: &str) -> Option<i32> {
+        self.extended_numbers.get(name).copied()
+    }
+    /// Returns the extended string of the name, if present
+    #[must_use]
+    pub fn extended_string(&self, name: &str) -> Option<&[u8]> {
+        self.extended_strings.get(name).map(Vec::as_slice)
+    }
+    /// Returns the number of colors, `colors`, if present
+    #[must_use]
+    pub fn max_colors(&self) -> Option<i32> {
+        self.number(MAX_COLORS)
+    }
+    /// Returns the [`Capabilities`] described by the entry
+    ///
+    /// RGB colors are supported given the `RGB` or `Tc` extended flags, or `colors` of `2^24`.
+    /// Italics are supported given `sitm` & strikethrough given the extended `smxx`
+    #[must_use]
+    pub fn capabilities(&self) -> Capabilities {
+        let colors = if self.extended_flag("RGB") || self.extended_flag("Tc") {
+            ColorSupport::Rgb
+        } else {
+            self.max_colors()
+                .and_then(|max| u32::try_from(max).ok())
+                .map_or(ColorSupport::None, ColorSupport::from_max_colors)
+        };
+        Capabilities {
+            colors,
+            supports_italic: self.string(ENTER_ITALICS).is_some(),
+            supports_strikethrough: self.extended_string("smxx").is_some(),
+        }
+    }
+}
+/// Returns the [`Capabilities`] of the terminal, given by its terminfo entry
+///
+/// Reads the entry named by `TERM`, falling back to [`Capabilities::from_env`]
+/// when it cannot be loaded. Nothing is supported when codes are not written at all,
+/// [`enabled`] being `false`
+#[must_use]
+pub fn detect() -> Capabilities {
+    if !enabled() {
+        return Capabilities::for_env(None, None);
+    }
+    Terminfo::from_env().map_or_else(|_| Capabilities::from_env(), |entry| entry.capabilities())
+}
+/// Returns the directories searched for entries, in order
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    dirs.extend(env::var_os("TERMINFO").map(PathBuf::from));
+    dirs.extend(env::var_os("HOME").map(|home| Path::new(&home).join(".terminfo")));
+    if let Some(list) = env::var_os("TERMINFO_DIRS") {
+        // an empty entry stands for the default location
+        dirs.extend(env::split_paths(&list).map(|dir| {
+            if dir.as_os_str().is_empty() {
+                PathBuf::from("/usr/share/terminfo")
+            } else {
+                dir
+            }
+        }));
+    }
+    dirs.extend(["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"].map(PathBuf::from));
+    dirs
+}
+/// Returns the string starting at the offset within the table, [`None`] if absent
+fn string_at(table: &[u8], offset: i16) -> Result<Option<Vec<u8>>, ParseError> {
+    let Ok(offset) = usize::try_from(offset) else {
+        return Ok(None);
+    };
+    let rest = table
+        .get(offset..)
+        .ok_or_else(|| ParseError::new("terminfo string out of bounds"))?;
+    let end = rest
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| ParseError::new("terminfo string not terminated"))?;
+    Ok(Some(rest[..end].to_vec()))
+}
+/// Reads the little endian values of an entry
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        let taken = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| ParseError::new("terminfo entry truncated"))?;
+        self.pos += len;
+        Ok(taken)
+    }
+    fn u16(&mut self) -> Result<u16, ParseError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+    fn i16(&mut self) -> Result<i16, ParseError> {
+        let bytes = self.take(2)?;
+        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+    /// Reads a number of 2 or 4 bytes, negative numbers being absent
+    fn number(&mut self, width: usize) -> Result<Option<i32>, ParseError> {
+        let number = if width == 2 {
+            i32::from(self.i16()?)
+        } else {
+            let bytes = self.take(4)?;
+            i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        };
+        Ok((number >= 0).then_some(number))
+    }
+    /// Reads the five counts of a header, none being negative
+    fn counts(&mut self) -> Result<[usize; 5], ParseError> {
+        let mut counts = [0; 5];
+        for count in &mut counts {
+            *count = usize::try_from(self.i16()?)
+                .map_err(|_| ParseError::new("negative terminfo count"))?;
+        }
+        Ok(counts)
+    }
+    /// Skips to an even position, as sections are aligned to two bytes
+    const fn align(&mut self) {
+        self.pos += self.pos % 2;
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt::{Display, Write};
+
+use crate::{
+    escapes::{annotate, apply_params, find_sgr},
+    Color, DisplayEscaped, EasySGR, SGRBuilder, Style, StyleSet,
+};
+
+/// Splits styled text into segments of visible text & the attributes in effect
+///
+/// Attributes are tracked across sequences: turned off attributes & default colors are left unset,
+/// `0` clears everything & unknown codes stay in effect until then.
+/// Sequences other than SGR are kept as text.
+/// Empty segments are skipped, adjacent segments with the same attributes are merged
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{sgr_segments, Style::*, StyleSet};
+///
+///let segments = sgr_segments("\x1b[1ma\x1b[22mb");
+///assert_eq!(
+///    segments,
+///    [
+///        (String::from("a"), StyleSet::from(Bold)),
+///        (String::from("b"), StyleSet::default()),
+///    ]
+///);
+///```
+#[must_use]
+pub fn sgr_segments(text: &str) -> Vec<(String, StyleSet)> {
+    let mut segments: Vec<(String, StyleSet)> = Vec::new();
+    let mut state = StyleSet::default();
+    let mut rest = text;
+    loop {
+        let (visible, sequence) = match find_sgr(rest) {
+            Some((start, params, end)) => (&rest[..start], Some((params, end))),
+            None => (rest, None),
+        };
+        if !visible.is_empty() {
+            match segments.last_mut() {
+                Some((text, last)) if *last == state => text.push_str(visible),
+                _ => segments.push((visible.to_string(), state.clone())),
+            }
+        }
+        let Some((params, end)) = sequence else {
+            return segments;
+        };
+        apply_params(&mut state, params);
+        rest = &rest[end..];
+    }
+}
+/// Returns a description of the first difference between the [segments](sgr_segments) of each side
+///
+/// Returns [`None`] if both render the same, even if encoded differently
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::sgr_diff;
+///
+///assert_eq!(sgr_diff("\x1b[1m\x1b[31mX", "\x1b[31;1mX"), None);
+///assert!(sgr_diff("\x1b[1mX", "\x1b[2mX").is_some());
+///```
+#[must_use]
+pub fn sgr_diff(left: &str, right: &str) -> Option<String> {
+    let (left_segments, right_segments) = (sgr_segments(left), sgr_segments(right));
+    if left_segments == right_segments {
+        return None;
+    }
+    let index = left_segments
+        .iter()
+        .zip(&right_segments)
+        .take_while(|(l, r)| l == r)
+        .count();
+    let describe = |segments: &[(String, StyleSet)]| {
+        segments.get(index).map_or_else(
+            || String::from("no segment"),
+            |(text, style)| format!("{text:?} styled {style:?}"),
+        )
+    };
+    Some(format!(
+        "styled text differs at segment {index}\n  \
+        left: {:?}\n \
+        right: {:?}\n\
+        left segment: {}\n\
+        right segment: {}",
+        DisplayEscaped(left),
+        DisplayEscaped(right),
+        describe(&left_segments),
+        describe(&right_segments),
+    ))
+}
+/// A difference between the [segments](sgr_segments) of two styled strings, found by [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// The segments at the same index have differing text
+    Text {
+        /// The index of the segments
+        index: usize,
+        /// The text of the old segment
+        old: String,
+        /// The text of the new segment
+        new: String,
+    },
+    /// The same text is styled differently on each side
+    Attributes {
+        /// The text styled differently
+        text: String,
+        /// The attributes of the old side
+        old: StyleSet,
+        /// The attributes of the new side
+        new: StyleSet,
+    },
+    /// The text is split into a different number of segments, so they no longer line up
+    Segmentation {
+        /// The index of the first differing segment
+        index: usize,
+        /// The old segments from `index` on
+        old: Vec<(String, StyleSet)>,
+        /// The new segments from `index` on
+        new: Vec<(String, StyleSet)>,
+    },
+}
+/// The differences between two styled strings, created by [`diff`]
+///
+/// [`Display`] writes a readable report, plain text with a line per [`Difference`].
+/// [`StyledDiff::render`] can also style the report itself, for printing to a terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledDiff {
+    old: String,
+    new: String,
+    index: usize,
+    differences: Vec<Difference>,
+}
+impl StyledDiff {
+    /// Returns `true` if both sides render the same
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+    /// Returns each difference, in the order of the text
+    #[must_use]
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+    /// Returns the report, styling its labels & both sides when `styled` is `true`
+    ///
+    /// The expected side is green & the actual side red
+    #[must_use]
+    pub fn render(&self, styled: bool) -> String {
+        let paint = |text: String, color: Color| {
+            if styled {
+                text.color(color).to_string()
+            } else {
+                text
+            }
+        };
+        let expected = |text: String| paint(text, Color::GreenFg);
+        let got = |text: String| paint(text, Color::RedFg);
+        let label = |text: &str| {
+            if styled {
+                text.style(Style::Bold).to_string()
+            } else {
+                text.to_string()
+            }
+        };
+        let mut out = format!(
+            "{}\n  old: {}\n  new: {}",
+            label(&format!("styled text differs at segment {}", self.index)),
+            expected(format!("{:?}", DisplayEscaped(&self.old))),
+            got(format!("{:?}", DisplayEscaped(&self.new))),
+        );
+        for difference in &self.differences {
+            let _ = match difference {
+                Difference::Text { index, old, new } => write!(
+                    out,
+                    "\n{} {index}: expected {}, got {}",
+                    label("text of segment"),
+                    expected(format!("{old:?}")),
+                    got(format!("{new:?}"))
+                ),
+                Difference::Attributes { text, old, new } => write!(
+                    out,
+                    "\n{} {text:?}: expected {}, got {}",
+                    label("attributes of"),
+                    expected(describe(old)),
+                    got(describe(new))
+                ),
+                Difference::Segmentation { index, old, new } => write!(
+                    out,
+                    "\n{} {index}: expected {}, got {}",
+                    label("segments differ from"),
+                    expected(describe_segments(old)),
+                    got(describe_segments(new))
+                ),
+            };
+        }
+        out
+    }
+}
+impl Display for StyledDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(false))
+    }
+}
+/// Compares the [segments](sgr_segments) of two styled strings, classifying each difference
+///
+/// When the visible text is the same, the text styled differently is found
+/// regardless of how each side is split into segments.
+/// Otherwise segments are compared by index, unless their numbers differ
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{diff, Difference};
+///
+///assert!(diff("\x1b[1;31mX", "\x1b[31;1mX").is_empty());
+///
+///let report = diff("\x1b[1;31mX", "\x1b[31mX");
+///assert!(matches!(report.differences(), [Difference::Attributes { .. }]));
+///assert!(report.to_string().contains("expected red+bold, got red"));
+///```
+#[must_use]
+pub fn diff(old: &str, new: &str) -> StyledDiff {
+    let (old_segments, new_segments) = (sgr_segments(old), sgr_segments(new));
+    let index = old_segments
+        .iter()
+        .zip(&new_segments)
+        .take_while(|(o, n)| o == n)
+        .count();
+    let visible = |segments: &[(String, StyleSet)]| -> String {
+        segments.iter().map(|(text, _)| text.as_str()).collect()
+    };
+    let differences = if old_segments == new_segments {
+        Vec::new()
+    } else if visible(&old_segments) == visible(&new_segments) {
+        attribute_differences(&old_segments, &new_segments)
+    } else if old_segments.len() == new_segments.len() {
+        let mut differences = Vec::new();
+        for (i, ((old_text, old_style), (new_text, new_style))) in
+            old_segments.iter().zip(&new_segments).enumerate()
+        {
+            if old_text != new_text {
+                differences.push(Difference::Text {
+                    index: i,
+                    old: old_text.clone(),
+                    new: new_text.clone(),
+                });
+            }
+            if old_style != new_style {
+                differences.push(Difference::Attributes {
+                    text: new_text.clone(),
+                    old: old_style.clone(),
+                    new: new_style.clone(),
+                });
+            }
+        }
+        differences
+    } else {
+        vec![Difference::Segmentation {
+            index,
+            old: old_segments[index..].to_vec(),
+            new: new_segments[index..].to_vec(),
+        }]
+    };
+    StyledDiff {
+        old: old.to_string(),
+        new: new.to_string(),
+        index,
+        differences,
+    }
+}
+/// Returns the text styled differently by segments of the same visible text
+///
+/// Both sides are split at the boundaries of either,
+/// adjacent pieces differing in the same way being merged
+fn attribute_differences(
+    old: &[(String, StyleSet)],
+    new: &[(String, StyleSet)],
+) -> Vec<Difference> {
+    let mut differences: Vec<Difference> = Vec::new();
+    let (mut old_iter, mut new_iter) = (old.iter(), new.iter());
+    let (mut old_next, mut new_next) = (old_iter.next(), new_iter.next());
+    // the byte offsets consumed within the current segment of each side
+    let (mut old_at, mut new_at) = (0, 0);
+    // whether the previous piece differed, so the next can be merged into it
+    let mut merge = false;
+    while let (Some((old_text, old_style)), Some((new_text, new_style))) = (old_next, new_next) {
+        let len = (old_text.len() - old_at).min(new_text.len() - new_at);
+        let piece = &old_text[old_at..old_at + len];
+        if old_style == new_style {
+            merge = false;
+        } else {
+            match differences.last_mut() {
+                Some(Difference::Attributes { text, old, new })
+                    if merge && old == old_style && new == new_style =>
+                {
+                    text.push_str(piece);
+                }
+                _ => differences.push(Difference::Attributes {
+                    text: piece.to_string(),
+                    old: old_style.clone(),
+                    new: new_style.clone(),
+                }),
+            }
+            merge = true;
+        }
+        old_at += len;
+        new_at += len;
+        if old_at == old_text.len() {
+            old_next = old_iter.next();
+            old_at = 0;
+        }
+        if new_at == new_text.len() {
+            new_next = new_iter.next();
+            new_at = 0;
+        }
+    }
+    differences
+}
+/// Returns the keywords of the attributes joined by `+`, i.e. `red+bold`, or `plain` when empty
+fn describe(style: &StyleSet) -> String {
+    let mut builder = SGRBuilder::default();
+    StyleSet::default().transition(style, &mut builder);
+    if builder.codes().is_empty() {
+        return String::from("plain");
+    }
+    let params: Vec<String> = builder.codes().iter().map(u8::to_string).collect();
+    let mut keywords = String::new();
+    annotate(&mut keywords, &params.join(";"));
+    keywords.replace(' ', "+")
+}
+/// Returns each segment as its text followed by its [described](describe) attributes
+fn describe_segments(segments: &[(String, StyleSet)]) -> String {
+    let segments: Vec<String> = segments
+        .iter()
+        .map(|(text, style)| format!("{text:?} {}", describe(style)))
+        .collect();
+    format!("[{}]", segments.join(", "))
+}
+/// Asserts that both sides render the same visible text with the same attributes
+///
+/// Both sides are [`Display`](std::fmt::Display)ed, then compared through [`diff`],
+/// so the order & merging of sequences do not matter.
+/// On failure the [report](StyledDiff) is printed, styled when stderr is a terminal
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{assert_sgr_eq, Color::*, EasySGR, Style::*};
+///
+///assert_sgr_eq!("X".color(RedFg).style(Bold), "\x1b[1m\x1b[31mX");
+///```
+#[macro_export]
+macro_rules! assert_sgr_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        let diff = $crate::diff(
+            &::std::string::ToString::to_string(&$left),
+            &::std::string::ToString::to_string(&$right),
+        );
+        if !diff.is_empty() {
+            ::std::panic!(
+                "assertion `left == right` failed: {}",
+                diff.render(::std::io::IsTerminal::is_terminal(&::std::io::stderr()))
+            );
+        }
+    };
+}
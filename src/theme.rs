@@ -0,0 +1,133 @@
+use std::{
+    cell::RefCell,
+    sync::{Arc, LazyLock, PoisonError, RwLock},
+};
+
+use crate::{CleanKind, Color, EasySGR, SGRString, Style};
+
+/// The styles of a program's messages, looked up through [`current`]
+///
+/// Each style is an [`SGRString`] whose codes are applied to the text,
+/// its own text is ignored
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{theme::{self, Theme}, Color::*, ColorKind, EasySGR};
+///
+///theme::with_scoped(Theme { error: MagentaFg.to_sgr(), ..Theme::default() }, || {
+///    assert_eq!(theme::error_style().foreground, ColorKind::Magenta);
+///});
+///```
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Applied to errors
+    pub error: SGRString,
+    /// Applied to warnings
+    pub warn: SGRString,
+    /// Applied to information
+    pub info: SGRString,
+    /// Applied to what succeeded
+    pub success: SGRString,
+    /// Applied to hints & other secondary text
+    pub hint: SGRString,
+}
+impl Default for Theme {
+    /// Error as bold red, warn as yellow, info as green,
+    /// success as bold green & hints as dim
+    ///
+    /// The same as the default `LevelStyle` of the `log` feature
+    fn default() -> Self {
+        Self {
+            error: Style::Bold.color(Color::RedFg).clean(CleanKind::Reverse),
+            warn: Color::YellowFg.clean(CleanKind::Reverse),
+            info: Color::GreenFg.clean(CleanKind::Reverse),
+            success: Style::Bold.color(Color::GreenFg).clean(CleanKind::Reverse),
+            hint: Style::Dim.clean(CleanKind::Reverse),
+        }
+    }
+}
+
+/// The theme of every thread not within a scope
+static GLOBAL: LazyLock<RwLock<Arc<Theme>>> = LazyLock::new(RwLock::default);
+
+thread_local! {
+    /// The themes of this thread's scopes, innermost last
+    static SCOPES: RefCell<Vec<Arc<Theme>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Replaces the global theme, used by every thread outside of a [scope](with_scoped)
+///
+/// Defaults to [`Theme::default`]
+pub fn set_global(theme: Theme) {
+    let theme = Arc::new(theme);
+    // the lock is never held across anything that panics, so a poisoned lock is still valid
+    *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = theme;
+}
+/// Returns the global theme, ignoring this thread's scopes
+#[must_use]
+pub fn global() -> Arc<Theme> {
+    Arc::clone(&GLOBAL.read().unwrap_or_else(PoisonError::into_inner))
+}
+/// Returns the theme in effect on this thread:
+/// the innermost [scope](with_scoped)'s, else the [global] one
+#[must_use]
+pub fn current() -> Arc<Theme> {
+    SCOPES
+        .with(|scopes| scopes.borrow().last().cloned())
+        .unwrap_or_else(global)
+}
+/// Runs the function with the theme in effect on this thread, returning its result
+///
+/// Scopes nest, the innermost being [current] until it returns.
+/// Other threads, including those spawned within the function, are not affected.
+/// The theme is removed once the function returns or panics
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{theme::{self, Theme}, EasySGR, Style::*, StyleKind};
+///
+///let italic = Theme { hint: Italic.to_sgr(), ..Theme::default() };
+///let hint = theme::with_scoped(italic, theme::hint_style);
+///assert_eq!(hint.italic, StyleKind::Place);
+///assert_eq!(theme::hint_style().italic, StyleKind::None);
+///```
+pub fn with_scoped<R>(theme: Theme, f: impl FnOnce() -> R) -> R {
+    /// Removes the innermost scope when dropped, even while unwinding
+    struct Guard;
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SCOPES.with(|scopes| scopes.borrow_mut().pop());
+        }
+    }
+
+    SCOPES.with(|scopes| scopes.borrow_mut().push(Arc::new(theme)));
+    let _guard = Guard;
+    f()
+}
+/// Returns the [current] theme's [error](Theme::error) style
+#[must_use]
+pub fn error_style() -> SGRString {
+    current().error.clone()
+}
+/// Returns the [current] theme's [warn](Theme::warn) style
+#[must_use]
+pub fn warn_style() -> SGRString {
+    current().warn.clone()
+}
+/// Returns the [current] theme's [info](Theme::info) style
+#[must_use]
+pub fn info_style() -> SGRString {
+    current().info.clone()
+}
+/// Returns the [current] theme's [success](Theme::success) style
+#[must_use]
+pub fn success_style() -> SGRString {
+    current().success.clone()
+}
+/// Returns the [current] theme's [hint](Theme::hint) style
+#[must_use]
+pub fn hint_style() -> SGRString {
+    current().hint.clone()
+}
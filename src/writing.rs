@@ -1,7 +1,37 @@
-use std::{fmt, io};
+use std::{
+    fmt, io,
+    sync::atomic::{AtomicU8, Ordering},
+    time::Instant,
+};
 
-use crate::{DiscreteSGR, SGRString};
+use crate::{
+    encode::{encode_into, encoded_len, param_len, write_param},
+    escapes::{shortest_transition, Sanitizer},
+    palette256::{self, PaletteMap},
+    DiscreteSGR, DiscreteSGRs, DynSGR, SGRString, SanitizePolicy, Style, StyleKind, StyleSet,
+};
 
+/// The string that begins a control sequence, `\x1b[`
+pub const ESCAPE: &str = "\x1b[";
+/// The string that ends an SGR sequence, `m`
+pub const END: &str = "m";
+/// The C1 control sequence introducer, `\u{9b}`, the single character form of [`ESCAPE`]
+///
+/// Refer to [`EscapeKind::C1`]
+pub const C1_ESCAPE: &str = "\u{9b}";
+/// Which introducer begins the control sequences written by an [`EscapeWriter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EscapeKind {
+    /// The escape character followed by `[`, [`ESCAPE`]
+    #[default]
+    Esc,
+    /// The 8-bit C1 control sequence introducer, the byte `0x9b`
+    ///
+    /// For legacy environments & serial consoles expecting it instead of [`ESCAPE`].
+    /// The byte alone is not valid UTF-8, so [`IoWriter`] writes it as is,
+    /// while writers of a [`str`] write [`C1_ESCAPE`], encoded in UTF-8 as `0xc2 0x9b`
+    C1,
+}
 /// An interface for an [`SGRWriter`] to work with
 ///
 /// Does not provide SGR writing capability itself
@@ -12,8 +42,9 @@ pub trait CapableWriter: Sized {
     type Writer;
     /// The type of error returned by trait methods
     ///
-    /// Will typically be [`std::io::Error`] or [`std::fmt::Error`]
-    type Error: std::error::Error;
+    /// Will typically be [`std::io::Error`] or [`std::fmt::Error`],
+    /// always convertible into the crate's [`Error`](crate::Error)
+    type Error: std::error::Error + Into<crate::Error>;
     /// Writes a [`str`] to the inner writer
     ///
     /// # Errors
@@ -21,9 +52,146 @@ pub trait CapableWriter: Sized {
     /// Returns an error if writing fails.
     /// Error type specified by [`CapableWriter::Error`]
     fn write(&mut self, s: &str) -> Result<(), Self::Error>;
+    /// Writes a numeric parameter in decimal, i.e. `300` or `65535`
+    ///
+    /// By default formats the parameter without allocating,
+    /// then calls [`CapableWriter::write`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.write(format_param(param, &mut [0; 5]))
+    }
+    /// Writes the introducer of a control sequence
+    ///
+    /// By default writes [`ESCAPE`] or [`C1_ESCAPE`], depending on the [`EscapeKind`].
+    /// Every control sequence written goes through here, with [`EscapeKind::Esc`]
+    /// unless changed by an [`EscapeWriter`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.write(match kind {
+            EscapeKind::Esc => ESCAPE,
+            EscapeKind::C1 => C1_ESCAPE,
+        })
+    }
+    /// Writes a complete SGR sequence, i.e. `\x1b[1;31m`
+    ///
+    /// By default the sequence is encoded by [`encode_into`](crate::encode::encode_into),
+    /// its introducer written through [`CapableWriter::write_escape`] & the rest,
+    /// the codes separated by `;` then [`END`], in one call to [`CapableWriter::write`].
+    /// Every sequence written by an [`SGRBuilder`] goes through here,
+    /// codes written without the escape & end sequences do not
+    ///
+    /// Writes nothing if there are no codes, as most terminals read the empty
+    /// sequence `\x1b[m` as a reset. [`SGRWriter::write_reset`] writes one explicitly
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        if codes.is_empty() {
+            return Ok(());
+        }
+        let (mut inline, mut spilled) = ([0; SGR_INLINE_LEN], Vec::new());
+        let sequence = encode_sgr(codes, &mut inline, &mut spilled);
+        self.write_escape(EscapeKind::Esc)?;
+        self.write(&sequence[ESCAPE.len()..])
+    }
     /// Returns the type specified by [`CapableWriter::Writer`]
     fn get_writer(self) -> Self::Writer;
 }
+/// Prevents [`UnderlyingWrite`] from being implemented outside this crate
+mod sealed {
+    pub trait Sealed {}
+}
+/// A writer that can be turned into a [`CapableWriter`]
+///
+/// Implemented for common [`std::fmt::Write`] & [`std::io::Write`] types,
+/// allowing generic code to work with both without wrapping them first.
+/// Their errors convert into [`Error`](crate::Error) through `?`.
+///
+/// A blanket impl over every `&mut W` is ruled out by coherence, as [`String`] or
+/// [`fmt::Formatter`] could gain an [`io::Write`] impl upstream, overlapping their own impls.
+/// Borrowed writers are instead passed as `&mut dyn io::Write` or `&mut dyn fmt::Write`.
+/// This trait is sealed, for other types use [`FmtWriter`] or [`IoWriter`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Error, EasySGR, SGRString, SGRWriter, Style::*, UnderlyingWrite};
+///
+///fn render<W: UnderlyingWrite>(writer: W, sgr: &SGRString) -> Result<W, Error> {
+///    let mut writer = SGRWriter::new(writer);
+///    writer.place_sgr(sgr)?;
+///    writer.write_inner(&sgr.text)?;
+///    writer.clean_sgr(sgr)?;
+///    Ok(writer.internal())
+///}
+///
+///let sgr = "text".style(Bold);
+///assert_eq!(render(String::new(), &sgr).unwrap(), "\x1b[1mtext");
+///assert_eq!(render(Vec::new(), &sgr).unwrap(), b"\x1b[1mtext");
+///
+///let mut out = Vec::new();
+///render(&mut out as &mut dyn std::io::Write, &sgr).unwrap();
+///assert_eq!(out, b"\x1b[1mtext");
+///```
+pub trait UnderlyingWrite: sealed::Sealed + Sized {
+    /// The [`CapableWriter`] wrapping this writer
+    type Writer: CapableWriter<Writer = Self, Error: crate::WriteError>;
+    /// Wraps self in its [`CapableWriter`]
+    fn into_capable(self) -> Self::Writer;
+}
+/// Implements [`UnderlyingWrite`] for the given types using the given wrapper
+macro_rules! underlying_write {
+    ($wrapper:ident: $([$($gen:tt)*] $ty:ty),+ $(,)?) => {
+        $(
+            impl<$($gen)*> sealed::Sealed for $ty {}
+            impl<$($gen)*> UnderlyingWrite for $ty {
+                type Writer = $wrapper<Self>;
+                #[inline]
+                fn into_capable(self) -> Self::Writer {
+                    $wrapper(self)
+                }
+            }
+        )+
+    };
+}
+underlying_write!(FmtWriter:
+    [] String,
+    ['a] &'a mut String,
+    ['a, 'b] &'a mut fmt::Formatter<'b>,
+    ['a] &'a mut dyn fmt::Write,
+);
+underlying_write!(IoWriter:
+    [] Vec<u8>,
+    ['a] &'a mut Vec<u8>,
+    [] io::Cursor<Vec<u8>>,
+    ['a] io::Cursor<&'a mut Vec<u8>>,
+    ['a] io::Cursor<&'a mut [u8]>,
+    [] std::fs::File,
+    ['a] &'a std::fs::File,
+    [] io::Stdout,
+    [] io::Stderr,
+    ['a] io::StdoutLock<'a>,
+    ['a] io::StderrLock<'a>,
+    [W: io::Write] io::BufWriter<W>,
+    [W: io::Write] io::LineWriter<W>,
+    [] std::net::TcpStream,
+    ['a] &'a std::net::TcpStream,
+    ['a] &'a mut dyn io::Write,
+    [] Box<dyn io::Write>,
+);
 /// A Standard SGR writer
 #[derive(Debug, Clone)]
 pub struct SGRWriter<W: CapableWriter> {
@@ -31,6 +199,15 @@ pub struct SGRWriter<W: CapableWriter> {
     pub writer: W,
 }
 impl<W: CapableWriter> SGRWriter<W> {
+    /// Creates a new [`SGRWriter`] from any [`UnderlyingWrite`]
+    ///
+    /// i.e. a [`String`], [`Vec<u8>`] or [`std::io::Stdout`]
+    #[inline]
+    pub fn new<U: UnderlyingWrite<Writer = W>>(writer: U) -> Self {
+        Self {
+            writer: writer.into_capable(),
+        }
+    }
     /// Writes a [`str`] to the inner writer
     ///
     /// A shortcut to [`CapableWriter::write`] without having to import it
@@ -96,6 +273,32 @@ impl<W: CapableWriter> SGRWriter<W> {
         sgr.write(&mut builder);
         builder.write_to(self)
     }
+    /// Writes the codes of several [`DiscreteSGR`]s to the writer as one sequence
+    ///
+    /// Nothing is written if there are no codes,
+    /// as an empty sequence would reset every attribute
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Color::*, SGRWriter, Style::{self, *}};
+    ///
+    ///let mut writer = SGRWriter::from(String::new());
+    ///writer.inline_sgr_all(&(Bold, RgbFg(1, 2, 3), Italic)).unwrap();
+    ///writer.inline_sgr_all(&Vec::<Style>::new()).unwrap();
+    ///assert_eq!(writer.internal(), "\x1b[1;38;2;1;2;3;3m");
+    ///```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    pub fn inline_sgr_all(&mut self, sgrs: &(impl DiscreteSGRs + ?Sized)) -> Result<(), W::Error> {
+        let mut builder = SGRBuilder::default();
+        sgrs.write_each(&mut builder);
+        builder.write_to(self)
+    }
     /// Writes the contained SGR codes to the writer
     ///
     /// Uses [`EasyWrite`] so the it can be used for both
@@ -105,11 +308,72 @@ impl<W: CapableWriter> SGRWriter<W> {
     ///
     /// Returns an error if writing fails.
     /// Error type specified by [`CapableWriter::Error`]
-    pub fn sgr(&mut self, sgr: &impl EasyWrite) -> Result<(), W::Error> {
+    pub fn sgr(&mut self, sgr: &(impl EasyWrite + ?Sized)) -> Result<(), W::Error> {
         let mut builder = SGRBuilder::default();
         sgr.sgr(&mut builder);
         builder.write_to(self)
     }
+    /// Writes the codes buffered in an [`SGRBuilder`] to the writer
+    ///
+    /// The builder is left untouched, so the same codes can be written to multiple writers.
+    /// Writes nothing if the builder is empty
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    pub fn write_builder(&mut self, builder: &SGRBuilder) -> Result<(), W::Error> {
+        builder.write_to(self)
+    }
+    /// Writes `\x1b[0m`, resetting every attribute
+    ///
+    /// Empty sequences are never written, so this is the way to reset
+    /// rather than writing an empty [`SGRBuilder`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    pub fn write_reset(&mut self) -> Result<(), W::Error> {
+        self.writer.write_sgr(&[0])
+    }
+    /// Writes a control sequence that is not an SGR sequence
+    ///
+    /// Writes [`ESCAPE`], the parameters separated by `;`, then `final_byte`.
+    /// i.e. `write_raw_csi('K', &[2])` writes `\x1b[2K`, erasing the line
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    #[inline]
+    pub fn write_raw_csi(&mut self, final_byte: char, params: &[u16]) -> Result<(), W::Error> {
+        write_csi(self, final_byte, params)
+    }
+    /// Writes a [`char`] repeated `len` times, surrounded by the codes of an [`SGRString`]
+    ///
+    /// The codes are written through [`SGRWriter::place_sgr`] & [`SGRWriter::clean_sgr`],
+    /// the text of the [`SGRString`] is not written.
+    /// No intermediate [`String`] is built, and nothing is written when `len` is `0`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn fill(&mut self, sgr: &SGRString, ch: char, len: usize) -> Result<(), W::Error> {
+        if len == 0 {
+            return Ok(());
+        }
+        let mut buf = [0; 4];
+        let ch = ch.encode_utf8(&mut buf);
+        self.place_sgr(sgr)?;
+        for _ in 0..len {
+            self.write(ch)?;
+        }
+        self.clean_sgr(sgr)
+    }
     /// Writes the contained SGR codes to the writer
     ///
     /// Does not write the escape or end sequences
@@ -122,7 +386,7 @@ impl<W: CapableWriter> SGRWriter<W> {
     /// Returns an error if writing fails.
     /// Error type specified by [`CapableWriter::Error`]
     #[inline]
-    pub fn partial_sgr(&mut self, sgr: &impl EasyWrite) -> Result<(), W::Error> {
+    pub fn partial_sgr(&mut self, sgr: &(impl EasyWrite + ?Sized)) -> Result<(), W::Error> {
         let mut builder = SGRBuilder::default();
         sgr.sgr(&mut builder);
         builder.write_partial(self)
@@ -154,12 +418,51 @@ impl<W: CapableWriter> CapableWriter for SGRWriter<W> {
     fn write(&mut self, s: &str) -> Result<(), Self::Error> {
         self.writer.write(s)
     }
-    #[must_use]
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    #[inline]
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.writer.write_sgr(codes)
+    }
     fn get_writer(self) -> Self::Writer {
         self.writer
     }
 }
 /// Used to implement [`CapableWriter`] for [`std::io::Write`]
+///
+/// Does not implement [`std::io::Write`] itself, so with both traits in scope
+/// [`CapableWriter::write`] is never shadowed, writing a [`str`] rather than bytes.
+/// The inner writer is reached through `.0`
+///
+/// # Examples
+///
+///```rust
+///use std::io::Write;
+///
+///use easy_sgr::{CapableWriter, IoWriter};
+///
+///let mut writer = IoWriter(Vec::new());
+///writer.write_sgr(&[1])?;
+///writer.write("bold")?;
+///writer.0.write_all(b"!")?;
+///assert_eq!(writer.0, b"\x1b[1mbold!");
+///# Ok::<(), std::io::Error>(())
+///```
+///
+/// The two are never mixed up, as an [`IoWriter`] is not an [`std::io::Write`]:
+///
+///```rust,compile_fail
+///use easy_sgr::IoWriter;
+///
+///fn bytes(_: &mut impl std::io::Write) {}
+///bytes(&mut IoWriter(Vec::new()));
+///```
 #[derive(Debug, Clone)]
 pub struct IoWriter<W: std::io::Write>(pub W);
 impl<W: std::io::Write> CapableWriter for IoWriter<W> {
@@ -169,8 +472,13 @@ impl<W: std::io::Write> CapableWriter for IoWriter<W> {
     fn write(&mut self, s: &str) -> Result<(), Self::Error> {
         self.0.write_all(s.as_bytes())
     }
-
-    #[must_use]
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        match kind {
+            EscapeKind::Esc => self.0.write_all(ESCAPE.as_bytes()),
+            EscapeKind::C1 => self.0.write_all(&[0x9b]),
+        }
+    }
     fn get_writer(self) -> Self::Writer {
         self.0
     }
@@ -185,106 +493,2138 @@ impl<W: std::fmt::Write> CapableWriter for FmtWriter<W> {
     fn write(&mut self, s: &str) -> Result<(), Self::Error> {
         self.0.write_str(s)
     }
-    #[must_use]
     fn get_writer(self) -> Self::Writer {
         self.0
     }
 }
-/// Builds a SGR sequence
-#[derive(Debug, Default)]
-pub struct SGRBuilder(pub Vec<u8>);
-
-impl SGRBuilder {
-    /// Writes a code to the internal buffer
+/// A source of the current time, used by [`ThrottledWriter`]
+///
+/// Implemented for closures returning an [`Instant`], so tests can control time
+pub trait Clock {
+    /// Returns the current time
+    fn now(&self) -> Instant;
+}
+impl<F: Fn() -> Instant> Clock for F {
     #[inline]
-    pub fn write_code(&mut self, code: u8) {
-        self.0.push(code);
+    fn now(&self) -> Instant {
+        self()
     }
-    /// Writes codes to the internal buffer
+}
+/// The system [`Clock`], calling [`Instant::now`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+impl Clock for SystemClock {
     #[inline]
-    pub fn write_codes(&mut self, codes: &[u8]) {
-        self.0.extend_from_slice(codes);
+    fn now(&self) -> Instant {
+        Instant::now()
     }
-    /// Writes a code to the internal buffer
+}
+/// A [`CapableWriter`] that stops styling when SGR sequences are written too often
+///
+/// Each [SGR sequence](CapableWriter::write_sgr) takes a token from a bucket,
+/// refilled at `per_second` tokens a second up to the [burst](ThrottledWriter::burst) size.
+/// Sequences written while the bucket is empty are dropped, leaving plain text,
+/// and styling resumes once the rate drops enough for tokens to refill.
+/// If a sequence written earlier left anything on, a reset is written in place of the first one dropped,
+/// so nothing stays styled past its clean. Text & non-SGR sequences are always written
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{EasySGR, FmtWriter, SGRWriter, Style::*, ThrottledWriter};
+///
+///let throttled = ThrottledWriter::new(FmtWriter(String::new()), 10).burst(1);
+///let mut writer = SGRWriter::from(throttled);
+///writer.sgr(&"styled".style(Bold)).unwrap();
+///writer.write_inner("styled").unwrap();
+///writer.sgr(&"plain".style(Bold)).unwrap();
+///writer.write_inner("plain").unwrap();
+///assert_eq!(writer.writer.styled_writes(), 1);
+///assert_eq!(writer.writer.stripped_writes(), 1);
+///assert_eq!(writer.internal(), "\x1b[1mstyled\x1b[0mplain");
+///```
+#[derive(Debug, Clone)]
+pub struct ThrottledWriter<W: CapableWriter, C: Clock = SystemClock> {
+    writer: W,
+    clock: C,
+    per_second: u32,
+    burst: u32,
+    tokens: u128,
+    last: Option<Instant>,
+    state: StyleSet,
+    styled_writes: u64,
+    stripped_writes: u64,
+}
+/// A single token, counted in billionths so refilling by the nanosecond stays exact
+const TOKEN: u128 = 1_000_000_000;
+impl<W: CapableWriter> ThrottledWriter<W> {
+    /// Creates a new [`ThrottledWriter`], styling at most `per_second` sequences a second
     ///
-    /// Returns self to allow for chaining
+    /// The burst size defaults to `per_second`
+    #[must_use]
+    pub fn new(writer: W, per_second: u32) -> Self {
+        Self {
+            writer,
+            clock: SystemClock,
+            per_second,
+            burst: per_second,
+            tokens: u128::from(per_second) * TOKEN,
+            last: None,
+            state: StyleSet::default(),
+            styled_writes: 0,
+            stripped_writes: 0,
+        }
+    }
+}
+impl<W: CapableWriter, C: Clock> ThrottledWriter<W, C> {
+    /// Sets the number of sequences that can be styled at once, starting with a full bucket
+    #[must_use]
+    pub fn burst(self, burst: u32) -> Self {
+        Self {
+            burst,
+            tokens: u128::from(burst) * TOKEN,
+            ..self
+        }
+    }
+    /// Replaces the [`Clock`] used to refill tokens
+    #[must_use]
+    pub fn clock<D: Clock>(self, clock: D) -> ThrottledWriter<W, D> {
+        ThrottledWriter {
+            writer: self.writer,
+            clock,
+            per_second: self.per_second,
+            burst: self.burst,
+            tokens: self.tokens,
+            last: self.last,
+            state: self.state,
+            styled_writes: self.styled_writes,
+            stripped_writes: self.stripped_writes,
+        }
+    }
+    /// Returns the number of SGR sequences written
+    #[must_use]
+    pub const fn styled_writes(&self) -> u64 {
+        self.styled_writes
+    }
+    /// Returns the number of SGR sequences dropped
+    #[must_use]
+    pub const fn stripped_writes(&self) -> u64 {
+        self.stripped_writes
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+    /// Takes a token, returning `false` if none are left
+    fn take_token(&mut self) -> bool {
+        let now = self.clock.now();
+        if let Some(last) = self.last {
+            let refill =
+                now.saturating_duration_since(last).as_nanos() * u128::from(self.per_second);
+            self.tokens = self
+                .tokens
+                .saturating_add(refill)
+                .min(u128::from(self.burst) * TOKEN);
+        }
+        self.last = Some(now);
+        self.tokens
+            .checked_sub(TOKEN)
+            .map(|tokens| self.tokens = tokens)
+            .is_some()
+    }
+}
+impl<W: CapableWriter, C: Clock> CapableWriter for ThrottledWriter<W, C> {
+    type Writer = W::Writer;
+    type Error = W::Error;
     #[inline]
-    pub fn chain_code(&mut self, code: u8) -> &mut Self {
-        self.0.push(code);
-        self
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
     }
-    /// Writes codes to the internal buffer
-    ///
-    /// Returns self to allow for chaining
     #[inline]
-    pub fn chain_codes(&mut self, codes: &[u8]) -> &mut Self {
-        self.0.extend_from_slice(codes);
-        self
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
     }
-    /// Writes buffered codes to the provided writer
-    ///
-    /// # Errors
-    ///
-    /// Writing failed
-    pub fn write_to<W: CapableWriter>(&mut self, writer: &mut W) -> Result<(), W::Error> {
-        if self.0.is_empty() {
-            Ok(())
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        if codes.is_empty() {
+            return Ok(());
+        }
+        if self.take_token() {
+            self.styled_writes += 1;
+            self.state.apply_codes(codes);
+            self.writer.write_sgr(codes)
         } else {
-            writer.write("\x1b[")?;
-            self.codes_inner(writer)?;
-            writer.write("m")
+            self.stripped_writes += 1;
+            if self.state.is_empty() {
+                return Ok(());
+            }
+            self.state = StyleSet::default();
+            self.writer.write_sgr(&[0])
         }
     }
-    /// Writes buffered codes to the writer
-    ///
-    /// Does not write the escape or end sequences
-    ///
-    /// Performs IO operations with the inputted [`SGRWriter`]
-    ///
-    /// # Errors
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// How bright colors, such as [`Color::BrightRedFg`](crate::Color::BrightRedFg), are written by a [`BrightWriter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BrightPolicy {
+    /// Writes the aixterm codes, `90` to `97` & `100` to `107`
+    #[default]
+    Codes90,
+    /// Writes bright foregrounds as bold & the basic color, i.e. `91` as `1;31`
     ///
-    /// Writing failed
-    pub fn write_partial<W: CapableWriter>(&mut self, writer: &mut W) -> Result<(), W::Error> {
-        if !self.0.is_empty() {
-            self.codes_inner(writer)?;
+    /// For terminals without the aixterm codes that brighten bold text.
+    /// Bold never brightens backgrounds, so bright backgrounds become the basic color
+    BoldBasic,
+}
+/// A [`CapableWriter`] writing bright colors according to a [`BrightPolicy`]
+///
+/// With [`BrightPolicy::BoldBasic`] the bold implied by a bright foreground is turned off
+/// once the foreground changes, unless bold was asked for itself,
+/// & placed again when bold is turned off while the bright foreground stays.
+/// This is tracked across [SGR sequences](CapableWriter::write_sgr),
+/// so codes written without the escape & end sequences are not seen
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{BrightPolicy, BrightWriter, Color::*, EasySGR, FmtWriter, SGRWriter, Style::*};
+///
+///let bright = BrightWriter::new(FmtWriter(String::new()), BrightPolicy::BoldBasic);
+///let mut writer = SGRWriter::from(bright);
+///writer.inline_sgr(&BrightRedFg)?;
+///writer.write_inner("error")?;
+///writer.inline_sgr(&DefaultFg)?;
+///assert_eq!(writer.internal(), "\x1b[1;31merror\x1b[39;22m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct BrightWriter<W: CapableWriter> {
+    writer: W,
+    policy: BrightPolicy,
+    bright: bool,
+    bold: bool,
+    dim: bool,
+}
+impl<W: CapableWriter> BrightWriter<W> {
+    /// Creates a new [`BrightWriter`] using the given [`BrightPolicy`]
+    #[must_use]
+    pub const fn new(writer: W, policy: BrightPolicy) -> Self {
+        Self {
+            writer,
+            policy,
+            bright: false,
+            bold: false,
+            dim: false,
         }
-        Ok(())
     }
-    /// Writes the buffered codes into the inputted writer
-    fn codes_inner<W: CapableWriter>(&mut self, writer: &mut W) -> Result<(), W::Error> {
-        writer.write(&self.0[0].to_string())?;
-
-        for code in &self.0[1..] {
-            writer.write(";")?;
-            writer.write(&code.to_string())?;
+    /// Returns the [`BrightPolicy`] in use
+    #[must_use]
+    pub const fn policy(&self) -> BrightPolicy {
+        self.policy
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+    /// Rewrites the codes for [`BrightPolicy::BoldBasic`]
+    fn bold_basic(&mut self, codes: &[u8]) -> SGRBuilder {
+        let mut builder = SGRBuilder::default();
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => (self.bright, self.bold, self.dim) = (false, false, false),
+                // already bold through the bright foreground
+                1 if self.bright && !self.bold => {
+                    self.bold = true;
+                    continue;
+                }
+                1 => self.bold = true,
+                2 => self.dim = true,
+                22 => {
+                    (self.bold, self.dim) = (false, false);
+                    if self.bright {
+                        builder.write_codes(&[22, 1]);
+                        continue;
+                    }
+                }
+                90..=97 => {
+                    if !self.bold && !self.bright {
+                        builder.write_code(1);
+                    }
+                    self.bright = true;
+                    builder.write_code(code - 60);
+                    continue;
+                }
+                100..=107 => {
+                    builder.write_code(code - 60);
+                    continue;
+                }
+                30..=39 => {
+                    builder.write_code(code);
+                    if code == 38 {
+                        extended(&mut codes, &mut builder);
+                    }
+                    if std::mem::take(&mut self.bright) && !self.bold {
+                        builder.write_code(22);
+                        if self.dim {
+                            builder.write_code(2);
+                        }
+                    }
+                    continue;
+                }
+                48 => {
+                    builder.write_code(code);
+                    extended(&mut codes, &mut builder);
+                    continue;
+                }
+                _ => (),
+            }
+            builder.write_code(code);
         }
-        Ok(())
+        builder
     }
 }
-
-/// Helps to make writing easier
+/// Copies the rest of an extended color, `5;n` or `2;r;g;b`
+pub(crate) fn extended(codes: &mut impl Iterator<Item = u8>, builder: &mut SGRBuilder) {
+    let len = match codes.next() {
+        Some(5) => 1,
+        Some(2) => 3,
+        Some(kind) => {
+            builder.write_code(kind);
+            return;
+        }
+        None => return,
+    };
+    builder.write_code(if len == 1 { 5 } else { 2 });
+    for code in codes.take(len) {
+        builder.write_code(code);
+    }
+}
+impl<W: CapableWriter> CapableWriter for BrightWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    #[inline]
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        match self.policy {
+            BrightPolicy::Codes90 => self.writer.write_sgr(codes),
+            BrightPolicy::BoldBasic => {
+                let builder = self.bold_basic(codes);
+                self.writer.write_sgr(builder.codes())
+            }
+        }
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// A [`CapableWriter`] writing the basic & bright colors as the RGB colors of a [`PaletteMap`]
 ///
-/// Allows to use the same method for both
-/// [`SGRString`] and [`DiscreteSGR`] types
-pub trait EasyWrite {
-    /// Writes a set of codes to the builder
-    fn sgr(&self, builder: &mut SGRBuilder);
+/// `30` to `37`, `40` to `47`, `90` to `97` & `100` to `107` are written as `38;2;r;g;b` or `48;2;r;g;b`,
+/// as are 256 colors the map has a color for. RGB & default colors are left untouched,
+/// so remapping already remapped output changes nothing.
+/// Only [SGR sequences](CapableWriter::write_sgr) are remapped,
+/// codes written without the escape & end sequences are not seen
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{palette256::PaletteMap, Color::*, FmtWriter, RemapWriter, SGRWriter};
+///
+///let map = PaletteMap::default().with_color(1, (220, 50, 47));
+///let mut writer = SGRWriter::from(RemapWriter::new(FmtWriter(String::new()), map));
+///writer.inline_sgr(&RedFg)?;
+///writer.write_inner("error")?;
+///writer.inline_sgr(&RgbFg(1, 2, 3))?;
+///assert_eq!(writer.internal(), "\x1b[38;2;220;50;47merror\x1b[38;2;1;2;3m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct RemapWriter<W: CapableWriter> {
+    writer: W,
+    map: PaletteMap,
 }
-
-impl EasyWrite for SGRString {
-    /// Writes a set of codes to the builder
-    ///
-    /// Uses [`SGRString::place_all`]
-    fn sgr(&self, builder: &mut SGRBuilder) {
-        self.place_all(builder);
+impl<W: CapableWriter> RemapWriter<W> {
+    /// Creates a new [`RemapWriter`] using the given [`PaletteMap`]
+    #[must_use]
+    pub const fn new(writer: W, map: PaletteMap) -> Self {
+        Self { writer, map }
+    }
+    /// Returns the [`PaletteMap`] in use
+    #[must_use]
+    pub const fn map(&self) -> &PaletteMap {
+        &self.map
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+    /// Rewrites the colors of the codes through the map
+    fn remap(&self, codes: &[u8]) -> SGRBuilder {
+        let mut builder = SGRBuilder::default();
+        let rgb = |builder: &mut SGRBuilder, kind: u8, (r, g, b): (u8, u8, u8)| {
+            builder.write_codes(&[kind, 2, r, g, b]);
+        };
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                30..=37 => rgb(&mut builder, 38, self.map.color(code - 30)),
+                40..=47 => rgb(&mut builder, 48, self.map.color(code - 40)),
+                90..=97 => rgb(&mut builder, 38, self.map.color(code - 82)),
+                100..=107 => rgb(&mut builder, 48, self.map.color(code - 92)),
+                38 | 48 => {
+                    let mut rest = codes.clone();
+                    let mapped = match rest.next() {
+                        Some(5) => rest.next().and_then(|index| self.map.get(index)),
+                        _ => None,
+                    };
+                    if let Some(color) = mapped {
+                        rgb(&mut builder, code, color);
+                        codes = rest;
+                    } else {
+                        builder.write_code(code);
+                        extended(&mut codes, &mut builder);
+                    }
+                }
+                code => builder.write_code(code),
+            }
+        }
+        builder
     }
 }
-
-impl<D: DiscreteSGR> EasyWrite for D {
-    /// Writes a set of codes to the builder
-    ///
-    /// Uses [`DiscreteSGR::write`]
-    fn sgr(&self, builder: &mut SGRBuilder) {
-        self.write(builder);
+impl<W: CapableWriter> CapableWriter for RemapWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    #[inline]
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        let builder = self.remap(codes);
+        self.writer.write_sgr(builder.codes())
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// A [`CapableWriter`] beginning control sequences with the introducer of an [`EscapeKind`]
+///
+/// Changes every [escape](CapableWriter::write_escape) written through it,
+/// including those of [`SGRWriter::write_raw_csi`].
+/// It should wrap the writer of a [`str`] or bytes directly, other writers such as
+/// [`BrightWriter`] going around it, as [SGR sequences](CapableWriter::write_sgr) are written by the writer within
+/// With [`EscapeKind::C1`] an [`IoWriter`] writes the single byte `0x9b`,
+/// a [`FmtWriter`] the UTF-8 encoding of [`C1_ESCAPE`]
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{EscapeKind, EscapeWriter, IoWriter, SGRWriter, Style::*};
+///
+///let mut writer = SGRWriter::from(EscapeWriter::new(IoWriter(Vec::new()), EscapeKind::C1));
+///writer.inline_sgr(&Bold)?;
+///writer.write_inner("bold")?;
+///writer.write_reset()?;
+///assert_eq!(writer.internal(), b"\x9b1mbold\x9b0m");
+///# Ok::<(), std::io::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct EscapeWriter<W: CapableWriter> {
+    writer: W,
+    kind: EscapeKind,
+}
+impl<W: CapableWriter> EscapeWriter<W> {
+    /// Creates a new [`EscapeWriter`] using the given [`EscapeKind`]
+    #[must_use]
+    pub const fn new(writer: W, kind: EscapeKind) -> Self {
+        Self { writer, kind }
+    }
+    /// Returns the [`EscapeKind`] in use
+    #[must_use]
+    pub const fn kind(&self) -> EscapeKind {
+        self.kind
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+impl<W: CapableWriter> CapableWriter for EscapeWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    #[inline]
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, _: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(self.kind)
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// How a [`ResetWriter`] writes a full reset, the code `0`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ResetStrategy {
+    /// Writes `0` as is
+    #[default]
+    Sgr0,
+    /// Writes each attribute being turned off, the codes of [`ResetStrategy::OFF_CODES`]
+    ///
+    /// For terminals that mishandle `0`, i.e. resetting more than the attributes.
+    /// Codes unknown to this crate are left in effect
+    ExplicitOffCodes,
+}
+impl ResetStrategy {
+    /// The codes turning off every attribute written by this crate, `22;23;24;25;27;28;29;39;49;54;55`
+    ///
+    /// Refer to [`consts::RESET_EXPLICIT`](crate::consts::RESET_EXPLICIT)
+    pub const OFF_CODES: [u8; 11] = [22, 23, 24, 25, 27, 28, 29, 39, 49, 54, 55];
+}
+/// A [`CapableWriter`] writing full resets according to a [`ResetStrategy`]
+///
+/// Every `0` within an [SGR sequence](CapableWriter::write_sgr) is rewritten,
+/// including those of [`CleanKind::Reset`](crate::CleanKind::Reset), [`Style::Reset`](crate::Style::Reset)
+/// & [`SGRWriter::write_reset`], the parameters of extended colors being left as is.
+/// Resets written as text, such as [`consts::RESET`](crate::consts::RESET) or the output
+/// of the macros, are fixed at compile time & not seen
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{CleanKind, EasySGR, FmtWriter, ResetStrategy, ResetWriter, SGRWriter, Style::*};
+///
+///let sgr = "bold".style(Bold).clean(CleanKind::Reset);
+///let reset = ResetWriter::new(FmtWriter(String::new()), ResetStrategy::ExplicitOffCodes);
+///let mut writer = SGRWriter::from(reset);
+///writer.place_sgr(&sgr)?;
+///writer.write_inner(&sgr.text)?;
+///writer.clean_sgr(&sgr)?;
+///assert_eq!(writer.internal(), "\x1b[1mbold\x1b[22;23;24;25;27;28;29;39;49;54;55m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct ResetWriter<W: CapableWriter> {
+    writer: W,
+    strategy: ResetStrategy,
+}
+impl<W: CapableWriter> ResetWriter<W> {
+    /// Creates a new [`ResetWriter`] using the given [`ResetStrategy`]
+    #[must_use]
+    pub const fn new(writer: W, strategy: ResetStrategy) -> Self {
+        Self { writer, strategy }
+    }
+    /// Returns the [`ResetStrategy`] in use
+    #[must_use]
+    pub const fn strategy(&self) -> ResetStrategy {
+        self.strategy
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+impl<W: CapableWriter> CapableWriter for ResetWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    #[inline]
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        if self.strategy == ResetStrategy::Sgr0 || !codes.contains(&0) {
+            return self.writer.write_sgr(codes);
+        }
+        let mut builder = SGRBuilder::default();
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => builder.write_codes(&ResetStrategy::OFF_CODES),
+                38 | 48 => {
+                    builder.write_code(code);
+                    extended(&mut codes, &mut builder);
+                }
+                _ => builder.write_code(code),
+            }
+        }
+        self.writer.write_sgr(builder.codes())
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// A [`CapableWriter`] capping the bytes of SGR sequences written on each line
+///
+/// Counts the bytes of each [SGR sequence](CapableWriter::write_sgr) written since the last `\n`,
+/// as if written with [`ESCAPE`]. A sequence that would exceed the budget is not written,
+/// nor is any other on the rest of the line, the [trigger](BudgetWriter::triggered) being counted.
+/// If anything is left open at that point a reset is written, room for it always being kept,
+/// so the line ends unstyled & the budget is never exceeded.
+/// Text is always written in full, control sequences written as text are not counted
+///
+/// Escapes & parameters written on their own, i.e. through [`SGRWriter::partial_sgr`],
+/// are counted as well, each parameter along with a separator & room for a reset kept.
+/// Their codes are tracked once the sequence ends in `m`, or at the next write if there was no escape.
+/// Once exhausted they are not written either, nor is the final byte of a sequence whose escape was not
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{BudgetWriter, Color::*, FmtWriter, SGRWriter, Style::*};
+///
+///let mut writer = SGRWriter::from(BudgetWriter::new(FmtWriter(String::new()), 12));
+///writer.inline_sgr(&RedFg)?;
+///writer.write_inner("red ")?;
+///writer.inline_sgr(&Bold)?;
+///writer.write_inner("plain\n")?;
+///writer.inline_sgr(&Bold)?;
+///writer.write_inner("bold")?;
+///assert_eq!(writer.writer.triggered(), 1);
+///assert_eq!(writer.internal(), "\x1b[31mred \x1b[0mplain\n\x1b[1mbold");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct BudgetWriter<W: CapableWriter> {
+    writer: W,
+    budget: usize,
+    used: usize,
+    exhausted: bool,
+    state: StyleSet,
+    triggered: u64,
+    run: Option<Run>,
+}
+/// The escape & parameters a [`BudgetWriter`] was given on their own since the last write
+#[derive(Debug, Clone, Default)]
+struct Run {
+    /// Whether the run started with an escape, & whether it was written
+    escape: Option<bool>,
+    /// The codes written, tracked once the run ends
+    codes: SGRBuilder,
+    /// Whether the last parameter was written, so a separator may follow it
+    after_param: bool,
+    /// Whether a separator is held back, written along with the next parameter
+    separator: bool,
+}
+impl<W: CapableWriter> BudgetWriter<W> {
+    /// The bytes of the reset written once the budget is exhausted, `\x1b[0m`
+    const RESET_LEN: usize = ESCAPE.len() + 1 + END.len();
+    /// Creates a new [`BudgetWriter`] writing at most `max_overhead_bytes_per_line` bytes of sequences a line
+    #[must_use]
+    pub fn new(writer: W, max_overhead_bytes_per_line: usize) -> Self {
+        Self {
+            writer,
+            budget: max_overhead_bytes_per_line,
+            used: 0,
+            exhausted: false,
+            state: StyleSet::default(),
+            triggered: 0,
+            run: None,
+        }
+    }
+    /// Counts `len` bytes if they fit alongside `reserved`, otherwise exhausting the budget
+    const fn spend(&mut self, len: usize, reserved: usize) -> bool {
+        if !self.exhausted && self.used + len + reserved <= self.budget {
+            self.used += len;
+            return true;
+        }
+        if !self.exhausted {
+            self.exhausted = true;
+            self.triggered += 1;
+        }
+        false
+    }
+    /// Writes a reset if the budget is exhausted & anything is left open
+    fn settle(&mut self) -> Result<(), W::Error> {
+        if !self.exhausted || self.state.is_empty() {
+            return Ok(());
+        }
+        self.state = StyleSet::default();
+        self.used += Self::RESET_LEN;
+        self.writer.write_sgr(&[0])
+    }
+    /// Ends the current run before `s` is written, returning what is left of `s` to write
+    fn end_run<'s>(&mut self, mut s: &'s str) -> Result<&'s str, W::Error> {
+        let Some(run) = self.run.take() else {
+            return Ok(s);
+        };
+        if run.separator {
+            self.writer.write(";")?;
+        }
+        let final_byte = s.chars().next().filter(|ch| ('@'..='~').contains(ch));
+        match (run.escape, final_byte) {
+            (Some(false), Some(_)) => s = &s[1..],
+            (Some(true), Some(final_byte)) => {
+                self.writer.write(&s[..1])?;
+                s = &s[1..];
+                if final_byte == 'm' {
+                    self.state.apply_codes(run.codes.codes());
+                }
+            }
+            (None, _) => self.state.apply_codes(run.codes.codes()),
+            _ => (),
+        }
+        self.settle()?;
+        Ok(s)
+    }
+    /// Returns the bytes of sequences allowed on each line
+    #[must_use]
+    pub const fn budget(&self) -> usize {
+        self.budget
+    }
+    /// Returns the number of lines on which the budget was exhausted
+    #[must_use]
+    pub const fn triggered(&self) -> u64 {
+        self.triggered
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+impl<W: CapableWriter> CapableWriter for BudgetWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        if let Some(run) = &mut self.run {
+            // the separator between parameters, counted along with the one before it
+            if s == ";" {
+                run.separator = std::mem::take(&mut run.after_param);
+                return Ok(());
+            }
+        }
+        let s = self.end_run(s)?;
+        if s.contains('\n') {
+            (self.used, self.exhausted) = (0, false);
+        }
+        self.writer.write(s)
+    }
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        let len = format_param(param, &mut [0; 5]).len() + 1;
+        let written = self.spend(len, Self::RESET_LEN);
+        let run = self.run.get_or_insert_with(Run::default);
+        let separator = std::mem::take(&mut run.separator);
+        run.after_param = written;
+        if !written {
+            return Ok(());
+        }
+        if let Ok(code) = u8::try_from(param) {
+            run.codes.write_code(code);
+        }
+        if separator {
+            self.writer.write(";")?;
+        }
+        self.writer.write_param(param)
+    }
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.end_run("")?;
+        let introducer = match kind {
+            EscapeKind::Esc => ESCAPE.len(),
+            EscapeKind::C1 => C1_ESCAPE.len(),
+        };
+        let written = self.spend(introducer + END.len(), Self::RESET_LEN);
+        self.run = Some(Run {
+            escape: Some(written),
+            ..Run::default()
+        });
+        if !written {
+            return Ok(());
+        }
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.end_run("")?;
+        if self.exhausted || codes.is_empty() {
+            return Ok(());
+        }
+        let mut state = self.state.clone();
+        state.apply_codes(codes);
+        let reserved = if state.is_empty() { 0 } else { Self::RESET_LEN };
+        if self.spend(encoded_len(codes), reserved) {
+            self.state = state;
+            return self.writer.write_sgr(codes);
+        }
+        self.settle()
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// A [`CapableWriter`] applying a [`SanitizePolicy`] to the text written through it
+///
+/// Text is sanitized as by [`sanitize`](crate::sanitize), while [SGR sequences](CapableWriter::write_sgr)
+/// are written as is, so untrusted text can be placed between them.
+/// Sequences split across writes are still recognized,
+/// though control sequences written as text, i.e. through [`SGRWriter::write_raw_csi`], are sanitized as well
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, FmtWriter, SGRWriter, SanitizePolicy, SanitizingWriter};
+///
+///let sanitizing = SanitizingWriter::new(FmtWriter(String::new()), SanitizePolicy::StripAll);
+///let mut writer = SGRWriter::from(sanitizing);
+///writer.inline_sgr(&RedFg)?;
+///writer.write_inner("\x1b]0;")?;
+///writer.write_inner("title\x07file")?;
+///writer.inline_sgr(&DefaultFg)?;
+///assert_eq!(writer.internal(), "\x1b[31mfile\x1b[39m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct SanitizingWriter<W: CapableWriter> {
+    writer: W,
+    sanitizer: Sanitizer,
+    buf: String,
+}
+impl<W: CapableWriter> SanitizingWriter<W> {
+    /// Creates a new [`SanitizingWriter`] using the given [`SanitizePolicy`]
+    #[must_use]
+    pub const fn new(writer: W, policy: SanitizePolicy) -> Self {
+        Self {
+            writer,
+            sanitizer: Sanitizer::new(policy),
+            buf: String::new(),
+        }
+    }
+    /// Returns the [`SanitizePolicy`] in use
+    #[must_use]
+    pub const fn policy(&self) -> SanitizePolicy {
+        self.sanitizer.policy()
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+impl<W: CapableWriter> CapableWriter for SanitizingWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.buf.clear();
+        self.sanitizer.feed(s, &mut self.buf);
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        self.writer.write(&self.buf)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.writer.write_sgr(codes)
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// How a [`MaskingWriter`] writes text [hidden](crate::Style::Hidden) by code `8`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HiddenPolicy {
+    /// Writes the text as is, leaving the terminal to conceal it
+    #[default]
+    Conceal,
+    /// Writes each [`char`] of the text but `\n` as the mask, so the visible width is kept
+    Mask(char),
+}
+impl HiddenPolicy {
+    /// Returns the policy for the terminal, masking with `*` where code `8` is not supported
+    ///
+    /// Refer to [`HiddenPolicy::for_term`], given the `TERM` environment variable.
+    /// Masks when codes are not written at all, [`enabled`] being `false`
+    #[must_use]
+    pub fn detect() -> Self {
+        if enabled() {
+            Self::for_term(std::env::var("TERM").ok().as_deref())
+        } else {
+            Self::Mask('*')
+        }
+    }
+    /// Returns the policy for a value of `TERM`, masking with `*` where code `8` is not supported
+    ///
+    /// Only a heuristic, no terminfo being read: terminals known to ignore code `8`,
+    /// `dumb` & `linux`, are masked, as is an empty or missing `TERM`
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::HiddenPolicy;
+    ///
+    ///assert_eq!(HiddenPolicy::for_term(Some("xterm-256color")), HiddenPolicy::Conceal);
+    ///assert_eq!(HiddenPolicy::for_term(Some("linux")), HiddenPolicy::Mask('*'));
+    ///assert_eq!(HiddenPolicy::for_term(None), HiddenPolicy::Mask('*'));
+    ///```
+    #[must_use]
+    pub fn for_term(term: Option<&str>) -> Self {
+        match term.map(str::trim) {
+            None | Some("" | "dumb" | "linux") => Self::Mask('*'),
+            Some(_) => Self::Conceal,
+        }
+    }
+}
+/// A [`CapableWriter`] masking the text written while [hidden](crate::Style::Hidden),
+/// according to a [`HiddenPolicy`]
+///
+/// Whether text is hidden is tracked across [SGR sequences](CapableWriter::write_sgr),
+/// which are written as is. With [`HiddenPolicy::Mask`] the hidden text is replaced
+/// by the mask, so a secret is never written where code `8` may be ignored,
+/// nor revealed by a writer stripping the sequences further on
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{FmtWriter, HiddenPolicy, MaskingWriter, SGRWriter, Style::*};
+///
+///let masking = MaskingWriter::new(FmtWriter(String::new()), HiddenPolicy::Mask('*'));
+///let mut writer = SGRWriter::from(masking);
+///writer.write_inner("pass: ")?;
+///writer.inline_sgr(&Hidden)?;
+///writer.write_inner("hunter2")?;
+///writer.inline_sgr(&NotHidden)?;
+///assert_eq!(writer.internal(), "pass: \x1b[8m*******\x1b[28m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct MaskingWriter<W: CapableWriter> {
+    writer: W,
+    policy: HiddenPolicy,
+    state: StyleSet,
+    buf: String,
+}
+impl<W: CapableWriter> MaskingWriter<W> {
+    /// Creates a new [`MaskingWriter`] using the given [`HiddenPolicy`]
+    #[must_use]
+    pub fn new(writer: W, policy: HiddenPolicy) -> Self {
+        Self {
+            writer,
+            policy,
+            state: StyleSet::default(),
+            buf: String::new(),
+        }
+    }
+    /// Returns the [`HiddenPolicy`] in use
+    #[must_use]
+    pub const fn policy(&self) -> HiddenPolicy {
+        self.policy
+    }
+    /// Returns `true` if text written now is hidden
+    #[must_use]
+    pub fn hidden(&self) -> bool {
+        self.state.hidden == StyleKind::Place
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+impl<W: CapableWriter> CapableWriter for MaskingWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        match self.policy {
+            HiddenPolicy::Mask(mask) if self.hidden() => {
+                self.buf.clear();
+                self.buf
+                    .extend(s.chars().map(|ch| if ch == '\n' { ch } else { mask }));
+                self.writer.write(&self.buf)
+            }
+            _ => self.writer.write(s),
+        }
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.state.apply_codes(codes);
+        self.writer.write_sgr(codes)
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// The colors a terminal displays, from fewest to most
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+    /// No colors at all
+    None,
+    /// The 8 basic colors & their bright variants, `30` to `37` & `90` to `97`
+    Basic,
+    /// The 256 colors of `38;5;n`
+    Palette256,
+    /// Any RGB color, `38;2;r;g;b`
+    #[default]
+    Rgb,
+}
+impl ColorSupport {
+    /// Returns the support for a number of colors, such as the `colors` of a terminfo entry
+    ///
+    /// ```rust
+    ///use easy_sgr::ColorSupport;
+    ///
+    ///assert_eq!(ColorSupport::from_max_colors(8), ColorSupport::Basic);
+    ///assert_eq!(ColorSupport::from_max_colors(256), ColorSupport::Palette256);
+    ///assert_eq!(ColorSupport::from_max_colors(1 << 24), ColorSupport::Rgb);
+    ///```
+    #[must_use]
+    pub const fn from_max_colors(max_colors: u32) -> Self {
+        match max_colors {
+            0..=7 => Self::None,
+            8..=255 => Self::Basic,
+            256..=16_777_215 => Self::Palette256,
+            _ => Self::Rgb,
+        }
+    }
+}
+/// What a terminal displays, used by a [`DowngradeWriter`]
+///
+/// Defaults to supporting everything
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// The colors displayed
+    pub colors: ColorSupport,
+    /// Whether [`Italic`](crate::Style::Italic) is displayed
+    pub supports_italic: bool,
+    /// Whether [`Strikethrough`](crate::Style::Strikethrough) is displayed
+    pub supports_strikethrough: bool,
+}
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            colors: ColorSupport::Rgb,
+            supports_italic: true,
+            supports_strikethrough: true,
+        }
+    }
+}
+impl Capabilities {
+    /// Returns the capabilities of the terminal, given by the environment
+    ///
+    /// Refer to [`Capabilities::for_env`], given the `TERM` & `COLORTERM` environment variables.
+    /// Nothing is supported when codes are not written at all, [`enabled`] being `false`.
+    /// With the `terminfo` feature, `terminfo::detect`
+    /// reads the terminfo database instead
+    #[must_use]
+    pub fn from_env() -> Self {
+        if enabled() {
+            Self::for_env(
+                std::env::var("TERM").ok().as_deref(),
+                std::env::var("COLORTERM").ok().as_deref(),
+            )
+        } else {
+            Self::for_env(None, None)
+        }
+    }
+    /// Returns the capabilities for values of `TERM` & `COLORTERM`
+    ///
+    /// Only a heuristic: `COLORTERM` being `truecolor` or `24bit` gives RGB colors,
+    /// a `TERM` containing `256color` the 256 colors & any other the basic colors.
+    /// An empty or missing `TERM` & `dumb` support nothing, `linux`, `screen` & `vt100`
+    /// the colors without italics or strikethrough
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{Capabilities, ColorSupport};
+    ///
+    ///let xterm = Capabilities::for_env(Some("xterm-256color"), None);
+    ///assert_eq!(xterm.colors, ColorSupport::Palette256);
+    ///assert!(xterm.supports_italic);
+    ///let linux = Capabilities::for_env(Some("linux"), Some("truecolor"));
+    ///assert_eq!(linux.colors, ColorSupport::Rgb);
+    ///assert!(!linux.supports_italic);
+    ///```
+    #[must_use]
+    pub fn for_env(term: Option<&str>, colorterm: Option<&str>) -> Self {
+        let term = match term.map(str::trim) {
+            None | Some("" | "dumb") => {
+                return Self {
+                    colors: ColorSupport::None,
+                    supports_italic: false,
+                    supports_strikethrough: false,
+                }
+            }
+            Some(term) => term,
+        };
+        let colors = match colorterm.map(str::trim) {
+            Some("truecolor" | "24bit") => ColorSupport::Rgb,
+            _ if term.contains("256color") => ColorSupport::Palette256,
+            _ => ColorSupport::Basic,
+        };
+        let styled = !matches!(term.split('-').next(), Some("linux" | "screen" | "vt100"));
+        Self {
+            colors,
+            supports_italic: styled,
+            supports_strikethrough: styled,
+        }
+    }
+}
+/// What a [`DowngradeWriter`] writes in place of an unsupported style
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StyleFallback {
+    /// Writes nothing
+    #[default]
+    Drop,
+    /// Writes another style, placed & turned off with the unsupported one
+    Substitute(Style),
+}
+/// A [`CapableWriter`] rewriting what a terminal does not display, according to its [`Capabilities`]
+///
+/// Colors beyond the [`ColorSupport`] are written as the nearest color supported,
+/// using [`palette256::nearest`](crate::palette256::nearest) &
+/// [`palette256::nearest_system`](crate::palette256::nearest_system),
+/// or not at all without color support.
+/// Unsupported italics & strikethrough are written according to their [`StyleFallback`],
+/// i.e. italics as underline with [`StyleFallback::Substitute`]
+/// Only [SGR sequences](CapableWriter::write_sgr) are rewritten,
+/// codes written without the escape & end sequences are not seen
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{
+///    Capabilities, Color::*, ColorSupport, DowngradeWriter, FmtWriter, SGRWriter,
+///    Style::{self, *}, StyleFallback,
+///};
+///
+///let capabilities = Capabilities {
+///    colors: ColorSupport::Palette256,
+///    supports_italic: false,
+///    supports_strikethrough: false,
+///};
+///let downgrade = DowngradeWriter::new(FmtWriter(String::new()), capabilities)
+///    .italic_fallback(StyleFallback::Substitute(Style::Underline));
+///let mut writer = SGRWriter::from(downgrade);
+///writer.inline_sgr_all(&(Italic, RgbFg(250, 130, 10), Strikethrough))?;
+///writer.write_inner("note")?;
+///writer.inline_sgr_all(&(NotItalic, NotStrikethrough))?;
+///assert_eq!(writer.internal(), "\x1b[4;38;5;208mnote\x1b[24m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct DowngradeWriter<W: CapableWriter> {
+    writer: W,
+    capabilities: Capabilities,
+    italic: StyleFallback,
+    strikethrough: StyleFallback,
+}
+impl<W: CapableWriter> DowngradeWriter<W> {
+    /// Creates a new [`DowngradeWriter`] for the given [`Capabilities`],
+    /// dropping unsupported styles
+    #[must_use]
+    pub const fn new(writer: W, capabilities: Capabilities) -> Self {
+        Self {
+            writer,
+            capabilities,
+            italic: StyleFallback::Drop,
+            strikethrough: StyleFallback::Drop,
+        }
+    }
+    /// Sets what is written in place of italics when unsupported
+    #[must_use]
+    pub fn italic_fallback(self, italic: StyleFallback) -> Self {
+        Self { italic, ..self }
+    }
+    /// Sets what is written in place of strikethrough when unsupported
+    #[must_use]
+    pub fn strikethrough_fallback(self, strikethrough: StyleFallback) -> Self {
+        Self {
+            strikethrough,
+            ..self
+        }
+    }
+    /// Returns the [`Capabilities`] written for
+    #[must_use]
+    pub const fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+    /// Rewrites the codes for the capabilities
+    fn downgrade(&self, codes: &[u8]) -> SGRBuilder {
+        let mut builder = SGRBuilder::default();
+        let colors = self.capabilities.colors;
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                3 | 23 if !self.capabilities.supports_italic => {
+                    fallback(&mut builder, &self.italic, code == 3);
+                }
+                9 | 29 if !self.capabilities.supports_strikethrough => {
+                    fallback(&mut builder, &self.strikethrough, code == 9);
+                }
+                38 | 48 => match codes.next() {
+                    Some(5) => {
+                        if let Some(index) = codes.next() {
+                            palette(&mut builder, colors, code, index);
+                        }
+                    }
+                    Some(2) => {
+                        if let (Some(r), Some(g), Some(b)) =
+                            (codes.next(), codes.next(), codes.next())
+                        {
+                            rgb(&mut builder, colors, code, (r, g, b));
+                        }
+                    }
+                    Some(kind) => builder.write_codes(&[code, kind]),
+                    None => builder.write_code(code),
+                },
+                30..=37 | 39..=47 | 49 | 90..=97 | 100..=107 if colors == ColorSupport::None => (),
+                code => builder.write_code(code),
+            }
+        }
+        builder
+    }
+}
+/// Writes the place or off codes of a [`StyleFallback`]
+fn fallback(builder: &mut SGRBuilder, fallback: &StyleFallback, place: bool) {
+    if let StyleFallback::Substitute(style) = fallback {
+        if place {
+            style.write(builder);
+        } else {
+            StyleSet::from(style.clone()).transition(&StyleSet::default(), builder);
+        }
+    }
+}
+/// Writes a 256 color, `38` or `48` given by `code`, with the colors supported
+fn palette(builder: &mut SGRBuilder, colors: ColorSupport, code: u8, index: u8) {
+    match colors {
+        ColorSupport::None => (),
+        ColorSupport::Basic if index < 16 => system(builder, code, index),
+        ColorSupport::Basic => system(
+            builder,
+            code,
+            palette256::nearest_system(palette256::to_rgb(index)),
+        ),
+        ColorSupport::Palette256 | ColorSupport::Rgb => builder.write_codes(&[code, 5, index]),
+    }
+}
+/// Writes an RGB color, `38` or `48` given by `code`, with the colors supported
+fn rgb(builder: &mut SGRBuilder, colors: ColorSupport, code: u8, (r, g, b): (u8, u8, u8)) {
+    match colors {
+        ColorSupport::None => (),
+        ColorSupport::Basic => system(builder, code, palette256::nearest_system((r, g, b))),
+        ColorSupport::Palette256 => builder.write_codes(&[code, 5, palette256::nearest((r, g, b))]),
+        ColorSupport::Rgb => builder.write_codes(&[code, 2, r, g, b]),
+    }
+}
+/// Writes a system color as a basic or bright code, `38` or `48` given by `code`
+fn system(builder: &mut SGRBuilder, code: u8, index: u8) {
+    let base = if code == 38 { 30 } else { 40 };
+    builder.write_code(if index < 8 {
+        base + index
+    } else {
+        base + 52 + index
+    });
+}
+impl<W: CapableWriter> CapableWriter for DowngradeWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    #[inline]
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        let builder = self.downgrade(codes);
+        self.writer.write_sgr(builder.codes())
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// A [`CapableWriter`] keeping track of the attributes in effect
+///
+/// Every [SGR sequence](CapableWriter::write_sgr) is applied to the tracked [`StyleSet`]
+/// through [`StyleSet::apply_codes`], starting with no attributes set.
+/// Codes written without the escape & end sequences are not tracked
+///
+/// Within an [`SGRWriter`] the state can be [queried](SGRWriter::state),
+/// [changed](SGRWriter::apply_state) & [saved](SGRWriter::save)
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, FmtWriter, SGRWriter, StatefulWriter, Style::*, StyleSet};
+///
+///let mut writer = SGRWriter::from(StatefulWriter::new(FmtWriter(String::new())));
+///writer.sgr(&Bold)?;
+///writer.sgr(&RedFg)?;
+///assert_eq!(writer.state(), &StyleSet::from((Bold, RedFg)));
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct StatefulWriter<W: CapableWriter> {
+    writer: W,
+    state: StyleSet,
+}
+impl<W: CapableWriter> StatefulWriter<W> {
+    /// Creates a new [`StatefulWriter`], with no attributes set
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            state: StyleSet::default(),
+        }
+    }
+    /// Returns the attributes in effect
+    #[must_use]
+    pub const fn state(&self) -> &StyleSet {
+        &self.state
+    }
+}
+impl<W: CapableWriter> CapableWriter for StatefulWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    #[inline]
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.writer.write_sgr(codes)?;
+        self.state.apply_codes(codes);
+        Ok(())
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+impl<W: CapableWriter> SGRWriter<StatefulWriter<W>> {
+    /// Returns the attributes in effect
+    #[must_use]
+    pub const fn state(&self) -> &StyleSet {
+        self.writer.state()
+    }
+    /// Changes the attributes in effect into `state`
+    ///
+    /// Only the codes of the [transition](StyleSet::transition) are written,
+    /// so nothing is written when `state` is already in effect
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn apply_state(&mut self, state: &StyleSet) -> Result<(), W::Error> {
+        let mut builder = SGRBuilder::default();
+        self.state().transition(state, &mut builder);
+        builder.write_to(self)
+    }
+    /// Saves the attributes in effect, restoring them once the returned [`StateGuard`] is dropped
+    ///
+    /// The guard dereferences to this writer, so it can be written to in the meantime
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    ///use easy_sgr::{FmtWriter, SGRWriter, StatefulWriter, Style::*};
+    ///
+    ///let mut writer = SGRWriter::from(StatefulWriter::new(FmtWriter(String::new())));
+    ///writer.sgr(&Italic)?;
+    ///{
+    ///    let mut emphasis = writer.save();
+    ///    emphasis.sgr(&Bold)?;
+    ///    emphasis.write_inner("!")?;
+    ///}
+    ///assert_eq!(writer.internal(), "\x1b[3m\x1b[1m!\x1b[22m");
+    ///# Ok::<(), std::fmt::Error>(())
+    ///```
+    pub fn save(&mut self) -> StateGuard<'_, W> {
+        StateGuard {
+            saved: Some(self.state().clone()),
+            writer: self,
+        }
+    }
+}
+/// Restores the attributes saved by [`SGRWriter::save`] when dropped
+///
+/// Errors are ignored when dropped, use [`StateGuard::restore`] to handle them
+#[derive(Debug)]
+pub struct StateGuard<'a, W: CapableWriter> {
+    writer: &'a mut SGRWriter<StatefulWriter<W>>,
+    saved: Option<StyleSet>,
+}
+impl<W: CapableWriter> StateGuard<'_, W> {
+    /// Restores the saved attributes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn restore(mut self) -> Result<(), W::Error> {
+        self.saved
+            .take()
+            .map_or(Ok(()), |saved| self.writer.apply_state(&saved))
+    }
+}
+impl<W: CapableWriter> std::ops::Deref for StateGuard<'_, W> {
+    type Target = SGRWriter<StatefulWriter<W>>;
+
+    fn deref(&self) -> &Self::Target {
+        self.writer
+    }
+}
+impl<W: CapableWriter> std::ops::DerefMut for StateGuard<'_, W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.writer
+    }
+}
+impl<W: CapableWriter> Drop for StateGuard<'_, W> {
+    fn drop(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            let _ = self.writer.apply_state(&saved);
+        }
+    }
+}
+/// A [`CapableWriter`] for a standard stream, keeping a base style beneath what is written
+///
+/// The [base style](StandardWriter::base_style) is placed before the first text
+/// & every [SGR sequence](CapableWriter::write_sgr) is written as the transition into
+/// the attributes it leaves in effect [layered](StyleSet::layered) over the base.
+/// Cleaning, whether by [`CleanKind::Reverse`](crate::CleanKind::Reverse) or a reset,
+/// so returns to the base rather than the terminal's defaults.
+/// The base can be overridden but not turned off, i.e. `22` does not remove a dim base.
+/// Without a base style everything is written as is
+///
+/// Codes written without the escape & end sequences are not tracked
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{CleanKind, Color::*, EasySGR, FmtWriter, SGRWriter, StandardWriter, Style::*};
+///
+///let standard = StandardWriter::new(FmtWriter(String::new())).base_style((Dim, RedFg));
+///let mut writer = SGRWriter::from(standard);
+///let sgr = "error".color(YellowFg).style(Bold).clean(CleanKind::Reverse);
+///writer.write_inner("> ")?;
+///writer.place_sgr(&sgr)?;
+///writer.write_inner(&sgr.text)?;
+///writer.clean_sgr(&sgr)?;
+///writer.write_inner(" <")?;
+///writer.finish()?;
+///assert_eq!(
+///    writer.internal(),
+///    "\x1b[31;2m> \x1b[33;1merror\x1b[0;31;2m <\x1b[0m"
+///);
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct StandardWriter<W: CapableWriter> {
+    writer: W,
+    base: StyleSet,
+    state: StyleSet,
+    shown: StyleSet,
+}
+impl StandardWriter<IoWriter<io::Stdout>> {
+    /// Creates a new [`StandardWriter`] writing to [`io::stdout`], without a base style
+    #[must_use]
+    pub fn stdout() -> Self {
+        Self::new(IoWriter(io::stdout()))
+    }
+}
+impl StandardWriter<IoWriter<io::Stderr>> {
+    /// Creates a new [`StandardWriter`] writing to [`io::stderr`], without a base style
+    #[must_use]
+    pub fn stderr() -> Self {
+        Self::new(IoWriter(io::stderr()))
+    }
+}
+impl<W: CapableWriter> StandardWriter<W> {
+    /// Creates a new [`StandardWriter`], without a base style
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            base: StyleSet::default(),
+            state: StyleSet::default(),
+            shown: StyleSet::default(),
+        }
+    }
+    /// Sets the style kept beneath what is written, placed before the next text
+    #[must_use]
+    pub fn base_style(self, base: impl Into<StyleSet>) -> Self {
+        Self {
+            base: base.into(),
+            ..self
+        }
+    }
+    /// Returns the base style, empty if there is none
+    #[must_use]
+    pub const fn base(&self) -> &StyleSet {
+        &self.base
+    }
+    /// Returns the attributes in effect, the base included
+    #[must_use]
+    pub const fn shown(&self) -> &StyleSet {
+        &self.shown
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+    /// Writes a reset if any attribute is in effect, the base included
+    ///
+    /// The base is placed again before any further text
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn finish(&mut self) -> Result<(), W::Error> {
+        self.state = StyleSet::default();
+        if self.shown.is_empty() {
+            return Ok(());
+        }
+        self.shown = StyleSet::default();
+        self.writer.write_sgr(&[0])
+    }
+    /// Writes what brings the attributes shown to the base with the state on top
+    fn establish(&mut self) -> Result<(), W::Error> {
+        let target = self.base.clone().layered(&self.state);
+        if target == self.shown {
+            return Ok(());
+        }
+        shortest_transition(&self.shown, &target).write_to(&mut self.writer)?;
+        self.shown = target;
+        Ok(())
+    }
+}
+impl<W: CapableWriter> CapableWriter for StandardWriter<W> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        if !s.is_empty() {
+            self.establish()?;
+        }
+        self.writer.write(s)
+    }
+    #[inline]
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        self.writer.write_param(param)
+    }
+    #[inline]
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        self.state.apply_codes(codes);
+        if self.base.is_empty() {
+            self.shown.clone_from(&self.state);
+            return self.writer.write_sgr(codes);
+        }
+        self.establish()
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+impl<W: CapableWriter> SGRWriter<StandardWriter<W>> {
+    /// Writes a reset if any attribute is in effect, the base included
+    ///
+    /// Refer to [`StandardWriter::finish`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    /// Error type specified by [`CapableWriter::Error`]
+    pub fn finish(&mut self) -> Result<(), W::Error> {
+        self.writer.finish()
+    }
+}
+/// What passes through an [`ObservedWriter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SgrEvent {
+    /// The introducer of a control sequence, i.e. [`ESCAPE`]
+    EscapeStart,
+    /// A parameter, within a sequence or written on its own as by [`SGRBuilder::write_partial`]
+    Param(u16),
+    /// The final byte of a control sequence, i.e. [`END`]
+    End,
+    /// The number of bytes of text written
+    Text(usize),
+}
+/// A [`CapableWriter`] calling a function with each [`SgrEvent`] passing through it
+///
+/// Nothing written is changed, each event being observed before it is passed on.
+/// [SGR sequences](CapableWriter::write_sgr) & control sequences written as an introducer,
+/// parameters & a final byte, such as by [`SGRWriter::write_raw_csi`], are both observed as
+/// [`EscapeStart`](SgrEvent::EscapeStart), each [`Param`](SgrEvent::Param), then [`End`](SgrEvent::End).
+/// The `;` between parameters is not observed, all else written being [`Text`](SgrEvent::Text)
+///
+/// [`SgrStats`] collects the events into counts
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{FmtWriter, ObservedWriter, SGRWriter, SgrEvent::*, Style::*};
+///
+///let mut events = Vec::new();
+///let mut writer = SGRWriter::from(ObservedWriter::new(FmtWriter(String::new()), |e| events.push(e)));
+///writer.inline_sgr(&Bold)?;
+///writer.write_inner("hi")?;
+///assert_eq!(writer.internal(), "\x1b[1mhi");
+///assert_eq!(events, [EscapeStart, Param(1), End, Text(2)]);
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct ObservedWriter<W: CapableWriter, F: FnMut(SgrEvent)> {
+    writer: W,
+    observe: F,
+    in_sequence: bool,
+    after_param: bool,
+}
+impl<W: CapableWriter, F: FnMut(SgrEvent)> ObservedWriter<W, F> {
+    /// Creates a new [`ObservedWriter`], calling `observe` with each event
+    #[must_use]
+    pub const fn new(writer: W, observe: F) -> Self {
+        Self {
+            writer,
+            observe,
+            in_sequence: false,
+            after_param: false,
+        }
+    }
+    /// Returns the inner writer
+    pub const fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+impl<W: CapableWriter, F: FnMut(SgrEvent)> CapableWriter for ObservedWriter<W, F> {
+    type Writer = W::Writer;
+    type Error = W::Error;
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        match s {
+            // the separator between parameters
+            ";" if self.in_sequence || self.after_param => (),
+            _ if self.in_sequence => {
+                self.in_sequence = false;
+                (self.observe)(SgrEvent::End);
+            }
+            "" => (),
+            _ => (self.observe)(SgrEvent::Text(s.len())),
+        }
+        self.after_param = false;
+        self.writer.write(s)
+    }
+    fn write_param(&mut self, param: u16) -> Result<(), Self::Error> {
+        (self.observe)(SgrEvent::Param(param));
+        self.after_param = true;
+        self.writer.write_param(param)
+    }
+    fn write_escape(&mut self, kind: EscapeKind) -> Result<(), Self::Error> {
+        (self.observe)(SgrEvent::EscapeStart);
+        (self.in_sequence, self.after_param) = (true, false);
+        self.writer.write_escape(kind)
+    }
+    fn write_sgr(&mut self, codes: &[u8]) -> Result<(), Self::Error> {
+        if !codes.is_empty() {
+            (self.observe)(SgrEvent::EscapeStart);
+            for code in codes {
+                (self.observe)(SgrEvent::Param((*code).into()));
+            }
+            (self.observe)(SgrEvent::End);
+        }
+        self.after_param = false;
+        self.writer.write_sgr(codes)
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.writer.get_writer()
+    }
+}
+/// Counts the [`SgrEvent`]s observed by an [`ObservedWriter`]
+///
+/// Overhead is the bytes of everything but text: introducers counted as [`ESCAPE`],
+/// parameters, the `;` between them & final bytes
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{Color::*, EasySGR, FmtWriter, ObservedWriter, SGRWriter, SgrStats};
+///
+///let mut stats = SgrStats::default();
+///let mut writer = SGRWriter::from(ObservedWriter::new(FmtWriter(String::new()), |e| stats.observe(e)));
+///writer.sgr(&"hi".color(ByteFg(208)))?;
+///writer.write_inner("hi")?;
+///writer.write_reset()?;
+///let written = writer.internal();
+///assert_eq!((stats.sequences, stats.text_bytes, stats.max_params), (2, 2, 3));
+///assert_eq!(stats.overhead_bytes + stats.text_bytes, written.len());
+///# Ok::<(), std::fmt::Error>(())
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SgrStats {
+    /// The number of control sequences
+    pub sequences: usize,
+    /// The number of parameters, within sequences or not
+    pub params: usize,
+    /// The most parameters of a single sequence
+    pub max_params: usize,
+    /// The bytes of text
+    pub text_bytes: usize,
+    /// The bytes of everything but text
+    pub overhead_bytes: usize,
+    current: usize,
+}
+impl SgrStats {
+    /// Counts an event
+    pub fn observe(&mut self, event: SgrEvent) {
+        match event {
+            SgrEvent::EscapeStart => {
+                self.current = 0;
+                self.overhead_bytes += ESCAPE.len();
+            }
+            SgrEvent::Param(param) => {
+                if self.current != 0 {
+                    self.overhead_bytes += 1;
+                }
+                self.current += 1;
+                self.params += 1;
+                self.max_params = self.max_params.max(self.current);
+                self.overhead_bytes += param_len(param);
+            }
+            SgrEvent::End => {
+                self.current = 0;
+                self.sequences += 1;
+                self.overhead_bytes += END.len();
+            }
+            SgrEvent::Text(len) => {
+                self.current = 0;
+                self.text_bytes += len;
+            }
+        }
+    }
+}
+/// Builds a SGR sequence
+///
+/// Codes are stored inline until more than [`SGRBuilder::INLINE_CAPACITY`]
+/// are written, at which point they are moved to the heap
+///
+/// Buffering is purely in memory, so writing codes never fails.
+/// Errors can only occur once the sequence is written out by [`SGRBuilder::write_to`]
+/// or [`SGRBuilder::write_partial`], which stop at & return the first error of the writer.
+/// Every [`SGRWriter`] method works this way, so an error is returned exactly once
+/// & no codes are lost before it
+///
+/// The codes are read through [`SGRBuilder::codes`], & converted from & into
+/// a `Vec<u8>` through [`From`] or [`SGRBuilder::into_vec`]
+#[derive(Default, Clone)]
+pub struct SGRBuilder(CodeBuffer);
+
+impl SGRBuilder {
+    /// The number of codes that can be written without allocating
+    pub const INLINE_CAPACITY: usize = 8;
+    /// Writes a code to the internal buffer
+    #[inline]
+    pub fn write_code(&mut self, code: u8) {
+        self.0.extend_from_slice(&[code]);
+    }
+    /// Writes codes to the internal buffer
+    #[inline]
+    pub fn write_codes(&mut self, codes: &[u8]) {
+        self.0.extend_from_slice(codes);
+    }
+    /// Writes a code to the internal buffer
+    ///
+    /// Returns self to allow for chaining
+    #[inline]
+    pub fn chain_code(&mut self, code: u8) -> &mut Self {
+        self.write_code(code);
+        self
+    }
+    /// Writes codes to the internal buffer
+    ///
+    /// Returns self to allow for chaining
+    #[inline]
+    pub fn chain_codes(&mut self, codes: &[u8]) -> &mut Self {
+        self.write_codes(codes);
+        self
+    }
+    /// Removes all buffered codes
+    ///
+    /// Any allocated capacity is kept, so the builder can be reused without reallocating
+    #[inline]
+    pub fn clear(&mut self) {
+        match &mut self.0 {
+            CodeBuffer::Inline(_, len) => *len = 0,
+            CodeBuffer::Spilled(codes) => codes.clear(),
+        }
+    }
+    /// Returns `true` if no codes are buffered
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.codes().is_empty()
+    }
+    /// Returns `true` if the buffered codes have been moved to the heap
+    #[inline]
+    #[must_use]
+    pub const fn spilled(&self) -> bool {
+        matches!(self.0, CodeBuffer::Spilled(_))
+    }
+    /// Returns the buffered codes
+    #[inline]
+    #[must_use]
+    pub fn codes(&self) -> &[u8] {
+        match &self.0 {
+            CodeBuffer::Inline(codes, len) => &codes[..*len],
+            CodeBuffer::Spilled(codes) => codes,
+        }
+    }
+    /// Writes buffered codes to the provided writer
+    ///
+    /// Writes nothing if no codes are buffered, refer to [`CapableWriter::write_sgr`]
+    ///
+    /// # Errors
+    ///
+    /// Writing failed, nothing is written after the first error
+    pub fn write_to<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            writer.write_sgr(self.codes())
+        }
+    }
+    /// Writes buffered codes to the writer
+    ///
+    /// Does not write the escape or end sequences
+    ///
+    /// Performs IO operations with the inputted [`SGRWriter`]
+    ///
+    /// # Errors
+    ///
+    /// Writing failed, nothing is written after the first error
+    pub fn write_partial<W: CapableWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        write_params(writer, self.codes())
+    }
+    /// Returns the buffered codes as a [`Vec`]
+    ///
+    /// Codes moved to the heap are returned without copying
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.0 {
+            CodeBuffer::Inline(codes, len) => codes[..len].to_vec(),
+            CodeBuffer::Spilled(codes) => codes,
+        }
+    }
+}
+impl From<Vec<u8>> for SGRBuilder {
+    /// Buffers the codes, kept inline if they fit
+    fn from(codes: Vec<u8>) -> Self {
+        if codes.len() <= Self::INLINE_CAPACITY {
+            let mut builder = Self::default();
+            builder.write_codes(&codes);
+            builder
+        } else {
+            Self(CodeBuffer::Spilled(codes))
+        }
+    }
+}
+impl From<SGRBuilder> for Vec<u8> {
+    fn from(builder: SGRBuilder) -> Self {
+        builder.into_vec()
+    }
+}
+/// Writes the codes of a [`DiscreteSGR`] to an [`io::Write`] in one call, i.e. `\x1b[1m`
+///
+/// Always writes, regardless of [`enabled`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{write_sgr, Style::*};
+///
+///let mut out = Vec::new();
+///write_sgr(&mut out, &Bold)?;
+///assert_eq!(out, b"\x1b[1m");
+///# Ok::<(), std::io::Error>(())
+///```
+pub fn write_sgr(w: &mut impl io::Write, sgr: &impl DiscreteSGR) -> io::Result<()> {
+    SGRWriter {
+        writer: IoWriter(w),
+    }
+    .inline_sgr(sgr)
+}
+/// Writes the codes of a [`DiscreteSGR`] to a [`fmt::Write`] in one call,
+/// such as a [`fmt::Formatter`]
+///
+/// Always writes, regardless of [`enabled`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{fmt_sgr, Color::*};
+///
+///let mut out = String::new();
+///fmt_sgr(&mut out, &RedFg)?;
+///assert_eq!(out, "\x1b[31m");
+///# Ok::<(), std::fmt::Error>(())
+///```
+pub fn fmt_sgr(f: &mut impl fmt::Write, sgr: &impl DiscreteSGR) -> fmt::Result {
+    SGRWriter {
+        writer: FmtWriter(f),
+    }
+    .inline_sgr(sgr)
+}
+/// Writes the codes of a [`DiscreteSGR`] to the locked stdout, if [`enabled`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+///
+/// # Examples
+///
+///```rust
+///use easy_sgr::{print_sgr, Style::*};
+///
+///print_sgr(&Bold)?;
+///println!("bold");
+///print_sgr(&Reset)?;
+///# Ok::<(), std::io::Error>(())
+///```
+pub fn print_sgr(sgr: &impl DiscreteSGR) -> io::Result<()> {
+    if !enabled() {
+        return Ok(());
+    }
+    write_sgr(&mut io::stdout().lock(), sgr)
+}
+/// Writes the codes of a [`DiscreteSGR`] to the locked stderr, if [`enabled`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+pub fn eprint_sgr(sgr: &impl DiscreteSGR) -> io::Result<()> {
+    if !enabled() {
+        return Ok(());
+    }
+    write_sgr(&mut io::stderr().lock(), sgr)
+}
+/// The global switch of [`enabled`], `0` until set or first read
+static ENABLED: AtomicU8 = AtomicU8::new(UNSET);
+/// [`ENABLED`] before being set or first read
+const UNSET: u8 = 0;
+/// [`ENABLED`] when codes are written
+const ON: u8 = 1;
+/// [`ENABLED`] when no codes are written
+const OFF: u8 = 2;
+/// Returns whether [`print_sgr`] & [`eprint_sgr`] write codes
+///
+/// Set through [`set_enabled`], otherwise `false` when the `NO_COLOR`
+/// environment variable is set to a non-empty value, see [no-color.org](https://no-color.org).
+/// The environment is only read the first time
+#[must_use]
+pub fn enabled() -> bool {
+    match ENABLED.load(Ordering::Relaxed) {
+        ON => true,
+        OFF => false,
+        _ => {
+            let enabled = std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+            // a concurrent set_enabled wins over the environment
+            let _ = ENABLED.compare_exchange(
+                UNSET,
+                if enabled { ON } else { OFF },
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+            ENABLED.load(Ordering::Relaxed) == ON
+        }
+    }
+}
+/// Sets whether [`print_sgr`] & [`eprint_sgr`] write codes, for the whole program
+///
+/// Refer to [`enabled`]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(if enabled { ON } else { OFF }, Ordering::Relaxed);
+}
+/// Writes a control sequence into the inputted writer
+///
+/// See [`SGRWriter::write_raw_csi`]
+pub(crate) fn write_csi<W: CapableWriter>(
+    writer: &mut W,
+    final_byte: char,
+    params: &[u16],
+) -> Result<(), W::Error> {
+    let mut buf = [0; 4];
+    writer.write_escape(EscapeKind::Esc)?;
+    write_params(writer, params)?;
+    writer.write(final_byte.encode_utf8(&mut buf))
+}
+/// Writes parameters separated by `;` into the inputted writer
+fn write_params<W: CapableWriter, P: Copy + Into<u16>>(
+    writer: &mut W,
+    params: &[P],
+) -> Result<(), W::Error> {
+    let Some((first, rest)) = params.split_first() else {
+        return Ok(());
+    };
+    writer.write_param((*first).into())?;
+
+    for code in rest {
+        writer.write(";")?;
+        writer.write_param((*code).into())?;
+    }
+    Ok(())
+}
+/// The bytes encoded without allocating by [`encode_sgr`],
+/// enough for [`SGRBuilder::INLINE_CAPACITY`] codes of three digits
+const SGR_INLINE_LEN: usize = ESCAPE.len() + END.len() + SGRBuilder::INLINE_CAPACITY * 4;
+/// Encodes an SGR sequence through [`encode_into`](crate::encode::encode_into)
+///
+/// Sequences too long for `inline` are encoded into `spilled`
+fn encode_sgr<'a>(
+    codes: &[u8],
+    inline: &'a mut [u8; SGR_INLINE_LEN],
+    spilled: &'a mut Vec<u8>,
+) -> &'a str {
+    let encoded = match encode_into(codes, inline) {
+        Ok(len) => &inline[..len],
+        Err(too_small) => {
+            spilled.resize(too_small.needed(), 0);
+            let len = encode_into(codes, spilled).unwrap_or_default();
+            &spilled[..len]
+        }
+    };
+    // only ascii is encoded
+    std::str::from_utf8(encoded).unwrap_or_default()
+}
+/// Formats a parameter into the given buffer without allocating
+pub(crate) fn format_param(param: u16, buf: &mut [u8; 5]) -> &str {
+    let len = write_param(param, buf, 0);
+    // only ascii digits are written
+    std::str::from_utf8(&buf[..len]).unwrap_or_default()
+}
+impl std::fmt::Debug for SGRBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SGRBuilder").field(&self.codes()).finish()
+    }
+}
+/// The storage of an [`SGRBuilder`]
+#[derive(Clone)]
+enum CodeBuffer {
+    /// Codes stored inline, along with the number of codes written
+    Inline([u8; SGRBuilder::INLINE_CAPACITY], usize),
+    /// Codes that did not fit inline
+    Spilled(Vec<u8>),
+}
+impl Default for CodeBuffer {
+    fn default() -> Self {
+        Self::Inline([0; SGRBuilder::INLINE_CAPACITY], 0)
+    }
+}
+impl CodeBuffer {
+    /// Appends codes, moving them to the heap if needed
+    fn extend_from_slice(&mut self, codes: &[u8]) {
+        match self {
+            Self::Inline(buf, len) if *len + codes.len() <= buf.len() => {
+                buf[*len..*len + codes.len()].copy_from_slice(codes);
+                *len += codes.len();
+            }
+            Self::Inline(buf, len) => {
+                let mut spilled = Vec::with_capacity((*len + codes.len()).max(buf.len() * 2));
+                spilled.extend_from_slice(&buf[..*len]);
+                spilled.extend_from_slice(codes);
+                *self = Self::Spilled(spilled);
+            }
+            Self::Spilled(spilled) => spilled.extend_from_slice(codes),
+        }
+    }
+}
+
+/// Helps to make writing easier
+///
+/// Allows to use the same method for both
+/// [`SGRString`] and [`DiscreteSGR`] types
+pub trait EasyWrite {
+    /// Writes a set of codes to the builder
+    fn sgr(&self, builder: &mut SGRBuilder);
+}
+
+impl EasyWrite for SGRString {
+    /// Writes a set of codes to the builder
+    ///
+    /// Uses [`SGRString::place_all`]
+    fn sgr(&self, builder: &mut SGRBuilder) {
+        self.place_all(builder);
+    }
+}
+
+impl<D: DiscreteSGR> EasyWrite for D {
+    /// Writes a set of codes to the builder
+    ///
+    /// Uses [`DiscreteSGR::write`]
+    fn sgr(&self, builder: &mut SGRBuilder) {
+        self.write(builder);
+    }
+}
+impl EasyWrite for dyn DynSGR + '_ {
+    /// Writes a set of codes to the builder
+    ///
+    /// Uses [`DynSGR::write_dyn`]
+    fn sgr(&self, builder: &mut SGRBuilder) {
+        self.write_dyn(builder);
     }
 }
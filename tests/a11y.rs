@@ -0,0 +1,75 @@
+#![cfg(all(feature = "a11y", not(feature = "macro-only")))]
+use easy_sgr::{
+    a11y::{colorblind_safe, simulate_rgb, ColorVisionDeficiency::*},
+    Color::*,
+};
+
+#[test]
+fn reference_values() {
+    // Machado et al. (2009) at full severity, through the sRGB transfer function
+    for (deficiency, expected) in [
+        (
+            Protanopia,
+            [(109, 95, 0), (255, 229, 0), (0, 89, 255), (185, 162, 0)],
+        ),
+        (
+            Deuteranopia,
+            [(163, 144, 0), (239, 214, 58), (0, 61, 251), (202, 180, 17)],
+        ),
+        (
+            Tritanopia,
+            [(255, 0, 15), (0, 247, 217), (0, 107, 150), (251, 140, 135)],
+        ),
+    ] {
+        for (rgb, expected) in [(255, 0, 0), (0, 255, 0), (0, 0, 255), (230, 159, 0)]
+            .into_iter()
+            .zip(expected)
+        {
+            let simulated = simulate_rgb(rgb, deficiency);
+            for (channel, expected) in [
+                (simulated.0, expected.0),
+                (simulated.1, expected.1),
+                (simulated.2, expected.2),
+            ] {
+                assert!(
+                    channel.abs_diff(expected) <= 1,
+                    "{deficiency:?} {rgb:?}: {simulated:?} != {expected:?}"
+                );
+            }
+        }
+        // grays are unchanged
+        for level in [0, 128, 255] {
+            let gray = (level, level, level);
+            assert_eq!(simulate_rgb(gray, deficiency), gray, "{deficiency:?}");
+        }
+    }
+}
+#[test]
+fn simulated_colors() {
+    assert_eq!(RgbFg(255, 0, 0).simulate(Protanopia), RgbFg(109, 95, 0));
+    // named & byte colors go through the reference palette
+    assert_eq!(RedBg.simulate(Tritanopia), RgbBg(142, 0, 3));
+    assert_eq!(ByteFg(196).simulate(Deuteranopia), RgbFg(163, 144, 0));
+    assert_eq!(DefaultFg.simulate(Deuteranopia), DefaultFg);
+    assert_eq!(DefaultBg.simulate(Tritanopia), DefaultBg);
+}
+#[test]
+fn safe_palette() {
+    let distance = |a: (u8, u8, u8), b: (u8, u8, u8)| {
+        [(a.0, b.0), (a.1, b.1), (a.2, b.2)]
+            .into_iter()
+            .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+            .sum::<u32>()
+    };
+    for deficiency in [Protanopia, Deuteranopia, Tritanopia] {
+        let simulated = colorblind_safe().map(|color| color.simulate(deficiency).rgb().unwrap());
+        for (i, a) in simulated.iter().enumerate() {
+            for b in &simulated[i + 1..] {
+                assert!(
+                    distance(*a, *b) >= 25 * 25,
+                    "{deficiency:?}: {a:?} & {b:?} are alike"
+                );
+            }
+        }
+    }
+}
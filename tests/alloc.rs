@@ -0,0 +1,133 @@
+//! Counts allocations made while building SGR sequences
+//!
+//! Kept in its own test binary, as it replaces the global allocator
+#![cfg(not(feature = "macro-only"))]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use std::fmt::Write;
+
+use easy_sgr::{
+    BorrowSGR, CleanKind, Color::*, DiscreteSGR, EasySGR, SGRBuilder, SGRStr, SGRString, Style::*,
+};
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn inline_codes() {
+    assert_eq!(
+        0,
+        allocations(|| {
+            let mut builder = SGRBuilder::default();
+            RgbFg(1, 2, 3).write(&mut builder);
+            Bold.write(&mut builder);
+            assert_eq!(builder.codes().len(), 6);
+        })
+    );
+}
+
+#[test]
+fn spilled_codes() {
+    assert_eq!(
+        1,
+        allocations(|| {
+            let mut builder = SGRBuilder::default();
+            RgbFg(1, 2, 3).write(&mut builder);
+            RgbBg(1, 2, 3).write(&mut builder);
+            builder.clear();
+            RgbFg(1, 2, 3).write(&mut builder);
+            RgbBg(1, 2, 3).write(&mut builder);
+        })
+    );
+}
+#[test]
+fn borrowed_text() {
+    let line = String::from("key: value");
+    let mut out = String::with_capacity(64);
+    assert_eq!(
+        0,
+        allocations(|| {
+            let key = SGRStr::new(&line[..3])
+                .color(RgbFg(1, 2, 3))
+                .style(Bold)
+                .apply((Italic, BlueBg))
+                .clean(CleanKind::Reverse);
+            write!(out, "{key}").unwrap();
+        })
+    );
+    let owned = "key"
+        .color(RgbFg(1, 2, 3))
+        .style(Bold)
+        .apply((Italic, BlueBg))
+        .clean(CleanKind::Reverse);
+    assert_eq!(out, owned.to_string());
+    let borrowed = SGRStr::from(&owned);
+    assert_eq!(borrowed.text, "key");
+    assert_eq!(borrowed.to_string(), out);
+    assert_eq!(borrowed.clone().into_owned().to_string(), out);
+    assert_eq!(SGRStr::from(&SGRString::from(borrowed.clone())), borrowed);
+}
+#[test]
+fn borrowed_str() {
+    let mut out = String::with_capacity(64);
+    assert_eq!(
+        0,
+        allocations(|| {
+            let styled = "x".borrow_sgr().color(RedFg).style(Bold);
+            write!(out, "{styled}").unwrap();
+        })
+    );
+    assert_eq!(out, "x".color(RedFg).style(Bold).to_string());
+    let line = String::from("key: value");
+    assert_eq!(line[..3].borrow_sgr(), SGRStr::new("key"));
+}
+#[cfg(feature = "control")]
+#[test]
+fn frame_reuse() {
+    use easy_sgr::FrameBuffer;
+
+    let mut frame = FrameBuffer::new().retained(true);
+    let mut out = Vec::with_capacity(1024);
+    let mut draw = |frame: &mut FrameBuffer, i: usize| {
+        write!(frame, "status\n{}", ["idle", "busy"][i % 2]).unwrap();
+        frame.present(&mut out).unwrap();
+    };
+    // the buffers grow to fit over the first frames
+    for i in 0..3 {
+        draw(&mut frame, i);
+    }
+    assert_eq!(0, allocations(|| (3..20).for_each(|i| draw(&mut frame, i))));
+}
+#[test]
+fn rendered_once() {
+    let sgr = "text"
+        .color(RgbFg(100, 20, 3))
+        .style(Bold)
+        .clean(CleanKind::Reverse);
+    assert_eq!(1, allocations(|| drop(sgr.render_to_string())));
+}
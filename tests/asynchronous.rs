@@ -0,0 +1,67 @@
+#![cfg(all(feature = "tokio", not(feature = "macro-only")))]
+use std::error::Error;
+
+use easy_sgr::{
+    AsyncIoWriter, CleanKind, Color::*, DiscreteSGR, EasySGR, SGRBuilder, SGRWriter, Style::*,
+};
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn matches_sync() -> Result<(), Box<dyn Error>> {
+    let sgr = "text"
+        .style(Bold)
+        .color(RgbFg(255, 0, 128))
+        .color(ByteBg(200))
+        .clean(CleanKind::Reverse);
+    let (client, mut server) = tokio::io::duplex(64);
+
+    let write = async {
+        let mut writer = AsyncIoWriter(client);
+        sgr.write_async(&mut writer).await?;
+        writer.inline_sgr(&Italic).await?;
+        writer.sgr(&RedFg.style(Underline)).await?;
+        writer.write_inner("end").await
+    };
+    let mut read = Vec::new();
+    let (written, _) = tokio::join!(write, server.read_to_end(&mut read));
+    written?;
+
+    let mut sync = SGRWriter::from(Vec::new());
+    sync.write_inner(&sgr.to_string())?;
+    sync.inline_sgr(&Italic)?;
+    sync.sgr(&RedFg.style(Underline))?;
+    sync.write_inner("end")?;
+
+    assert_eq!(read, sync.internal());
+    Ok(())
+}
+
+#[tokio::test]
+async fn builder() -> Result<(), Box<dyn Error>> {
+    let mut builder = SGRBuilder::default();
+    RgbBg(10, 200, 255).write(&mut builder);
+    Bold.write(&mut builder);
+
+    let mut writer = AsyncIoWriter(Vec::new());
+    writer.write_builder(&builder).await?;
+    writer.write_partial(&builder).await?;
+    writer.write_builder(&SGRBuilder::default()).await?;
+    writer.sgr(&easy_sgr::SGRString::default()).await?;
+    writer.write_reset().await?;
+
+    assert_eq!(
+        writer.internal(),
+        b"\x1b[48;2;10;200;255;1m48;2;10;200;255;1\x1b[0m"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn wide_params() -> Result<(), Box<dyn Error>> {
+    let mut writer = AsyncIoWriter(Vec::new());
+    for param in [300, 4000, u16::MAX] {
+        writer.write_param(param).await?;
+    }
+    assert_eq!(writer.internal(), b"300400065535");
+    Ok(())
+}
@@ -0,0 +1,125 @@
+#![cfg(all(feature = "binary", not(feature = "macro-only")))]
+use easy_sgr::{
+    binary::{from_bytes, to_bytes, VERSION},
+    CleanKind,
+    Color::*,
+    ColorKind, EasySGR, SGRString,
+    Style::*,
+    StyleKind,
+};
+
+/// Compares every field, as [`SGRString`] does not implement `PartialEq`
+fn assert_same(left: &SGRString, right: &SGRString) {
+    assert_eq!(format!("{left:?}"), format!("{right:?}"));
+}
+#[test]
+fn round_trip() {
+    let colors = [
+        ColorKind::None,
+        ColorKind::Black,
+        ColorKind::Red,
+        ColorKind::Green,
+        ColorKind::Yellow,
+        ColorKind::Blue,
+        ColorKind::Magenta,
+        ColorKind::Cyan,
+        ColorKind::White,
+        ColorKind::Byte(208),
+        ColorKind::Rgb(1, 2, 3),
+        ColorKind::Default,
+    ];
+    let mut fragments = Vec::new();
+    for (i, color) in colors.iter().enumerate() {
+        let kind = [StyleKind::None, StyleKind::Place, StyleKind::Clean][i % 3].clone();
+        fragments.push(SGRString {
+            text: format!("fragment {i} ✓"),
+            clean: [CleanKind::None, CleanKind::Reset, CleanKind::Reverse][i % 3].clone(),
+            custom_places: vec![73; i % 2],
+            custom_cleans: (0..u8::try_from(i).unwrap()).collect(),
+            foreground: color.clone(),
+            background: colors[colors.len() - 1 - i].clone(),
+            reset: i % 2 == 0,
+            bold: kind.clone(),
+            dim: kind.clone(),
+            italic: kind.clone(),
+            underline: kind.clone(),
+            blinking: kind.clone(),
+            rapid_blinking: kind.clone(),
+            inverse: kind.clone(),
+            hidden: kind.clone(),
+            strikethrough: kind.clone(),
+            framed: kind.clone(),
+            encircled: kind.clone(),
+            overline: kind,
+        });
+    }
+    // fragment boundaries are kept
+    let decoded = from_bytes(&to_bytes(&fragments)).unwrap();
+    assert_eq!(decoded.len(), fragments.len());
+    for (decoded, fragment) in decoded.iter().zip(&fragments) {
+        assert_same(decoded, fragment);
+        assert_same(
+            &SGRString::from_bytes(&fragment.to_bytes()).unwrap(),
+            fragment,
+        );
+    }
+    // long text needs more than one length byte
+    let long = "x".repeat(300).style(Bold);
+    assert_same(&SGRString::from_bytes(&long.to_bytes()).unwrap(), &long);
+    assert!(from_bytes(&to_bytes(&[])).unwrap().is_empty());
+}
+#[test]
+fn invalid_input() {
+    let bytes = "styled".color(RgbFg(1, 2, 3)).style(Italic).to_bytes();
+    for len in 0..bytes.len() {
+        let result = SGRString::from_bytes(&bytes[..len]);
+        assert!(result.is_err(), "{len}: {result:?}");
+    }
+    let mut unknown = bytes.clone();
+    unknown[0] = VERSION + 1;
+    assert_eq!(
+        from_bytes(&unknown).unwrap_err().to_string(),
+        format!(
+            "Unsupported binary version `{}`, expected `{VERSION}`",
+            VERSION + 1
+        )
+    );
+    assert_eq!(
+        from_bytes(&[VERSION, 99]).unwrap_err().to_string(),
+        "Unknown binary attribute tag `99`"
+    );
+    assert_eq!(
+        from_bytes(&[VERSION, 0, 1, 0xff]).unwrap_err().to_string(),
+        "Invalid UTF-8 within binary text"
+    );
+    let two = to_bytes(&["a".into(), "b".into()]);
+    assert_eq!(
+        SGRString::from_bytes(&two).unwrap_err().to_string(),
+        "Expected a single fragment, found 2"
+    );
+}
+#[test]
+fn smaller_than_json() {
+    let line = [
+        "ERROR".style(Bold).color(RedFg),
+        " ".into(),
+        "src/main.rs:12".color(ByteFg(244)),
+        ": file not found".into(),
+    ];
+    let json = r#"[{"text":"ERROR","bold":"place","foreground":"red"},{"text":" "},{"text":"src/main.rs:12","foreground":{"byte":244}},{"text":": file not found"}]"#;
+    let bytes = to_bytes(&line);
+    assert!(
+        bytes.len() < json.len(),
+        "{} >= {}",
+        bytes.len(),
+        json.len()
+    );
+    // smaller than the rendered form too
+    let rendered: String = line.iter().map(ToString::to_string).collect();
+    assert!(
+        bytes.len() < rendered.len(),
+        "{} >= {}",
+        bytes.len(),
+        rendered.len()
+    );
+}
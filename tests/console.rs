@@ -0,0 +1,76 @@
+#![cfg(all(feature = "wasm-console", not(feature = "macro-only")))]
+use easy_sgr::{console::ConsoleWriter, Color::*, EasySGR, SGRString, SGRWriter, Style::*};
+
+fn args(format: &str, styles: &[&str]) -> (String, Vec<String>) {
+    (
+        format.to_string(),
+        styles.iter().map(ToString::to_string).collect(),
+    )
+}
+#[test]
+fn to_console_args() {
+    assert_eq!(SGRString::default().to_console_args(), args("", &[]));
+    assert_eq!("plain".to_sgr().to_console_args(), args("plain", &[]));
+    assert_eq!(
+        "50%"
+            .color(RgbFg(1, 2, 3))
+            .style(Underline)
+            .to_console_args(),
+        args(
+            "%c50%%",
+            &["color: rgb(1, 2, 3); text-decoration: underline"]
+        )
+    );
+    assert_eq!(
+        "x".color(ByteBg(208))
+            .style(Inverse)
+            .style(Strikethrough)
+            .to_console_args(),
+        args(
+            "%cx",
+            &["color: rgb(255, 135, 0); text-decoration: line-through"]
+        )
+    );
+}
+#[test]
+fn nested_styles() {
+    // styled text within styled text, as written by a style group
+    let inner = "\x1b[31mred\x1b[39m";
+    let outer = format!("a{inner}b").style(Bold);
+    assert_eq!(
+        outer.to_console_args(),
+        args(
+            "%ca%cred%cb",
+            &[
+                "font-weight: bold",
+                "color: rgb(128, 0, 0); font-weight: bold",
+                "font-weight: bold",
+            ]
+        )
+    );
+}
+#[test]
+fn resets() {
+    let mut writer = SGRWriter::from(ConsoleWriter::default());
+    writer.place_sgr(&"".style(Italic)).unwrap();
+    writer.write_inner("a").unwrap();
+    writer.write_inner("b\x1b[0mc\x1b[mc").unwrap();
+    // state changing without text between is only placed once
+    writer.write_inner("\x1b[1m\x1b[22;2md").unwrap();
+    writer.place_sgr(&"".style(Reset).style(Hidden)).unwrap();
+    writer.write_inner("e").unwrap();
+    writer.write_reset().unwrap();
+    assert_eq!(writer.writer.format(), "%cab%ccc%cd%ce");
+    assert_eq!(
+        writer.internal(),
+        args(
+            "%cab%ccc%cd%ce",
+            &[
+                "font-style: italic",
+                "",
+                "opacity: 0.5",
+                "color: transparent"
+            ]
+        )
+    );
+}
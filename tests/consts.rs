@@ -0,0 +1,50 @@
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{
+    consts::{self, codes},
+    Color::{self, *},
+    DiscreteSGR,
+    Style::{self, *},
+};
+
+/// Asserts the constants equal the rendered [`DiscreteSGR`]
+fn assert_renders(sequence: &str, code: u8, sgr: impl DiscreteSGR) {
+    assert_eq!(*sgr.codes(), [code]);
+    assert_eq!(sequence, sgr.to_sgr().to_string());
+    assert_eq!(sequence, format!("\x1b[{code}m"));
+}
+#[test]
+fn matches_enums() {
+    let styles: [(&str, u8, Style); 5] = [
+        (consts::RESET, codes::RESET, Reset),
+        (consts::BOLD, codes::BOLD, Bold),
+        (consts::DIM, codes::DIM, Dim),
+        (consts::ITALIC, codes::ITALIC, Italic),
+        (consts::UNDERLINE, codes::UNDERLINE, Underline),
+    ];
+    let colors: [(&str, u8, Color); 18] = [
+        (consts::BLACK_FG, codes::BLACK_FG, BlackFg),
+        (consts::RED_FG, codes::RED_FG, RedFg),
+        (consts::GREEN_FG, codes::GREEN_FG, GreenFg),
+        (consts::YELLOW_FG, codes::YELLOW_FG, YellowFg),
+        (consts::BLUE_FG, codes::BLUE_FG, BlueFg),
+        (consts::MAGENTA_FG, codes::MAGENTA_FG, MagentaFg),
+        (consts::CYAN_FG, codes::CYAN_FG, CyanFg),
+        (consts::WHITE_FG, codes::WHITE_FG, WhiteFg),
+        (consts::DEFAULT_FG, codes::DEFAULT_FG, DefaultFg),
+        (consts::BLACK_BG, codes::BLACK_BG, BlackBg),
+        (consts::RED_BG, codes::RED_BG, RedBg),
+        (consts::GREEN_BG, codes::GREEN_BG, GreenBg),
+        (consts::YELLOW_BG, codes::YELLOW_BG, YellowBg),
+        (consts::BLUE_BG, codes::BLUE_BG, BlueBg),
+        (consts::MAGENTA_BG, codes::MAGENTA_BG, MagentaBg),
+        (consts::CYAN_BG, codes::CYAN_BG, CyanBg),
+        (consts::WHITE_BG, codes::WHITE_BG, WhiteBg),
+        (consts::DEFAULT_BG, codes::DEFAULT_BG, DefaultBg),
+    ];
+    for (sequence, code, style) in styles {
+        assert_renders(sequence, code, style);
+    }
+    for (sequence, code, color) in colors {
+        assert_renders(sequence, code, color);
+    }
+}
@@ -0,0 +1,42 @@
+#![cfg(all(feature = "control", not(feature = "macro-only")))]
+use std::error::Error;
+
+use easy_sgr::{Control::*, EraseMode, SGRWriter, Style};
+
+#[test]
+fn sequences() {
+    for (correct, control) in [
+        ("\x1b[1A", CursorUp(1)),
+        ("\x1b[2B", CursorDown(2)),
+        ("\x1b[3C", CursorForward(3)),
+        ("\x1b[4D", CursorBack(4)),
+        ("\x1b[1G", CursorToColumn(1)),
+        ("\x1b[0K", EraseLine(EraseMode::ToEnd)),
+        ("\x1b[1K", EraseLine(EraseMode::ToStart)),
+        ("\x1b[2K", EraseLine(EraseMode::All)),
+        ("\x1b[0J", EraseScreen(EraseMode::default())),
+        ("\x1b[2J", EraseScreen(EraseMode::All)),
+        ("\x1b[s", SaveCursor),
+        ("\x1b[u", RestoreCursor),
+    ] {
+        assert_eq!(correct, control.to_string());
+    }
+}
+
+#[test]
+fn wide_params() {
+    assert_eq!("\x1b[256A", CursorUp(256).to_string());
+    assert_eq!("\x1b[65535G", CursorToColumn(u16::MAX).to_string());
+}
+
+#[test]
+fn write_to() -> Result<(), Box<dyn Error>> {
+    let mut w = SGRWriter::from(Vec::new());
+    CursorToColumn(1).write_to(&mut w)?;
+    EraseLine(EraseMode::All).write_to(&mut w)?;
+    w.sgr(&Style::Bold)?;
+    w.write_inner("tick")?;
+
+    assert_eq!(b"\x1b[1G\x1b[2K\x1b[1mtick", &*w.internal());
+    Ok(())
+}
@@ -1,3 +1,4 @@
+#![cfg(not(feature = "macro-only"))]
 use easy_sgr::Seq;
 
 #[test]
@@ -6,6 +7,285 @@ fn seq() {
     assert_eq!("m", Seq::End.to_string());
 }
 
+#[test]
+fn style_undo() {
+    use easy_sgr::Style::{self, *};
+    let undo = |style: &Style| match style {
+        // no wildcard, new variants must be added here
+        Reset => Reset,
+        Bold | NotBold => NotBold,
+        Dim | NotDim => NotDim,
+        Italic | NotItalic => NotItalic,
+        Underline | NotUnderline => NotUnderline,
+        Blinking | RapidBlinking | NotBlinking => NotBlinking,
+        Inverse | NotInverse => NotInverse,
+        Hidden | NotHidden => NotHidden,
+        Strikethrough | NotStrikethrough => NotStrikethrough,
+        Framed | Encircled | NotFramedEncircled => NotFramedEncircled,
+        Overline | NotOverline => NotOverline,
+    };
+    for style in [
+        Reset,
+        Bold,
+        Dim,
+        Italic,
+        Underline,
+        Blinking,
+        RapidBlinking,
+        Inverse,
+        Hidden,
+        Strikethrough,
+        Framed,
+        Encircled,
+        Overline,
+        NotBold,
+        NotDim,
+        NotItalic,
+        NotUnderline,
+        NotBlinking,
+        NotInverse,
+        NotHidden,
+        NotStrikethrough,
+        NotFramedEncircled,
+        NotOverline,
+    ] {
+        assert_eq!(undo(&style), style.undo(), "{style:?}");
+        assert_eq!(style.undo(), style.undo().undo(), "{style:?}");
+    }
+}
+
+#[test]
+fn color_undo() {
+    use easy_sgr::Color::{self, *};
+    let is_fg = |color: &Color| match color {
+        // no wildcard, new variants must be added here
+        BlackFg | RedFg | GreenFg | YellowFg | BlueFg | MagentaFg | CyanFg | WhiteFg
+        | ByteFg(_) | RgbFg(..) | DefaultFg => true,
+        BrightBlackFg | BrightRedFg | BrightGreenFg | BrightYellowFg | BrightBlueFg
+        | BrightMagentaFg | BrightCyanFg | BrightWhiteFg => true,
+        BlackBg | RedBg | GreenBg | YellowBg | BlueBg | MagentaBg | CyanBg | WhiteBg
+        | ByteBg(_) | RgbBg(..) | DefaultBg => false,
+        BrightBlackBg | BrightRedBg | BrightGreenBg | BrightYellowBg | BrightBlueBg
+        | BrightMagentaBg | BrightCyanBg | BrightWhiteBg => false,
+    };
+    for color in [
+        BlackFg,
+        RedFg,
+        GreenFg,
+        YellowFg,
+        BlueFg,
+        MagentaFg,
+        CyanFg,
+        WhiteFg,
+        ByteFg(208),
+        RgbFg(1, 2, 3),
+        DefaultFg,
+        BlackBg,
+        RedBg,
+        GreenBg,
+        YellowBg,
+        BlueBg,
+        MagentaBg,
+        CyanBg,
+        WhiteBg,
+        ByteBg(208),
+        RgbBg(1, 2, 3),
+        DefaultBg,
+        BrightBlackFg,
+        BrightRedFg,
+        BrightGreenFg,
+        BrightYellowFg,
+        BrightBlueFg,
+        BrightMagentaFg,
+        BrightCyanFg,
+        BrightWhiteFg,
+        BrightBlackBg,
+        BrightRedBg,
+        BrightGreenBg,
+        BrightYellowBg,
+        BrightBlueBg,
+        BrightMagentaBg,
+        BrightCyanBg,
+        BrightWhiteBg,
+    ] {
+        let expected = if is_fg(&color) { DefaultFg } else { DefaultBg };
+        assert_eq!(expected, color.undo(), "{color:?}");
+    }
+}
+
+#[test]
+fn codes() {
+    use easy_sgr::{Color::*, DiscreteSGR, SGRBuilder, Style::*};
+    fn written(sgr: &impl DiscreteSGR) -> Vec<u8> {
+        let mut builder = SGRBuilder::default();
+        sgr.write(&mut builder);
+        builder.codes().to_vec()
+    }
+    for style in [
+        Reset,
+        Bold,
+        Dim,
+        Italic,
+        Underline,
+        Blinking,
+        RapidBlinking,
+        Inverse,
+        Hidden,
+        Strikethrough,
+        Framed,
+        Encircled,
+        Overline,
+        NotBold,
+        NotDim,
+        NotItalic,
+        NotUnderline,
+        NotBlinking,
+        NotInverse,
+        NotHidden,
+        NotStrikethrough,
+        NotFramedEncircled,
+        NotOverline,
+    ] {
+        assert_eq!(written(&style), &*style.codes(), "{style:?}");
+        assert_eq!(1, style.codes().len(), "{style:?}");
+    }
+    for color in [
+        BlackFg,
+        RedFg,
+        GreenFg,
+        YellowFg,
+        BlueFg,
+        MagentaFg,
+        CyanFg,
+        WhiteFg,
+        ByteFg(208),
+        RgbFg(1, 2, 3),
+        DefaultFg,
+        BlackBg,
+        RedBg,
+        GreenBg,
+        YellowBg,
+        BlueBg,
+        MagentaBg,
+        CyanBg,
+        WhiteBg,
+        ByteBg(208),
+        RgbBg(1, 2, 3),
+        DefaultBg,
+        BrightRedFg,
+        BrightRedBg,
+    ] {
+        assert_eq!(written(&color), &*color.codes(), "{color:?}");
+        assert_eq!(
+            written(&color),
+            color.codes().into_iter().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn bright_colors() {
+    use easy_sgr::{Color::*, ColorKind, DiscreteSGR, EasySGR, StyleSet};
+    assert_eq!(BrightRedFg.sgr_params(), [91]);
+    assert_eq!(BrightWhiteBg.sgr_params(), [107]);
+    assert_eq!(BrightRedFg.rgb(), Some((255, 0, 0)));
+    assert_eq!(ColorKind::from(BrightCyanBg), ColorKind::BrightCyan);
+    let sgr = "text".color(BrightGreenFg).color(BrightBlackBg);
+    assert_eq!(sgr.foreground, ColorKind::BrightGreen);
+    assert_eq!(sgr.background, ColorKind::BrightBlack);
+    let mut state = StyleSet::default();
+    state.apply_codes(&[92, 100]);
+    assert_eq!(state, StyleSet::from(&sgr));
+    #[cfg(not(feature = "partial"))]
+    assert_eq!(sgr.to_string(), "\x1b[92;100mtext");
+}
+#[test]
+fn partial_resets() {
+    use easy_sgr::{Color, DiscreteSGR, Style};
+    assert_eq!(Color::reset_all().sgr_params(), [39, 49]);
+    assert_eq!(
+        Style::reset_effects().sgr_params(),
+        [22, 23, 24, 25, 27, 28, 29]
+    );
+    #[cfg(not(feature = "partial"))]
+    {
+        assert_eq!(Color::reset_all().to_string(), "\x1b[39;49m");
+        assert_eq!(
+            Style::reset_effects().to_string(),
+            "\x1b[22;23;24;25;27;28;29m"
+        );
+    }
+}
+
+#[test]
+fn display_matches_standard_display() {
+    use easy_sgr::{Color::*, DiscreteSGR, Style::*};
+    fn standard(sgr: &impl DiscreteSGR) -> String {
+        let mut s = String::new();
+        sgr.standard_display(&mut s).unwrap();
+        s
+    }
+    for style in [
+        Reset,
+        Bold,
+        Dim,
+        Italic,
+        Underline,
+        Blinking,
+        RapidBlinking,
+        Inverse,
+        Hidden,
+        Strikethrough,
+        Framed,
+        Encircled,
+        Overline,
+        NotBold,
+        NotDim,
+        NotItalic,
+        NotUnderline,
+        NotBlinking,
+        NotInverse,
+        NotHidden,
+        NotStrikethrough,
+        NotFramedEncircled,
+        NotOverline,
+    ] {
+        assert_eq!(standard(&style), style.to_string());
+    }
+    for color in [
+        BlackFg,
+        RedFg,
+        GreenFg,
+        YellowFg,
+        BlueFg,
+        MagentaFg,
+        CyanFg,
+        WhiteFg,
+        ByteFg(0),
+        ByteFg(255),
+        RgbFg(0, 10, 255),
+        DefaultFg,
+        BlackBg,
+        RedBg,
+        GreenBg,
+        YellowBg,
+        BlueBg,
+        MagentaBg,
+        CyanBg,
+        WhiteBg,
+        ByteBg(0),
+        ByteBg(255),
+        RgbBg(0, 10, 255),
+        DefaultBg,
+        BrightBlackFg,
+        BrightWhiteFg,
+        BrightBlackBg,
+        BrightWhiteBg,
+    ] {
+        assert_eq!(standard(&color), color.to_string());
+    }
+}
+
 #[cfg(not(feature = "partial"))]
 mod normal {
     use easy_sgr::{Color::*, Style::*};
@@ -18,9 +298,13 @@ mod normal {
             ("\x1b[3m", Italic),
             ("\x1b[4m", Underline),
             ("\x1b[5m", Blinking),
+            ("\x1b[6m", RapidBlinking),
             ("\x1b[7m", Inverse),
             ("\x1b[8m", Hidden),
             ("\x1b[9m", Strikethrough),
+            ("\x1b[51m", Framed),
+            ("\x1b[52m", Encircled),
+            ("\x1b[53m", Overline),
             ("\x1b[22m", NotBold),
             ("\x1b[22m", NotDim),
             ("\x1b[23m", NotItalic),
@@ -29,6 +313,8 @@ mod normal {
             ("\x1b[27m", NotInverse),
             ("\x1b[28m", NotHidden),
             ("\x1b[29m", NotStrikethrough),
+            ("\x1b[54m", NotFramedEncircled),
+            ("\x1b[55m", NotOverline),
         ] {
             assert_eq!(correct, format!("{style}"))
         }
@@ -104,9 +390,13 @@ mod from_str {
             ("Italic", Italic),
             ("Underline", Underline),
             ("Blinking", Blinking),
+            ("RapidBlinking", RapidBlinking),
             ("Inverse", Inverse),
             ("Hidden", Hidden),
             ("Strikethrough", Strikethrough),
+            ("Framed", Framed),
+            ("Encircled", Encircled),
+            ("Overline", Overline),
             ("NotBold", NotBold),
             ("NotDim", NotDim),
             ("NotItalic", NotItalic),
@@ -115,6 +405,8 @@ mod from_str {
             ("NotInverse", NotInverse),
             ("NotHidden", NotHidden),
             ("NotStrikethrough", NotStrikethrough),
+            ("NotFramedEncircled", NotFramedEncircled),
+            ("NotOverline", NotOverline),
         ] {
             assert_eq!(Ok(style), src.parse())
         }
@@ -162,6 +454,27 @@ mod from_str {
             }
         }
     }
+    #[test]
+    fn errors() {
+        use easy_sgr::{discrete::from_str::ParseColorError, Color, Error, Seq, Style};
+
+        let err = Error::from("Nope".parse::<Seq>().unwrap_err());
+        assert!(matches!(err, Error::Parse(_)));
+        assert_eq!(
+            err.to_string(),
+            "Error parsing: Invalid Seq, expected `Esc` or `End`"
+        );
+        let err = Error::from("bold".parse::<Style>().unwrap_err());
+        assert!(err.to_string().contains("`Bold`"));
+
+        assert!(matches!(
+            "RgbFg(1,x,3)".parse::<Color>(),
+            Err(ParseColorError::ParseIntError(_))
+        ));
+        let err = "RgbFg(1,2)".parse::<Color>().unwrap_err();
+        assert_eq!(err, ParseColorError::Len(2));
+        assert!(Error::from(err).to_string().ends_with("Needed 3"));
+    }
 }
 
 #[cfg(feature = "partial")]
@@ -176,9 +489,13 @@ mod partial {
             ("3", Italic),
             ("4", Underline),
             ("5", Blinking),
+            ("6", RapidBlinking),
             ("7", Inverse),
             ("8", Hidden),
             ("9", Strikethrough),
+            ("51", Framed),
+            ("52", Encircled),
+            ("53", Overline),
             ("22", NotBold),
             ("22", NotDim),
             ("23", NotItalic),
@@ -187,6 +504,8 @@ mod partial {
             ("27", NotInverse),
             ("28", NotHidden),
             ("29", NotStrikethrough),
+            ("54", NotFramedEncircled),
+            ("55", NotOverline),
         ] {
             assert_eq!(correct, format!("{style}"))
         }
@@ -235,3 +554,210 @@ mod partial {
         }
     }
 }
+
+#[test]
+fn fonts() {
+    use easy_sgr::{DiscreteSGR, EasySGR, Font, InvalidFont};
+    for index in 0..=9 {
+        let font = Font::new(index).unwrap();
+        let code = 10 + index;
+        assert_eq!(font.index(), index);
+        assert_eq!(&*font.codes(), [code]);
+        assert_eq!(font.undo(), Font::PRIMARY);
+
+        let mut standard = String::new();
+        font.standard_display(&mut standard).unwrap();
+        assert_eq!(standard, font.to_string());
+        #[cfg(not(feature = "partial"))]
+        assert_eq!(font.to_string(), format!("\x1b[{code}m"));
+        #[cfg(feature = "partial")]
+        assert_eq!(font.to_string(), code.to_string());
+
+        assert_eq!(font.text("a").to_string(), format!("\x1b[{code}ma"));
+    }
+    assert_eq!(Font::default(), Font::PRIMARY);
+    assert_eq!(&*Font::PRIMARY.codes(), [10]);
+    for index in [10, 19, 255] {
+        assert_eq!(Font::new(index), Err(InvalidFont(index)));
+    }
+    assert_eq!(
+        InvalidFont(10).to_string(),
+        "Invalid font 10, expected an index from 0 to 9"
+    );
+}
+#[test]
+fn combined() {
+    use easy_sgr::{Color::*, Combined, DiscreteSGR, EasySGR, Font, Style::*};
+    let combined = Bold.and(RedFg);
+    assert_eq!(combined, Combined(Bold, RedFg));
+    assert_eq!(&*combined.codes(), [1, 31]);
+    // codes past those stored inline are kept, read through the trait alone
+    fn codes<T: DiscreteSGR>(sgr: &T) -> Vec<u8> {
+        sgr.codes().into_iter().collect()
+    }
+    let long = Bold.and(RgbFg(1, 2, 3)).and(ByteBg(208));
+    assert_eq!(codes(&long), [1, 38, 2, 1, 2, 3, 48, 5, 208]);
+    assert_eq!(*long.codes(), long.sgr_params());
+    #[cfg(not(feature = "partial"))]
+    {
+        let naive = format!("{Bold}{RedFg}");
+        assert_eq!(naive, "\x1b[1m\x1b[31m");
+        assert_eq!(combined.to_string(), "\x1b[1;31m");
+        assert!(combined.to_string().len() < naive.len());
+    }
+    #[cfg(feature = "partial")]
+    assert_eq!(combined.to_string(), "1;31");
+
+    // nesting writes every code, past the capacity of Codes
+    let nested = Bold.and(Italic).and(RgbFg(1, 2, 3)).and(ByteBg(4));
+    assert_eq!(nested.sgr_params(), [1, 3, 38, 2, 1, 2, 3, 48, 5, 4]);
+    let mut standard = String::new();
+    nested.standard_display(&mut standard).unwrap();
+    assert_eq!(standard, nested.to_string());
+
+    // bytes written in a rendering loop
+    #[cfg(not(feature = "partial"))]
+    {
+        let (mut naive, mut merged) = (0, 0);
+        for i in 0..100u8 {
+            let (fg, bg) = (ByteFg(i), ByteBg(255 - i));
+            naive += format!("{fg}{bg}x").len();
+            merged += format!("{}x", fg.and(bg)).len();
+        }
+        // each merge drops `m\x1b[` for a `;`
+        assert_eq!(naive - merged, 100 * 2);
+    }
+
+    let sgr = Bold.and(Font::new(1).unwrap()).and(RedFg).text("a");
+    assert_eq!(sgr.to_string(), "\x1b[31;1;11ma");
+    assert_eq!(Reset.and(Dim).to_sgr().to_string(), "\x1b[0;2m");
+}
+#[test]
+fn dyn_sgrs() {
+    use easy_sgr::{Color::*, DynSGR, IoWriter, SGRWriter, Style::*};
+    let theme: Vec<Box<dyn DynSGR>> = vec![
+        Box::new(Bold),
+        Box::new(RedFg),
+        Box::new(Italic),
+        Box::new(RgbBg(1, 2, 3)),
+        Box::new(ByteFg(208)),
+    ];
+    let mut fmt = SGRWriter::from(String::new());
+    fmt.inline_sgr_all(&theme).unwrap();
+    for sgr in &theme {
+        fmt.sgr(&**sgr).unwrap();
+    }
+    fmt.partial_sgr(&*theme[1]).unwrap();
+    let fmt = fmt.internal();
+    assert_eq!(
+        fmt,
+        "\x1b[1;31;3;48;2;1;2;3;38;5;208m\
+        \x1b[1m\x1b[31m\x1b[3m\x1b[48;2;1;2;3m\x1b[38;5;208m31"
+    );
+
+    let mut io = SGRWriter::from(IoWriter(Vec::new()));
+    io.inline_sgr_all(&theme).unwrap();
+    for sgr in &theme {
+        io.sgr(&**sgr).unwrap();
+    }
+    io.partial_sgr(&*theme[1]).unwrap();
+    assert_eq!(io.internal(), fmt.as_bytes());
+
+    let displayed: String = theme.iter().map(ToString::to_string).collect();
+    let expected: String = [
+        Bold.to_string(),
+        RedFg.to_string(),
+        Italic.to_string(),
+        RgbBg(1, 2, 3).to_string(),
+        ByteFg(208).to_string(),
+    ]
+    .concat();
+    assert_eq!(displayed, expected);
+}
+#[test]
+fn params_display() {
+    use easy_sgr::{Color::*, Style::*};
+
+    let styles = [
+        ("0", Reset),
+        ("1", Bold),
+        ("2", Dim),
+        ("3", Italic),
+        ("4", Underline),
+        ("5", Blinking),
+        ("6", RapidBlinking),
+        ("7", Inverse),
+        ("8", Hidden),
+        ("9", Strikethrough),
+        ("51", Framed),
+        ("52", Encircled),
+        ("53", Overline),
+        ("22", NotBold),
+        ("22", NotDim),
+        ("23", NotItalic),
+        ("24", NotUnderline),
+        ("25", NotBlinking),
+        ("27", NotInverse),
+        ("28", NotHidden),
+        ("29", NotStrikethrough),
+        ("54", NotFramedEncircled),
+        ("55", NotOverline),
+    ];
+    let colors = [
+        ("30", BlackFg),
+        ("31", RedFg),
+        ("32", GreenFg),
+        ("33", YellowFg),
+        ("34", BlueFg),
+        ("35", MagentaFg),
+        ("36", CyanFg),
+        ("37", WhiteFg),
+        ("39", DefaultFg),
+        ("40", BlackBg),
+        ("41", RedBg),
+        ("42", GreenBg),
+        ("43", YellowBg),
+        ("44", BlueBg),
+        ("45", MagentaBg),
+        ("46", CyanBg),
+        ("47", WhiteBg),
+        ("49", DefaultBg),
+        ("90", BrightBlackFg),
+        ("91", BrightRedFg),
+        ("92", BrightGreenFg),
+        ("93", BrightYellowFg),
+        ("94", BrightBlueFg),
+        ("95", BrightMagentaFg),
+        ("96", BrightCyanFg),
+        ("97", BrightWhiteFg),
+        ("100", BrightBlackBg),
+        ("101", BrightRedBg),
+        ("102", BrightGreenBg),
+        ("103", BrightYellowBg),
+        ("104", BrightBlueBg),
+        ("105", BrightMagentaBg),
+        ("106", BrightCyanBg),
+        ("107", BrightWhiteBg),
+        ("38;5;208", ByteFg(208)),
+        ("48;5;0", ByteBg(0)),
+        ("38;2;1;2;3", RgbFg(1, 2, 3)),
+        ("48;2;255;0;10", RgbBg(255, 0, 10)),
+    ];
+    let displayed = styles
+        .iter()
+        .map(|(params, style)| (*params, style.params_display().to_string()))
+        .chain(
+            colors
+                .iter()
+                .map(|(params, color)| (*params, color.params_display().to_string())),
+        );
+    for (params, displayed) in displayed {
+        assert_eq!(params, displayed);
+        assert!(!displayed.contains('\x1b'));
+    }
+    // formatting options are not applied to each parameter
+    assert_eq!(
+        format!("{:>4}", RgbFg(1, 2, 3).params_display()),
+        "38;2;1;2;3"
+    );
+}
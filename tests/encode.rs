@@ -0,0 +1,112 @@
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{
+    consts::codes,
+    encode::{encode_attrs_into, encode_into, encoded_len},
+    write_sgr, Color,
+    Color::*,
+    DiscreteSGR, IoWriter, SGRBuilder, Style,
+    Style::*,
+};
+
+#[test]
+fn exact_size() {
+    let sets: [&[u8]; 5] = [
+        &[codes::BOLD],
+        &[codes::BOLD, codes::RED_FG],
+        &[38, 2, 255, 100, 0],
+        &[0, 9, 10, 99, 100, 255],
+        &[],
+    ];
+    for codes in sets {
+        let mut buf = vec![0; encoded_len(codes)];
+        assert_eq!(encode_into(codes, &mut buf), Ok(buf.len()));
+    }
+    let mut buf = [0; 7];
+    assert_eq!(encode_into(&[1, 31], &mut buf), Ok(7));
+    assert_eq!(&buf, b"\x1b[1;31m");
+
+    const BOLD_RED: [u8; 7] = {
+        let mut buf = [0; 7];
+        assert!(encode_into(&[codes::BOLD, codes::RED_FG], &mut buf).is_ok());
+        buf
+    };
+    assert_eq!(BOLD_RED, buf);
+}
+#[test]
+fn too_small() {
+    let mut buf = [b'x'; 6];
+    let err = encode_into(&[1, 31], &mut buf).unwrap_err();
+    assert_eq!(err.needed(), 7);
+    assert_eq!(buf, [b'x'; 6]);
+    assert_eq!(err.to_string(), "Buffer too small, 7 bytes needed");
+
+    let err = encode_attrs_into(&[RgbFg(255, 255, 255)], &mut buf).unwrap_err();
+    assert_eq!(err.needed(), "\x1b[38;2;255;255;255m".len());
+    assert_eq!(buf, [b'x'; 6]);
+    assert_eq!(encode_into(&[], &mut []), Ok(0));
+}
+#[test]
+fn matches_writers() {
+    let styles = [
+        Bold,
+        NotBold,
+        Italic,
+        Reset,
+        RapidBlinking,
+        NotFramedEncircled,
+    ];
+    let colors = [
+        RedFg,
+        ByteBg(208),
+        RgbFg(0, 10, 200),
+        DefaultBg,
+        BrightWhiteFg,
+    ];
+    fn check<T: DiscreteSGR>(attrs: &[T]) {
+        let mut buf = [0; 64];
+        for attr in attrs {
+            let mut expected = Vec::new();
+            write_sgr(&mut expected, attr).unwrap();
+            let len = encode_attrs_into(std::slice::from_ref(attr), &mut buf).unwrap();
+            assert_eq!(&buf[..len], expected);
+            let len = encode_into(&attr.codes(), &mut buf).unwrap();
+            assert_eq!(&buf[..len], expected);
+        }
+        let mut builder = SGRBuilder::default();
+        attrs.iter().for_each(|attr| attr.write(&mut builder));
+        let mut expected = Vec::new();
+        builder.write_to(&mut IoWriter(&mut expected)).unwrap();
+        let len = encode_attrs_into(attrs, &mut buf).unwrap();
+        assert_eq!(&buf[..len], expected);
+    }
+    check::<Style>(&styles);
+    check::<Color>(&colors);
+}
+#[test]
+fn combined_attrs() {
+    let mut buf = [0; 64];
+    let combined = [
+        Bold.and(Italic).and(RgbFg(1, 2, 3)),
+        Reset.and(Underline).and(ByteBg(4)),
+    ];
+    let len = encode_attrs_into(&combined, &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"\x1b[1;3;38;2;1;2;3;0;4;48;5;4m");
+
+    let len = encode_attrs_into(&[Style::reset_effects()], &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"\x1b[22;23;24;25;27;28;29m");
+    let mut expected = Vec::new();
+    write_sgr(&mut expected, &Style::reset_effects()).unwrap();
+    assert_eq!(&buf[..len], expected);
+}
+#[test]
+fn long_sequences() {
+    // past the inline buffer of the writers
+    let codes: Vec<u8> = (100..=140).collect();
+    let mut builder = SGRBuilder::default();
+    builder.write_codes(&codes);
+    let mut expected = vec![0; encoded_len(&codes)];
+    encode_into(&codes, &mut expected).unwrap();
+    let mut written = Vec::new();
+    builder.write_to(&mut IoWriter(&mut written)).unwrap();
+    assert_eq!(written, expected);
+}
@@ -0,0 +1,361 @@
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{
+    debug_escapes, debug_escapes_with, ends_with_reset, invert_region, offset_map, open_state,
+    optimize, recolor_lines, CleanKind, Color::*, ColorKind, DisplayEscaped, EasySGR,
+    EscapeOptions, Merge, Style::*, StyleSet,
+};
+
+const ANNOTATE: EscapeOptions = EscapeOptions {
+    symbol: false,
+    annotate: true,
+};
+
+#[test]
+fn escaped() {
+    assert_eq!(debug_escapes("plain"), "plain");
+    assert_eq!(debug_escapes("\x1b[1mb\x1b[0m"), r"\x1b[1mb\x1b[0m");
+    let symbol = EscapeOptions {
+        symbol: true,
+        annotate: false,
+    };
+    assert_eq!(debug_escapes_with("\x1b[1mb", symbol), "␛[1mb");
+}
+#[test]
+fn annotated() {
+    for (input, expected) in [
+        ("\x1b[1;31m", "<bold red>"),
+        ("\x1b[0m\x1b[m", "<reset><reset>"),
+        (
+            "\x1b[22;23;24;25;27;28;29;54;55m",
+            "<!bold !italic !underline !blink !inverse !hide !strike !framed !overline>",
+        ),
+        ("\x1b[38;2;1;2;3;48;2;255;0;0m", "<1,2,3 on-255,0,0>"),
+        ("\x1b[38;5;208;48;5;16m", "<208 on-16>"),
+        ("\x1b[39;49;42m", "<default on-default on-green>"),
+        ("\x1b[11;64m", "<font1 ideogram-stress>"),
+        // unknown & incomplete codes
+        ("\x1b[73;58m", "<?73 ?58>"),
+        ("\x1b[91;107m", "<bright-red on-bright-white>"),
+        ("\x1b[38;5m", "<?5>"),
+        ("\x1b[38;7m", "<?38>"),
+        // not SGR
+        ("\x1b[2K", r"\x1b[2K"),
+        ("\x1b[1", r"\x1b[1"),
+        ("\x1b", r"\x1b"),
+        ("a\x1b[1mb\x1b[22mc", "a<bold>b<!bold>c"),
+    ] {
+        assert_eq!(debug_escapes_with(input, ANNOTATE), expected, "{input:?}");
+    }
+}
+#[test]
+fn display_escaped() {
+    let text = "text"
+        .color(RgbFg(1, 2, 3))
+        .style(Bold)
+        .clean(easy_sgr::CleanKind::Reset);
+    assert_eq!(DisplayEscaped(&text).to_string(), "<1,2,3 bold>text<reset>");
+    assert_eq!(
+        format!("{:?}", DisplayEscaped(&text)),
+        "\"<1,2,3 bold>text<reset>\""
+    );
+    assert_eq!(
+        DisplayEscaped(&text),
+        DisplayEscaped("\x1b[38;2;1;2;3;1mtext\x1b[0m")
+    );
+    assert_ne!(
+        DisplayEscaped(&text),
+        DisplayEscaped("\x1b[1;38;2;1;2;3mtext\x1b[0m")
+    );
+}
+#[test]
+fn open_states() {
+    // ending exactly in a reset
+    assert!(ends_with_reset("\x1b[1;31mtext\x1b[0m"));
+    assert!(open_state("\x1b[1;31mtext\x1b[0m").is_empty());
+    // a reset followed by a color is not clean
+    assert!(!ends_with_reset("text\x1b[0;31m"));
+    assert_eq!(open_state("text\x1b[0;31m"), StyleSet::from(RedFg));
+    // styles opened & individually closed
+    let closed = "\x1b[1mbold\x1b[3mitalic\x1b[22m\x1b[23m\x1b[44mblue\x1b[49m";
+    assert!(ends_with_reset(closed));
+    assert_eq!(
+        open_state("\x1b[1mbold\x1b[3mitalic\x1b[22m"),
+        StyleSet::from(Italic)
+    );
+    assert!(!ends_with_reset(&"styled".style(Underline).to_string()));
+    // no escapes at all
+    assert!(ends_with_reset("plain"));
+    assert!(ends_with_reset(""));
+    // sequences other than SGR are ignored
+    assert!(ends_with_reset("\x1b[2Kplain"));
+}
+#[test]
+fn sanitized() {
+    use easy_sgr::{sanitize, SanitizePolicy::*};
+    use std::borrow::Cow;
+    for (text, all, non_sgr, neutralized) in [
+        ("plain", "plain", "plain", "plain"),
+        (
+            "\x1b[1;31mred\x1b[0m",
+            "red",
+            "\x1b[1;31mred\x1b[0m",
+            "␛[1;31mred␛[0m",
+        ),
+        // OSC ended by BEL & by ST
+        ("\x1b]0;title\x07a", "a", "a", "␛]0;title\x07a"),
+        ("\x1b]52;c;aGk=\x1b\\a", "a", "a", "␛]52;c;aGk=␛\\a"),
+        (
+            "\x1b]8;;https://x\x1b\\link",
+            "link",
+            "link",
+            "␛]8;;https://x␛\\link",
+        ),
+        // bare & unfinished escapes
+        ("a\x1b", "a", "a", "a␛"),
+        ("a\x1bb", "a", "a", "a␛b"),
+        ("a\x1b[31", "a", "a", "a␛[31"),
+        ("\x1b[1\nb", "\nb", "\nb", "␛[1\nb"),
+        ("\x1b(0a", "a", "a", "␛(0a"),
+        // C1 CSI & OSC
+        ("\u{9b}31mred", "red", "red", "␛31mred"),
+        ("\u{9d}0;title\u{9c}a", "a", "a", "␛0;title␛a"),
+        // non SGR control sequences
+        ("\x1b[2J\x1b[1mb", "b", "\x1b[1mb", "␛[2J␛[1mb"),
+        ("\x1b[?25lb", "b", "b", "␛[?25lb"),
+        ("\x1b[1:2mb", "b", "b", "␛[1:2mb"),
+    ] {
+        assert_eq!(sanitize(text, StripAll), all, "{text:?}");
+        assert_eq!(sanitize(text, StripNonSGR), non_sgr, "{text:?}");
+        assert_eq!(sanitize(text, Neutralize), neutralized, "{text:?}");
+    }
+    assert!(matches!(
+        sanitize("plain\ttext", StripAll),
+        Cow::Borrowed(_)
+    ));
+}
+#[test]
+fn sanitized_hidden() {
+    use easy_sgr::{sanitize, SanitizePolicy::*};
+    // stripping the sequence would reveal the hidden text, so it is masked
+    let text = "pass: \x1b[8mhunter2\x1b[28m, \x1b[8;1msecret\nline\x1b[0m ok \x1b[38;5;8mgray";
+    assert_eq!(
+        sanitize(text, StripAll),
+        "pass: *******, ******\n**** ok gray"
+    );
+    assert_eq!(sanitize(text, StripNonSGR), text);
+    assert_eq!(sanitize("\u{9b}8mab\u{9b}mc", StripAll), "**c");
+}
+#[test]
+fn c1_introducer() {
+    use easy_sgr::{sanitize, SanitizePolicy::*};
+    for text in ["\x1b[1;31mred\x1b[39m", "\u{9b}1;31mred\u{9b}39m"] {
+        assert_eq!(open_state(text), StyleSet::from(Bold), "{text:?}");
+        assert_eq!(DisplayEscaped(text).to_string(), "<bold red>red<default>");
+        assert_eq!(sanitize(text, StripAll), "red");
+    }
+    assert_eq!(debug_escapes("\u{9b}2K"), r"\u{9b}2K");
+}
+#[test]
+fn recolored_lines() {
+    let recolor = |input: &str, f: &mut dyn FnMut(&str, &StyleSet) -> StyleSet| {
+        let mut output = Vec::new();
+        recolor_lines(input.as_bytes(), &mut output, f).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    let carried = "a\x1b[31mb\nc\nd\x1b[39me\n\x1b[1mopen";
+    assert_eq!(
+        recolor(carried, &mut |_, style| style.clone()),
+        "a\x1b[31mb\nc\nd\x1b[39me\n\x1b[1mopen\x1b[0m"
+    );
+    let mut lines = Vec::new();
+    recolor(carried, &mut |text, style| {
+        lines.push((text.to_string(), style.foreground.clone()));
+        style.clone()
+    });
+    assert_eq!(
+        lines,
+        [
+            (String::from("a"), ColorKind::None),
+            (String::from("b\n"), ColorKind::Red),
+            (String::from("c\n"), ColorKind::Red),
+            (String::from("d"), ColorKind::Red),
+            (String::from("e\n"), ColorKind::None),
+            (String::from("open"), ColorKind::None),
+        ]
+    );
+    let mut uncolored = |_: &str, style: &StyleSet| StyleSet {
+        foreground: ColorKind::None,
+        background: ColorKind::None,
+        ..style.clone()
+    };
+    assert_eq!(
+        recolor(
+            "\x1b[31;44mred\n\x1b[1mbold\x1b[38;5;2m\x1b[0m\nplain\n",
+            &mut uncolored
+        ),
+        "red\n\x1b[1mbold\x1b[22m\nplain\n"
+    );
+
+    /// Records the output written before each flush
+    struct Flushes(Vec<u8>, Vec<usize>);
+    impl std::io::Write for Flushes {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.1.push(self.0.len());
+            Ok(())
+        }
+    }
+    let mut flushes = Flushes(Vec::new(), Vec::new());
+    recolor_lines("ab\ncd\n".as_bytes(), &mut flushes, |_, s| s.clone()).unwrap();
+    assert_eq!(flushes.1, [3, 6, 6]);
+}
+#[test]
+fn offset_maps() {
+    // escapes at the start, middle & end
+    let text = "\x1b[31mab\x1b[1mcd\x1b[0m";
+    let map = offset_map(text);
+    assert_eq!((map.width(), map.len()), (4, text.len()));
+    let expected = [(0, 5), (1, 6), (2, 11), (3, 12), (4, 17)];
+    for (column, byte) in expected {
+        assert_eq!(map.visible_to_byte(column), Some(byte), "{column}");
+        assert_eq!(map.byte_to_visible(byte), Some(column), "{byte}");
+    }
+    assert_eq!(map.visible_to_byte(5), None);
+    // within & on both sides of each escape
+    for (byte, column) in [(0, 0), (4, 0), (7, 2), (10, 2), (13, 4), (16, 4)] {
+        assert_eq!(map.byte_to_visible(byte), Some(column), "{byte}");
+    }
+    assert_eq!(map.byte_to_visible(18), None);
+}
+#[test]
+fn offset_maps_multibyte() {
+    let text = "é\u{9b}1m字x\x1b[38;2;1;2;3m";
+    let map = offset_map(text);
+    assert_eq!((map.width(), map.len()), (3, text.len()));
+    // é is 2 bytes, the C1 sequence 4, 字 3
+    for (column, byte) in [(0, 0), (1, 6), (2, 9), (3, text.len())] {
+        assert_eq!(map.visible_to_byte(column), Some(byte), "{column}");
+    }
+    for (byte, column) in [(1, 0), (2, 1), (7, 1), (8, 1), (9, 2), (10, 3)] {
+        assert_eq!(map.byte_to_visible(byte), Some(column), "{byte}");
+    }
+    let map = offset_map("");
+    assert!(map.is_empty());
+    assert_eq!(
+        (map.visible_to_byte(0), map.byte_to_visible(0)),
+        (Some(0), Some(0))
+    );
+    assert_eq!(offset_map("\x1b[0m").visible_to_byte(0), Some(4));
+}
+#[test]
+fn offset_maps_unterminated() {
+    // a lone escape is shown, an unterminated sequence runs to the end
+    let map = offset_map("a\x1bb");
+    assert_eq!(map.width(), 3);
+    let map = offset_map("a\x1b[12;3");
+    assert_eq!((map.width(), map.len()), (1, 7));
+    assert_eq!(map.visible_to_byte(1), Some(7));
+}
+#[test]
+fn merged() {
+    let red = "red".color(RedFg).clean(CleanKind::Reverse);
+    assert_eq!(
+        Merge::new(&red, Bold).to_string(),
+        "\x1b[31;1mred\x1b[39;22m"
+    );
+    // a reset within the value keeps the merged attributes
+    let text = "\x1b[1;32ma\x1b[0mb";
+    assert_eq!(
+        Merge::new(text, (Italic, BlueFg)).to_string(),
+        "\x1b[32;1;3ma\x1b[34;22mb\x1b[39;23m"
+    );
+    assert_eq!(Merge::new("", Bold).to_string(), "");
+    assert_eq!(
+        Merge::new("plain", StyleSet::default()).to_string(),
+        "plain"
+    );
+    assert_eq!(
+        Merge::from_codes(&red, &[38, 5, 208, 4]).to_string(),
+        "\x1b[31;4mred\x1b[39;24m"
+    );
+}
+#[test]
+fn optimized() {
+    use std::borrow::Cow;
+
+    // the same color applied again
+    assert_eq!(optimize("\x1b[31mfoo\x1b[31mbar"), "\x1b[31mfoobar");
+    assert_eq!(optimize("\x1b[31m\x1b[31m\x1b[1mfoo"), "\x1b[31;1mfoo");
+    // resets once already reset
+    assert_eq!(
+        optimize("\x1b[1mx\x1b[0m\x1b[0my\x1b[m"),
+        "\x1b[1mx\x1b[0my"
+    );
+    assert_eq!(optimize("\x1b[0m\x1b[mplain"), "plain");
+    // changes between text are kept
+    let changing = "\x1b[31ma\x1b[1mb\x1b[0mc\x1b[4md";
+    assert!(matches!(optimize(changing), Cow::Borrowed(text) if text == changing));
+    assert_eq!(optimize("a\x1b[4m\x1b[24mb\x1b[2Kc"), "ab\x1b[2Kc");
+    // unknown codes can only be turned off by a reset
+    assert_eq!(optimize("\x1b[21mx\x1b[0my"), "\x1b[21mx\x1b[0my");
+    assert!(matches!(optimize("\x1b[300mx\x1b[300m"), Cow::Borrowed(_)));
+    assert!(matches!(optimize("plain"), Cow::Borrowed("plain")));
+    // the attributes of each piece of text are unchanged
+    let sample = concat!(
+        "\x1b[0m\x1b[1m\x1b[34mINFO\x1b[0m \x1b[0m\x1b[2mserver\x1b[0m\x1b[0m: ",
+        "\x1b[0mlistening on \x1b[0m\x1b[32m\x1b[32m:8080\x1b[0m\x1b[0m\n",
+        "\x1b[0m\x1b[1m\x1b[34mINFO\x1b[0m \x1b[0m\x1b[2mserver\x1b[0m\x1b[0m: ",
+        "\x1b[0m\x1b[33mslow\x1b[33m request\x1b[39m\x1b[0m\n",
+    );
+    let optimized = optimize(sample);
+    assert_eq!(
+        optimized,
+        concat!(
+            "\x1b[34;1mINFO\x1b[0m \x1b[2mserver\x1b[0m: listening on \x1b[32m:8080\x1b[0m\n",
+            "\x1b[34;1mINFO\x1b[0m \x1b[2mserver\x1b[0m: \x1b[33mslow request\x1b[0m\n",
+        )
+    );
+    // a third smaller
+    assert_eq!((sample.len(), optimized.len()), (173, 114));
+    assert_eq!(
+        easy_sgr::debug_escapes(&optimize(&optimized)),
+        easy_sgr::debug_escapes(&optimized)
+    );
+}
+#[test]
+fn inverted_regions() {
+    let plain = invert_region(&"plain".to_sgr());
+    assert_eq!(plain.to_string(), "\x1b[7mplain\x1b[27m");
+    // inner inverse is shown plain, a reset keeping the region inverse
+    let inner = invert_region(&"a\x1b[7mb\x1b[0;1mc\x1b[38;5;7;27md".color(RedFg));
+    assert_eq!(
+        inner.to_string(),
+        "\x1b[31m\x1b[7ma\x1b[27mb\x1b[0;7;1mc\x1b[38;5;7;7md\x1b[27m"
+    );
+    // the string's own inverse is flipped, then cleaned as the original would be
+    let own = invert_region(&"x\x1b[27my".style(Inverse).clean(CleanKind::Reverse));
+    assert_eq!(own.to_string(), "\x1b[27mx\x1b[7my\x1b[27m");
+    let reset = invert_region(&"x".style(Inverse).clean(CleanKind::Reset));
+    assert_eq!(reset.to_string(), "\x1b[27mx\x1b[0m");
+    // unknown parameters are kept as is
+    let unknown = invert_region(&"\x1b[300;7mx".to_sgr());
+    assert_eq!(unknown.to_string(), "\x1b[7m\x1b[300;7mx\x1b[27m");
+}
+#[test]
+fn nested_regions_cancel_out() {
+    for sgr in [
+        "plain".to_sgr(),
+        "a\x1b[7mb\x1b[27mc\x1b[0md".style(Bold),
+        "x\x1b[27my".style(Inverse).clean(CleanKind::Reverse),
+        "x\x1b[7my".style(NotInverse),
+    ] {
+        let twice = invert_region(&invert_region(&sgr));
+        let (left, right) = (twice.to_string(), sgr.to_string());
+        assert_eq!(open_state(&left), open_state(&right));
+        #[cfg(feature = "testing")]
+        easy_sgr::assert_sgr_eq!(left, right);
+    }
+}
@@ -0,0 +1,10 @@
+use easy_sgr::format;
+
+fn main() {
+    let names = ["tim", "tom"];
+    assert_eq!(
+        format!("{[bold]}{names[1].to_uppercase()}{[]}, {:>3}", 1),
+        "\x1b[1mTOM\x1b[0m,   1"
+    );
+    assert_eq!(format!("{{[bold]}} {}", names.len()), "{[bold]} 2");
+}
@@ -0,0 +1,13 @@
+use easy_sgr::format;
+
+fn main() {
+    let (a, b) = ("x", 2);
+    assert_eq!(
+        format!("{[>bold red]}{a} and {b}{[<]} plain"),
+        "\x1b[1;31mx and 2\x1b[22;39m plain"
+    );
+    assert_eq!(
+        format!("{[>blue]}a{[>red]}b{[<]}c{[<]}"),
+        "\x1b[34ma\x1b[31mb\x1b[39;34mc\x1b[39m"
+    );
+}
@@ -0,0 +1,10 @@
+use easy_sgr::{format, sgr, sgr_static};
+
+const PROMPT: &str = sgr_static!("{[bold green]}>{[]} ");
+
+fn main() {
+    assert_eq!(sgr!("{[bold red on-bright-blue]}a{[]}"), "\x1b[1;31;104ma\x1b[0m");
+    assert_eq!(sgr!("{[15]}{[on-0,128,255]}{[#ff]}"), "\x1b[38;5;15m\x1b[48;2;0;128;255m\x1b[38;5;255m");
+    assert_eq!(PROMPT, "\x1b[1;32m>\x1b[0m ");
+    assert_eq!(format!(if false; "{[bold]}{}{[]}", 1), "1");
+}
@@ -0,0 +1,78 @@
+#![cfg(all(feature = "extended", not(feature = "macro-only")))]
+use easy_sgr::{DiscreteSGR, EasySGR, Extended::*, SGRString};
+
+const ALL: [(easy_sgr::Extended, u8); 8] = [
+    (ProportionalSpacing, 26),
+    (NotProportionalSpacing, 50),
+    (IdeogramUnderline, 60),
+    (IdeogramDoubleUnderline, 61),
+    (IdeogramOverline, 62),
+    (IdeogramDoubleOverline, 63),
+    (IdeogramStress, 64),
+    (NotIdeogram, 65),
+];
+
+#[test]
+fn codes() {
+    for (extended, code) in ALL {
+        assert_eq!(&*extended.codes(), [code]);
+
+        let mut standard = String::new();
+        extended.standard_display(&mut standard).unwrap();
+        assert_eq!(standard, extended.to_string());
+        #[cfg(not(feature = "partial"))]
+        assert_eq!(extended.to_string(), format!("\x1b[{code}m"));
+        #[cfg(feature = "partial")]
+        assert_eq!(extended.to_string(), code.to_string());
+
+        assert_eq!(
+            SGRString::from(extended).text("a").to_string(),
+            format!("\x1b[{code}ma")
+        );
+    }
+}
+#[test]
+fn undo() {
+    for (extended, _) in ALL {
+        let expected = match extended {
+            // no wildcard, new variants must be added here
+            ProportionalSpacing | NotProportionalSpacing => NotProportionalSpacing,
+            IdeogramUnderline
+            | IdeogramDoubleUnderline
+            | IdeogramOverline
+            | IdeogramDoubleOverline
+            | IdeogramStress
+            | NotIdeogram => NotIdeogram,
+        };
+        assert_eq!(extended.undo(), expected);
+    }
+}
+#[cfg(feature = "from-str")]
+#[test]
+fn from_str() {
+    use easy_sgr::from_str::ParseExtendedError;
+    for (extended, _) in ALL {
+        assert_eq!(format!("{extended:?}").parse(), Ok(extended));
+    }
+    assert_eq!(
+        "Ideogram".parse::<easy_sgr::Extended>(),
+        Err(ParseExtendedError)
+    );
+}
+#[cfg(feature = "macros")]
+#[test]
+fn keywords() {
+    use easy_sgr::sgr;
+    assert_eq!(sgr!("{[proportional]}"), "\x1b[26m");
+    assert_eq!(sgr!("{[!proportional]}"), "\x1b[50m");
+    assert_eq!(sgr!("{[ideogram-underline]}"), "\x1b[60m");
+    assert_eq!(sgr!("{[ideogram-double-underline]}"), "\x1b[61m");
+    assert_eq!(sgr!("{[ideogram-overline]}"), "\x1b[62m");
+    assert_eq!(sgr!("{[ideogram-double-overline]}"), "\x1b[63m");
+    assert_eq!(sgr!("{[ideogram-stress]}"), "\x1b[64m");
+    assert_eq!(sgr!("{[!ideogram]}"), "\x1b[65m");
+    assert_eq!(
+        sgr!("{[bold ideogram-stress]}text{[!ideogram !bold]}"),
+        "\x1b[1;64mtext\x1b[65;22m"
+    );
+}
@@ -0,0 +1,338 @@
+//! Checks that every feature is documented, enabled by `full`,
+//! & that the items documented as belonging to it exist under it
+//!
+//! Each module below is only compiled with its feature, naming its items,
+//! so building the tests with a single feature checks that feature alone
+use std::collections::BTreeSet;
+
+/// The features changing the crate rather than adding to it, left out of `full`
+const CHANGING: [&str; 2] = ["partial", "macro-only"];
+/// The features only used while developing the crate, also left out of `full`
+const DEVELOPMENT: [&str; 1] = ["bench-compare"];
+
+/// Returns the `[features]` table of the manifest as each name & what it enables
+fn features() -> Vec<(String, String)> {
+    let manifest = include_str!("../Cargo.toml");
+    let table = manifest
+        .split_once("[features]\n")
+        .map_or("", |(_, table)| table);
+    let mut features: Vec<(String, String)> = Vec::new();
+    for line in table.lines().take_while(|line| !line.starts_with('[')) {
+        if line.starts_with('#') {
+            continue;
+        }
+        match line.split_once(" = ") {
+            Some((name, value)) if !line.starts_with(' ') => {
+                features.push((name.to_string(), value.to_string()));
+            }
+            _ => {
+                if let Some((_, value)) = features.last_mut() {
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+    features
+}
+#[test]
+fn documented() {
+    let docs = [include_str!("../src/lib.rs"), include_str!("../README.md")];
+    for (name, _) in features() {
+        // `macros` & `macro-only` are documented under the macros section
+        let heading = match &*name {
+            "macros" | "macro-only" => String::from("features = [\"macro-only\"]"),
+            name => format!("### `{name}`"),
+        };
+        for doc in docs {
+            assert!(doc.contains(&heading), "`{name}` is not documented");
+        }
+    }
+}
+#[test]
+fn full_enables_every_addition() {
+    let features = features();
+    let full = &features
+        .iter()
+        .find(|(name, _)| name == "full")
+        .expect("`full` is declared")
+        .1;
+    let enabled: BTreeSet<&str> = full
+        .trim_matches(['[', ']'])
+        .split(',')
+        .map(|feature| feature.trim().trim_matches('"'))
+        .filter(|feature| !feature.is_empty())
+        .collect();
+    let additions: BTreeSet<&str> = features
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| *name != "full" && !CHANGING.contains(name) && !DEVELOPMENT.contains(name))
+        .collect();
+    assert_eq!(enabled, additions);
+}
+#[cfg(all(feature = "from-str", not(feature = "macro-only")))]
+mod from_str {
+    use easy_sgr::{
+        discrete::from_str::{ParseColorError, ParseSeqError, ParseStyleError},
+        Color, Seq, Style,
+    };
+
+    #[test]
+    fn items() {
+        assert_eq!("Esc".parse::<Seq>(), Ok(Seq::Esc));
+        assert_eq!("Bold".parse::<Style>(), Ok(Style::Bold));
+        assert_eq!("RedFg".parse::<Color>(), Ok(Color::RedFg));
+        assert_eq!("?".parse::<Seq>(), Err(ParseSeqError));
+        assert!("?"
+            .parse::<Style>()
+            .is_err_and(|e: ParseStyleError| !e.to_string().is_empty()));
+        assert!("?"
+            .parse::<Color>()
+            .is_err_and(|e: ParseColorError| !e.to_string().is_empty()));
+    }
+}
+#[cfg(feature = "macros")]
+mod macros {
+    #[test]
+    fn items() {
+        assert_eq!(easy_sgr::format!("{[bold]}x{[]}"), "\x1b[1mx\x1b[0m");
+        assert_eq!(easy_sgr::sgr!("{[bold]}"), "\x1b[1m");
+        assert_eq!(easy_sgr::sgr_static!("{[bold]}"), "\x1b[1m");
+    }
+}
+#[cfg(all(feature = "partial", not(feature = "macro-only")))]
+mod partial {
+    use easy_sgr::{Color::*, Style::*};
+
+    #[test]
+    fn items() {
+        assert_eq!(Bold.to_string(), "1");
+        assert_eq!(RedFg.to_string(), "31");
+    }
+}
+#[cfg(all(feature = "control", not(feature = "macro-only")))]
+mod control {
+    use easy_sgr::{Control, EraseMode, FrameBuffer};
+
+    #[test]
+    fn items() {
+        assert_eq!(Control::EraseLine(EraseMode::All).to_string(), "\x1b[2K");
+        let _ = std::mem::size_of::<FrameBuffer>();
+    }
+}
+#[cfg(all(feature = "osc", not(feature = "macro-only")))]
+mod osc {
+    use easy_sgr::osc::{CopyToClipboard, SetTitle, OSC, ST};
+
+    #[test]
+    fn items() {
+        assert!(SetTitle("t").to_string().starts_with(OSC));
+        assert!(CopyToClipboard(b"c").to_string().ends_with(ST));
+    }
+}
+#[cfg(all(feature = "log", not(feature = "macro-only")))]
+mod log {
+    use easy_sgr::{env_logger_format, format_record, format_record_with, FmtWriter, LevelStyle};
+
+    #[test]
+    fn items() {
+        let _ = (
+            env_logger_format::<Vec<u8>>,
+            format_record::<FmtWriter<String>>,
+            format_record_with::<FmtWriter<String>>,
+            LevelStyle::default(),
+        );
+    }
+}
+#[cfg(all(feature = "table", not(feature = "macro-only")))]
+mod table {
+    use easy_sgr::{visible_width, Align, Column, Row, Table};
+
+    #[test]
+    fn items() {
+        assert_eq!(visible_width("\x1b[1mab"), 2);
+        let _ = (
+            Align::Left,
+            Column::default(),
+            Row::default(),
+            Table::default(),
+        );
+    }
+}
+#[cfg(all(feature = "regex", not(feature = "macro-only")))]
+mod regex {
+    use easy_sgr::{highlight_regex, Style::*};
+
+    #[test]
+    fn items() {
+        let regex = regex::Regex::new("b").unwrap();
+        assert_eq!(
+            highlight_regex("ab", &regex, Bold).to_string(),
+            "a\x1b[1mb\x1b[22m"
+        );
+    }
+}
+#[cfg(all(feature = "testing", not(feature = "macro-only")))]
+mod testing {
+    use easy_sgr::{assert_sgr_eq, diff, sgr_diff, sgr_segments, Difference, StyledDiff};
+
+    #[test]
+    fn items() {
+        assert_sgr_eq!("\x1b[1;31mx", "\x1b[31;1mx");
+        assert!(sgr_diff("x", "x").is_none() && sgr_segments("x").len() == 1);
+        let _: (StyledDiff, &[Difference]) = (diff("x", "x"), &[]);
+    }
+}
+#[cfg(all(feature = "proptest", not(feature = "macro-only")))]
+mod proptest {
+    use easy_sgr::proptest::{any_color, any_sgr_string, any_style};
+
+    #[test]
+    fn items() {
+        let _ = (any_color(), any_style(), any_sgr_string());
+    }
+}
+#[cfg(all(feature = "rand", not(feature = "macro-only")))]
+mod rand {
+    use easy_sgr::Color;
+
+    #[test]
+    fn items() {
+        assert!(matches!(
+            Color::random_fg(&mut rand::rng()),
+            Color::ByteFg(_)
+        ));
+    }
+}
+#[cfg(all(feature = "a11y", not(feature = "macro-only")))]
+mod a11y {
+    use easy_sgr::a11y::{colorblind_safe, simulate_rgb, ColorVisionDeficiency};
+
+    #[test]
+    fn items() {
+        let _ = (
+            colorblind_safe,
+            simulate_rgb,
+            ColorVisionDeficiency::Deuteranopia,
+        );
+    }
+}
+#[cfg(all(feature = "binary", not(feature = "macro-only")))]
+mod binary {
+    use easy_sgr::{
+        binary::{from_bytes, to_bytes, VERSION},
+        SGRString,
+    };
+
+    #[test]
+    fn items() {
+        let bytes = to_bytes(&[SGRString::default()]);
+        assert_eq!(bytes[0], VERSION);
+        assert_eq!(from_bytes(&bytes).map(|fragments| fragments.len()), Ok(1));
+    }
+}
+#[cfg(all(feature = "extended", not(feature = "macro-only")))]
+mod extended {
+    use easy_sgr::{DiscreteSGR, Extended::*};
+
+    #[test]
+    fn items() {
+        assert_eq!(*ProportionalSpacing.codes(), [26]);
+    }
+}
+#[cfg(all(feature = "report", not(feature = "macro-only")))]
+mod report {
+    use easy_sgr::{report, report_with, ReportStyle};
+
+    #[test]
+    fn items() {
+        let err = std::fmt::Error;
+        assert!(report(&err).text.contains("error"));
+        let _ = report_with(&err, &ReportStyle::default());
+    }
+}
+#[cfg(all(feature = "tokio", not(feature = "macro-only")))]
+mod tokio {
+    use easy_sgr::{AsyncCapableWriter, AsyncIoWriter};
+
+    #[test]
+    fn items() {
+        fn capable<W: AsyncCapableWriter>(_: &W) {}
+        capable(&AsyncIoWriter(Vec::<u8>::new()));
+    }
+}
+#[cfg(all(feature = "query", not(feature = "macro-only")))]
+mod query {
+    use easy_sgr::query::{
+        detect_background, parse_background_reply, query_background, Luma, ATTRIBUTES_QUERY,
+        BACKGROUND_QUERY,
+    };
+
+    #[test]
+    fn items() {
+        assert_eq!(Luma::from_rgb16((0, 0, 0)), Luma::Dark);
+        assert!(parse_background_reply(BACKGROUND_QUERY.as_bytes()).is_none());
+        let _ = (detect_background, query_background, ATTRIBUTES_QUERY);
+    }
+}
+#[cfg(all(feature = "wasm-console", not(feature = "macro-only")))]
+mod wasm_console {
+    use easy_sgr::{
+        console::{css, ConsoleWriter},
+        SGRString, StyleSet,
+    };
+
+    #[test]
+    fn items() {
+        assert_eq!(SGRString::default().to_console_args().0, "");
+        assert_eq!(css(&StyleSet::default()), "");
+        let _ = ConsoleWriter::default();
+    }
+}
+#[cfg(all(feature = "palettes", not(feature = "macro-only")))]
+mod palettes {
+    use easy_sgr::palette256::{PaletteMap, DRACULA, GRUVBOX, SOLARIZED};
+
+    #[test]
+    fn items() {
+        let _: [PaletteMap; 3] = [SOLARIZED, DRACULA, GRUVBOX];
+    }
+}
+#[cfg(all(feature = "html", not(feature = "macro-only")))]
+mod html {
+    use easy_sgr::{
+        html::{css, HtmlEscape, HtmlWriter},
+        SGRString, StyleSet,
+    };
+
+    #[test]
+    fn items() {
+        assert_eq!(SGRString::default().to_html(), "");
+        assert_eq!(css(&StyleSet::default()), "");
+        assert_eq!(HtmlEscape("<").to_string(), "&lt;");
+        let _ = HtmlWriter::default();
+    }
+}
+#[cfg(all(feature = "theme", not(feature = "macro-only")))]
+mod theme {
+    use easy_sgr::theme::{
+        current, error_style, global, hint_style, info_style, set_global, success_style,
+        warn_style, with_scoped, Theme,
+    };
+
+    #[test]
+    fn items() {
+        assert_eq!(with_scoped(Theme::default(), || 1), 1);
+        let _ = (current, global, set_global, error_style, warn_style);
+        let _ = (info_style, success_style, hint_style);
+    }
+}
+#[cfg(all(feature = "terminfo", not(feature = "macro-only")))]
+mod terminfo {
+    use easy_sgr::terminfo::{detect, Terminfo};
+
+    #[test]
+    fn items() {
+        assert!(Terminfo::parse(&[]).is_err());
+        let _ = (detect, Terminfo::load, Terminfo::from_env);
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(all(feature = "control", not(feature = "macro-only")))]
+use std::{error::Error, fmt::Write as _, io};
+
+use easy_sgr::{Color::*, Control, EasySGR, EraseMode, FrameBuffer};
+
+/// Records every call to [`io::Write::write`]
+#[derive(Default)]
+struct CountingWriter {
+    calls: Vec<Vec<u8>>,
+}
+impl io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.calls.push(buf.to_vec());
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Draws & presents a frame, returning what was written
+///
+/// Nothing is written when no line changed, otherwise in one call
+fn present(frame: &mut FrameBuffer, text: &str) -> Result<String, Box<dyn Error>> {
+    write!(frame, "{text}")?;
+    let mut out = CountingWriter::default();
+    frame.present(&mut out)?;
+    assert!(out.calls.len() <= 1, "{text:?}");
+    Ok(String::from_utf8(out.calls.concat())?)
+}
+
+#[test]
+fn single_write() -> Result<(), Box<dyn Error>> {
+    let mut frame = FrameBuffer::new().before([
+        Control::CursorToColumn(1),
+        Control::EraseScreen(EraseMode::All),
+    ]);
+    for i in 0..3 {
+        frame.writer().place_sgr(&GreenFg.to_sgr())?;
+        write!(frame, "tick {i}")?;
+        frame
+            .writer()
+            .clean_sgr(&GreenFg.clean(easy_sgr::CleanKind::Reverse))?;
+        assert_eq!(frame.len(), 16);
+        assert_eq!(
+            frame.as_bytes(),
+            format!("\x1b[32mtick {i}\x1b[39m").as_bytes()
+        );
+
+        let mut out = CountingWriter::default();
+        frame.present(&mut out)?;
+        assert_eq!(
+            out.calls,
+            [format!("\x1b[1G\x1b[2J\x1b[32mtick {i}\x1b[39m").into_bytes()]
+        );
+        assert!(frame.is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn changed_lines() -> Result<(), Box<dyn Error>> {
+    let mut frame = FrameBuffer::new().retained(true);
+    assert_eq!(present(&mut frame, "a\nb\nc")?, "a\nb\nc");
+    // only the middle line is rewritten, the cursor returning to the last line
+    assert_eq!(
+        present(&mut frame, "a\nB\nc")?,
+        "\x1b[1A\x1b[1GB\x1b[0K\x1b[1B"
+    );
+    assert_eq!(present(&mut frame, "a\nB\nc")?, "");
+    // new lines are written past the end
+    assert_eq!(
+        present(&mut frame, "A\nB\nc\nd")?,
+        "\x1b[2A\x1b[1GA\x1b[0K\x1b[2B\n\x1b[1Gd\x1b[0K"
+    );
+    // lines no longer drawn are erased
+    assert_eq!(
+        present(&mut frame, "A\nx")?,
+        "\x1b[2A\x1b[1Gx\x1b[0K\x1b[1B\x1b[2K\x1b[1B\x1b[2K\x1b[2A"
+    );
+
+    frame.invalidate();
+    assert_eq!(present(&mut frame, "A\nx")?, "A\nx");
+    Ok(())
+}
+
+#[test]
+fn failed_present() {
+    struct Failing;
+    impl io::Write for Failing {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut frame = FrameBuffer::new().retained(true);
+    write!(frame, "a").unwrap();
+    assert!(frame.present(&mut Failing).is_err());
+    // written in full once writing succeeds again
+    assert_eq!(present(&mut frame, "a").unwrap(), "a");
+}
+#[test]
+fn push() {
+    let mut frame = FrameBuffer::new();
+    let line = "line".color(RgbFg(1, 22, 133));
+    frame.push(&line);
+    assert_eq!(frame.len(), line.rendered_len());
+    assert_eq!(frame.as_bytes(), line.to_string().as_bytes());
+}
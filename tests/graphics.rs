@@ -1,6 +1,13 @@
+#![cfg(not(feature = "macro-only"))]
 use std::default::Default;
 
-use easy_sgr::{CleanKind, Color::*, ColorKind, EasySGR, SGRString, Style::*, StyleKind};
+use easy_sgr::{
+    fill, CleanKind,
+    Color::{self, *},
+    ColorKind, EasySGR, ProgressBar, SGRBuilder, SGRString,
+    Style::*,
+    StyleKind,
+};
 
 #[test]
 fn general() {
@@ -36,7 +43,7 @@ fn general() {
 #[test]
 fn fully_loaded() {
     assert_eq!(
-        "\x1b[0;31;41;1;2;3;4;5;7;8;9;100mtest\x1b[39;49;22;22;23;24;25;27;28;29;100m",
+        "\x1b[0;31;41;1;2;3;4;5;6;7;8;9;51;52;53;100mtest\x1b[39;49;22;22;23;24;25;25;27;28;29;54;54;55;100m",
         SGRString {
             text: "test".to_string(),
             clean: CleanKind::Reverse,
@@ -50,14 +57,18 @@ fn fully_loaded() {
             italic: StyleKind::Place,
             underline: StyleKind::Place,
             blinking: StyleKind::Place,
+            rapid_blinking: StyleKind::Place,
             inverse: StyleKind::Place,
             hidden: StyleKind::Place,
-            strikethrough: StyleKind::Place
+            strikethrough: StyleKind::Place,
+            framed: StyleKind::Place,
+            encircled: StyleKind::Place,
+            overline: StyleKind::Place,
         }
         .to_string()
     );
     assert_eq!(
-        "\x1b[0;31;41;22;22;23;24;25;27;28;29;100mtest\x1b[39;49;1;2;3;4;5;7;8;9;100m",
+        "\x1b[0;31;41;22;22;23;24;25;25;27;28;29;54;54;55;100mtest\x1b[39;49;1;2;3;4;5;6;7;8;9;51;52;53;100m",
         SGRString {
             text: "test".to_string(),
             clean: CleanKind::Reverse,
@@ -71,9 +82,13 @@ fn fully_loaded() {
             italic: StyleKind::Clean,
             underline: StyleKind::Clean,
             blinking: StyleKind::Clean,
+            rapid_blinking: StyleKind::Clean,
             inverse: StyleKind::Clean,
             hidden: StyleKind::Clean,
-            strikethrough: StyleKind::Clean
+            strikethrough: StyleKind::Clean,
+            framed: StyleKind::Clean,
+            encircled: StyleKind::Clean,
+            overline: StyleKind::Clean,
         }
         .to_string()
     );
@@ -131,9 +146,13 @@ fn easy_sgr_style() {
         ("\x1b[3m", Italic),
         ("\x1b[4m", Underline),
         ("\x1b[5m", Blinking),
+        ("\x1b[6m", RapidBlinking),
         ("\x1b[7m", Inverse),
         ("\x1b[8m", Hidden),
         ("\x1b[9m", Strikethrough),
+        ("\x1b[51m", Framed),
+        ("\x1b[52m", Encircled),
+        ("\x1b[53m", Overline),
         ("\x1b[22m", NotBold),
         ("\x1b[22m", NotDim),
         ("\x1b[23m", NotItalic),
@@ -142,6 +161,8 @@ fn easy_sgr_style() {
         ("\x1b[27m", NotInverse),
         ("\x1b[28m", NotHidden),
         ("\x1b[29m", NotStrikethrough),
+        ("\x1b[54m", NotFramedEncircled),
+        ("\x1b[55m", NotOverline),
     ] {
         assert_eq!(correct, "".style(style).to_string());
     }
@@ -176,3 +197,573 @@ fn easy_sgr_color() {
         assert_eq!(correct, "".color(color).to_string())
     }
 }
+
+#[test]
+fn masks() {
+    use easy_sgr::masked;
+    assert_eq!(masked(0, '*').text, "");
+    assert_eq!(masked(3, '•').to_string(), "•••");
+}
+#[test]
+fn fills() {
+    assert_eq!("", fill('-', 0).to_string());
+    assert_eq!("", fill('-', 0).styled(Dim).to_string());
+    assert_eq!("---", fill('-', 3).to_string());
+    assert_eq!("\x1b[2m───", fill('─', 3).styled(Dim).to_string());
+    assert_eq!(
+        "\x1b[31m🚋🚋\x1b[39m",
+        fill('🚋', 2)
+            .styled(RedFg.clean(CleanKind::Reverse))
+            .to_string()
+    );
+    // the text of the SGRString is not written
+    assert_eq!(
+        "\x1b[1m==",
+        fill('=', 2).styled("text".style(Bold)).to_string()
+    );
+}
+
+#[test]
+fn progress_bar() {
+    let bar = |ratio| {
+        ProgressBar::new(4, ratio)
+            .filled('█', GreenFg)
+            .empty('░', BlackFg)
+            .to_string()
+    };
+    assert_eq!("\x1b[30m░░░░\x1b[39m", bar(0.0));
+    assert_eq!("\x1b[32m██\x1b[39m\x1b[30m░░\x1b[39m", bar(0.5));
+    assert_eq!("\x1b[32m████\x1b[39m", bar(1.0));
+    assert_eq!(bar(-1.0), bar(0.0));
+    assert_eq!(bar(f64::NAN), bar(0.0));
+    assert_eq!(bar(2.0), bar(1.0));
+
+    assert_eq!("", ProgressBar::new(0, 0.5).to_string());
+    assert_eq!(
+        "\x1b[39m###\x1b[39m\x1b[39m-\x1b[39m",
+        ProgressBar::new(4, 0.7).to_string()
+    );
+}
+
+#[test]
+fn undo_all() {
+    let undo = |sgr: &SGRString| {
+        let mut builder = SGRBuilder::default();
+        sgr.undo_all(&mut builder);
+        builder.codes().to_vec()
+    };
+    assert_eq!(Vec::<u8>::new(), undo(&SGRString::default()));
+    assert_eq!(Vec::<u8>::new(), undo(&Reset.custom(100)));
+    assert_eq!(Vec::<u8>::new(), undo(&NotItalic.into()));
+    assert_eq!(vec![22], undo(&Bold.style(Dim)));
+    assert_eq!(vec![25], undo(&Blinking.style(RapidBlinking)));
+    assert_eq!(vec![54, 55], undo(&Framed.style(Encircled).style(Overline)));
+    assert_eq!(
+        vec![39, 49, 22, 23, 24, 25, 27, 28, 29],
+        undo(&SGRString {
+            foreground: ColorKind::Rgb(1, 2, 3),
+            background: ColorKind::Default,
+            bold: StyleKind::Place,
+            dim: StyleKind::Place,
+            italic: StyleKind::Place,
+            underline: StyleKind::Place,
+            blinking: StyleKind::Place,
+            inverse: StyleKind::Place,
+            hidden: StyleKind::Place,
+            strikethrough: StyleKind::Place,
+            ..Default::default()
+        })
+    );
+}
+
+#[test]
+fn style_paths_agree() {
+    use easy_sgr::{DiscreteSGR, Style, StyleSet};
+    let code = |style: &Style| match style {
+        // no wildcard, new variants must be added here
+        Reset => 0,
+        Bold => 1,
+        Dim => 2,
+        Italic => 3,
+        Underline => 4,
+        Blinking => 5,
+        RapidBlinking => 6,
+        Inverse => 7,
+        Hidden => 8,
+        Strikethrough => 9,
+        Framed => 51,
+        Encircled => 52,
+        Overline => 53,
+        NotBold | NotDim => 22,
+        NotItalic => 23,
+        NotUnderline => 24,
+        NotBlinking => 25,
+        NotInverse => 27,
+        NotHidden => 28,
+        NotStrikethrough => 29,
+        NotFramedEncircled => 54,
+        NotOverline => 55,
+    };
+    let codes = |f: &dyn Fn(&mut SGRBuilder)| {
+        let mut builder = SGRBuilder::default();
+        f(&mut builder);
+        builder.codes().to_vec()
+    };
+    for style in [
+        Reset,
+        Bold,
+        Dim,
+        Italic,
+        Underline,
+        Blinking,
+        RapidBlinking,
+        Inverse,
+        Hidden,
+        Strikethrough,
+        Framed,
+        Encircled,
+        Overline,
+        NotBold,
+        NotDim,
+        NotItalic,
+        NotUnderline,
+        NotBlinking,
+        NotInverse,
+        NotHidden,
+        NotStrikethrough,
+        NotFramedEncircled,
+        NotOverline,
+    ] {
+        let sgr = SGRString::from(style.clone());
+        assert_eq!(&*style.codes(), [code(&style)], "{style:?}");
+        assert_eq!(codes(&|b| sgr.place_all(b)), [code(&style)], "{style:?}");
+        if style == Reset {
+            continue;
+        }
+        let set = StyleSet::from(style.clone());
+        let empty = StyleSet::default();
+        assert_eq!(
+            codes(&|b| empty.transition(&set, b)),
+            [code(&style)],
+            "{style:?}"
+        );
+        if style != style.undo() {
+            assert_eq!(
+                codes(&|b| set.transition(&empty, b)),
+                [code(&style.undo())],
+                "{style:?}"
+            );
+            assert_eq!(
+                codes(&|b| sgr.undo_all(b)),
+                [code(&style.undo())],
+                "{style:?}"
+            );
+        }
+    }
+}
+#[test]
+fn style_set_bundles() {
+    use easy_sgr::StyleSet;
+    let expected = "\x1b[31;1;3mtext";
+    let tuple = "text".apply((RedFg, Bold, Italic));
+    let array = "text".apply([Bold.to_sgr(), Italic.to_sgr(), RedFg.to_sgr()]);
+    let iter = "text".apply(StyleSet::from_iter([Bold, Italic]).layered(&RedFg.into()));
+    assert_eq!(tuple.to_string(), expected);
+    assert_eq!(array.to_string(), expected);
+    assert_eq!(iter.to_string(), expected);
+
+    // the last of each kind wins
+    let set = StyleSet::from((RedFg, Bold, BlueFg, NotBold));
+    assert_eq!(set.foreground, ColorKind::Blue);
+    assert_eq!(set.bold, StyleKind::Clean);
+    assert_eq!(StyleSet::from([RedFg, BlueFg]), StyleSet::from(BlueFg));
+    assert_eq!(
+        StyleSet::from(&[Bold, Dim][..]),
+        StyleSet::from(Bold.style(Dim))
+    );
+    assert_eq!(
+        StyleSet::from_iter([RedBg, GreenBg]),
+        StyleSet::from(GreenBg)
+    );
+    assert!(StyleSet::from_iter(Vec::<Color>::new()).is_empty());
+
+    // applied over what is already set
+    let text = "text".color(GreenFg).style(Dim).apply((RedFg, Bold));
+    assert_eq!(text.to_string(), "\x1b[31;1;2mtext");
+}
+#[test]
+fn sgr_params() {
+    use easy_sgr::StyleSet;
+    let set = StyleSet::from_sgr_params("01;4;38;2;1;2;3;48;5;208").unwrap();
+    assert_eq!(set.bold, StyleKind::Place);
+    assert_eq!(set.underline, StyleKind::Place);
+    assert_eq!(set.foreground, ColorKind::Rgb(1, 2, 3));
+    assert_eq!(set.background, ColorKind::Byte(208));
+
+    let set = StyleSet::from_sgr_params("22;39;49").unwrap();
+    assert_eq!(set.bold, StyleKind::Clean);
+    assert_eq!(set.foreground, ColorKind::Default);
+    assert_eq!(set.background, ColorKind::Default);
+    // codes without a typed attribute are kept, while 0 clears what came before it
+    let set = StyleSet::from_sgr_params("01;00;73;26").unwrap();
+    assert_eq!(set.custom_places, [73, 26]);
+    assert_eq!(set.bold, StyleKind::None);
+    // shared off codes clean every style they turn off, as a terminal would
+    let set = StyleSet::from_sgr_params("1;2;22").unwrap();
+    assert_eq!(
+        (&set.bold, &set.dim),
+        (&StyleKind::Clean, &StyleKind::Clean)
+    );
+    let set = StyleSet::from_sgr_params("5;6;25;51;52;54").unwrap();
+    assert_eq!(set.blinking, StyleKind::Clean);
+    assert_eq!(set.rapid_blinking, StyleKind::Clean);
+    assert_eq!(set.framed, StyleKind::Clean);
+    assert_eq!(set.encircled, StyleKind::Clean);
+    let set = StyleSet::from_sgr_params("91;107").unwrap();
+    assert_eq!(set.foreground, ColorKind::BrightRed);
+    assert_eq!(set.background, ColorKind::BrightWhite);
+    assert!(StyleSet::from_sgr_params("").unwrap().is_empty());
+
+    for invalid in ["1;", "bold", "256", "38;5", "38;2;1;2", "48;3;1"] {
+        assert!(StyleSet::from_sgr_params(invalid).is_err(), "{invalid}");
+    }
+}
+#[test]
+fn color_specs() {
+    use easy_sgr::{parse_color_spec, StyleSet};
+    let ls_colors = parse_color_spec(
+        "rs=0:di=01;34:ln=01;36:mh=00:pi=40;33:so=01;35:bd=40;33;01:\
+        su=37;41:tw=30;42:ex=01;32:*.tar=01;31:*.tgz=01;31:",
+    )
+    .unwrap();
+    assert_eq!(ls_colors.len(), 12);
+    assert_eq!(ls_colors["di"], StyleSet::from(Bold.color(BlueFg)));
+    assert_eq!(ls_colors["bd"], StyleSet::from((BlackBg, YellowFg, Bold)));
+    assert_eq!(ls_colors["su"], StyleSet::from((WhiteFg, RedBg)));
+    assert_eq!(ls_colors["*.tgz"], StyleSet::from((Bold, RedFg)));
+    assert!(ls_colors["rs"].is_empty());
+    assert!(ls_colors["mh"].is_empty());
+
+    // resets & shared off codes, as terminals read them
+    let ls_colors =
+        parse_color_spec("ln=01;02;22;36:or=05;06;25;01:ca=00;30;41:st=51;52;54").unwrap();
+    let ln = &ls_colors["ln"];
+    assert_eq!((&ln.bold, &ln.dim), (&StyleKind::Clean, &StyleKind::Clean));
+    assert_eq!(ln.foreground, ColorKind::Cyan);
+    let or = &ls_colors["or"];
+    assert_eq!(or.bold, StyleKind::Place);
+    assert_eq!(or.blinking, StyleKind::Clean);
+    assert_eq!(or.rapid_blinking, StyleKind::Clean);
+    assert_eq!(ls_colors["ca"], StyleSet::from((BlackFg, RedBg)));
+    let st = &ls_colors["st"];
+    assert_eq!(
+        (&st.framed, &st.encircled),
+        (&StyleKind::Clean, &StyleKind::Clean)
+    );
+
+    let grep_colors =
+        parse_color_spec("ms=01;31:mc=01;31:sl=:cx=:fn=35:ln=32:bn=32:se=36:ne").unwrap();
+    assert_eq!(grep_colors.len(), 8);
+    assert_eq!(grep_colors["ms"], StyleSet::from((Bold, RedFg)));
+    assert!(grep_colors["sl"].is_empty());
+    assert_eq!(grep_colors["fn"], StyleSet::from(MagentaFg));
+    assert_eq!(grep_colors["se"], StyleSet::from(CyanFg));
+
+    assert!(parse_color_spec("di=01;bold").is_err());
+}
+#[test]
+fn layer_agnostic_colors() {
+    use easy_sgr::Rgb;
+    assert_eq!(Rgb::from((1, 2, 3)), Rgb(1, 2, 3));
+    assert_eq!(Rgb::from(0x01_02_03), Rgb(1, 2, 3));
+    // the highest byte is ignored
+    assert_eq!(Rgb::from_hex(0xff00_00ff), Rgb(0, 0, 255));
+    assert_eq!(Rgb(1, 2, 3).fg(), Color::RgbFg(1, 2, 3));
+    assert_eq!(Rgb(1, 2, 3).bg(), Color::RgbBg(1, 2, 3));
+
+    for (color, kind) in [
+        (Color::RedBg, ColorKind::Red),
+        (Color::ByteFg(208), ColorKind::Byte(208)),
+        (Color::RgbBg(1, 2, 3), ColorKind::Rgb(1, 2, 3)),
+        (Color::DefaultFg, ColorKind::Default),
+    ] {
+        assert_eq!(ColorKind::from(color), kind);
+    }
+
+    let theme = [Rgb(255, 0, 0), Rgb::from_hex(0x00ff00)];
+    let sgr = "x".fg(theme[0]).bg(theme[1]);
+    assert_eq!(
+        sgr.to_string(),
+        "x".color(RgbFg(255, 0, 0))
+            .color(RgbBg(0, 255, 0))
+            .to_string()
+    );
+    assert_eq!("x".fg((255, 0, 0)).foreground, ColorKind::Rgb(255, 0, 0));
+    assert_eq!("x".bg(0x0000ff).background, ColorKind::Rgb(0, 0, 255));
+    assert_eq!("x".bg(Color::CyanFg).background, ColorKind::Cyan);
+    assert_eq!("x".color(RedFg).fg(ColorKind::None).to_string(), "x");
+}
+#[test]
+fn inverse_toggle() {
+    use easy_sgr::{StyleSet, TransitionOptions};
+    let reapply = TransitionOptions {
+        reapply_colors_after_inverse_toggle: true,
+    };
+    let codes = |prev: &StyleSet, next: &StyleSet, options| {
+        let mut builder = SGRBuilder::default();
+        prev.transition_with(next, &mut builder, options);
+        builder.codes().to_vec()
+    };
+    let inverse = StyleSet::from(RedFg.color(BlueBg).style(Inverse));
+    let green = StyleSet::from(GreenFg.color(BlueBg));
+    // colors changed as inverse is turned off
+    assert_eq!(
+        codes(&inverse, &green, TransitionOptions::default()),
+        [32, 27]
+    );
+    assert_eq!(codes(&inverse, &green, reapply), [32, 27, 32, 44]);
+    // unset colors are written as their defaults
+    let plain = StyleSet::default();
+    assert_eq!(codes(&inverse, &plain, reapply), [39, 49, 27, 39, 49]);
+    let byte = StyleSet::from(ByteFg(208).style(Hidden));
+    assert_eq!(
+        codes(&inverse, &byte, reapply),
+        [38, 5, 208, 49, 27, 8, 38, 5, 208, 49]
+    );
+    // nothing extra unless inverse is turned off
+    for (prev, next) in [(&green, &inverse), (&inverse, &inverse), (&green, &plain)] {
+        assert_eq!(
+            codes(prev, next, reapply),
+            codes(prev, next, TransitionOptions::default())
+        );
+    }
+}
+#[test]
+fn place_clean_symmetry() -> Result<(), std::fmt::Error> {
+    use easy_sgr::SGRWriter;
+    let body = ["a large ", "streamed ", "body"];
+    for clean in [CleanKind::None, CleanKind::Reset, CleanKind::Reverse] {
+        let sgr = SGRString {
+            custom_places: vec![73],
+            custom_cleans: vec![74],
+            .."".style(Bold)
+                .style(Underline)
+                .color(RgbFg(1, 2, 3))
+                .color(BlueBg)
+                .clean(clean.clone())
+        };
+        let mut writer = SGRWriter::from(String::new());
+        sgr.write_place(&mut writer)?;
+        for piece in body {
+            writer.write_inner(piece)?;
+        }
+        sgr.write_clean(&mut writer)?;
+        let rendered = SGRString {
+            text: body.concat(),
+            ..sgr.clone()
+        };
+        assert_eq!(writer.internal(), rendered.to_string(), "{clean:?}");
+    }
+    Ok(())
+}
+#[test]
+fn text_access() {
+    let mut sgr = "header".color(RedFg).style(Bold).clean(CleanKind::Reverse);
+    assert_eq!(sgr.as_str(), "header");
+    assert!(!sgr.is_empty());
+    sgr.text_mut().push('!');
+    let sgr = sgr.map_text(|text| text.to_uppercase());
+    assert_eq!(sgr.to_string(), "\x1b[31;1mHEADER!\x1b[39;22m");
+
+    let mut empty = sgr.clone().map_text(|_| String::new());
+    assert!(empty.is_empty());
+    assert_eq!(empty.foreground, sgr.foreground);
+    empty.clear_styles();
+    assert_eq!(empty.to_string(), "");
+
+    let mut cleared = sgr;
+    cleared.clear_styles();
+    assert_eq!(cleared.to_string(), "HEADER!");
+    assert_eq!(cleared.clean, CleanKind::None);
+}
+#[test]
+fn runs_text_access() {
+    use std::borrow::Cow;
+    let runs = "one two".to_sgr().style_range(4.., Bold);
+    assert_eq!(runs.pieces().collect::<Vec<_>>(), ["one ", "two"]);
+    assert!(matches!(runs.as_str(), Cow::Owned(text) if text == "one two"));
+    let single = "one".to_sgr().style_range(.., Bold);
+    assert!(matches!(single.as_str(), Cow::Borrowed("one")));
+
+    let upper = runs.clone().map_text(str::to_uppercase);
+    assert_eq!(upper.as_str(), "ONE TWO");
+    assert_eq!(
+        upper
+            .runs()
+            .iter()
+            .map(|(_, style)| style)
+            .collect::<Vec<_>>(),
+        runs.runs()
+            .iter()
+            .map(|(_, style)| style)
+            .collect::<Vec<_>>(),
+    );
+    let dropped = runs.map_text(|text| text.replace("one ", ""));
+    assert_eq!(dropped.pieces().collect::<Vec<_>>(), ["two"]);
+    assert!(!dropped.is_empty());
+    assert!(dropped.map_text(|_| String::new()).is_empty());
+}
+#[test]
+fn attribute_introspection() {
+    let plain = "plain".to_sgr();
+    assert_eq!((plain.foreground(), plain.background()), (None, None));
+    assert_eq!(plain.styles().count(), 0);
+
+    let sgr = "text"
+        .color(RedFg)
+        .color(BlueFg)
+        .color(RgbBg(1, 2, 3))
+        .style(Bold)
+        .style(NotItalic)
+        .custom(58);
+    assert_eq!(sgr.foreground(), Some(BlueFg));
+    assert_eq!(sgr.background(), Some(RgbBg(1, 2, 3)));
+    assert!(sgr.styles().eq([Bold, NotItalic]));
+    assert!(sgr.has_style(&Bold) && sgr.has_style(&NotItalic));
+    assert!(!sgr.has_style(&Italic));
+    assert_eq!(sgr.custom_places, [58]);
+
+    let reset = "text"
+        .style(Reset)
+        .style(RapidBlinking)
+        .style(NotFramedEncircled);
+    assert!(reset
+        .styles()
+        .eq([Reset, RapidBlinking, NotFramedEncircled]));
+    assert_eq!(ColorKind::Default.as_bg(), Some(DefaultBg));
+}
+#[test]
+fn application_order() {
+    assert_eq!("x".style(Bold).style(NotBold).to_string(), "\x1b[22mx");
+    assert_eq!("x".style(NotBold).style(Bold).to_string(), "\x1b[1mx");
+    assert_eq!("x".color(RedFg).color(GreenFg).to_string(), "\x1b[32mx");
+    assert_eq!(
+        "x".color(RedFg)
+            .color(ByteBg(4))
+            .color(DefaultFg)
+            .to_string(),
+        "\x1b[39;48;5;4mx"
+    );
+    assert_eq!(
+        "x".style(Bold).color(RedFg).style(NotBold).to_string(),
+        "\x1b[31;22mx"
+    );
+    assert_eq!(
+        "x".style(Bold)
+            .color(RedFg)
+            .custom(58)
+            .style(Reset)
+            .style(Italic)
+            .color(BlueBg)
+            .to_string(),
+        "\x1b[0;44;3mx"
+    );
+    // the reset keeps the clean kind, but nothing placed before it
+    assert_eq!(
+        "x".style(Bold)
+            .clean(CleanKind::Reverse)
+            .style(Reset)
+            .style(Italic)
+            .to_string(),
+        "\x1b[0;3mx\x1b[23m"
+    );
+    // codes shared between styles
+    assert_eq!("x".style(NotDim).style(Bold).to_string(), "\x1b[22;1mx");
+    assert_eq!("x".style(Dim).style(NotBold).to_string(), "\x1b[22mx");
+    assert_eq!("x".style(Bold).style(NotDim).to_string(), "\x1b[22mx");
+    assert_eq!(
+        "x".style(RapidBlinking).style(NotBlinking).to_string(),
+        "\x1b[25mx"
+    );
+    assert_eq!(
+        "x".style(Encircled)
+            .style(NotFramedEncircled)
+            .style(Framed)
+            .to_string(),
+        "\x1b[51mx"
+    );
+    assert_eq!(
+        "x".style(NotItalic)
+            .style(Underline)
+            .style(NotDim)
+            .style(Bold)
+            .clean(CleanKind::Reverse)
+            .to_string(),
+        "\x1b[22;23;1;4mx\x1b[22;24;2;3m"
+    );
+}
+#[test]
+fn rendered_len() {
+    let colors = [
+        ColorKind::None,
+        ColorKind::Default,
+        ColorKind::Red,
+        ColorKind::BrightWhite,
+        ColorKind::Byte(7),
+        ColorKind::Byte(42),
+        ColorKind::Byte(255),
+        ColorKind::Rgb(0, 9, 10),
+        ColorKind::Rgb(99, 100, 255),
+    ];
+    let styles = [StyleKind::None, StyleKind::Place, StyleKind::Clean];
+    for clean in [CleanKind::None, CleanKind::Reset, CleanKind::Reverse] {
+        for (foreground, background) in colors
+            .iter()
+            .flat_map(|fg| colors.iter().map(move |bg| (fg, bg)))
+        {
+            for (bold, italic) in styles
+                .iter()
+                .flat_map(|bold| styles.iter().map(move |italic| (bold, italic)))
+            {
+                for (reset, custom) in [(false, vec![]), (true, vec![5, 100]), (false, vec![255])] {
+                    let sgr = SGRString {
+                        text: String::from("tëxt"),
+                        clean: clean.clone(),
+                        foreground: foreground.clone(),
+                        background: background.clone(),
+                        bold: bold.clone(),
+                        italic: italic.clone(),
+                        reset,
+                        custom_places: custom.clone(),
+                        custom_cleans: custom,
+                        ..Default::default()
+                    };
+                    let rendered = sgr.render_to_string();
+                    assert_eq!(sgr.rendered_len(), rendered.len(), "{rendered:?}");
+                    assert_eq!(rendered, sgr.to_string());
+                }
+            }
+        }
+    }
+}
+#[test]
+fn style_macro() {
+    use easy_sgr::style;
+
+    let n = 5;
+    let mixed = style!(Bold, RedFg, Underline, BlueBg; "count = {}", n);
+    assert_eq!(mixed.text, "count = 5");
+    assert_eq!(
+        mixed.to_string(),
+        "\x1b[31;44;1;4mcount = 5\x1b[39;49;22;24m"
+    );
+    let single = style!(Italic; "{n:>3}");
+    assert_eq!(
+        single.to_string(),
+        "  5".style(Italic).clean(CleanKind::Reverse).to_string()
+    );
+    // attributes are any expression, the last color of each kind winning
+    let accent = Color::RgbFg(1, 2, 3);
+    let variable = style!(GreenFg, accent, easy_sgr::Style::Dim,; "{}{}", 'a', "b");
+    assert_eq!(variable.to_string(), "\x1b[38;2;1;2;3;2mab\x1b[39;22m");
+}
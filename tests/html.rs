@@ -0,0 +1,68 @@
+#![cfg(all(feature = "html", not(feature = "macro-only")))]
+use easy_sgr::{
+    html::{css, HtmlEscape, HtmlWriter},
+    CleanKind,
+    Color::*,
+    EasySGR, SGRString, SGRWriter,
+    Style::*,
+    StyleSet,
+};
+
+#[test]
+fn escaped() {
+    assert_eq!(
+        HtmlEscape(r#"<a href="x">'&'</a>"#).to_string(),
+        "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+    );
+    assert_eq!(
+        HtmlEscape(format_args!("{:>4}", "<")).to_string(),
+        "   &lt;"
+    );
+    assert_eq!(HtmlEscape("plain é").to_string(), "plain é");
+}
+#[test]
+fn to_html() {
+    assert_eq!(SGRString::default().to_html(), "");
+    assert_eq!("a&b".to_sgr().to_html(), "a&amp;b");
+    assert_eq!(
+        "x".color(ByteBg(208))
+            .style(Dim)
+            .clean(CleanKind::Reset)
+            .to_html(),
+        r#"<span style="background-color: rgb(255, 135, 0); opacity: 0.5">x</span>"#
+    );
+    // sequences within the text are applied
+    assert_eq!(
+        "a\x1b[3mb\x1b[23mc".color(GreenFg).to_html(),
+        concat!(
+            r#"<span style="color: rgb(0, 128, 0)">a</span>"#,
+            r#"<span style="color: rgb(0, 128, 0); font-style: italic">b</span>"#,
+            r#"<span style="color: rgb(0, 128, 0)">c</span>"#
+        )
+    );
+    assert_eq!("x".style(Blinking).to_html(), "x");
+}
+#[test]
+fn writer() -> Result<(), std::fmt::Error> {
+    let mut writer = SGRWriter::from(HtmlWriter::default());
+    writer.inline_sgr(&Bold)?;
+    writer.write_inner("a")?;
+    // the same CSS keeps the span open
+    writer.inline_sgr(&Blinking)?;
+    writer.write_inner("b")?;
+    assert_eq!(
+        writer.writer.html(),
+        r#"<span style="font-weight: bold">ab"#
+    );
+    writer.inline_sgr(&Reset)?;
+    writer.write_inner("c")?;
+    assert_eq!(
+        writer.internal(),
+        r#"<span style="font-weight: bold">ab</span>c"#
+    );
+    assert_eq!(
+        css(&StyleSet::from((Hidden, RedFg, Framed))),
+        "color: transparent; border: 1px solid"
+    );
+    Ok(())
+}
@@ -0,0 +1,138 @@
+#![cfg(all(feature = "log", not(feature = "macro-only")))]
+use easy_sgr::{
+    env_logger_format, format_record, format_record_with, set_enabled, Color::*, EasySGR,
+    LevelStyle, SGRWriter,
+};
+use log::{Level, Record};
+
+fn render(level: Level, style: &LevelStyle) -> String {
+    let name = "world";
+    let mut writer = SGRWriter::from(String::new());
+    format_record_with(
+        &Record::builder()
+            .level(level)
+            .target("app::net")
+            .args(format_args!("hello {name}"))
+            .build(),
+        &mut writer,
+        style,
+    )
+    .unwrap();
+    writer.internal()
+}
+fn colored() -> LevelStyle {
+    LevelStyle {
+        enabled: true,
+        ..LevelStyle::default()
+    }
+}
+#[test]
+fn levels() {
+    let style = colored();
+    assert_eq!(
+        render(Level::Error, &style),
+        "\x1b[31;1mERROR\x1b[39;22m \x1b[2mapp::net\x1b[22m: hello world"
+    );
+    assert_eq!(
+        render(Level::Warn, &style),
+        "\x1b[33mWARN\x1b[39m  \x1b[2mapp::net\x1b[22m: hello world"
+    );
+    assert_eq!(
+        render(Level::Info, &style),
+        "\x1b[32mINFO\x1b[39m  \x1b[2mapp::net\x1b[22m: hello world"
+    );
+    assert_eq!(
+        render(Level::Debug, &style),
+        "\x1b[36mDEBUG\x1b[39m \x1b[2mapp::net\x1b[22m: hello world"
+    );
+    assert_eq!(
+        render(Level::Trace, &style),
+        "\x1b[2mTRACE\x1b[22m \x1b[2mapp::net\x1b[22m: hello world"
+    );
+}
+#[test]
+fn overrides() {
+    let style = LevelStyle {
+        info: MagentaFg.to_sgr(),
+        target: Default::default(),
+        ..colored()
+    };
+    assert_eq!(
+        render(Level::Info, &style),
+        "\x1b[35mINFO  app::net: hello world"
+    );
+}
+#[test]
+fn disabled() {
+    let style = LevelStyle {
+        enabled: false,
+        ..LevelStyle::default()
+    };
+    assert_eq!(render(Level::Error, &style), "ERROR app::net: hello world");
+}
+#[test]
+fn global_switch() {
+    let record = Record::builder()
+        .level(Level::Info)
+        .target("app")
+        .args(format_args!("ready"))
+        .build();
+    let render = || {
+        let mut writer = SGRWriter::from(String::new());
+        format_record(&record, &mut writer).unwrap();
+        let mut buf = Vec::new();
+        env_logger_format(LevelStyle::default())(&mut buf, &record).unwrap();
+        (writer.internal(), String::from_utf8(buf).unwrap())
+    };
+    set_enabled(false);
+    let (formatted, env_logger) = render();
+    assert_eq!(formatted, "INFO  app: ready");
+    assert_eq!(env_logger, "INFO  app: ready\n");
+
+    set_enabled(true);
+    let (formatted, _) = render();
+    assert_eq!(formatted, "\x1b[32mINFO\x1b[39m  \x1b[2mapp\x1b[22m: ready");
+}
+#[test]
+fn env_logger() {
+    let format = env_logger_format(colored());
+    let mut buf = Vec::new();
+    format(
+        &mut buf,
+        &Record::builder()
+            .level(Level::Warn)
+            .target("app")
+            .args(format_args!("low disk"))
+            .build(),
+    )
+    .unwrap();
+    assert_eq!(buf, b"\x1b[33mWARN\x1b[39m  \x1b[2mapp\x1b[22m: low disk\n");
+
+    env_logger::Builder::new().format(env_logger_format(colored()));
+}
+#[cfg(feature = "theme")]
+#[test]
+fn themed() {
+    use easy_sgr::theme::{self, Theme};
+    use easy_sgr::StyleSet;
+
+    // the default theme & level styles agree
+    let (theme, style) = (Theme::default(), LevelStyle::default());
+    for (theme, level) in [
+        (&theme.error, &style.error),
+        (&theme.warn, &style.warn),
+        (&theme.info, &style.info),
+        (&theme.hint, &style.trace),
+    ] {
+        assert_eq!(StyleSet::from(theme), StyleSet::from(level));
+    }
+
+    let info = theme::with_scoped(
+        Theme {
+            info: MagentaFg.to_sgr(),
+            ..Theme::default()
+        },
+        || render(Level::Info, &colored()),
+    );
+    assert_eq!(info, "\x1b[35mINFO  \x1b[2mapp::net\x1b[22m: hello world");
+}
@@ -2,7 +2,10 @@
 mod macros {
     use std::fmt::Write;
 
-    use easy_sgr::{eprint, eprintln, format, format_args, print, println, sgr, write, writeln};
+    use easy_sgr::{
+        eprint, eprintln, format, format_args, keyword_manifest, print, println, sgr, sgr_static,
+        write, writeln,
+    };
 
     macro_rules! sgr_tests {
         ($($input:tt = $result:literal),*) => {
@@ -27,8 +30,21 @@ mod macros {
     fn raw_strings() {
         sgr_tests!(
             r"Not much to test for this one maybe, this can't really fail" =
-                "Not much to test for this one maybe, this can't really fail"
+                "Not much to test for this one maybe, this can't really fail",
+            r"#RedFg text#" = "#RedFg text#",
+            r##"#"# text ending with #"## = "#\"# text ending with #"
         );
+        // params turn the raw string into a str literal, escaping its contents
+        sgr_tests!(
+            r#"{[bold]}C:\dir\"quoted"{[]}"# = "\x1b[1mC:\\dir\\\"quoted\"\x1b[0m",
+            r"{ [red] }\n" = "\x1b[31m\\n"
+        );
+        let name = "file";
+        assert_eq!(
+            format!(r#"{[italic]}"{name}"\{:>3}{{[]}}"#, 1),
+            "\x1b[3m\"file\"\\  1{[]}"
+        );
+        assert_eq!(sgr_static!(r#"{[bold]}\"{{}}"#), "\x1b[1m\\\"{}");
     }
     #[test]
     fn styles() {
@@ -39,9 +55,13 @@ mod macros {
             "{[italic]}" = "\x1b[3m",
             "{[underline]}" = "\x1b[4m",
             "{[blink]}" = "\x1b[5m",
+            "{[rapid-blink]}" = "\x1b[6m",
             "{[inverse]}" = "\x1b[7m",
             "{[hide]}" = "\x1b[8m",
             "{[strike]}" = "\x1b[9m",
+            "{[framed]}" = "\x1b[51m",
+            "{[encircled]}" = "\x1b[52m",
+            "{[overline]}" = "\x1b[53m",
             "{[!bold]}" = "\x1b[22m",
             "{[!dim]}" = "\x1b[22m",
             "{[!italic]}" = "\x1b[23m",
@@ -49,7 +69,26 @@ mod macros {
             "{[!blink]}" = "\x1b[25m",
             "{[!inverse]}" = "\x1b[27m",
             "{[!hide]}" = "\x1b[28m",
-            "{[!strike]}" = "\x1b[29m"
+            "{[!strike]}" = "\x1b[29m",
+            "{[!framed]}" = "\x1b[54m",
+            "{[!encircled]}" = "\x1b[54m",
+            "{[!overline]}" = "\x1b[55m"
+        );
+    }
+    #[test]
+    fn fonts() {
+        sgr_tests!(
+            "{[font0]}" = "\x1b[10m",
+            "{[font1]}" = "\x1b[11m",
+            "{[font2]}" = "\x1b[12m",
+            "{[font3]}" = "\x1b[13m",
+            "{[font4]}" = "\x1b[14m",
+            "{[font5]}" = "\x1b[15m",
+            "{[font6]}" = "\x1b[16m",
+            "{[font7]}" = "\x1b[17m",
+            "{[font8]}" = "\x1b[18m",
+            "{[font9]}" = "\x1b[19m",
+            "{[font3 bold]}text{[font0]}" = "\x1b[13;1mtext\x1b[10m"
         );
     }
     #[test]
@@ -103,6 +142,280 @@ mod macros {
             "{[on-#ffffff]}" = "\x1b[48;2;255;255;255m"
         );
     }
+    #[test]
+    fn conditional() {
+        let name = "world";
+        let render = |use_color: bool| format!(if use_color; "{[bold red]}hello {name}{[]}");
+        assert_eq!(render(true), "\x1b[1;31mhello world\x1b[0m");
+        assert_eq!(render(false), "hello world");
+
+        let literal = |use_color: bool| sgr!(if use_color; "{[italic]}{{[]}{[!italic]}");
+        assert_eq!(literal(true), "\x1b[3m{[]}\x1b[23m");
+        assert_eq!(literal(false), "{[]}");
+
+        let mut written_to = String::new();
+        for use_color in [true, false] {
+            writeln!(if use_color; written_to, "{[on-#ff0000]}{}{[]}", 10 * 2).unwrap();
+        }
+        assert_eq!(written_to, "\x1b[48;2;255;0;0m20\x1b[0m\n20\n");
+
+        print!(if written_to.is_empty(); "{[green]}{name}{[]}");
+        println!();
+    }
+    #[test]
+    fn format_arguments() {
+        let user = "tim";
+        let capture = 'c';
+        // SGR-only groups do not consume positional arguments
+        assert_eq!(
+            format!(
+                "{[bold]}{}{[]} {name}: {count} {[red]}{}{capture}{1}{0}{[]}",
+                'a',
+                'b',
+                name = user,
+                count = 3
+            ),
+            "\x1b[1ma\x1b[0m tim: 3 \x1b[31mbcba\x1b[0m"
+        );
+        assert_eq!(
+            format!(
+                "{[italic]}{:>4}|{:<w$}|{value:.2}{[]}",
+                1,
+                "x",
+                w = 3,
+                value = 0.5
+            ),
+            "\x1b[3m   1|x  |0.50\x1b[0m"
+        );
+    }
+    #[test]
+    fn whitespace() {
+        assert_eq!(
+            sgr!(
+                "{ [ bold  red ] }text{[
+                italic
+                on-0, 0, 255
+            ]}"
+            ),
+            "\x1b[1;31mtext\x1b[3;48;2;0;0;255m"
+        );
+        assert_eq!(
+            format!(
+                "{[bold \
+                     red]}{}",
+                1
+            ),
+            "\x1b[1;31m1"
+        );
+    }
+    #[test]
+    fn expressions() {
+        struct User(&'static str);
+        impl User {
+            const fn name(&self) -> &str {
+                self.0
+            }
+        }
+        let user = User("tim");
+        let values = [4, 5];
+        assert_eq!(
+            format!("{[bold]}{user.name()}{[]} has {values[1] - values[0]:>3}"),
+            "\x1b[1mtim\x1b[0m has   1"
+        );
+        // expressions are added after the other arguments, leaving their order intact
+        assert_eq!(
+            format!("{} {(|| { 2 })()} {} {:?}", 'a', 'b', "c",),
+            "a 2 b \"c\""
+        );
+        assert_eq!(
+            format!("{(|x: u8| { x * 2 })(4)}{[red]}{values.len()}"),
+            "8\x1b[31m2"
+        );
+        let mut written_to = String::new();
+        writeln!(written_to, "{[green]}{user.name().len()}{}", 0).unwrap();
+        assert_eq!(written_to, "\x1b[32m30\n");
+    }
+    #[test]
+    fn fragments() {
+        let name = "world";
+        assert_eq!(
+            sgr!("{[bold]}two " "fragments{[]}"),
+            "\x1b[1mtwo fragments\x1b[0m"
+        );
+        assert_eq!(
+            sgr!(concat!("one, ", r"two\, ", "three{[red]}")),
+            "one, two\\, three\x1b[31m"
+        );
+        assert_eq!(
+            format!(
+                "{[italic]}hello "
+                "{name}, "
+                "{}{[]}",
+                1
+            ),
+            "\x1b[3mhello world, 1\x1b[0m"
+        );
+        // a param spanning the seam between fragments
+        assert_eq!(sgr!("{[bold " "red]}text"), "\x1b[1;31mtext");
+        assert_eq!(
+            format!(concat!("{[on-#ff", "0000]}{}", "{name}",), 2),
+            "\x1b[48;2;255;0;0m2world"
+        );
+
+        let mut written_to = String::new();
+        write!(written_to, concat!("{[green]}", "{}"), 3).unwrap();
+        writeln!(written_to, "{[]}" " done").unwrap();
+        assert_eq!(written_to, "\x1b[32m3\x1b[0m done\n");
+    }
+    #[test]
+    fn forwarded_literals() {
+        macro_rules! styled {
+            ($lit:literal) => {
+                sgr!($lit)
+            };
+            ($lit:literal, $($arg:tt)*) => {
+                format!($lit, $($arg)*)
+            };
+        }
+        macro_rules! twice {
+            ($lit:literal) => {
+                styled!($lit)
+            };
+        }
+        assert_eq!(styled!("{[bold]}a{[]}"), "\x1b[1ma\x1b[0m");
+        assert_eq!(styled!("{[red]}{}{[]}", 1), "\x1b[31m1\x1b[0m");
+        assert_eq!(twice!("{[italic]}b"), "\x1b[3mb");
+    }
+    #[test]
+    fn groups() {
+        let (a, b) = ("x", 2);
+        assert_eq!(
+            format!("{[>bold red]}{a} and {b:>3}{[<]}!"),
+            "\x1b[1;31mx and   2\x1b[22;39m!"
+        );
+        assert_eq!(
+            format!("{[>blue]}{a}{[>red italic]}{b}{[<]}{a}{[<]}"),
+            "\x1b[34mx\x1b[31;3m2\x1b[39;23;34mx\x1b[39m"
+        );
+        assert_eq!(format!(if false; "{[>bold]}{a}{[<]}"), "x");
+    }
+    #[test]
+    #[cfg(not(feature = "macro-only"))]
+    fn dynamic_colors() {
+        use easy_sgr::{Color, Style};
+
+        let colors = [Color::RedFg, Color::RgbBg(1, 2, 3)];
+        assert_eq!(format!("{[bold (colors[0])]}a{[]}"), "\x1b[1;31ma\x1b[0m");
+        assert_eq!(
+            format!("{[(colors[1]) italic]}{}", 1),
+            "\x1b[48;2;1;2;3;3m1"
+        );
+        // the default color is written as its code, resetting only the color
+        let default = Color::DefaultFg;
+        assert_eq!(format!("{[bold (default)]}x"), "\x1b[1;39mx");
+        assert_eq!(format!("{[(Style::Underline)]}x"), "\x1b[4mx");
+        assert_eq!(format!(if false; "{[bold (default)]}x"), "x");
+    }
+    #[test]
+    #[cfg(not(feature = "macro-only"))]
+    fn merged_captures() {
+        use easy_sgr::{CleanKind, Color::*, EasySGR};
+
+        let red = "red".color(RedFg).clean(CleanKind::Reset);
+        assert_eq!(format!("{red&[bold]}"), "\x1b[31;1mred\x1b[39;22m");
+        // the value's own colors win over the merged ones
+        assert_eq!(
+            format!("{red&[blue underline]}!"),
+            "\x1b[31;4mred\x1b[39;24m!"
+        );
+        assert_eq!(format!("{1 + 1&[bold]}"), "\x1b[1m2\x1b[22m");
+        assert_eq!(format!(if false; "{red&[bold]}"), "\x1b[31mred\x1b[0m");
+    }
+    #[test]
+    #[cfg(all(feature = "html", not(feature = "macro-only")))]
+    fn format_html() {
+        use easy_sgr::{format_html, CleanKind, Color::*, EasySGR, Style::*};
+
+        assert_eq!(
+            format_html!("{[bold red]}hi{[]}"),
+            "hi".style(Bold)
+                .color(RedFg)
+                .clean(CleanKind::Reset)
+                .to_html()
+        );
+        assert_eq!(
+            format_html!("{[on-1,2,3 italic]}a < b{[]}"),
+            "a < b"
+                .color(RgbBg(1, 2, 3))
+                .style(Italic)
+                .clean(CleanKind::Reset)
+                .to_html()
+        );
+        let value = "<&>";
+        assert_eq!(
+            format_html!("{[underline]}{value}{[!underline]} & {value:?}"),
+            r#"<span style="text-decoration: underline">&lt;&amp;&gt;</span> &amp; &quot;&lt;&amp;&gt;&quot;"#
+        );
+        assert_eq!(
+            format_html!(if false; "{[bold]}<{value}"),
+            "&lt;&lt;&amp;&gt;"
+        );
+    }
+    #[test]
+    fn partial_resets() {
+        assert_eq!(
+            format!("{[bold red]}a{[reset-colors]}b{[reset-effects]}"),
+            "\x1b[1;31ma\x1b[39;49mb\x1b[22;23;24;25;27;28;29m"
+        );
+    }
+    #[test]
+    fn format_specs() {
+        let (name, width, prec) = ("x", 6, 2);
+        assert_eq!(
+            format!("{:>5} {[bold]}x{[]}", 1),
+            std::format!("{:>5} \x1b[1mx\x1b[0m", 1)
+        );
+        assert_eq!(
+            format!("{[red]}{0:>8}|{:+.3e}|{:#x}|{[]}", 1.5, 255),
+            std::format!("\x1b[31m{0:>8}|{:+.3e}|{:#x}|\x1b[0m", 1.5, 255)
+        );
+        assert_eq!(
+            format!("{[italic]}{:width$.prec$}|{:.*}|{name:[^5}", 1.0, 1, 2.25),
+            std::format!("\x1b[3m{:width$.prec$}|{:.*}|{name:[^5}", 1.0, 1, 2.25)
+        );
+    }
+    #[test]
+    fn static_strings() {
+        const PROMPT: &str = sgr_static!("{[bold green]}>{[]} ");
+        assert_eq!(PROMPT, "\x1b[1;32m>\x1b[0m ");
+        // the same bytes as formatting the literal
+        for (literal, formatted) in [
+            (
+                sgr_static!("{[bold green]}>{[]} "),
+                format!("{[bold green]}>{[]} "),
+            ),
+            (sgr_static!("{{[red]}} {{}}"), format!("{{[red]}} {{}}")),
+            (
+                sgr_static!("{[italic]}a " "b{[]}"),
+                format!("{[italic]}a " "b{[]}"),
+            ),
+        ] {
+            assert_eq!(literal, formatted);
+        }
+        assert_eq!(sgr_static!(r"{x}"), "{x}");
+    }
+    #[test]
+    fn manifest() {
+        const MANIFEST: &str = keyword_manifest!();
+        assert!(MANIFEST
+            .starts_with(r#"{"keywords":[{"keyword":"reset","category":"style","codes":[0]}"#));
+        assert!(MANIFEST.contains(r#"{"keyword":"on-red","category":"background","codes":[41]}"#));
+        assert!(MANIFEST.ends_with("]}"));
+        // written out for editor tooling
+        let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("keywords.json");
+        std::fs::write(&path, MANIFEST).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), MANIFEST);
+    }
     /// really just for improving coverage numbers
     /// the other tests cover pretty much everything
     #[test]
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "osc", not(feature = "macro-only")))]
+use easy_sgr::{
+    osc::{CopyToClipboard, SetTitle},
+    IoWriter,
+};
+
+#[test]
+fn titles() {
+    for (title, sequence) in [
+        ("", "\x1b]2;\x1b\\"),
+        ("build: ok", "\x1b]2;build: ok\x1b\\"),
+        ("日本語 – ünïcode", "\x1b]2;日本語 – ünïcode\x1b\\"),
+        ("a\x07b\x1b\\c\u{9c}d\ne", "\x1b]2;ab\\cde\x1b\\"),
+    ] {
+        assert_eq!(SetTitle(title).to_string(), sequence, "{title:?}");
+    }
+}
+#[test]
+fn clipboard() {
+    for (bytes, sequence) in [
+        (&b""[..], "\x1b]52;c;\x1b\\"),
+        (b"f", "\x1b]52;c;Zg==\x1b\\"),
+        (b"fo", "\x1b]52;c;Zm8=\x1b\\"),
+        (b"foo", "\x1b]52;c;Zm9v\x1b\\"),
+        (b"foobar", "\x1b]52;c;Zm9vYmFy\x1b\\"),
+        (b"\xff\xfe\x00", "\x1b]52;c;//4A\x1b\\"),
+    ] {
+        assert_eq!(CopyToClipboard(bytes).to_string(), sequence, "{bytes:?}");
+    }
+    // spans several chunks
+    let bytes: Vec<u8> = (0..=255).collect();
+    let mut out = IoWriter(Vec::new());
+    CopyToClipboard(&bytes).write_to(&mut out).unwrap();
+    let out = String::from_utf8(out.0).unwrap();
+    let payload = &out["\x1b]52;c;".len()..out.len() - 2];
+    assert_eq!(payload.len(), 344);
+    assert!(payload.starts_with("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+P0BBQkNERUZHSElKS0xNTk9Q"));
+    assert!(payload.ends_with("+/w=="));
+}
@@ -0,0 +1,230 @@
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{
+    palette256::{cube, gray, to_rgb},
+    Color,
+};
+
+#[test]
+fn system_colors() {
+    let xterm = [
+        (0, 0, 0),
+        (128, 0, 0),
+        (0, 128, 0),
+        (128, 128, 0),
+        (0, 0, 128),
+        (128, 0, 128),
+        (0, 128, 128),
+        (192, 192, 192),
+        (128, 128, 128),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (0, 0, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    for (index, rgb) in (0..16).zip(xterm) {
+        assert_eq!(to_rgb(index), rgb, "{index}");
+    }
+}
+#[test]
+fn cube_entries() {
+    for (components, index, rgb) in [
+        ((0, 0, 0), 16, (0, 0, 0)),
+        ((0, 0, 1), 17, (0, 0, 95)),
+        ((0, 1, 0), 22, (0, 95, 0)),
+        ((1, 0, 0), 52, (95, 0, 0)),
+        ((2, 3, 4), 110, (135, 175, 215)),
+        ((5, 2, 0), 208, (255, 135, 0)),
+        ((5, 5, 5), 231, (255, 255, 255)),
+    ] {
+        let (r, g, b) = components;
+        assert_eq!(cube(r, g, b), index);
+        assert_eq!(to_rgb(index), rgb);
+    }
+}
+#[test]
+fn gray_ramp() {
+    assert_eq!(gray(0), 232);
+    assert_eq!(gray(23), 255);
+    assert_eq!(to_rgb(232), (8, 8, 8));
+    assert_eq!(to_rgb(243), (118, 118, 118));
+    assert_eq!(to_rgb(255), (238, 238, 238));
+}
+#[test]
+fn color_constructors() {
+    assert_eq!(Color::cube_fg(5, 2, 0), Color::ByteFg(208));
+    assert_eq!(Color::cube_bg(0, 0, 0), Color::ByteBg(16));
+    assert_eq!(Color::gray_fg(1), Color::ByteFg(233));
+    assert_eq!(Color::gray_bg(23), Color::ByteBg(255));
+    // out of range is clamped
+    assert_eq!(Color::cube_fg(9, 0, 200), Color::ByteFg(cube(5, 0, 5)));
+    assert_eq!(Color::gray_bg(100), Color::ByteBg(255));
+}
+#[test]
+#[should_panic = "cube components range from 0 to 5"]
+fn cube_out_of_range() {
+    let _ = cube(std::hint::black_box(6), 0, 0);
+}
+#[test]
+#[should_panic = "gray levels range from 0 to 23"]
+fn gray_out_of_range() {
+    let _ = gray(std::hint::black_box(24));
+}
+#[test]
+fn contrasting_fg() {
+    use easy_sgr::{Color::*, ColorKind, EasySGR, SGRString};
+    for (background, expected) in [
+        (BlackBg, WhiteFg),
+        (WhiteBg, BlackFg),
+        (BlueBg, WhiteFg),
+        (YellowBg, BlackFg),
+        (CyanBg, WhiteFg),
+        (ByteBg(231), BlackFg),
+        (ByteBg(16), WhiteFg),
+        (Color::gray_bg(0), WhiteFg),
+        (Color::gray_bg(23), BlackFg),
+        (RgbBg(255, 255, 0), BlackFg),
+        (RgbBg(0, 0, 255), WhiteFg),
+        // mid-grays either side of the threshold
+        (RgbBg(117, 117, 117), WhiteFg),
+        (RgbBg(118, 118, 118), BlackFg),
+        (DefaultBg, DefaultFg),
+    ] {
+        assert_eq!(background.contrasting_fg(), expected, "{background:?}");
+    }
+    assert_eq!(
+        WhiteBg.contrasting_fg_with(ByteFg(232), ByteFg(255)),
+        ByteFg(232)
+    );
+    assert_eq!(
+        " tag ".color(ByteBg(17)).auto_fg().foreground,
+        ColorKind::White
+    );
+    assert_eq!(
+        SGRString::from("text").auto_fg().foreground,
+        ColorKind::None
+    );
+}
+#[test]
+fn hashed_colors() {
+    use easy_sgr::palette256::is_readable;
+    // stable across runs & builds
+    assert_eq!(Color::hashed_fg("web"), Color::ByteFg(90));
+    assert_eq!(Color::hashed_fg("db"), Color::ByteFg(96));
+    assert_eq!(Color::hashed_fg(42u32), Color::ByteFg(219));
+
+    let mut seen = std::collections::HashSet::new();
+    for key in 0..10_000 {
+        let Color::ByteFg(index) = Color::hashed_fg(key) else {
+            panic!("hashed_fg returned a non byte color");
+        };
+        assert!(is_readable(index), "{index}");
+        seen.insert(index);
+    }
+    assert!(seen.len() > 100, "only {} colors used", seen.len());
+    for unreadable in [cube(0, 0, 0), cube(0, 0, 1), cube(5, 5, 5), cube(5, 5, 4)] {
+        assert!(!seen.contains(&unreadable), "{unreadable}");
+    }
+}
+#[cfg(feature = "rand")]
+#[test]
+fn random_colors() {
+    use easy_sgr::palette256::is_readable;
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let Color::ByteFg(index) = Color::random_fg(&mut rng) else {
+            panic!("random_fg returned a non byte color");
+        };
+        assert!(is_readable(index), "{index}");
+    }
+}
+#[test]
+fn nearest_colors() {
+    use easy_sgr::palette256::nearest;
+    // exact matches of the cube & ramp
+    for index in 16..=255 {
+        assert_eq!(to_rgb(nearest(to_rgb(index))), to_rgb(index), "{index}");
+    }
+    assert_eq!(nearest((0, 0, 0)), cube(0, 0, 0));
+    assert_eq!(nearest((255, 255, 255)), cube(5, 5, 5));
+    assert_eq!(nearest((1, 2, 200)), cube(0, 0, 4));
+    assert_eq!(nearest((120, 122, 119)), gray(11));
+}
+#[test]
+fn nearest_cache() {
+    use easy_sgr::palette256::{nearest, NearestCache};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // identical results to the uncached conversion, over random colors & many evictions
+    let mut rng = StdRng::seed_from_u64(362);
+    let mut cache = NearestCache::new();
+    for _ in 0..10_000 {
+        let rgb = (rng.random(), rng.random(), rng.random());
+        assert_eq!(cache.get(rgb), nearest(rgb), "{rgb:?}");
+    }
+    assert_eq!(cache.hits() + cache.misses(), 10_000);
+    // black is not mistaken for an empty slot
+    let mut cache = NearestCache::new();
+    assert_eq!(cache.get((0, 0, 0)), nearest((0, 0, 0)));
+    assert_eq!(cache.get((0, 0, 0)), nearest((0, 0, 0)));
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    // a highlighted file uses a handful of theme colors, nearly every lookup hits
+    let theme = [
+        (198, 120, 221),
+        (97, 175, 239),
+        (152, 195, 121),
+        (229, 192, 123),
+        (224, 108, 117),
+        (171, 178, 191),
+        (92, 99, 112),
+        (86, 182, 194),
+    ];
+    let mut cache = NearestCache::new();
+    for token in 0..5_000 {
+        let rgb = theme[(token * 7 + token / 3) % theme.len()];
+        assert_eq!(cache.get(rgb), nearest(rgb));
+    }
+    assert_eq!(cache.misses(), theme.len() as u64);
+    assert_eq!(cache.hits(), 5_000 - theme.len() as u64);
+    cache.clear();
+    cache.get(theme[0]);
+    assert_eq!(cache.misses(), theme.len() as u64 + 1);
+}
+#[test]
+fn palette_maps() {
+    use easy_sgr::palette256::PaletteMap;
+    let map = PaletteMap::default();
+    for index in 0..16 {
+        assert_eq!(map.color(index), to_rgb(index));
+        assert_eq!(map.get(index), Some(to_rgb(index)));
+    }
+    assert_eq!(map.color(200), to_rgb(15));
+    assert_eq!(map.get(16), None);
+    let map = map.with_override(16, (1, 1, 1)).with_override(0, (2, 2, 2));
+    assert_eq!(
+        (map.get(16), map.get(0)),
+        (Some((1, 1, 1)), Some((2, 2, 2)))
+    );
+    // overrides leave the system colors as they are
+    assert_eq!(map.color(0), (0, 0, 0));
+}
+#[test]
+#[cfg(feature = "palettes")]
+fn builtin_palettes() {
+    use easy_sgr::palette256::{PaletteMap, DRACULA, GRUVBOX, SOLARIZED};
+    for (map, red) in [
+        (SOLARIZED, (220, 50, 47)),
+        (DRACULA, (255, 85, 85)),
+        (GRUVBOX, (204, 36, 29)),
+    ] {
+        assert_eq!(map.color(1), red);
+        assert_ne!(map, PaletteMap::default());
+        // every entry is its own color
+        for (i, j) in (0..16).flat_map(|i| (0..i).map(move |j| (i, j))) {
+            assert_ne!(map.color(i), map.color(j), "{i} & {j}");
+        }
+    }
+}
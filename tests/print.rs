@@ -0,0 +1,74 @@
+//! Runs itself as a child process, so output written to stdout can be captured
+#![cfg(not(feature = "macro-only"))]
+use std::process::Command;
+
+use easy_sgr::{eprint_sgr, fmt_sgr, print_sgr, set_enabled, write_sgr, Color::*, Style::*};
+
+/// Set for the child, to the switch it is to use
+const CHILD: &str = "EASY_SGR_PRINT_CHILD";
+
+/// Prints through the global switch, only doing anything within the child
+#[test]
+fn child() {
+    let Some(switch) = std::env::var_os(CHILD) else {
+        return;
+    };
+    match switch.to_str() {
+        Some("on") => set_enabled(true),
+        Some("off") => set_enabled(false),
+        _ => (),
+    }
+    print_sgr(&Bold).unwrap();
+    print_sgr(&RedFg).unwrap();
+    eprint_sgr(&Italic).unwrap();
+}
+/// Returns the stdout & stderr of the child using the given switch
+fn run_child(switch: &str, no_color: bool) -> (Vec<u8>, Vec<u8>) {
+    let mut command = Command::new(std::env::current_exe().unwrap());
+    command
+        .args(["--exact", "child", "--nocapture", "--test-threads=1"])
+        .env(CHILD, switch)
+        .env_remove("NO_COLOR");
+    if no_color {
+        command.env("NO_COLOR", "1");
+    }
+    let output = command.output().unwrap();
+    assert!(output.status.success());
+    (output.stdout, output.stderr)
+}
+/// Returns true if `needle` is within `haystack`
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+#[test]
+fn printed() {
+    let (stdout, stderr) = run_child("on", true);
+    assert!(contains(&stdout, b"\x1b[1m\x1b[31m"));
+    assert!(contains(&stderr, b"\x1b[3m"));
+    let (stdout, _) = run_child("unset", false);
+    assert!(contains(&stdout, b"\x1b[1m\x1b[31m"));
+}
+#[test]
+fn disabled() {
+    for (switch, no_color) in [("off", false), ("unset", true)] {
+        let (stdout, stderr) = run_child(switch, no_color);
+        assert!(!contains(&stdout, b"\x1b["), "{switch}");
+        assert!(!contains(&stderr, b"\x1b["), "{switch}");
+    }
+}
+#[test]
+fn one_call() {
+    let mut out = Vec::new();
+    write_sgr(&mut out, &Bold).unwrap();
+    write_sgr(&mut out, &RgbBg(1, 2, 3)).unwrap();
+    assert_eq!(out, b"\x1b[1m\x1b[48;2;1;2;3m");
+
+    struct Styled;
+    impl std::fmt::Display for Styled {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fmt_sgr(f, &Underline)?;
+            f.write_str("text")
+        }
+    }
+    assert_eq!(Styled.to_string(), "\x1b[4mtext");
+}
@@ -0,0 +1,46 @@
+#![cfg(all(feature = "proptest", not(feature = "macro-only")))]
+use easy_sgr::{
+    proptest::{any_color, any_sgr_string, any_style},
+    DiscreteSGR, EasySGR, SGRBuilder, SGRString, StyleSet,
+};
+use proptest::prelude::*;
+
+/// Renders the codes placed by the string as a parameter list
+fn render(sgr: &SGRString) -> String {
+    let mut builder = SGRBuilder::default();
+    sgr.place_all(&mut builder);
+    let codes: Vec<_> = builder.codes().iter().map(u8::to_string).collect();
+    codes.join(";")
+}
+/// Parses a parameter list back into a string's attributes
+fn parse(params: &str) -> SGRString {
+    SGRString::default().apply(StyleSet::from_sgr_params(params).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn render_parse_idempotent(sgr in any_sgr_string()) {
+        // a lone custom 38 or 48 reads as an incomplete extended color
+        prop_assume!(!sgr.custom_places.iter().any(|&code| code == 38 || code == 48));
+        let once = render(&parse(&render(&sgr)));
+        let twice = render(&parse(&once));
+        prop_assert_eq!(once, twice);
+    }
+    #[test]
+    fn discrete_round_trip(style in any_style(), color in any_color()) {
+        for params in [style.sgr_params(), color.sgr_params()] {
+            let params: Vec<_> = params.iter().map(u8::to_string).collect();
+            let params = params.join(";");
+            // read back as the same attributes, though a reset clears the set
+            // & shared off codes, such as `22`, are written for each style they clean
+            let parsed = StyleSet::from_sgr_params(&params).unwrap();
+            let rendered = render(&SGRString::default().apply(parsed.clone()));
+            prop_assert_eq!(StyleSet::from_sgr_params(&rendered).unwrap(), parsed);
+        }
+    }
+    #[test]
+    fn text_is_kept(sgr in any_sgr_string()) {
+        prop_assert!(sgr.text.chars().count() <= easy_sgr::proptest::MAX_TEXT_LEN);
+        prop_assert!(sgr.to_string().contains(&sgr.text));
+    }
+}
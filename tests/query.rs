@@ -0,0 +1,76 @@
+#![cfg(all(feature = "query", not(feature = "macro-only")))]
+use easy_sgr::query::{parse_background_reply, query_background, Luma};
+
+#[test]
+fn replies() {
+    for (reply, rgb) in [
+        // xterm
+        (
+            &b"\x1b]11;rgb:ffff/ffff/ffff\x1b\\"[..],
+            Some((0xffff, 0xffff, 0xffff)),
+        ),
+        // gnome terminal & other vte terminals, ended by BEL
+        (
+            b"\x1b]11;rgb:1717/1717/1717\x07",
+            Some((0x1717, 0x1717, 0x1717)),
+        ),
+        // kitty, followed by the answer to the attributes query
+        (
+            b"\x1b]11;rgb:2828/2c2c/3434\x1b\\\x1b[?62;c",
+            Some((0x2828, 0x2c2c, 0x3434)),
+        ),
+        // rxvt-unicode, with an alpha channel
+        (b"\x1b]11;rgba:0000/0000/0000/e5e5\x1b\\", Some((0, 0, 0))),
+        // fewer digits are scaled
+        (b"\x1b]11;rgb:ff/80/0\x07", Some((0xffff, 0x8080, 0))),
+        (b"\x1b]11;rgb:fff/000/888\x07", Some((0xffff, 0, 0x8888))),
+        // terminals not supporting the query only answer the attributes query
+        (b"\x1b[?1;2c", None),
+        (b"", None),
+        (b"\x1b]11;rgb:ffff/ffff\x1b\\", None),
+        (b"\x1b]11;rgb:ffff/ffff/ffff/ffff\x1b\\", None),
+        (b"\x1b]11;rgb:fffff/0/0\x1b\\", None),
+        (b"\x1b]11;rgb:gg/0/0\x1b\\", None),
+        (b"\x1b]11;hsl:0/0/0\x1b\\", None),
+        // cut short
+        (b"\x1b]11;rgb:ffff/ffff/ffff", None),
+    ] {
+        assert_eq!(parse_background_reply(reply), rgb, "{reply:?}");
+    }
+}
+#[test]
+fn luma() {
+    for (rgb, luma) in [
+        ((0xffff, 0xffff, 0xffff), Luma::Light),
+        ((0xfdfd, 0xf6f6, 0xe3e3), Luma::Light),
+        ((0x8080, 0x8080, 0x8080), Luma::Light),
+        ((0x0000, 0x0000, 0x0000), Luma::Dark),
+        ((0x2828, 0x2c2c, 0x3434), Luma::Dark),
+        ((0x0000, 0x2b2b, 0x3636), Luma::Dark),
+    ] {
+        assert_eq!(Luma::from_rgb16(rgb), luma, "{rgb:x?}");
+    }
+}
+#[test]
+fn colorfgbg() {
+    for (value, luma) in [
+        ("15;0", Some(Luma::Dark)),
+        ("0;15", Some(Luma::Light)),
+        ("0;7", Some(Luma::Light)),
+        ("7;8", Some(Luma::Dark)),
+        // rxvt places the xpm field between
+        ("0;default;15", Some(Luma::Light)),
+        ("default;default", None),
+        ("15;16", None),
+        ("", None),
+    ] {
+        assert_eq!(Luma::from_colorfgbg(value), luma, "{value:?}");
+    }
+}
+/// Needs a terminal answering the query, run with `cargo test -F query -- --ignored`
+#[test]
+#[ignore = "needs a terminal"]
+fn round_trip() {
+    let rgb = query_background(std::time::Duration::from_millis(500));
+    assert!(rgb.is_some(), "the terminal did not answer");
+}
@@ -0,0 +1,57 @@
+#![cfg(all(feature = "report", not(feature = "macro-only")))]
+use std::{error::Error, fmt};
+
+use easy_sgr::{report, report_with, CleanKind, EasySGR, ReportStyle, SGRString};
+
+#[derive(Debug)]
+struct Chain(&'static str, Option<Box<Chain>>);
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+impl Error for Chain {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.1.as_deref().map(|e| e as _)
+    }
+}
+fn chain() -> Chain {
+    Chain(
+        "failed to load config",
+        Some(Box::new(Chain(
+            "failed to read file\npermission denied",
+            Some(Box::new(Chain("os error 13", None))),
+        ))),
+    )
+}
+#[test]
+fn default_style() {
+    assert_eq!(
+        report(&chain()).to_string(),
+        "\x1b[2merror:\x1b[22m \x1b[31;1mfailed to load config\x1b[39;22m\n\
+        \x20 \x1b[2mcaused by:\x1b[22m \x1b[31mfailed to read file\x1b[39m\n\
+        \x20            \x1b[31mpermission denied\x1b[39m\n\
+        \x20   \x1b[2mcaused by:\x1b[22m \x1b[31mos error 13\x1b[39m"
+    );
+}
+#[test]
+fn custom_style() {
+    let style = ReportStyle {
+        label: SGRString::default(),
+        message: "".clean(CleanKind::Reset),
+        source: SGRString::default(),
+        indent: 4,
+    };
+    assert_eq!(
+        report_with(&chain(), &style).to_string(),
+        "error: failed to load config\x1b[0m\n    \
+        caused by: failed to read file\n               \
+        permission denied\n        \
+        caused by: os error 13"
+    );
+}
+#[test]
+fn single() {
+    let err = Chain("", None);
+    assert_eq!(report(&err).to_string(), "\x1b[2merror:\x1b[22m ");
+}
@@ -0,0 +1,400 @@
+//! Checks every representation of each [`Style`] & [`Color`] against a single table
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{Color, DiscreteSGR, SGRWriter, Style};
+
+/// A row of the table: the value, its codes, its name, its macro keyword & its undo
+struct Case<T: 'static> {
+    value: T,
+    codes: &'static [u8],
+    #[cfg_attr(not(feature = "from-str"), allow(dead_code))]
+    name: &'static str,
+    #[cfg(feature = "macros")]
+    keyword: &'static str,
+    undo: T,
+}
+macro_rules! case {
+    ($value:expr, $codes:expr, $name:literal, $keyword:tt, $undo:expr) => {
+        Case {
+            value: $value,
+            codes: &$codes,
+            name: $name,
+            #[cfg(feature = "macros")]
+            keyword: easy_sgr::sgr!($keyword),
+            undo: $undo,
+        }
+    };
+}
+/// The position of each style within [`STYLES`]
+const fn style_ordinal(style: &Style) -> usize {
+    use Style::*;
+    match style {
+        // no wildcard, new variants must be added here & to STYLES
+        Reset => 0,
+        Bold => 1,
+        Dim => 2,
+        Italic => 3,
+        Underline => 4,
+        Blinking => 5,
+        RapidBlinking => 6,
+        Inverse => 7,
+        Hidden => 8,
+        Strikethrough => 9,
+        Framed => 10,
+        Encircled => 11,
+        Overline => 12,
+        NotBold => 13,
+        NotDim => 14,
+        NotItalic => 15,
+        NotUnderline => 16,
+        NotBlinking => 17,
+        NotInverse => 18,
+        NotHidden => 19,
+        NotStrikethrough => 20,
+        NotFramedEncircled => 21,
+        NotOverline => 22,
+    }
+}
+const STYLES: [Case<Style>; 23] = {
+    use Style::*;
+    [
+        case!(Reset, [0], "Reset", "{[reset]}", Reset),
+        case!(Bold, [1], "Bold", "{[bold]}", NotBold),
+        case!(Dim, [2], "Dim", "{[dim]}", NotDim),
+        case!(Italic, [3], "Italic", "{[italic]}", NotItalic),
+        case!(Underline, [4], "Underline", "{[underline]}", NotUnderline),
+        case!(Blinking, [5], "Blinking", "{[blink]}", NotBlinking),
+        case!(
+            RapidBlinking,
+            [6],
+            "RapidBlinking",
+            "{[rapid-blink]}",
+            NotBlinking
+        ),
+        case!(Inverse, [7], "Inverse", "{[inverse]}", NotInverse),
+        case!(Hidden, [8], "Hidden", "{[hide]}", NotHidden),
+        case!(
+            Strikethrough,
+            [9],
+            "Strikethrough",
+            "{[strike]}",
+            NotStrikethrough
+        ),
+        case!(Framed, [51], "Framed", "{[framed]}", NotFramedEncircled),
+        case!(
+            Encircled,
+            [52],
+            "Encircled",
+            "{[encircled]}",
+            NotFramedEncircled
+        ),
+        case!(Overline, [53], "Overline", "{[overline]}", NotOverline),
+        case!(NotBold, [22], "NotBold", "{[!bold]}", NotBold),
+        case!(NotDim, [22], "NotDim", "{[!dim]}", NotDim),
+        case!(NotItalic, [23], "NotItalic", "{[!italic]}", NotItalic),
+        case!(
+            NotUnderline,
+            [24],
+            "NotUnderline",
+            "{[!underline]}",
+            NotUnderline
+        ),
+        case!(NotBlinking, [25], "NotBlinking", "{[!blink]}", NotBlinking),
+        case!(NotInverse, [27], "NotInverse", "{[!inverse]}", NotInverse),
+        case!(NotHidden, [28], "NotHidden", "{[!hide]}", NotHidden),
+        case!(
+            NotStrikethrough,
+            [29],
+            "NotStrikethrough",
+            "{[!strike]}",
+            NotStrikethrough
+        ),
+        case!(
+            NotFramedEncircled,
+            [54],
+            "NotFramedEncircled",
+            "{[!framed]}",
+            NotFramedEncircled
+        ),
+        case!(
+            NotOverline,
+            [55],
+            "NotOverline",
+            "{[!overline]}",
+            NotOverline
+        ),
+    ]
+};
+/// The position of each color within [`COLORS`]
+const fn color_ordinal(color: &Color) -> usize {
+    use Color::*;
+    match color {
+        // no wildcard, new variants must be added here & to COLORS
+        BlackFg => 0,
+        RedFg => 1,
+        GreenFg => 2,
+        YellowFg => 3,
+        BlueFg => 4,
+        MagentaFg => 5,
+        CyanFg => 6,
+        WhiteFg => 7,
+        ByteFg(_) => 8,
+        RgbFg(..) => 9,
+        DefaultFg => 10,
+        BlackBg => 11,
+        RedBg => 12,
+        GreenBg => 13,
+        YellowBg => 14,
+        BlueBg => 15,
+        MagentaBg => 16,
+        CyanBg => 17,
+        WhiteBg => 18,
+        ByteBg(_) => 19,
+        RgbBg(..) => 20,
+        DefaultBg => 21,
+        BrightBlackFg => 22,
+        BrightRedFg => 23,
+        BrightGreenFg => 24,
+        BrightYellowFg => 25,
+        BrightBlueFg => 26,
+        BrightMagentaFg => 27,
+        BrightCyanFg => 28,
+        BrightWhiteFg => 29,
+        BrightBlackBg => 30,
+        BrightRedBg => 31,
+        BrightGreenBg => 32,
+        BrightYellowBg => 33,
+        BrightBlueBg => 34,
+        BrightMagentaBg => 35,
+        BrightCyanBg => 36,
+        BrightWhiteBg => 37,
+    }
+}
+const COLORS: [Case<Color>; 38] = {
+    use Color::*;
+    [
+        case!(BlackFg, [30], "BlackFg", "{[black]}", DefaultFg),
+        case!(RedFg, [31], "RedFg", "{[red]}", DefaultFg),
+        case!(GreenFg, [32], "GreenFg", "{[green]}", DefaultFg),
+        case!(YellowFg, [33], "YellowFg", "{[yellow]}", DefaultFg),
+        case!(BlueFg, [34], "BlueFg", "{[blue]}", DefaultFg),
+        case!(MagentaFg, [35], "MagentaFg", "{[magenta]}", DefaultFg),
+        case!(CyanFg, [36], "CyanFg", "{[cyan]}", DefaultFg),
+        case!(WhiteFg, [37], "WhiteFg", "{[white]}", DefaultFg),
+        case!(
+            ByteFg(208),
+            [38, 5, 208],
+            "ByteFg(208)",
+            "{[208]}",
+            DefaultFg
+        ),
+        case!(
+            RgbFg(1, 2, 3),
+            [38, 2, 1, 2, 3],
+            "RgbFg(1,2,3)",
+            "{[1,2,3]}",
+            DefaultFg
+        ),
+        case!(DefaultFg, [39], "DefaultFg", "{[default]}", DefaultFg),
+        case!(BlackBg, [40], "BlackBg", "{[on-black]}", DefaultBg),
+        case!(RedBg, [41], "RedBg", "{[on-red]}", DefaultBg),
+        case!(GreenBg, [42], "GreenBg", "{[on-green]}", DefaultBg),
+        case!(YellowBg, [43], "YellowBg", "{[on-yellow]}", DefaultBg),
+        case!(BlueBg, [44], "BlueBg", "{[on-blue]}", DefaultBg),
+        case!(MagentaBg, [45], "MagentaBg", "{[on-magenta]}", DefaultBg),
+        case!(CyanBg, [46], "CyanBg", "{[on-cyan]}", DefaultBg),
+        case!(WhiteBg, [47], "WhiteBg", "{[on-white]}", DefaultBg),
+        case!(
+            ByteBg(208),
+            [48, 5, 208],
+            "ByteBg(208)",
+            "{[on-#d0]}",
+            DefaultBg
+        ),
+        case!(
+            RgbBg(1, 2, 3),
+            [48, 2, 1, 2, 3],
+            "RgbBg(1,2,3)",
+            "{[on-1,2,3]}",
+            DefaultBg
+        ),
+        case!(DefaultBg, [49], "DefaultBg", "{[on-default]}", DefaultBg),
+        case!(
+            BrightBlackFg,
+            [90],
+            "BrightBlackFg",
+            "{[bright-black]}",
+            DefaultFg
+        ),
+        case!(
+            BrightRedFg,
+            [91],
+            "BrightRedFg",
+            "{[bright-red]}",
+            DefaultFg
+        ),
+        case!(
+            BrightGreenFg,
+            [92],
+            "BrightGreenFg",
+            "{[bright-green]}",
+            DefaultFg
+        ),
+        case!(
+            BrightYellowFg,
+            [93],
+            "BrightYellowFg",
+            "{[bright-yellow]}",
+            DefaultFg
+        ),
+        case!(
+            BrightBlueFg,
+            [94],
+            "BrightBlueFg",
+            "{[bright-blue]}",
+            DefaultFg
+        ),
+        case!(
+            BrightMagentaFg,
+            [95],
+            "BrightMagentaFg",
+            "{[bright-magenta]}",
+            DefaultFg
+        ),
+        case!(
+            BrightCyanFg,
+            [96],
+            "BrightCyanFg",
+            "{[bright-cyan]}",
+            DefaultFg
+        ),
+        case!(
+            BrightWhiteFg,
+            [97],
+            "BrightWhiteFg",
+            "{[bright-white]}",
+            DefaultFg
+        ),
+        case!(
+            BrightBlackBg,
+            [100],
+            "BrightBlackBg",
+            "{[on-bright-black]}",
+            DefaultBg
+        ),
+        case!(
+            BrightRedBg,
+            [101],
+            "BrightRedBg",
+            "{[on-bright-red]}",
+            DefaultBg
+        ),
+        case!(
+            BrightGreenBg,
+            [102],
+            "BrightGreenBg",
+            "{[on-bright-green]}",
+            DefaultBg
+        ),
+        case!(
+            BrightYellowBg,
+            [103],
+            "BrightYellowBg",
+            "{[on-bright-yellow]}",
+            DefaultBg
+        ),
+        case!(
+            BrightBlueBg,
+            [104],
+            "BrightBlueBg",
+            "{[on-bright-blue]}",
+            DefaultBg
+        ),
+        case!(
+            BrightMagentaBg,
+            [105],
+            "BrightMagentaBg",
+            "{[on-bright-magenta]}",
+            DefaultBg
+        ),
+        case!(
+            BrightCyanBg,
+            [106],
+            "BrightCyanBg",
+            "{[on-bright-cyan]}",
+            DefaultBg
+        ),
+        case!(
+            BrightWhiteBg,
+            [107],
+            "BrightWhiteBg",
+            "{[on-bright-white]}",
+            DefaultBg
+        ),
+    ]
+};
+/// Checks every representation of the value against its row
+fn check<T>(ordinal: fn(&T) -> usize, undo: fn(&T) -> T, cases: &[Case<T>])
+where
+    T: DiscreteSGR + Clone + PartialEq + std::fmt::Debug,
+{
+    for (i, case) in cases.iter().enumerate() {
+        let value = &case.value;
+        assert_eq!(ordinal(value), i, "{value:?} is out of place");
+        assert_eq!(&*value.codes(), case.codes, "{value:?} codes");
+        assert_eq!(value.sgr_params(), case.codes, "{value:?} params");
+
+        let params = case
+            .codes
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(";");
+        let sequence = format!("\x1b[{params}m");
+        #[cfg(not(feature = "partial"))]
+        assert_eq!(value.to_string(), sequence, "{value:?} display");
+        #[cfg(feature = "partial")]
+        assert_eq!(value.to_string(), params, "{value:?} display");
+
+        let mut writer = SGRWriter::from(String::new());
+        writer.inline_sgr(value).unwrap();
+        assert_eq!(writer.internal(), sequence, "{value:?} writer");
+        assert_eq!(
+            value.clone().to_sgr().to_string(),
+            sequence,
+            "{value:?} SGRString"
+        );
+
+        #[cfg(feature = "macros")]
+        assert_eq!(case.keyword, sequence, "{value:?} keyword");
+
+        assert_eq!(undo(value), case.undo, "{value:?} undo");
+    }
+}
+#[test]
+fn styles() {
+    assert_eq!(STYLES.len(), style_ordinal(&Style::NotOverline) + 1);
+    check(style_ordinal, Style::undo, &STYLES);
+    #[cfg(feature = "from-str")]
+    for case in &STYLES {
+        assert_eq!(
+            case.name.parse(),
+            Ok(case.value.clone()),
+            "{} from str",
+            case.name
+        );
+    }
+}
+#[test]
+fn colors() {
+    assert_eq!(COLORS.len(), color_ordinal(&Color::BrightWhiteBg) + 1);
+    check(color_ordinal, Color::undo, &COLORS);
+    #[cfg(feature = "from-str")]
+    for case in &COLORS {
+        assert_eq!(
+            case.name.parse(),
+            Ok(case.value.clone()),
+            "{} from str",
+            case.name
+        );
+    }
+}
@@ -0,0 +1,219 @@
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{
+    highlight_matches, highlight_matches_with, CleanKind, Color::*, ColorKind, EasySGR,
+    HighlightOptions, SGRBuilder, SGRString, Style::*, StyleSet, TransitionOptions,
+};
+
+#[test]
+fn highlight() {
+    let text = SGRString::from("find the needle here").style_range(9..15, Bold.color(YellowBg));
+    assert_eq!(
+        text.to_string(),
+        "find the \x1b[43;1mneedle\x1b[49;22m here"
+    );
+    assert_eq!(text.runs().len(), 3);
+    assert_eq!(text.text(), "find the needle here");
+}
+#[test]
+fn rainbow() {
+    let colors = [RedFg, YellowFg, GreenFg, BlueFg];
+    let text = "hey!"
+        .to_sgr()
+        .map_chars(|i, _| colors[i % colors.len()].clone());
+    assert_eq!(
+        text.to_string(),
+        "\x1b[31mh\x1b[33me\x1b[32my\x1b[34m!\x1b[39m"
+    );
+}
+#[test]
+fn overlapping() {
+    let text = SGRString::from("abcdef")
+        .style_range(0..4, RedFg.style(Italic))
+        .style_range(2..6, BlueFg);
+    assert_eq!(text.to_string(), "\x1b[31;3mab\x1b[34mcd\x1b[23mef\x1b[39m");
+    assert_eq!(text.runs()[1].1.foreground, ColorKind::Blue);
+}
+#[test]
+fn merged_runs() {
+    let text = SGRString::from("aaaa")
+        .style_range(0..2, Bold)
+        .style_range(2..4, Bold);
+    assert_eq!(text.runs().len(), 1);
+    assert_eq!(text.to_string(), "\x1b[1maaaa\x1b[22m");
+}
+#[test]
+fn clamped() {
+    assert_eq!(
+        SGRString::from("abc").style_range(1..100, Bold).to_string(),
+        "a\x1b[1mbc\x1b[22m"
+    );
+    assert_eq!(
+        SGRString::from("abc").style_range(5..10, Bold).to_string(),
+        "abc"
+    );
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = SGRString::from("abc").style_range(2..1, Bold);
+    assert_eq!(reversed.to_string(), "abc");
+    assert_eq!(SGRString::default().style_range(.., Bold).to_string(), "");
+}
+#[test]
+fn base_style() {
+    let text = "a b"
+        .color(GreenFg)
+        .clean(CleanKind::Reverse)
+        .style_range(2..=2, RedFg.style(Bold));
+    assert_eq!(text.to_string(), "\x1b[32ma \x1b[31;1mb\x1b[32;22;39m");
+    assert_eq!(
+        "ab".style(Underline)
+            .clean(CleanKind::Reset)
+            .style_range(1.., Inverse)
+            .to_string(),
+        "\x1b[4ma\x1b[7mb\x1b[0m"
+    );
+}
+#[test]
+fn words() {
+    let text = SGRString::from("  one two  three ").map_words(|i, word| {
+        if i % 2 == 0 {
+            Bold.to_sgr()
+        } else {
+            word.color(CyanFg)
+        }
+    });
+    assert_eq!(
+        text.to_string(),
+        "  \x1b[1mone\x1b[22m \x1b[36mtwo\x1b[39m  \x1b[1mthree\x1b[22m "
+    );
+}
+#[test]
+fn transition() {
+    let mut builder = SGRBuilder::default();
+    let bold_dim = StyleSet::from(Bold.style(Dim));
+    bold_dim.transition(&StyleSet::from(Dim), &mut builder);
+    assert_eq!(builder.codes(), [22, 2]);
+
+    builder.clear();
+    StyleSet::from(Framed).transition(&StyleSet::from(Encircled.style(Overline)), &mut builder);
+    assert_eq!(builder.codes(), [54, 52, 53]);
+
+    builder.clear();
+    StyleSet::default().transition(&StyleSet::from(NotBold.custom(58)), &mut builder);
+    assert_eq!(builder.codes(), [22, 58]);
+
+    let mut layered = StyleSet::from(RedFg.style(Bold));
+    layered.layer(&StyleSet::from(NotBold.color(BlueBg)));
+    assert_eq!(layered, StyleSet::from(RedFg.style(NotBold).color(BlueBg)));
+    assert!(StyleSet::from("text").is_empty());
+}
+#[test]
+fn matches() {
+    let none = highlight_matches("nothing here", "zzz", Bold);
+    assert_eq!(none.to_string(), "nothing here");
+    assert_eq!(none.runs().len(), 1);
+    assert_eq!(highlight_matches("abc", "", Bold).to_string(), "abc");
+    assert_eq!(highlight_matches("", "a", Bold).to_string(), "");
+
+    assert_eq!(
+        highlight_matches("xabab-ab", "ab", RedFg).to_string(),
+        "x\x1b[31mabab\x1b[39m-\x1b[31mab\x1b[39m"
+    );
+    assert_eq!(
+        highlight_matches("aaa", "aa", Underline).to_string(),
+        "\x1b[4maa\x1b[24ma"
+    );
+    assert_eq!(
+        highlight_matches("ends with end", "end", Bold).to_string(),
+        "\x1b[1mend\x1b[22ms with \x1b[1mend\x1b[22m"
+    );
+    assert_eq!(
+        highlight_matches("ünï ünï", "nï", Bold).to_string(),
+        "ü\x1b[1mnï\x1b[22m ü\x1b[1mnï\x1b[22m"
+    );
+}
+#[test]
+fn case_insensitive() {
+    let options = HighlightOptions {
+        case_insensitive: true,
+    };
+    assert_eq!(
+        highlight_matches_with("Rust rust RÜST", "rüst", Bold, options).to_string(),
+        "Rust rust \x1b[1mRÜST\x1b[22m"
+    );
+    assert_eq!(
+        highlight_matches_with("Rust rust", "RUST", Bold, options).to_string(),
+        "\x1b[1mRust\x1b[22m \x1b[1mrust\x1b[22m"
+    );
+    assert_eq!(
+        highlight_matches("Rust rust", "RUST", Bold).to_string(),
+        "Rust rust"
+    );
+}
+#[cfg(feature = "regex")]
+#[test]
+fn regex() {
+    use easy_sgr::highlight_regex;
+    use regex::Regex;
+
+    let digits = Regex::new(r"\d").unwrap();
+    assert_eq!(
+        highlight_regex("a12b3", &digits, GreenFg).to_string(),
+        "a\x1b[32m12\x1b[39mb\x1b[32m3\x1b[39m"
+    );
+    assert_eq!(
+        highlight_regex("none", &digits, GreenFg).to_string(),
+        "none"
+    );
+    let word = Regex::new("(?i)wörd").unwrap();
+    assert_eq!(
+        highlight_regex("ä WÖRD", &word, Bold).to_string(),
+        "ä \x1b[1mWÖRD\x1b[22m"
+    );
+}
+#[test]
+fn transition_options() {
+    let text = SGRString::from("ab")
+        .color(RedFg)
+        .style_range(0..1, Inverse)
+        .style_range(1..2, GreenFg);
+    assert_eq!(text.to_string(), "\x1b[31;7ma\x1b[32;27mb\x1b[31m");
+    let text = text.transition_options(TransitionOptions {
+        reapply_colors_after_inverse_toggle: true,
+    });
+    assert_eq!(text.to_string(), "\x1b[31;7ma\x1b[32;27;32;49mb\x1b[31m");
+}
+#[test]
+fn joined() {
+    use easy_sgr::{join, join_styled};
+
+    assert_eq!(join(Vec::<SGRString>::new(), ", ").to_string(), "");
+    assert!(join(Vec::<SGRString>::new(), ", ").runs().is_empty());
+    // a single element has no separator
+    let single = join(["only".style(Bold)], ", ");
+    assert_eq!(single.to_string(), "\x1b[1monly\x1b[22m");
+    assert_eq!(single.text(), "only");
+    // the separator stays unstyled between identically styled fragments
+    let same = join(["a".style(Bold), "b".style(Bold), "c".style(Bold)], ", ");
+    assert_eq!(
+        same.to_string(),
+        "\x1b[1ma\x1b[22m, \x1b[1mb\x1b[22m, \x1b[1mc\x1b[22m"
+    );
+    assert_eq!(same.runs().len(), 5);
+    // a styled separator between plain fragments
+    let styled = join_styled(["a", "b", "c"], "|".color(BlueFg));
+    assert_eq!(styled.to_string(), "a\x1b[34m|\x1b[39mb\x1b[34m|\x1b[39mc");
+    // resets & cleans of fragments are left to the transitions
+    let cleaned = join(
+        [
+            "a".color(RedFg).clean(CleanKind::Reset),
+            SGRString {
+                reset: true,
+                ..SGRString::from("b")
+            },
+        ],
+        " ",
+    );
+    assert_eq!(cleaned.to_string(), "\x1b[31ma\x1b[39m b");
+    // empty separators & fragments add no runs
+    assert_eq!(join(["a", "", "b"], "").runs().len(), 1);
+    assert_eq!(join(["a", "", "b"], ",").text(), "a,,b");
+}
@@ -0,0 +1,72 @@
+#![cfg(not(feature = "macro-only"))]
+use easy_sgr::{CleanKind, Color::*, EasySGR, Span, SpanChild, Style::*, StyleSet};
+
+/// A block, a title within it & a keyword within the title, each overriding the color
+/// (the title being both dim & bold)
+fn nested() -> Span {
+    let keyword = Span::new((Italic, GreenFg)).child("run");
+    let title = Span::new((Bold, RedFg))
+        .child("Use ")
+        .child(keyword)
+        .child(" now");
+    Span::new((Dim, BlueFg))
+        .child("> ")
+        .child(title)
+        .child(" <")
+}
+#[test]
+fn nesting() {
+    assert_eq!(
+        nested().to_string(),
+        concat!(
+            "\x1b[34;2m> ",
+            "\x1b[31;1mUse ",
+            "\x1b[32;3mrun",
+            "\x1b[31;23m now",
+            "\x1b[34;22;2m <",
+            "\x1b[39;22m",
+        )
+    );
+    assert_eq!(nested().plain_text(), "> Use run now <");
+}
+#[test]
+fn siblings_restore_the_parent() {
+    let span = Span::new(Underline)
+        .child(Span::new(Bold).child("a"))
+        .child("b")
+        .child(Span::new(Underline.color(YellowFg)).child("c"))
+        .child(Span::new(NotUnderline).child("d"))
+        .child("e");
+    assert_eq!(
+        span.to_string(),
+        "\x1b[1;4ma\x1b[22mb\x1b[33mc\x1b[39;24md\x1b[4me\x1b[24m"
+    );
+    // nothing is written for empty children or spans
+    let cleared = Span::new(Bold).child(Span::new(RedFg)).child("").child("x");
+    assert_eq!(cleared.to_string(), "\x1b[1mx\x1b[22m");
+    assert_eq!(Span::new(Bold).to_string(), "");
+}
+#[test]
+fn from_sgr_string() {
+    let sgr = "x".color(CyanFg).style(Italic).clean(CleanKind::Reset);
+    let span = Span::from(sgr.clone());
+    assert_eq!(span.style, StyleSet::from(&sgr));
+    assert_eq!(span.children, [SpanChild::Text(String::from("x"))]);
+    let within = Span::new(Bold).child(sgr).child("y");
+    assert_eq!(within.to_string(), "\x1b[36;1;3mx\x1b[39;23my\x1b[22m");
+}
+#[test]
+#[cfg(feature = "testing")]
+fn rendered_equivalently() {
+    use easy_sgr::assert_sgr_eq;
+    assert_sgr_eq!(
+        nested(),
+        concat!(
+            "\x1b[2;34m> \x1b[0m",
+            "\x1b[2;1;31mUse \x1b[0m",
+            "\x1b[1;2;3;32mrun\x1b[0m",
+            "\x1b[1;2;31m now\x1b[0m",
+            "\x1b[2;34m <\x1b[0m",
+        )
+    );
+}
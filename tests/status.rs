@@ -0,0 +1,93 @@
+#![cfg(not(feature = "macro-only"))]
+use std::io;
+
+use easy_sgr::{EasySGR, StatusLine};
+
+/// Returns what `set` writes for each of the contents, in order
+fn updates(contents: &[&str]) -> io::Result<Vec<String>> {
+    let mut status = StatusLine::new(Vec::new());
+    let mut updates = Vec::new();
+    for content in contents {
+        let before = status.get_ref().len();
+        status.set(&content.to_sgr())?;
+        updates.push(String::from_utf8_lossy(&status.get_ref()[before..]).into_owned());
+    }
+    Ok(updates)
+}
+#[test]
+fn growing() -> io::Result<()> {
+    assert_eq!(updates(&["a", "ab", "abc"])?, ["\ra", "\rab", "\rabc"]);
+    Ok(())
+}
+#[test]
+#[cfg(not(feature = "control"))]
+fn shrinking() -> io::Result<()> {
+    assert_eq!(
+        updates(&["abcd", "ab", "", "é"])?,
+        ["\rabcd", "\rab  ", "\r  ", "\ré"]
+    );
+    Ok(())
+}
+#[test]
+#[cfg(feature = "control")]
+fn shrinking() -> io::Result<()> {
+    assert_eq!(
+        updates(&["abcd", "ab", "", "é"])?,
+        ["\rabcd", "\rab\x1b[K", "\r\x1b[K", "\ré"]
+    );
+    Ok(())
+}
+#[test]
+fn identical() -> io::Result<()> {
+    assert_eq!(
+        updates(&["a", "a", "b", "b", "a"])?,
+        ["\ra", "", "\rb", "", "\ra"]
+    );
+    Ok(())
+}
+#[test]
+#[cfg(not(feature = "partial"))]
+fn styled() -> io::Result<()> {
+    use easy_sgr::{Color::*, Style::*};
+
+    let mut status = StatusLine::new(Vec::new());
+    status.set(&"12345".color(RedFg).clean(easy_sgr::CleanKind::Reverse))?;
+    // same text, but different styles
+    status.set(&"12345".style(Bold))?;
+    // the width excludes the escapes
+    assert_eq!(status.width(), 5);
+    status.set(&"1\x1b[1m2".to_sgr())?;
+    let erase = if cfg!(feature = "control") {
+        "\x1b[K"
+    } else {
+        "   "
+    };
+    assert_eq!(
+        String::from_utf8_lossy(status.get_ref()),
+        format!("\r\x1b[31m12345\x1b[39m\r\x1b[1m12345\r1\x1b[1m2{erase}")
+    );
+    Ok(())
+}
+#[test]
+fn clear_and_finish() -> io::Result<()> {
+    let mut status = StatusLine::new(Vec::new());
+    // nothing to clear
+    status.clear()?;
+    status.set(&"abc".to_sgr())?;
+    status.clear()?;
+    status.clear()?;
+    status.set(&"abc".to_sgr())?;
+    status.finish()?;
+    // a new line is started, so nothing is erased
+    status.set(&"a".to_sgr())?;
+    let clear = if cfg!(feature = "control") {
+        "\r\x1b[K"
+    } else {
+        "\r   \r"
+    };
+    assert_eq!(
+        String::from_utf8_lossy(&status.into_inner()),
+        format!("\rabc{clear}\rabc\x1b[0m\n\ra")
+    );
+    Ok(())
+}
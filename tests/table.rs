@@ -0,0 +1,102 @@
+#![cfg(all(feature = "table", not(feature = "macro-only")))]
+use easy_sgr::{visible_width, Align, Color::*, Column, EasySGR, Row, SGRWriter, Style::*, Table};
+
+/// Strips escape sequences, leaving what a terminal would show
+fn visible(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("\x1b[") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..].find('m').unwrap();
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+#[test]
+fn mixed_cells() {
+    let table = Table::new()
+        .separator(" | ")
+        .row(Row::from_iter(["plain", "x"]))
+        .row(Row::from_iter([
+            "ok".color(GreenFg).style(Bold),
+            "y".to_sgr(),
+        ]))
+        .row(Row::from_iter(["longer cell", "z"]));
+
+    let out = table.to_string();
+    assert_eq!(
+        visible(&out),
+        "plain       | x\n\
+         ok          | y\n\
+         longer cell | z\n"
+    );
+    assert_eq!(
+        out.lines().nth(1),
+        Some("\x1b[32;1mok\x1b[39;22m          | y")
+    );
+    let starts: Vec<_> = visible(&out).lines().map(|line| line.find('|')).collect();
+    assert!(starts.windows(2).all(|w| w[0] == w[1]));
+}
+#[test]
+fn layout() {
+    let table = Table::new()
+        .column(Column::default().min(4).align(Align::Right))
+        .column(Column::default().max(3).align(Align::Center))
+        .column(Column::default().min(3))
+        .row(Row::from_iter([
+            "1".to_sgr(),
+            "abcdef".color(RedFg),
+            "a".into(),
+        ]))
+        .row(Row::from_iter(["22", "b"]));
+
+    assert_eq!(table.widths(), [4, 3, 3]);
+    assert_eq!(table.to_string(), "   1 \x1b[31mabc\x1b[39m a\n  22  b  \n");
+}
+#[test]
+fn wide_chars() {
+    // each char is a column, wide or not
+    assert_eq!(visible_width("\x1b[1m日本\x1b[0m"), 2);
+    let table = Table::new()
+        .column(Column::default().max(2))
+        .row(Row::from_iter(["日本語", "a"]))
+        .row(Row::from_iter(["x", "b"]));
+    assert_eq!(table.widths(), [2, 1]);
+    assert_eq!(table.to_string(), "日本 a\nx  b\n");
+}
+#[test]
+fn matches_offset_map() {
+    for text in [
+        "\x1b[1;31mab\x1b[0mc",
+        "é\u{9b}1m字x",
+        "a\x1bb",
+        "a\x1b[12;3",
+        "\x1b[2Kdone",
+    ] {
+        assert_eq!(
+            visible_width(text),
+            easy_sgr::offset_map(text).width(),
+            "{text:?}"
+        );
+    }
+}
+#[test]
+fn embedded_codes() {
+    assert_eq!(visible_width(""), 0);
+    assert_eq!(visible_width("\x1b[1;31mab\x1b[0mc"), 3);
+    assert_eq!(visible_width("\u{9b}1;31mab\u{9b}0mc"), 3);
+    assert_eq!(visible_width("é\x1b[K"), 1);
+
+    let table = Table::new()
+        .column(Column::default().max(2))
+        .row(Row::from_iter(["a\x1b[1mbcd\x1b[0m", "|"]));
+    let mut writer = SGRWriter::from(String::new());
+    table.write(&mut writer).unwrap();
+    assert_eq!(writer.internal(), "a\x1b[1mb\x1b[0m |\n");
+}
+#[test]
+fn empty() {
+    assert_eq!(Table::new().to_string(), "");
+    assert!(Table::default().widths().is_empty());
+}
@@ -0,0 +1,77 @@
+#![cfg(all(feature = "terminfo", not(feature = "macro-only")))]
+use easy_sgr::{
+    terminfo::Terminfo, Capabilities, ColorSupport, DowngradeWriter, FmtWriter, SGRWriter,
+    Style::*, StyleFallback,
+};
+
+/// Compiled by `tic` with 32 bit numbers & extended capabilities
+const XTERM_256COLOR: &[u8] = include_bytes!("terminfo/x/xterm-256color");
+/// Compiled by `tic` with 16 bit numbers
+const SCREEN: &[u8] = include_bytes!("terminfo/s/screen");
+
+#[test]
+fn xterm_256color() {
+    let entry = Terminfo::parse(XTERM_256COLOR).unwrap();
+    assert_eq!(entry.names(), ["xterm-256color", "xterm with 256 colors"]);
+    assert_eq!(entry.max_colors(), Some(256));
+    assert_eq!(entry.string(311), Some(&b"\x1b[3m"[..]));
+    assert_eq!(entry.extended_string("smxx"), Some(&b"\x1b[9m"[..]));
+    assert!(entry.extended_flag("XT"));
+    assert!(!entry.extended_flag("Tc"));
+    assert_eq!(
+        entry.capabilities(),
+        Capabilities {
+            colors: ColorSupport::Palette256,
+            supports_italic: true,
+            supports_strikethrough: true,
+        }
+    );
+}
+#[test]
+fn screen() {
+    let entry = Terminfo::parse(SCREEN).unwrap();
+    assert_eq!(entry.names()[0], "screen");
+    assert_eq!(entry.max_colors(), Some(8));
+    // `smso` is italics on screen, not `sitm`
+    assert_eq!(entry.string(35), Some(&b"\x1b[3m"[..]));
+    assert_eq!(entry.string(311), None);
+    assert_eq!(
+        entry.capabilities(),
+        Capabilities {
+            colors: ColorSupport::Basic,
+            supports_italic: false,
+            supports_strikethrough: false,
+        }
+    );
+}
+#[test]
+fn malformed() {
+    assert!(Terminfo::parse(&XTERM_256COLOR[..40]).is_err());
+    assert!(Terminfo::parse(b"\x1a\x01").is_err());
+    assert!(Terminfo::load("../etc/passwd").is_err());
+}
+#[test]
+fn substituted() {
+    fn render(capabilities: Capabilities, fallback: StyleFallback) -> String {
+        let downgrade = DowngradeWriter::new(FmtWriter(String::new()), capabilities)
+            .italic_fallback(fallback.clone())
+            .strikethrough_fallback(fallback);
+        let mut writer = SGRWriter::from(downgrade);
+        writer.inline_sgr_all(&(Italic, Strikethrough)).unwrap();
+        writer.write_inner("x").unwrap();
+        writer
+            .inline_sgr_all(&(NotItalic, NotStrikethrough))
+            .unwrap();
+        writer.internal()
+    }
+    let xterm = Terminfo::parse(XTERM_256COLOR).unwrap().capabilities();
+    let screen = Terminfo::parse(SCREEN).unwrap().capabilities();
+    let underline = StyleFallback::Substitute(Underline);
+    assert_eq!(render(xterm, underline.clone()), "\x1b[3;9mx\x1b[23;29m");
+    assert_eq!(render(screen, underline), "\x1b[4;4mx\x1b[24;24m");
+    assert_eq!(
+        render(screen, StyleFallback::Substitute(Dim)),
+        "\x1b[2;2mx\x1b[22;22m"
+    );
+    assert_eq!(render(screen, StyleFallback::Drop), "x");
+}
@@ -0,0 +1,197 @@
+#![cfg(all(feature = "testing", not(feature = "macro-only")))]
+use easy_sgr::{
+    assert_sgr_eq, sgr_diff, sgr_segments, Color::*, DiscreteSGR, DisplaySegments, EasySGR,
+    IoWriter, Style::*, StyleSet,
+};
+
+#[test]
+fn equivalent_encodings() {
+    for (left, right) in [
+        ("\x1b[1m\x1b[31mX", "\x1b[31;1mX"),
+        ("\x1b[1;2mX\x1b[22mY", "\x1b[2;1mX\x1b[0mY"),
+        ("\x1b[31mX\x1b[39m", "\x1b[31mX"),
+        ("\x1b[1mX\x1b[1mY", "\x1b[1mXY"),
+        ("\x1b[mX", "\x1b[0mX"),
+        ("\x1b[38;5;1mX", "\x1b[32m\x1b[38;5;1mX"),
+        ("\x1b[1m\x1b[22mX", "X"),
+        ("\x1b[6mX\x1b[25mY", "\x1b[6mX\x1b[0mY"),
+        ("\x1b[2KX", "\x1b[1m\x1b[0m\x1b[2KX"),
+    ] {
+        assert_eq!(sgr_diff(left, right), None, "{left:?} {right:?}");
+    }
+    assert_sgr_eq!("X".color(RedFg).style(Bold), Bold.and(RedFg).text("X"));
+    assert_sgr_eq!(
+        "a".style(Italic).clean(easy_sgr::CleanKind::Reverse),
+        "\x1b[3ma\x1b[23m",
+    );
+}
+#[test]
+fn differing() {
+    for (left, right) in [
+        ("\x1b[1mX", "\x1b[2mX"),
+        ("\x1b[1mX", "X"),
+        ("\x1b[31mX", "\x1b[41mX"),
+        ("\x1b[1mXY", "\x1b[1mX\x1b[22mY"),
+        ("\x1b[38;2;1;2;3mX", "\x1b[38;2;1;2;4mX"),
+        ("X", "Y"),
+    ] {
+        assert!(sgr_diff(left, right).is_some(), "{left:?} {right:?}");
+    }
+    let diff = sgr_diff("a\x1b[1mb", "a\x1b[2mb").unwrap();
+    assert!(
+        diff.starts_with("styled text differs at segment 1\n"),
+        "{diff}"
+    );
+    assert!(diff.contains(r#""a<bold>b""#), "{diff}");
+    assert!(diff.contains(r#""a<dim>b""#), "{diff}");
+}
+#[test]
+#[should_panic = "styled text differs at segment 0"]
+fn assert_fails() {
+    assert_sgr_eq!("\x1b[1mX", "\x1b[3mX");
+}
+#[test]
+fn segments() {
+    assert_eq!(
+        sgr_segments("\x1b[1ma\x1b[31mb\x1b[0;73mc"),
+        [
+            (String::from("a"), StyleSet::from(Bold)),
+            (String::from("b"), StyleSet::from((Bold, RedFg))),
+            (
+                String::from("c"),
+                StyleSet {
+                    custom_places: vec![73],
+                    ..StyleSet::default()
+                }
+            ),
+        ]
+    );
+    assert!(sgr_segments("").is_empty());
+}
+#[test]
+fn partial_resets() {
+    use easy_sgr::{Color, Style};
+    let styled = format!("{}{}{}{}{}", Bold, Italic, Underline, RedFg, BlueBg);
+    let state = |reset: &dyn std::fmt::Display| {
+        sgr_segments(&format!("{styled}a{reset}b"))
+            .pop()
+            .map(|(_, state)| state)
+            .unwrap_or_default()
+    };
+    assert_eq!(
+        state(&Color::reset_all()),
+        StyleSet::from((Bold, Italic, Underline))
+    );
+    assert_eq!(
+        state(&Style::reset_effects()),
+        StyleSet::from((RedFg, BlueBg))
+    );
+    assert_eq!(state(&Reset), StyleSet::default());
+}
+#[test]
+fn classified() {
+    use easy_sgr::{diff, Difference};
+    // only attributes
+    let report = diff("\x1b[1;31mab", "\x1b[31mab");
+    assert_eq!(
+        report.differences(),
+        [Difference::Attributes {
+            text: String::from("ab"),
+            old: StyleSet::from((Bold, RedFg)),
+            new: StyleSet::from(RedFg),
+        }]
+    );
+    assert!(
+        report
+            .to_string()
+            .ends_with(r#"attributes of "ab": expected red+bold, got red"#),
+        "{report}"
+    );
+    // split differently, only the differing text reported
+    let report = diff("\x1b[1mabc", "\x1b[1ma\x1b[22mbc");
+    assert_eq!(
+        report.differences(),
+        [Difference::Attributes {
+            text: String::from("bc"),
+            old: StyleSet::from(Bold),
+            new: StyleSet::default(),
+        }]
+    );
+    assert!(report.to_string().contains("expected bold, got plain"));
+    // only text
+    let report = diff("\x1b[1ma\x1b[0mb", "\x1b[1mx\x1b[0mb");
+    assert_eq!(
+        report.differences(),
+        [Difference::Text {
+            index: 0,
+            old: String::from("a"),
+            new: String::from("x"),
+        }]
+    );
+    // segmentation
+    let report = diff("\x1b[1mab", "\x1b[1ma\x1b[2mc");
+    assert_eq!(
+        report.differences(),
+        [Difference::Segmentation {
+            index: 0,
+            old: vec![(String::from("ab"), StyleSet::from(Bold))],
+            new: vec![
+                (String::from("a"), StyleSet::from(Bold)),
+                (String::from("c"), StyleSet::from((Bold, Dim))),
+            ],
+        }]
+    );
+    assert!(diff("\x1b[1m\x1b[31mX", "\x1b[31;1mX").is_empty());
+    let report = diff("X", "Y");
+    assert!(report.render(true).contains("\x1b[32m"));
+    assert_eq!(report.render(false), report.to_string());
+}
+#[test]
+fn display_segments() {
+    for input in [
+        "plain",
+        "\x1b[1mbold\x1b[0m plain",
+        "\x1b[1m\x1b[31mX\x1b[2mY\x1b[22mZ",
+        "\x1b[38;5;208;4mX\x1b[24;48;2;1;2;3mY\x1b[0m",
+        "\x1b[7ma\x1b[27mb\x1b[3mc",
+    ] {
+        let segments = sgr_segments(input);
+        assert_sgr_eq!(DisplaySegments(&segments), input);
+        assert_eq!(
+            sgr_segments(&DisplaySegments(&segments).to_string()),
+            segments
+        );
+    }
+    // recoloring every styled segment, then rendering it back out
+    let recolored: Vec<_> = sgr_segments("a\x1b[31mb\x1b[1mc\x1b[0md")
+        .into_iter()
+        .map(|(text, style)| {
+            if style.is_empty() {
+                (text, style)
+            } else {
+                (text, style.layered(&GreenFg.into()))
+            }
+        })
+        .collect();
+    assert_sgr_eq!(DisplaySegments(&recolored), "a\x1b[32mb\x1b[1mc\x1b[0md");
+
+    let bold = StyleSet::from(Bold);
+    let same = [
+        (String::from("a"), bold.clone()),
+        (String::from("b"), bold.clone()),
+    ];
+    assert_eq!(DisplaySegments(&same).to_string(), "\x1b[1mab\x1b[0m");
+    let closed = [
+        (String::from("a"), bold),
+        (String::from("b"), StyleSet::default()),
+    ];
+    assert_eq!(DisplaySegments(&closed).to_string(), "\x1b[1ma\x1b[22mb");
+    assert_eq!(
+        DisplaySegments(&[] as &[(String, StyleSet)]).to_string(),
+        ""
+    );
+
+    let mut io = IoWriter(Vec::new());
+    DisplaySegments(same.iter()).write_to(&mut io).unwrap();
+    assert_eq!(io.0, b"\x1b[1mab\x1b[0m");
+}
@@ -0,0 +1,93 @@
+#![cfg(all(feature = "theme", not(feature = "macro-only")))]
+use std::{panic, thread};
+
+use easy_sgr::{
+    theme::{self, Theme},
+    Color::*,
+    ColorKind, EasySGR,
+};
+
+/// A theme whose error style is the given color
+fn error(color: easy_sgr::Color) -> Theme {
+    Theme {
+        error: color.to_sgr(),
+        ..Theme::default()
+    }
+}
+#[test]
+fn nested_scopes() {
+    let outer = theme::with_scoped(error(BlueFg), || {
+        let inner = theme::with_scoped(error(GreenFg), || {
+            // the warn style is the inner theme's, not the outer's
+            let warn = theme::warn_style().foreground;
+            (theme::error_style().foreground, warn)
+        });
+        assert_eq!(inner, (ColorKind::Green, ColorKind::Yellow));
+        theme::error_style().foreground
+    });
+    assert_eq!(outer, ColorKind::Blue);
+    assert_eq!(
+        theme::current().error.foreground,
+        theme::global().error.foreground
+    );
+}
+#[test]
+fn scopes_per_thread() {
+    theme::with_scoped(error(BlueFg), || {
+        let handles: Vec<_> = [MagentaFg, CyanFg, WhiteFg]
+            .into_iter()
+            .map(|color| {
+                thread::spawn(move || {
+                    // the spawning thread's scope is not seen
+                    let before = theme::error_style().foreground;
+                    let within = theme::with_scoped(error(color), || {
+                        theme::with_scoped(error(BlackFg), theme::error_style);
+                        theme::error_style().foreground
+                    });
+                    (before, within)
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        for (before, _) in &results {
+            assert_ne!(*before, ColorKind::Blue);
+        }
+        let within: Vec<_> = results.into_iter().map(|(_, within)| within).collect();
+        assert_eq!(
+            within,
+            [ColorKind::Magenta, ColorKind::Cyan, ColorKind::White]
+        );
+        assert_eq!(theme::error_style().foreground, ColorKind::Blue);
+    });
+}
+#[test]
+fn panic_safe() {
+    theme::with_scoped(error(BlueFg), || {
+        let result = panic::catch_unwind(|| {
+            theme::with_scoped(error(GreenFg), || -> () { panic!("within the scope") });
+        });
+        assert!(result.is_err());
+        // the panicking scope was removed, leaving the outer one
+        assert_eq!(theme::error_style().foreground, ColorKind::Blue);
+        // & the scopes still work
+        let inner = theme::with_scoped(error(RedFg), theme::error_style);
+        assert_eq!(inner.foreground, ColorKind::Red);
+    });
+}
+/// The only test changing the global theme, as tests share it
+#[test]
+fn global() {
+    theme::set_global(error(YellowFg));
+    assert_eq!(theme::global().error.foreground, ColorKind::Yellow);
+    assert_eq!(theme::error_style().foreground, ColorKind::Yellow);
+    let seen = thread::spawn(|| theme::error_style().foreground).join();
+    assert_eq!(seen.unwrap(), ColorKind::Yellow);
+    theme::with_scoped(error(GreenFg), || {
+        assert_eq!(theme::error_style().foreground, ColorKind::Green);
+        assert_eq!(theme::global().error.foreground, ColorKind::Yellow);
+    });
+    // a panic while a scope is in effect does not affect the global theme
+    let _ = thread::spawn(|| theme::with_scoped(error(RedFg), || panic!())).join();
+    theme::set_global(Theme::default());
+    assert_eq!(theme::error_style().foreground, ColorKind::Red);
+}
@@ -0,0 +1,9 @@
+//! Compile tests checking the diagnostics & full expansions of the macros
+#![cfg(feature = "macros")]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/expand/*.rs");
+}
@@ -0,0 +1,7 @@
+use easy_sgr::{format, sgr};
+
+fn main() {
+    let _ = sgr!(b"{[bold]}bytes");
+    let _ = sgr!(br#"{[bold]}bytes"#);
+    let _ = format!(b"{[bold]}bytes");
+}
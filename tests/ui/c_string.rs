@@ -0,0 +1,6 @@
+use easy_sgr::{println, sgr};
+
+fn main() {
+    let _ = sgr!(c"{[bold]}c string");
+    println!(c"{[bold]}c string");
+}
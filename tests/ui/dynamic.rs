@@ -0,0 +1,8 @@
+use easy_sgr::{format, sgr};
+
+fn main() {
+    let color = 1;
+    let _ = sgr!("{[(color)]}");
+    let _ = format!("{[>(color)]}a{[<]}");
+    let _ = format!("{[bold ()]}");
+}
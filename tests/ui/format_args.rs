@@ -0,0 +1,7 @@
+use easy_sgr::{format, println};
+
+fn main() {
+    let _ = format!("{[bold]}{} {}{[]}", 1);
+    let _ = format!("{[red]}{}{[]}", 1, 2);
+    println!("{[italic]}{name}{[]}", nam = 1);
+}
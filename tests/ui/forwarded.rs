@@ -0,0 +1,17 @@
+use easy_sgr::{format, sgr};
+
+macro_rules! styled {
+    ($lit:literal) => {
+        sgr!($lit)
+    };
+    ($lit:literal, $($arg:tt)*) => {
+        format!($lit, $($arg)*)
+    };
+}
+
+fn main() {
+    let _ = styled!("{[bold]}fine{[]}");
+    let _ = styled!("{[bold]}{}{[]}", 1);
+    let _ = styled!("{[not_a_keyword]}");
+    let _ = styled!("{[#000]}{}", 1);
+}
@@ -0,0 +1,6 @@
+use easy_sgr::{format, sgr};
+
+fn main() {
+    let _ = sgr!("{[bold]}fine " "{[#000]}");
+    let _ = format!(concat!("{[red]}", "fine ", "{[not_a_keyword]}"));
+}
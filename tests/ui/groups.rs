@@ -0,0 +1,8 @@
+use easy_sgr::{format, println};
+
+fn main() {
+    let name = "world";
+    let _ = format!("{[>bold]}hello {name}");
+    println!("{[>bold]}a{[>red]}b{[<]}");
+    println!("closed{[<]}");
+}
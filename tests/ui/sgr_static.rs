@@ -0,0 +1,8 @@
+use easy_sgr::sgr_static;
+
+fn main() {
+    let name = "world";
+    let _ = sgr_static!("{[bold]}{}{[]}");
+    let _ = sgr_static!("hello {name}");
+    let _ = sgr_static!("{[red]}{name.len():>3}");
+}
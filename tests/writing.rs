@@ -1,6 +1,10 @@
+#![cfg(not(feature = "macro-only"))]
 use std::error::Error;
 
-use easy_sgr::{Color, EasySGR, SGRWriter, Style};
+use easy_sgr::{
+    CapableWriter, CleanKind, Codes, Color, DiscreteSGR, EasySGR, SGRBuilder, SGRString, SGRWriter,
+    Style, UnderlyingWrite, END, ESCAPE,
+};
 
 #[test]
 fn sgr_writer() -> Result<(), Box<dyn Error>> {
@@ -32,3 +36,971 @@ fn sgr_builder() -> Result<(), Box<dyn Error>> {
     assert_eq!("\x1b[0;1;2;3;4;5m", w.internal());
     Ok(())
 }
+
+#[test]
+fn sgr_writer_fill() -> Result<(), Box<dyn Error>> {
+    let mut w = SGRWriter::from(Vec::new());
+    w.fill(&Style::Bold.clean(CleanKind::Reset), '─', 2)?;
+    w.fill(&Style::Bold.to_sgr(), '─', 0)?;
+
+    assert_eq!("\x1b[1m──\x1b[0m".as_bytes(), w.internal());
+    Ok(())
+}
+
+#[test]
+fn sgr_builder_codes() {
+    let mut builder = SGRBuilder::default();
+    assert_eq!(builder.codes(), &[] as &[u8]);
+    Color::RgbFg(1, 2, 3)
+        .style(Style::Bold)
+        .place_all(&mut builder);
+    assert_eq!(builder.codes(), &[38, 2, 1, 2, 3, 1]);
+}
+
+#[test]
+fn sgr_builder_vec() {
+    let builder = SGRBuilder::from(vec![1, 31]);
+    assert!(!builder.spilled());
+    assert_eq!(builder.codes(), &[1, 31]);
+    assert_eq!(Vec::from(builder), vec![1, 31]);
+
+    let long = vec![38, 2, 1, 2, 3, 48, 2, 4, 5, 6];
+    let builder = SGRBuilder::from(long.clone());
+    assert!(builder.spilled());
+    assert_eq!(builder.into_vec(), long);
+}
+
+#[test]
+fn codes_capacity() {
+    // codes past the capacity are moved to the heap rather than dropped
+    let long = Color::RgbFg(1, 2, 3).and(Style::Bold);
+    assert_eq!(&*long.codes(), &[38, 2, 1, 2, 3, 1]);
+    assert_eq!(long.codes().into_iter().count(), Codes::CAPACITY + 1);
+    assert_eq!(&*Codes::from(7), &[7]);
+}
+
+#[test]
+fn sgr_builder_replay() -> Result<(), Box<dyn Error>> {
+    let mut builder = SGRBuilder::default();
+    Color::RgbBg(1, 2, 3)
+        .style(Style::Bold)
+        .place_all(&mut builder);
+
+    let mut fmt = SGRWriter::from(String::new());
+    let mut io = SGRWriter::from(Vec::new());
+    fmt.write_builder(&builder)?;
+    io.write_builder(&builder)?;
+    builder.write_to(&mut io)?;
+
+    assert_eq!(fmt.internal().repeat(2).as_bytes(), io.internal());
+
+    builder.clear();
+    assert!(builder.is_empty());
+
+    Style::Italic.write(&mut builder);
+    assert_eq!(builder.codes(), &[3]);
+    Ok(())
+}
+
+#[test]
+fn sgr_builder_spill() -> Result<(), Box<dyn Error>> {
+    let mut builder = SGRBuilder::default();
+    builder.write_codes(&[1; SGRBuilder::INLINE_CAPACITY - 1]);
+    builder.write_code(2);
+    assert!(!builder.spilled());
+    builder.write_code(3);
+    assert!(builder.spilled());
+
+    let mut builder = SGRBuilder::default();
+    Color::RgbFg(1, 2, 3).write(&mut builder);
+    Color::RgbBg(4, 5, 6).write(&mut builder);
+    assert!(builder.spilled());
+    assert_eq!(builder.codes(), &[38, 2, 1, 2, 3, 48, 2, 4, 5, 6]);
+    assert_eq!(
+        format!("{builder:?}"),
+        "SGRBuilder([38, 2, 1, 2, 3, 48, 2, 4, 5, 6])"
+    );
+
+    let mut w = SGRWriter::from(String::new());
+    w.write_builder(&builder)?;
+    assert_eq!("\x1b[38;2;1;2;3;48;2;4;5;6m", w.internal());
+
+    builder.clear();
+    assert!(builder.is_empty() && builder.spilled());
+    Ok(())
+}
+
+#[test]
+fn raw_csi() -> Result<(), Box<dyn Error>> {
+    assert_eq!(("\x1b[", "m"), (ESCAPE, END));
+
+    let mut w = SGRWriter::from(String::new());
+    w.sgr(&Style::Bold)?;
+    w.write_raw_csi('K', &[2])?;
+    w.write_raw_csi('s', &[])?;
+    w.write_raw_csi('H', &[1, 20])?;
+    w.sgr(&Color::RedFg)?;
+
+    assert_eq!("\x1b[1m\x1b[2K\x1b[s\x1b[1;20H\x1b[31m", w.internal());
+    Ok(())
+}
+
+#[test]
+fn wide_params() -> Result<(), Box<dyn Error>> {
+    let expected = "300;4000;65535\x1b[300G\x1b[4000;65535H";
+    let mut fmt = SGRWriter::from(String::new());
+    let mut io = SGRWriter::from(Vec::new());
+    for param in [300, 4000, 65535] {
+        if param != 300 {
+            fmt.write_inner(";")?;
+            io.write_inner(";")?;
+        }
+        fmt.write_param(param)?;
+        io.write_param(param)?;
+    }
+    fmt.write_raw_csi('G', &[300])?;
+    io.write_raw_csi('G', &[300])?;
+    fmt.write_raw_csi('H', &[4000, u16::MAX])?;
+    io.write_raw_csi('H', &[4000, u16::MAX])?;
+
+    assert_eq!(fmt.internal(), expected);
+    assert_eq!(io.internal(), expected.as_bytes());
+    Ok(())
+}
+
+fn render<W: UnderlyingWrite>(writer: W, sgr: &SGRString) -> Result<W, easy_sgr::Error> {
+    let mut writer = SGRWriter::new(writer);
+    writer.place_sgr(sgr)?;
+    writer.write_inner(&sgr.text)?;
+    writer.clean_sgr(sgr)?;
+    Ok(writer.internal())
+}
+#[test]
+fn underlying_write() -> Result<(), Box<dyn Error>> {
+    let sgr = "text".style(Style::Bold).clean(CleanKind::Reset);
+    let expected = "\x1b[1mtext\x1b[0m";
+
+    assert_eq!(render(String::new(), &sgr)?, expected);
+    assert_eq!(render(Vec::new(), &sgr)?, expected.as_bytes());
+    let cursor = render(std::io::Cursor::new(Vec::new()), &sgr)?;
+    assert_eq!(cursor.into_inner(), expected.as_bytes());
+
+    let mut s = String::from("> ");
+    render(&mut s, &sgr)?;
+    assert_eq!(s, format!("> {expected}"));
+    // borrowed & boxed writers of either kind
+    let mut s = String::new();
+    render(&mut s as &mut dyn std::fmt::Write, &sgr)?;
+    assert_eq!(s, expected);
+    let mut out = Vec::new();
+    render(&mut out as &mut dyn std::io::Write, &sgr)?;
+    assert_eq!(out, expected.as_bytes());
+    let boxed: Box<dyn std::io::Write> = Box::new(std::io::sink());
+    render(boxed, &sgr)?;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let stream = std::net::TcpStream::connect(listener.local_addr()?)?;
+    drop(render(stream, &sgr)?);
+    let mut received = String::new();
+    std::io::Read::read_to_string(&mut listener.accept()?.0, &mut received)?;
+    assert_eq!(received, expected);
+    Ok(())
+}
+#[test]
+fn error_conversions() {
+    let err = easy_sgr::Error::from(std::fmt::Error);
+    assert!(matches!(err, easy_sgr::Error::Fmt(_)));
+    assert!(err.source().is_some());
+    let io = std::io::Error::from(err);
+    assert_eq!(io.kind(), std::io::ErrorKind::Other);
+
+    let err = easy_sgr::Error::from(std::io::Error::from(std::io::ErrorKind::BrokenPipe));
+    let io = std::io::Error::from(err);
+    assert_eq!(io.kind(), std::io::ErrorKind::BrokenPipe);
+}
+struct FailingFmt;
+impl std::fmt::Write for FailingFmt {
+    fn write_str(&mut self, _: &str) -> std::fmt::Result {
+        Err(std::fmt::Error)
+    }
+}
+fn write_error<W: CapableWriter>(writer: W) -> easy_sgr::Error {
+    let mut writer = SGRWriter::from(writer);
+    writer.sgr(&Style::Bold).map_err(Into::into).unwrap_err()
+}
+#[test]
+fn error_variants() {
+    let err = write_error(easy_sgr::FmtWriter(FailingFmt));
+    assert!(matches!(err, easy_sgr::Error::Fmt(_)));
+    assert!(err.to_string().starts_with("Error writing to formatter"));
+
+    let mut buf = [0; 2];
+    let err = write_error(easy_sgr::IoWriter(std::io::Cursor::new(&mut buf[..])));
+    assert!(matches!(err, easy_sgr::Error::Io(_)));
+    assert!(err.to_string().starts_with("Error writing to io"));
+
+    let err = easy_sgr::Error::from(easy_sgr::ParseError::new("expected a color"));
+    assert!(matches!(err, easy_sgr::Error::Parse(_)));
+    assert_eq!(err.to_string(), "Error parsing: expected a color");
+}
+/// Fails the write at index `fail_at`, panicking if written to afterwards
+struct FailingWriter {
+    out: String,
+    fail_at: usize,
+    writes: usize,
+}
+impl CapableWriter for FailingWriter {
+    type Writer = String;
+    type Error = std::fmt::Error;
+
+    fn write(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writes += 1;
+        match (self.writes - 1).cmp(&self.fail_at) {
+            std::cmp::Ordering::Less => {
+                self.out.push_str(s);
+                Ok(())
+            }
+            std::cmp::Ordering::Equal => Err(std::fmt::Error),
+            std::cmp::Ordering::Greater => panic!("written to after failing"),
+        }
+    }
+    fn get_writer(self) -> Self::Writer {
+        self.out
+    }
+}
+impl std::fmt::Write for FailingWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        CapableWriter::write(self, s)
+    }
+}
+#[test]
+fn failures_surfaced() {
+    type Stage = fn(&mut SGRWriter<FailingWriter>) -> Result<(), std::fmt::Error>;
+    let stages: [(&str, Stage); 5] = [
+        ("inline_sgr", |w| w.inline_sgr(&Color::RgbBg(4, 5, 6))),
+        ("place_all", |w| {
+            let sgr = "text".color(Color::RgbFg(1, 2, 3)).style(Style::Bold);
+            w.place_sgr(&sgr)
+        }),
+        ("clean_all", |w| {
+            let sgr = "text".style(Style::Bold).clean(CleanKind::Reverse);
+            w.clean_sgr(&sgr)
+        }),
+        ("partial", |w| w.partial_sgr(&Style::Bold.and(Color::RedFg))),
+        ("display", |w| {
+            let sgr = "text"
+                .color(Color::RgbFg(1, 2, 3))
+                .style(Style::Bold)
+                .clean(CleanKind::Reverse);
+            std::fmt::Write::write_fmt(&mut w.writer, format_args!("{sgr}"))
+        }),
+    ];
+    for (name, stage) in stages {
+        let mut full = SGRWriter::from(FailingWriter {
+            out: String::new(),
+            fail_at: usize::MAX,
+            writes: 0,
+        });
+        stage(&mut full).unwrap();
+        let total = full.writer.writes;
+        assert!(total > 0, "{name}");
+
+        for fail_at in 0..total {
+            let mut writer = SGRWriter::from(FailingWriter {
+                out: String::new(),
+                fail_at,
+                writes: 0,
+            });
+            assert!(stage(&mut writer).is_err(), "{name} failing at {fail_at}");
+            // stopped at the failing write, everything before it was written
+            assert_eq!(writer.writer.writes, fail_at + 1, "{name}");
+            assert!(full.writer.out.starts_with(&writer.writer.out), "{name}");
+        }
+    }
+}
+#[test]
+fn throttled_bursts() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{FmtWriter, ThrottledWriter};
+    use std::{
+        cell::Cell,
+        time::{Duration, Instant},
+    };
+
+    let start = Instant::now();
+    let elapsed = Cell::new(Duration::ZERO);
+    let clock = || start + elapsed.get();
+    let throttled = ThrottledWriter::new(FmtWriter(String::new()), 10)
+        .burst(3)
+        .clock(clock);
+    let mut w = SGRWriter::from(throttled);
+    let span = |w: &mut SGRWriter<_>, text: &str| -> Result<(), std::fmt::Error> {
+        w.sgr(&Style::Bold)?;
+        w.write_inner(text)?;
+        w.sgr(&Style::Reset)
+    };
+    // a burst of five spans styles only the first three sequences
+    for text in ["a", "b", "c", "d", "e"] {
+        span(&mut w, text)?;
+    }
+    assert_eq!(w.writer.styled_writes(), 3);
+    assert_eq!(w.writer.stripped_writes(), 7);
+    // a tenth of a second refills a single token
+    elapsed.set(Duration::from_millis(100));
+    span(&mut w, "f")?;
+    // a quiet second refills the bucket, never past the burst size
+    elapsed.set(Duration::from_secs(2));
+    span(&mut w, "g")?;
+    span(&mut w, "h")?;
+    assert_eq!(w.writer.styled_writes(), 7);
+    assert_eq!(w.writer.stripped_writes(), 9);
+    // non-SGR sequences are never stripped
+    w.write_raw_csi('K', &[2])?;
+
+    assert_eq!(
+        w.internal(),
+        "\x1b[1ma\x1b[0m\x1b[1mb\x1b[0mcde\x1b[1mf\x1b[0m\x1b[1mg\x1b[0m\x1b[1mh\x1b[0m\x1b[2K"
+    );
+
+    // a clean dropped between a place & its text leaves the rest plain
+    let throttled = ThrottledWriter::new(FmtWriter(String::new()), 10)
+        .burst(1)
+        .clock(|| start);
+    let mut w = SGRWriter::from(throttled);
+    let sgr = "a".style(Style::Bold).clean(CleanKind::Reverse);
+    w.sgr(&sgr)?;
+    w.write_inner("a")?;
+    w.clean_sgr(&sgr)?;
+    w.write_inner(" tail")?;
+    w.sgr(&Style::Italic)?;
+    w.write_inner(" plain")?;
+    assert_eq!(w.writer.stripped_writes(), 2);
+    assert_eq!(w.internal(), "\x1b[1ma\x1b[0m tail plain");
+    Ok(())
+}
+#[test]
+fn saved_states() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{Color::*, FmtWriter, StatefulWriter, Style::*, StyleSet};
+    fn stateful() -> SGRWriter<StatefulWriter<FmtWriter<String>>> {
+        SGRWriter::from(StatefulWriter::new(FmtWriter(String::new())))
+    }
+
+    // nested saves restore in order
+    let mut w = stateful();
+    w.sgr(&RedFg)?;
+    {
+        let mut outer = w.save();
+        outer.sgr(&Bold)?;
+        {
+            let mut inner = outer.save();
+            inner.sgr(&BlueFg.style(Italic))?;
+            assert_eq!(inner.state(), &StyleSet::from((Bold, BlueFg, Italic)));
+        }
+        assert_eq!(outer.state(), &StyleSet::from((Bold, RedFg)));
+        outer.write_inner("|")?;
+    }
+    assert_eq!(w.state(), &StyleSet::from(RedFg));
+    assert_eq!(
+        w.internal(),
+        "\x1b[31m\x1b[1m\x1b[34;3m\x1b[31;23m|\x1b[22m"
+    );
+
+    // applying the state in effect writes nothing
+    let mut w = stateful();
+    w.sgr(&GreenBg.style(Underline))?;
+    let state = w.state().clone();
+    w.apply_state(&state)?;
+    w.save().restore()?;
+    assert_eq!(w.internal(), "\x1b[42;4m");
+
+    // a reset within a save is undone
+    let mut w = stateful();
+    w.sgr(&Bold.and(RedFg))?;
+    {
+        let mut guard = w.save();
+        guard.sgr(&Reset)?;
+        assert!(guard.state().is_empty());
+        guard.restore()?;
+    }
+    assert_eq!(w.state(), &StyleSet::from((Bold, RedFg)));
+    assert_eq!(w.internal(), "\x1b[1;31m\x1b[0m\x1b[31;1m");
+    Ok(())
+}
+#[test]
+fn inline_all() -> Result<(), Box<dyn Error>> {
+    let written = |f: &dyn Fn(&mut SGRWriter<easy_sgr::FmtWriter<String>>) -> std::fmt::Result| {
+        let mut w = SGRWriter::from(String::new());
+        f(&mut w).map(|()| w.internal())
+    };
+    // nothing at all, rather than an empty sequence resetting everything
+    assert_eq!(written(&|w| w.inline_sgr_all(&Vec::<Style>::new()))?, "");
+    assert_eq!(written(&|w| w.inline_sgr_all(&[] as &[Color]))?, "");
+    assert_eq!(written(&|w| w.inline_sgr_all(&(Style::Bold,)))?, "\x1b[1m");
+    assert_eq!(
+        written(&|w| w.inline_sgr_all(&(Style::Bold, Color::RgbBg(1, 2, 3), Style::Italic)))?,
+        "\x1b[1;48;2;1;2;3;3m"
+    );
+    assert_eq!(
+        written(&|w| w.inline_sgr_all(&[Color::RedFg, Color::ByteBg(208), Color::DefaultFg]))?,
+        "\x1b[31;48;5;208;39m"
+    );
+    Ok(())
+}
+#[test]
+fn empty_sequences() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{FmtWriter, StatefulWriter, StyleSet, ThrottledWriter};
+
+    let empty = SGRString::default();
+    let mut w = SGRWriter::from(String::new());
+    w.write_builder(&SGRBuilder::default())?;
+    w.place_sgr(&empty)?;
+    w.clean_sgr(&empty)?;
+    w.sgr(&empty)?;
+    w.partial_sgr(&empty)?;
+    w.inline_sgr_all(&Vec::<Style>::new())?;
+    w.write_sgr(&[])?;
+    SGRBuilder::default().write_to(&mut w)?;
+    SGRBuilder::default().write_partial(&mut w)?;
+    let mut builder = SGRBuilder::default();
+    StyleSet::from(Style::Bold).transition(&StyleSet::from(Style::Bold), &mut builder);
+    w.write_builder(&builder)?;
+    easy_sgr::join(Vec::<SGRString>::new(), ", ").write(&mut w)?;
+    w.write_inner(&empty.to_string())?;
+    assert_eq!(w.internal(), "");
+    // wrapping writers do not write or count them either
+    let mut throttled = SGRWriter::from(ThrottledWriter::new(FmtWriter(String::new()), 1));
+    throttled.write_sgr(&[])?;
+    assert_eq!(throttled.writer.styled_writes(), 0);
+    let mut stateful = SGRWriter::from(StatefulWriter::new(FmtWriter(String::new())));
+    stateful.sgr(&Style::Bold)?;
+    stateful.write_sgr(&[])?;
+    assert_eq!(stateful.state(), &StyleSet::from(Style::Bold));
+    // resets are still written when asked for
+    let mut w = SGRWriter::from(String::new());
+    w.write_reset()?;
+    w.inline_sgr(&Style::Reset)?;
+    w.place_sgr(&SGRString {
+        reset: true,
+        ..SGRString::default()
+    })?;
+    assert_eq!(w.internal(), "\x1b[0m\x1b[0m\x1b[0m");
+    Ok(())
+}
+#[test]
+fn bright_policies() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{BrightPolicy, BrightWriter, Color::*, FmtWriter, Style::*};
+    fn written(
+        policy: BrightPolicy,
+        f: impl FnOnce(&mut SGRWriter<BrightWriter<FmtWriter<String>>>) -> std::fmt::Result,
+    ) -> Result<String, std::fmt::Error> {
+        let mut w = SGRWriter::from(BrightWriter::new(FmtWriter(String::new()), policy));
+        f(&mut w)?;
+        Ok(w.internal())
+    }
+    let styled = "x".style(Bold).color(BrightRedFg).clean(CleanKind::Reverse);
+    let write_styled = |w: &mut SGRWriter<_>| {
+        w.place_sgr(&styled)?;
+        w.write_inner(&styled.text)?;
+        w.clean_sgr(&styled)
+    };
+    // aixterm codes by default
+    assert_eq!(BrightPolicy::default(), BrightPolicy::Codes90);
+    assert_eq!(
+        written(BrightPolicy::Codes90, |w| w.inline_sgr(&BrightRedFg))?,
+        "\x1b[91m"
+    );
+    assert_eq!(
+        written(BrightPolicy::Codes90, write_styled)?,
+        "\x1b[91;1mx\x1b[39;22m"
+    );
+    // bold & the basic color, the implied bold turned off with the color
+    let bold_basic = BrightPolicy::BoldBasic;
+    assert_eq!(
+        written(bold_basic, |w| {
+            w.inline_sgr(&BrightRedFg)?;
+            w.inline_sgr(&DefaultFg)
+        })?,
+        "\x1b[1;31m\x1b[39;22m"
+    );
+    assert_eq!(
+        written(bold_basic, |w| w.inline_sgr(&BrightBlueBg))?,
+        "\x1b[44m"
+    );
+    // an explicit bold in the same sequence is kept until turned off itself
+    assert_eq!(written(bold_basic, write_styled)?, "\x1b[1;31mx\x1b[39;22m");
+    assert_eq!(
+        written(bold_basic, |w| {
+            w.inline_sgr(&Bold)?;
+            w.inline_sgr(&BrightRedFg)?;
+            w.inline_sgr(&GreenFg)
+        })?,
+        "\x1b[1m\x1b[31m\x1b[32m"
+    );
+    // turning bold off keeps the bright foreground bright
+    assert_eq!(
+        written(bold_basic, |w| {
+            w.inline_sgr(&Bold.and(BrightCyanFg))?;
+            w.inline_sgr(&NotBold)?;
+            w.inline_sgr(&DefaultFg)
+        })?,
+        "\x1b[1;36m\x1b[22;1m\x1b[39;22m"
+    );
+    // dim is placed again after the implied bold is turned off
+    assert_eq!(
+        written(bold_basic, |w| {
+            w.inline_sgr(&Dim)?;
+            w.inline_sgr(&BrightWhiteFg)?;
+            w.inline_sgr(&ByteFg(91))
+        })?,
+        "\x1b[2m\x1b[1;37m\x1b[38;5;91;22;2m"
+    );
+    // a reset clears the implied bold
+    assert_eq!(
+        written(bold_basic, |w| {
+            w.inline_sgr(&BrightRedFg)?;
+            w.write_reset()?;
+            w.inline_sgr(&RedFg)
+        })?,
+        "\x1b[1;31m\x1b[0m\x1b[31m"
+    );
+    Ok(())
+}
+#[test]
+fn sanitizing_writer() {
+    use easy_sgr::{FmtWriter, SanitizePolicy, SanitizingWriter};
+    let write = |policy, parts: &[&str]| {
+        let mut writer = SGRWriter::from(SanitizingWriter::new(FmtWriter(String::new()), policy));
+        writer.inline_sgr(&Style::Bold).unwrap();
+        for part in parts {
+            writer.write_inner(part).unwrap();
+        }
+        writer.write_reset().unwrap();
+        writer.internal()
+    };
+    let parts = ["a\x1b]0;ti", "tle\x07b\x1b", "[2Jc\x1b[3", "1md\u{9b}1m"];
+    assert_eq!(
+        write(SanitizePolicy::StripAll, &parts),
+        "\x1b[1mabcd\x1b[0m"
+    );
+    assert_eq!(
+        write(SanitizePolicy::StripNonSGR, &parts),
+        "\x1b[1mabc\x1b[31md\x1b[0m"
+    );
+    assert_eq!(
+        write(SanitizePolicy::Neutralize, &parts),
+        "\x1b[1ma␛]0;title\x07b␛[2Jc␛[31md␛1m\x1b[0m"
+    );
+}
+#[test]
+fn escape_kinds() {
+    use easy_sgr::{EscapeKind, EscapeWriter, FmtWriter, IoWriter};
+    let sgr = "text"
+        .style(Style::Bold)
+        .color(Color::RedFg)
+        .clean(CleanKind::Reverse);
+    let mut io = SGRWriter::from(EscapeWriter::new(IoWriter(Vec::new()), EscapeKind::C1));
+    sgr.write_place(&mut io).unwrap();
+    io.write_inner(&sgr.text).unwrap();
+    sgr.write_clean(&mut io).unwrap();
+    io.write_raw_csi('K', &[2]).unwrap();
+    assert_eq!(io.internal(), b"\x9b31;1mtext\x9b39;22m\x9b2K");
+
+    let mut fmt = SGRWriter::from(EscapeWriter::new(FmtWriter(String::new()), EscapeKind::C1));
+    sgr.write_place(&mut fmt).unwrap();
+    fmt.write_inner(&sgr.text).unwrap();
+    sgr.write_clean(&mut fmt).unwrap();
+    assert_eq!(fmt.internal(), "\u{9b}31;1mtext\u{9b}39;22m");
+
+    let mut esc = SGRWriter::from(EscapeWriter::new(FmtWriter(String::new()), EscapeKind::Esc));
+    sgr.write_place(&mut esc).unwrap();
+    esc.write_inner(&sgr.text).unwrap();
+    sgr.write_clean(&mut esc).unwrap();
+    assert_eq!(esc.internal(), sgr.to_string());
+}
+#[test]
+fn reset_strategies() {
+    use easy_sgr::{open_state, FmtWriter, ResetStrategy, ResetWriter, StyleSet};
+    let sgr = "text"
+        .style(Style::Reset)
+        .style(Style::Bold)
+        .style(Style::Framed)
+        .color(Color::ByteFg(0))
+        .color(Color::RgbBg(0, 0, 0))
+        .clean(CleanKind::Reset);
+    let render = |strategy| {
+        let mut writer = SGRWriter::from(ResetWriter::new(FmtWriter(String::new()), strategy));
+        sgr.write_place(&mut writer).unwrap();
+        writer.write_inner(&sgr.text).unwrap();
+        sgr.write_clean(&mut writer).unwrap();
+        writer.write_reset().unwrap();
+        writer.internal()
+    };
+    let sgr0 = render(ResetStrategy::Sgr0);
+    assert_eq!(sgr0, format!("{sgr}\x1b[0m"));
+    let explicit = render(ResetStrategy::ExplicitOffCodes);
+    let off = easy_sgr::consts::RESET_EXPLICIT;
+    assert_eq!(
+        explicit,
+        format!("\x1b[22;23;24;25;27;28;29;39;49;54;55;38;5;0;48;2;0;0;0;1;51mtext{off}{off}")
+    );
+    assert!(!explicit.contains("[0") && !explicit.contains(";0m"));
+    for text in [sgr0, explicit] {
+        assert_eq!(open_state(&text), StyleSet::default());
+    }
+}
+#[test]
+fn budget_writer() {
+    use easy_sgr::{BudgetWriter, FmtWriter};
+    let render = |budget, lines: &[&[(&SGRString, &str)]]| {
+        let mut writer = SGRWriter::from(BudgetWriter::new(FmtWriter(String::new()), budget));
+        for line in lines {
+            for (sgr, text) in *line {
+                sgr.write_place(&mut writer).unwrap();
+                writer.write_inner(text).unwrap();
+            }
+            writer.write_inner("\n").unwrap();
+        }
+        let triggered = writer.writer.triggered();
+        (writer.internal(), triggered)
+    };
+    let red = Color::RedFg.to_sgr();
+    let bold = Style::Bold.to_sgr();
+    let plain = Color::DefaultFg.to_sgr();
+
+    // the bold would leave no room for the reset
+    let (out, triggered) = render(12, &[&[(&red, "a"), (&bold, "b"), (&plain, "c")]]);
+    assert_eq!(out, "\x1b[31ma\x1b[0mbc\n");
+    assert_eq!(triggered, 1);
+    // turning off fits without a reset
+    let (out, triggered) = render(10, &[&[(&red, "a"), (&plain, "b")], &[(&red, "c")]]);
+    assert_eq!(out, "\x1b[31ma\x1b[39mb\n\x1b[31mc\n");
+    assert_eq!(triggered, 0);
+    // nothing fits alongside its reset, so nothing is opened or reset
+    let (out, triggered) = render(8, &[&[(&red, "a"), (&bold, "b")], &[(&red, "c")]]);
+    assert_eq!(out, "ab\nc\n");
+    assert_eq!(triggered, 2);
+
+    let styled = "x".color(Color::RgbFg(1, 2, 3)).style(Style::Italic);
+    for budget in [0, 4, 9, 16, 24, 64] {
+        let line: &[(&SGRString, &str)] = &[(&styled, "one "), (&bold, "two "), (&red, "three")];
+        let (out, _) = render(budget, &[line, line]);
+        let visible =
+            |text: &str| easy_sgr::sanitize(text, easy_sgr::SanitizePolicy::StripAll).into_owned();
+        assert_eq!(visible(&out), "one two three\n".repeat(2));
+        for line in out.lines() {
+            let overhead = line.len() - visible(line).len();
+            assert!(overhead <= budget, "{overhead} > {budget} in {line:?}");
+            // only the largest budget fits every sequence, the others being cut off
+            if budget < 64 {
+                assert!(easy_sgr::ends_with_reset(line), "{line:?} left open");
+            }
+        }
+    }
+}
+#[test]
+fn budget_writer_partial() -> Result<(), std::fmt::Error> {
+    use easy_sgr::{BudgetWriter, FmtWriter};
+    let mut writer = SGRWriter::from(BudgetWriter::new(FmtWriter(String::new()), 10));
+    // counted & tracked, so the bold leaves no room for the reset
+    writer.partial_sgr(&Color::RedFg)?;
+    writer.write_inner("a")?;
+    writer.inline_sgr(&Style::Bold)?;
+    writer.partial_sgr(&Style::Italic)?;
+    writer.write_inner("b\n")?;
+    // cut off before the second parameter, the sequence still ending
+    writer.write_raw_csi('m', &[1, 31])?;
+    writer.write_raw_csi('K', &[2])?;
+    writer.write_inner("c\n")?;
+    // a separator written as text is kept
+    writer.partial_sgr(&Style::Italic)?;
+    writer.write_inner(";d")?;
+    writer.write_inner(";")?;
+    assert_eq!(writer.writer.triggered(), 2);
+    assert_eq!(writer.internal(), "31a\x1b[0mb\n\x1b[1m\x1b[0mc\n3;d;");
+    Ok(())
+}
+#[test]
+fn remap_writer() {
+    use easy_sgr::{palette256::PaletteMap, BrightPolicy, BrightWriter, FmtWriter, RemapWriter};
+    // each system color i is its own index, scaled to be told apart
+    let mut map = PaletteMap::default();
+    for i in 0..16 {
+        map = map.with_color(i, (i, i * 2, i * 3));
+    }
+    let map = map
+        .with_override(208, (1, 2, 3))
+        .with_override(3, (9, 9, 9));
+    fn remapped<W: CapableWriter>(mut writer: SGRWriter<W>, codes: &[u8]) -> W::Writer {
+        writer.write_sgr(codes).unwrap();
+        writer.internal()
+    }
+    let remap = |codes: &[u8]| {
+        remapped(
+            SGRWriter::from(RemapWriter::new(FmtWriter(String::new()), map.clone())),
+            codes,
+        )
+    };
+    for (codes, index) in [(30..=37, 0), (90..=97, 8)] {
+        for (code, i) in codes.zip(index..) {
+            let (r, g, b) = (i, i * 2, i * 3);
+            assert_eq!(remap(&[code]), format!("\x1b[38;2;{r};{g};{b}m"));
+            assert_eq!(remap(&[code + 10]), format!("\x1b[48;2;{r};{g};{b}m"));
+        }
+    }
+    // 256 colors are remapped by their override, or their system color
+    assert_eq!(remap(&[38, 5, 208]), "\x1b[38;2;1;2;3m");
+    assert_eq!(remap(&[48, 5, 1]), "\x1b[48;2;1;2;3m");
+    assert_eq!(remap(&[38, 5, 3]), "\x1b[38;2;9;9;9m");
+    // other colors are left untouched, as are the codes around them
+    for codes in [
+        &[38, 5, 209][..],
+        &[38, 2, 30, 31, 32],
+        &[48, 2, 90, 91, 92],
+        &[39, 49],
+        &[1, 38, 2, 0, 0, 0, 4],
+    ] {
+        let written: Vec<String> = codes.iter().map(u8::to_string).collect();
+        assert_eq!(remap(codes), format!("\x1b[{}m", written.join(";")));
+    }
+    assert_eq!(remap(&[1, 31, 4]), "\x1b[1;38;2;1;2;3;4m");
+    // remapping then downgrading bright colors is stable, as no bright colors are left
+    let pipeline = |codes: &[u8]| {
+        let bright = BrightWriter::new(FmtWriter(String::new()), BrightPolicy::BoldBasic);
+        remapped(
+            SGRWriter::from(RemapWriter::new(bright, map.clone())),
+            codes,
+        )
+    };
+    for codes in [&[91][..], &[1, 101, 39], &[97, 22, 38, 5, 12]] {
+        let once = pipeline(codes);
+        assert_eq!(once, remap(codes));
+        let again: Vec<u8> = once
+            .trim_start_matches("\x1b[")
+            .trim_end_matches('m')
+            .split(';')
+            .map(|code| code.parse().unwrap())
+            .collect();
+        assert_eq!(pipeline(&again), once);
+    }
+}
+#[test]
+fn io_write_in_scope() -> Result<(), Box<dyn Error>> {
+    use std::io::Write as _;
+
+    use easy_sgr::{FmtWriter, IoWriter};
+    let mut io = IoWriter(Vec::new());
+    let mut fmt = FmtWriter(String::new());
+    io.write_sgr(&[38, 5, 208])?;
+    fmt.write_sgr(&[38, 5, 208])?;
+    io.write_param(65535)?;
+    fmt.write_param(65535)?;
+    io.write("text")?;
+    fmt.write("text")?;
+    io.0.write_all(b"")?;
+    assert_eq!(String::from_utf8(io.0)?, fmt.0);
+    Ok(())
+}
+#[test]
+fn observed_writer() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{FmtWriter, ObservedWriter, SgrEvent::*, SgrStats};
+
+    /// Writes through every path of a writer
+    fn render<W: CapableWriter>(writer: &mut SGRWriter<W>) -> Result<(), W::Error> {
+        let sgr = "hi"
+            .color(Color::ByteFg(208))
+            .style(Style::Bold)
+            .clean(CleanKind::Reset);
+        writer.place_sgr(&sgr)?;
+        writer.write_inner(&sgr.text)?;
+        writer.clean_sgr(&sgr)?;
+        writer.inline_sgr(&Style::Italic)?;
+        writer.write_raw_csi('K', &[2])?;
+        writer.write_raw_csi('u', &[])?;
+        writer.partial_sgr(&Color::RedFg.style(Style::Dim))?;
+        writer.write_inner("")?;
+        writer.write_inner(";")
+    }
+    let mut events = Vec::new();
+    let mut observed = SGRWriter::from(ObservedWriter::new(FmtWriter(String::new()), |e| {
+        events.push(e);
+    }));
+    render(&mut observed)?;
+    let observed = observed.internal();
+    let mut plain = SGRWriter::from(FmtWriter(String::new()));
+    render(&mut plain)?;
+    assert_eq!(observed, plain.internal());
+    assert_eq!(
+        events,
+        [
+            // the place, text & clean of the SGRString
+            EscapeStart,
+            Param(38),
+            Param(5),
+            Param(208),
+            Param(1),
+            End,
+            Text(2),
+            EscapeStart,
+            Param(0),
+            End,
+            // inline
+            EscapeStart,
+            Param(3),
+            End,
+            // raw CSI
+            EscapeStart,
+            Param(2),
+            End,
+            EscapeStart,
+            End,
+            // partial, the `;` after them being text
+            Param(31),
+            Param(2),
+            Text(1),
+        ]
+    );
+
+    let mut stats = SgrStats::default();
+    events.into_iter().for_each(|e| stats.observe(e));
+    assert_eq!(
+        (
+            stats.sequences,
+            stats.params,
+            stats.max_params,
+            stats.text_bytes
+        ),
+        (5, 9, 4, 3)
+    );
+    assert_eq!(stats.overhead_bytes + stats.text_bytes, observed.len());
+    Ok(())
+}
+#[test]
+fn masking_writer() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{FmtWriter, HiddenPolicy, MaskingWriter, ThrottledWriter};
+
+    /// Writes a prompt & a hidden secret
+    fn prompt<W: CapableWriter>(writer: &mut SGRWriter<W>) -> Result<(), W::Error> {
+        writer.write_inner("key: ")?;
+        writer.sgr(&Color::ByteFg(8))?;
+        writer.write_inner("shown ")?;
+        writer.sgr(&Style::Hidden)?;
+        writer.write_inner("pässwörd\nline")?;
+        writer.sgr(&Style::Reset)?;
+        writer.write_inner(" done")
+    }
+    let render = |policy| -> Result<String, std::fmt::Error> {
+        let mut writer = SGRWriter::from(MaskingWriter::new(FmtWriter(String::new()), policy));
+        prompt(&mut writer)?;
+        Ok(writer.internal())
+    };
+    // left to the terminal, the text is passed through
+    assert_eq!(
+        render(HiddenPolicy::Conceal)?,
+        "key: \x1b[38;5;8mshown \x1b[8mpässwörd\nline\x1b[0m done"
+    );
+    // the width is kept, as is the line, the color 8 not hiding anything
+    let masked = render(HiddenPolicy::Mask('•'))?;
+    assert_eq!(
+        masked,
+        "key: \x1b[38;5;8mshown \x1b[8m••••••••\n••••\x1b[0m done"
+    );
+    assert_eq!(
+        easy_sgr::offset_map(&masked).width(),
+        easy_sgr::offset_map(&render(HiddenPolicy::Conceal)?).width()
+    );
+
+    // a writer stripping the sequences after masking does not reveal the secret
+    let throttled = ThrottledWriter::new(FmtWriter(String::new()), 0);
+    let mut writer = SGRWriter::from(MaskingWriter::new(throttled, HiddenPolicy::Mask('*')));
+    prompt(&mut writer)?;
+    assert_eq!(writer.internal(), "key: shown ********\n**** done");
+    Ok(())
+}
+#[test]
+fn standard_writer() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{Color::*, FmtWriter, StandardWriter, Style::*, StyleSet};
+
+    fn render(standard: StandardWriter<FmtWriter<String>>, sgrs: &[SGRString]) -> String {
+        let mut writer = SGRWriter::from(standard);
+        for sgr in sgrs {
+            writer.place_sgr(sgr).unwrap();
+            writer.write_inner(&sgr.text).unwrap();
+            writer.clean_sgr(sgr).unwrap();
+            writer.write_inner(" ").unwrap();
+        }
+        writer.finish().unwrap();
+        writer.internal()
+    }
+    let sgrs = [
+        "a".color(GreenFg).clean(CleanKind::Reverse),
+        "b".style(Bold).clean(CleanKind::Reset),
+        "c".color(RedBg).style(Dim).clean(CleanKind::Reverse),
+    ];
+    let plain = StandardWriter::new(FmtWriter(String::new()));
+    assert_eq!(
+        render(plain, &sgrs),
+        "\x1b[32ma\x1b[39m \x1b[1mb\x1b[0m \x1b[41;2mc\x1b[49;22m "
+    );
+    let tinted = StandardWriter::new(FmtWriter(String::new())).base_style((Dim, RedFg));
+    assert_eq!(
+        render(tinted, &sgrs),
+        concat!(
+            "\x1b[32;2ma\x1b[31m ",
+            "\x1b[1mb\x1b[22;2m ",
+            "\x1b[41mc\x1b[49m ",
+            "\x1b[0m",
+        )
+    );
+    // a base is placed again after a reset, even one finishing the output
+    let mut writer =
+        SGRWriter::from(StandardWriter::new(FmtWriter(String::new())).base_style(Italic));
+    writer.write_inner("")?;
+    writer.finish()?;
+    writer.write_inner("x")?;
+    writer.write_reset()?;
+    writer.write_inner("y")?;
+    assert_eq!(writer.writer.shown(), &StyleSet::from(Italic));
+    writer.finish()?;
+    assert!(writer.writer.shown().is_empty());
+    assert_eq!(writer.internal(), "\x1b[3mxy\x1b[0m");
+    Ok(())
+}
+#[test]
+fn downgrade_writer() -> Result<(), Box<dyn Error>> {
+    use easy_sgr::{Capabilities, Color::*, ColorSupport, DowngradeWriter, FmtWriter, Style::*};
+
+    fn render(colors: ColorSupport) -> Result<String, std::fmt::Error> {
+        let capabilities = Capabilities {
+            colors,
+            ..Capabilities::default()
+        };
+        let mut writer =
+            SGRWriter::from(DowngradeWriter::new(FmtWriter(String::new()), capabilities));
+        writer.inline_sgr_all(&(Bold, RgbFg(250, 20, 10), ByteBg(208)))?;
+        writer.write_inner("a")?;
+        writer.inline_sgr_all(&(ByteFg(4), BrightCyanBg, Italic))?;
+        writer.write_inner("b")?;
+        writer.inline_sgr_all(&(DefaultFg, DefaultBg))?;
+        Ok(writer.internal())
+    }
+    assert_eq!(
+        render(ColorSupport::Rgb)?,
+        "\x1b[1;38;2;250;20;10;48;5;208ma\x1b[38;5;4;106;3mb\x1b[39;49m"
+    );
+    assert_eq!(
+        render(ColorSupport::Palette256)?,
+        "\x1b[1;38;5;196;48;5;208ma\x1b[38;5;4;106;3mb\x1b[39;49m"
+    );
+    assert_eq!(
+        render(ColorSupport::Basic)?,
+        "\x1b[1;91;103ma\x1b[34;106;3mb\x1b[39;49m"
+    );
+    assert_eq!(render(ColorSupport::None)?, "\x1b[1ma\x1b[3mb");
+    // everything is supported by default
+    let mut writer = SGRWriter::from(DowngradeWriter::new(
+        FmtWriter(String::new()),
+        Capabilities::default(),
+    ));
+    writer.inline_sgr_all(&(Italic, Strikethrough, RgbBg(1, 2, 3)))?;
+    assert_eq!(writer.internal(), "\x1b[3;9;48;2;1;2;3m");
+    Ok(())
+}